@@ -0,0 +1,101 @@
+//! 基准：5 级处理链。生产方发布 `--messages` 条，依次经 5 个组件转换，末级记录端到端延迟。
+//!
+//! `cargo run --release --example bench_chain -- --messages 200000`
+
+mod common;
+
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+// 每级一个类型：延迟随链路累计
+#[derive(Debug)]
+struct S0(Instant);
+#[derive(Debug)]
+struct S1(Instant);
+#[derive(Debug)]
+struct S2(Instant);
+#[derive(Debug)]
+struct S3(Instant);
+#[derive(Debug)]
+struct S4(Instant);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Producer {
+    sent: AtomicU64,
+}
+
+#[mmg_microbus::component]
+impl Producer {
+    #[mmg_microbus::active]
+    async fn produce(&self) -> ControlFlow<(), S0> {
+        if self.sent.fetch_add(1, Ordering::Relaxed) < common::messages() {
+            ControlFlow::Continue(S0(common::stamp()))
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Stage1;
+#[mmg_microbus::component]
+impl Stage1 {
+    #[mmg_microbus::handle]
+    async fn on(&self, m: &S0) -> S1 {
+        S1(m.0)
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Stage2;
+#[mmg_microbus::component]
+impl Stage2 {
+    #[mmg_microbus::handle]
+    async fn on(&self, m: &S1) -> S2 {
+        S2(m.0)
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Stage3;
+#[mmg_microbus::component]
+impl Stage3 {
+    #[mmg_microbus::handle]
+    async fn on(&self, m: &S2) -> S3 {
+        S3(m.0)
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Stage4;
+#[mmg_microbus::component]
+impl Stage4 {
+    #[mmg_microbus::handle]
+    async fn on(&self, m: &S3) -> S4 {
+        S4(m.0)
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Stage5;
+#[mmg_microbus::component]
+impl Stage5 {
+    #[mmg_microbus::handle]
+    async fn on(&self, m: &S4) {
+        common::record(m.0);
+    }
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() {
+    let params = common::Params::from_args(200_000);
+    let deliveries = params.messages;
+    common::run("chain of 5 stages", params, deliveries, |_| async {}).await;
+}
@@ -0,0 +1,47 @@
+//! 基准：32 个生产方争用同一订阅队列。32 个外部任务经 `BusHandle` 各发布 `--messages` 条，由单个 handler 消费。
+//!
+//! `cargo run --release --example bench_contention -- --messages 20000 --queue-capacity 256`
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+const PRODUCERS: u64 = 32;
+
+#[derive(Debug)]
+struct Order {
+    sent: Instant,
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Matcher;
+
+#[mmg_microbus::component]
+impl Matcher {
+    #[mmg_microbus::handle]
+    async fn on_order(&self, o: &Order) {
+        common::record(o.sent);
+    }
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() {
+    let params = common::Params::from_args(20_000);
+    let deliveries = params.messages * PRODUCERS;
+    common::run("contention 32 -> 1", params, deliveries, |bus| async move {
+        for _ in 0..PRODUCERS {
+            let bus = bus.clone();
+            tokio::spawn(async move {
+                for _ in 0..common::messages() {
+                    let order = Order {
+                        sent: common::stamp(),
+                    };
+                    bus.publish_any_arc(Arc::new(order)).await;
+                }
+            });
+        }
+    })
+    .await;
+}
@@ -0,0 +1,84 @@
+//! 基准：1 → 8 扇出。一个 `#[active]` 生产方发布 `--messages` 条，8 个 handler 各自订阅全部消息。
+//!
+//! `cargo run --release --example bench_fanout -- --messages 200000 --queue-capacity 4096`
+
+mod common;
+
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+const FANOUT: u64 = 8;
+
+#[derive(Debug)]
+struct Quote {
+    sent: Instant,
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Producer {
+    sent: AtomicU64,
+}
+
+#[mmg_microbus::component]
+impl Producer {
+    #[mmg_microbus::active]
+    async fn produce(&self) -> ControlFlow<(), Quote> {
+        if self.sent.fetch_add(1, Ordering::Relaxed) < common::messages() {
+            ControlFlow::Continue(Quote {
+                sent: common::stamp(),
+            })
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+}
+
+// 每个 handler 独立订阅、独立 worker，等价于 8 个订阅方
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Consumers;
+
+#[mmg_microbus::component]
+impl Consumers {
+    #[mmg_microbus::handle]
+    async fn c0(&self, q: &Quote) {
+        common::record(q.sent);
+    }
+    #[mmg_microbus::handle]
+    async fn c1(&self, q: &Quote) {
+        common::record(q.sent);
+    }
+    #[mmg_microbus::handle]
+    async fn c2(&self, q: &Quote) {
+        common::record(q.sent);
+    }
+    #[mmg_microbus::handle]
+    async fn c3(&self, q: &Quote) {
+        common::record(q.sent);
+    }
+    #[mmg_microbus::handle]
+    async fn c4(&self, q: &Quote) {
+        common::record(q.sent);
+    }
+    #[mmg_microbus::handle]
+    async fn c5(&self, q: &Quote) {
+        common::record(q.sent);
+    }
+    #[mmg_microbus::handle]
+    async fn c6(&self, q: &Quote) {
+        common::record(q.sent);
+    }
+    #[mmg_microbus::handle]
+    async fn c7(&self, q: &Quote) {
+        common::record(q.sent);
+    }
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() {
+    let params = common::Params::from_args(200_000);
+    let deliveries = params.messages * FANOUT;
+    common::run("fanout 1 -> 8", params, deliveries, |_| async {}).await;
+}
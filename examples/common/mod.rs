@@ -0,0 +1,156 @@
+//! 基准示例公用部分：命令行参数、逐条延迟记录与结果输出。
+//!
+//! 参数（均可选）：`--messages N`、`--queue-capacity N`、`--worker-threads N`、
+//! `--max-concurrent-handlers N`、`--dedicated-actives`、`--sequence-numbers`。
+//! 建议以 `--release` 运行；结果只用于同机比较不同配置，不代表绝对性能。
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use mmg_microbus::bus::BusHandle;
+use mmg_microbus::config::{ActiveScheduling, AppConfig};
+use mmg_microbus::prelude::*;
+
+pub struct Params {
+    pub messages: u64,
+    pub cfg: AppConfig,
+}
+
+impl Params {
+    /// 解析命令行；未知参数或取值无效时输出用法并退出。
+    pub fn from_args(default_messages: u64) -> Self {
+        let mut params = Self {
+            messages: default_messages,
+            cfg: AppConfig::default(),
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or_else(|| usage(&arg))
+            };
+            match arg.as_str() {
+                "--messages" => params.messages = value() as u64,
+                "--queue-capacity" => params.cfg.queue_capacity = value(),
+                "--worker-threads" => params.cfg.topology.worker_threads = Some(value()),
+                "--max-concurrent-handlers" => {
+                    params.cfg.topology.max_concurrent_handlers = Some(value());
+                }
+                "--dedicated-actives" => params.cfg.topology.actives = ActiveScheduling::Dedicated,
+                "--sequence-numbers" => params.cfg.sequence_numbers = true,
+                _ => usage(&arg),
+            }
+        }
+        params
+    }
+}
+
+fn usage(arg: &str) -> ! {
+    eprintln!(
+        "invalid argument: {arg}\n\
+         options: --messages N --queue-capacity N --worker-threads N \
+         --max-concurrent-handlers N --dedicated-actives --sequence-numbers"
+    );
+    std::process::exit(2)
+}
+
+// 逐条延迟（纳秒）写入预分配槽位，记录路径无锁
+struct Recorder {
+    messages: u64,
+    slots: Box<[AtomicU64]>,
+    next: AtomicUsize,
+    first_sent: OnceLock<Instant>,
+    last_recv: OnceLock<Instant>,
+    done: tokio::sync::Notify,
+}
+
+static RECORDER: OnceLock<Recorder> = OnceLock::new();
+
+fn recorder() -> &'static Recorder {
+    RECORDER.get().expect("bench recorder not initialised")
+}
+
+/// 每个生产方应发布的条数（`--messages`）。
+pub fn messages() -> u64 {
+    recorder().messages
+}
+
+/// 发布时刻：生产方在消息中携带，接收方据此计算端到端延迟。
+pub fn stamp() -> Instant {
+    let now = Instant::now();
+    recorder().first_sent.get_or_init(|| now);
+    now
+}
+
+/// 记录一次投递；达到预期条数时唤醒 [`run`]。
+pub fn record(sent: Instant) {
+    let now = Instant::now();
+    let rec = recorder();
+    let i = rec.next.fetch_add(1, Ordering::Relaxed);
+    let nanos = u64::try_from(now.duration_since(sent).as_nanos()).unwrap_or(u64::MAX);
+    if let Some(slot) = rec.slots.get(i) {
+        slot.store(nanos, Ordering::Relaxed);
+    }
+    if i + 1 == rec.slots.len() {
+        rec.last_recv.get_or_init(|| now);
+        rec.done.notify_one();
+    }
+}
+
+/// 启动 App，等待 `deliveries` 次投递全部记录后停机并输出吞吐与延迟分位。
+/// `drive` 在启动后执行（外部生产方在此发布；组件内生产方传入空 future）。
+pub async fn run<F>(name: &str, params: Params, deliveries: u64, drive: impl FnOnce(BusHandle) -> F)
+where
+    F: std::future::Future<Output = ()>,
+{
+    let slots = (0..deliveries).map(|_| AtomicU64::new(0)).collect();
+    let fresh = RECORDER.set(Recorder {
+        messages: params.messages,
+        slots,
+        next: AtomicUsize::new(0),
+        first_sent: OnceLock::new(),
+        last_recv: OnceLock::new(),
+        done: tokio::sync::Notify::new(),
+    });
+    assert!(fresh.is_ok(), "bench run called twice");
+    let rec = recorder();
+
+    let mut app = App::new(params.cfg);
+    let bus = app.bus_handle();
+    app.start().await.expect("app start failed");
+    drive(bus).await;
+    rec.done.notified().await;
+    app.stop();
+
+    let elapsed = match (rec.first_sent.get(), rec.last_recv.get()) {
+        (Some(a), Some(b)) => b.duration_since(*a),
+        _ => Duration::ZERO,
+    };
+    let mut lat: Vec<u64> = rec
+        .slots
+        .iter()
+        .map(|s| s.load(Ordering::Relaxed))
+        .collect();
+    lat.sort_unstable();
+    let pct = |p: f64| {
+        // 最近秩法：p 分位取第 ceil(p * n) 个样本
+        let rank = ((p * lat.len() as f64).ceil() as usize).clamp(1, lat.len());
+        Duration::from_nanos(lat[rank - 1])
+    };
+    println!("{name}");
+    println!(
+        "  messages {}  deliveries {deliveries}  elapsed {elapsed:.2?}  throughput {:.0} deliveries/s",
+        params.messages,
+        deliveries as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+    );
+    println!(
+        "  latency p50 {:.2?}  p90 {:.2?}  p99 {:.2?}  p99.9 {:.2?}  max {:.2?}",
+        pct(0.50),
+        pct(0.90),
+        pct(0.99),
+        pct(0.999),
+        pct(1.0)
+    );
+}
@@ -0,0 +1,33 @@
+/* mmg-microbus C 嵌入接口（Rust 侧开启特性 `ffi`）。语义见 docs/FULL_GUIDE.md「C 嵌入（ffi）」。 */
+#ifndef MMG_MICROBUS_H
+#define MMG_MICROBUS_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define MMG_OK 0
+#define MMG_ERR_NULL (-1)
+#define MMG_ERR_UTF8 (-2)
+#define MMG_ERR_UNKNOWN_TYPE (-3)
+#define MMG_ERR_DECODE (-4)
+#define MMG_ERR_STATE (-5)
+#define MMG_ERR_START (-6)
+#define MMG_ERR_BUSY (-7)
+
+typedef struct MmgApp MmgApp;
+
+/* type_name 与 json 仅在回调期间有效；回调在运行时工作线程上调用。 */
+typedef void (*mmg_callback)(const char *type_name, const char *json, void *user_data);
+
+MmgApp *mmg_app_new(void);
+int mmg_subscribe_callback(MmgApp *app, const char *type_name, mmg_callback callback, void *user_data);
+int mmg_app_start(MmgApp *app);
+int mmg_publish_json(MmgApp *app, const char *type_name, const char *json);
+void mmg_app_free(MmgApp *app);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MMG_MICROBUS_H */
@@ -0,0 +1,163 @@
+//! 由字节驱动拼装“近似合法”的 `impl` 块（属性 / 接收者 / 参数 / 返回类型的各种组合），
+//! 覆盖 analyze 的判别分支：非法组合须产出 `compile_error!` 诊断，不得 panic。
+#![no_main]
+
+#[allow(dead_code)]
+#[path = "../../src/codegen/mod.rs"]
+mod codegen;
+
+use libfuzzer_sys::fuzz_target;
+use proc_macro2::TokenStream;
+
+const SELF_TYS: &[&str] = &[
+    "Svc",
+    "Svc<T>",
+    "crate::a::Svc",
+    "&'static Svc",
+    "[u8; 3]",
+    "(Svc, Svc)",
+    "dyn Tr",
+];
+const ATTRS: &[&str] = &[
+    "",
+    "#[handle]",
+    "#[handle(x)]",
+    "#[handle = 1]",
+    "#[mmg_microbus::handle]",
+    "#[active]",
+    "#[active()]",
+    "#[active(once)]",
+    "#[active(once, once)]",
+    "#[active(foo)]",
+    "#[active = \"once\"]",
+    "#[init]",
+    "#[init(x)]",
+    "#[stop]",
+    "#[doc = \"x\"]",
+    "#[cfg(test)]",
+];
+const RECEIVERS: &[&str] = &[
+    "&self",
+    "&mut self",
+    "self",
+    "self: Box<Self>",
+    "mut self",
+    "",
+];
+const PARAMS: &[&str] = &[
+    "ctx: &ComponentContext",
+    "ctx: &mmg_microbus::component::ComponentContext",
+    "ctx: ComponentContext",
+    "m: &Tick",
+    "m: &mut Tick",
+    "m: Tick",
+    "m: &Option<Tick>",
+    "m: &'a Tick",
+    "m: &[u8]",
+    "m: &(u8, u8)",
+    "m: &dyn Tr",
+    "m: &impl Tr",
+    "(a, b): (u8, u8)",
+    "_: &Tick",
+];
+const RETS: &[&str] = &[
+    "",
+    "-> ()",
+    "-> Tick",
+    "-> Option<Tick>",
+    "-> Result<Tick>",
+    "-> Result<(), E>",
+    "-> Result<Option<Tick>, E>",
+    "-> anyhow::Result<Option<Tick>>",
+    "-> std::result::Result<Tick, String>",
+    "-> Vec<Tick>",
+    "-> Option<Option<Tick>>",
+    "-> impl Tr",
+    "-> Self",
+    "-> !",
+    "-> &'static Tick",
+    "-> Box<dyn Tr>",
+];
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Bytes<'_> {
+    fn next(&mut self) -> Option<u8> {
+        let (b, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(*b)
+    }
+    fn pick<'s>(&mut self, table: &[&'s str]) -> Option<&'s str> {
+        Some(table[usize::from(self.next()?) % table.len()])
+    }
+}
+
+fn gen_method(src: &mut Bytes<'_>, idx: usize, out: &mut String) -> Option<()> {
+    let flags = src.next()?;
+    for _ in 0..flags % 3 {
+        out.push_str(src.pick(ATTRS)?);
+        out.push(' ');
+    }
+    if flags & 0x04 != 0 {
+        out.push_str("async ");
+    }
+    if flags & 0x08 != 0 {
+        out.push_str("unsafe ");
+    }
+    out.push_str(&format!("fn m{idx}"));
+    if flags & 0x10 != 0 {
+        out.push_str("<'a, U>");
+    }
+    out.push('(');
+    let recv = src.pick(RECEIVERS)?;
+    out.push_str(recv);
+    for _ in 0..(flags >> 5) % 4 {
+        if !out.ends_with('(') {
+            out.push_str(", ");
+        }
+        out.push_str(src.pick(PARAMS)?);
+    }
+    out.push_str(") ");
+    out.push_str(src.pick(RETS)?);
+    out.push_str(" {}\n");
+    Some(())
+}
+
+fn gen_impl(data: &[u8]) -> String {
+    let mut src = Bytes(data);
+    let mut out = String::new();
+    let Some(head) = src.next() else {
+        return out;
+    };
+    out.push_str("impl");
+    if head & 0x01 != 0 {
+        out.push_str("<T>");
+    }
+    out.push(' ');
+    if head & 0x02 != 0 {
+        out.push_str("Tr for ");
+    }
+    out.push_str(src.pick(SELF_TYS).unwrap_or("Svc"));
+    out.push_str(" {\n");
+    if head & 0x04 != 0 {
+        out.push_str("const C: u8 = 0;\ntype A = u8;\n");
+    }
+    let mut idx = 0;
+    while gen_method(&mut src, idx, &mut out).is_some() {
+        idx += 1;
+    }
+    out.push('}');
+    out
+}
+
+fuzz_target!(|data: &[u8]| {
+    let text = gen_impl(data);
+    let Ok(input) = text.parse::<TokenStream>() else {
+        return;
+    };
+    let out = codegen::expand(TokenStream::new(), input);
+    assert!(
+        syn::parse2::<syn::File>(out).is_ok(),
+        "expansion is not a valid item list for:\n{text}"
+    );
+});
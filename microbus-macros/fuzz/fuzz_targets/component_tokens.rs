@@ -0,0 +1,24 @@
+//! 任意文本 -> token 流 -> `#[component]` 展开：不得 panic，输出须始终是合法的条目序列。
+#![no_main]
+
+#[allow(dead_code)]
+#[path = "../../src/codegen/mod.rs"]
+mod codegen;
+
+use libfuzzer_sys::fuzz_target;
+use proc_macro2::TokenStream;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    // 词法不合法的输入到不了宏
+    let Ok(input) = text.parse::<TokenStream>() else {
+        return;
+    };
+    let out = codegen::expand(TokenStream::new(), input);
+    assert!(
+        syn::parse2::<syn::File>(out).is_ok(),
+        "expansion is not a valid item list"
+    );
+});
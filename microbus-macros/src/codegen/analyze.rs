@@ -1,14 +1,18 @@
 use super::msgs::{
-    ERR_ACTIVE_CTX_DUP, ERR_ACTIVE_MUT_SELF, ERR_ACTIVE_ONLY_CTX, ERR_HANDLE_CTX_DUP,
-    ERR_HANDLE_MULTI_ATTR, ERR_HANDLE_MUT_SELF, ERR_HANDLE_NEED_ONE_T, ERR_HANDLE_NO_ARGS,
-    ERR_HANDLE_ONLY_ONE_T, ERR_INIT_SIG, ERR_STOP_ASYNC_NOT_ALLOWED, ERR_STOP_CTX_DUP,
-    ERR_STOP_MUT_SELF, ERR_STOP_SIG,
+    ERR_ACTIVE_BACKOFF_ONCE, ERR_ACTIVE_BACKOFF_RET, ERR_ACTIVE_CTX_DUP, ERR_ACTIVE_FLOW_BREAK,
+    ERR_ACTIVE_FLOW_ONCE, ERR_ACTIVE_MUT_SELF, ERR_ACTIVE_ONLY_CTX, ERR_HANDLE_CTX_DUP,
+    ERR_HANDLE_MULTI_ATTR, ERR_HANDLE_MUT_SELF, ERR_HANDLE_NEED_ONE_T, ERR_HANDLE_ONLY_ONE_T,
+    ERR_HANDLE_ON_ERROR_RESULT, ERR_HANDLE_WINDOW_ARG, ERR_INIT_SIG, ERR_ROLE_CONFLICT,
+    ERR_SNAPSHOT_DUP, ERR_SNAPSHOT_PAIR, ERR_SNAPSHOT_RESTORE_SIG, ERR_SNAPSHOT_SAVE_SIG,
+    ERR_STOP_ASYNC_NOT_ALLOWED, ERR_STOP_CTX_DUP, ERR_STOP_MUT_SELF, ERR_STOP_SIG,
 };
 use quote::quote;
 use syn::{ItemImpl, Type};
 
 use super::parse::{
-    is_ctx_type, parse_active_kind, parse_handle_attr, parse_msg_arg_ref, ActiveKind,
+    is_ctx_type, parse_active_attr, parse_envelope_arg, parse_handle_attr, parse_msg_arg_ref,
+    parse_snapshot_kind, parse_window_arg, ActiveKind, ActiveOpts, HandleOpts, OnError, Pace,
+    Sample, SnapshotKind, WindowSpec,
 };
 
 #[derive(Clone)]
@@ -30,8 +34,77 @@ pub enum RetCase {
     ResultAnyArc,
 }
 
+impl RetCase {
+    // 可能不产出消息的返回（`None` 即空闲）：active 空闲退避仅对此类方法有意义
+    pub const fn is_option(&self) -> bool {
+        matches!(
+            self,
+            Self::OptionSome
+                | Self::OptionErased
+                | Self::OptionAnyBox
+                | Self::OptionAnyArc
+                | Self::ResultOption
+        )
+    }
+    // 动态类型发布（ErasedEvent / Any）：发布的具体类型在编译期未知
+    pub const fn is_dynamic(&self) -> bool {
+        matches!(
+            self,
+            Self::Erased
+                | Self::OptionErased
+                | Self::VecErased
+                | Self::AnyBox
+                | Self::AnyArc
+                | Self::OptionAnyBox
+                | Self::OptionAnyArc
+                | Self::ResultAnyBox
+                | Self::ResultAnyArc
+        )
+    }
+    // 返回 `Result<_, E>`：出错策略（重试 / 停机）仅对此类方法有意义
+    pub const fn is_result(&self) -> bool {
+        matches!(
+            self,
+            Self::ResultUnit
+                | Self::ResultSome
+                | Self::ResultOption
+                | Self::ResultAnyBox
+                | Self::ResultAnyArc
+        )
+    }
+}
+
 pub fn analyze_return(sig: &syn::Signature) -> RetCase {
-    match &sig.output {
+    analyze_output(&sig.output)
+}
+
+// 静态发布类型：按返回情形剥去 Option / Result 外层（供装配校验的发布清单）
+fn published_type(output: &syn::ReturnType, rc: &RetCase) -> Option<Type> {
+    fn first_arg(ty: &Type) -> Option<Type> {
+        let syn::Type::Path(tp) = ty else {
+            return None;
+        };
+        match &tp.path.segments.last()?.arguments {
+            syn::PathArguments::AngleBracketed(ab) => match ab.args.first()? {
+                syn::GenericArgument::Type(t) => Some(t.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+    let syn::ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    match rc {
+        RetCase::Some => Some((**ty).clone()),
+        RetCase::OptionSome | RetCase::ResultSome => first_arg(ty),
+        RetCase::ResultOption => first_arg(&first_arg(ty)?),
+        _ => None,
+    }
+}
+
+fn analyze_output(output: &syn::ReturnType) -> RetCase {
+    match output {
         syn::ReturnType::Default => RetCase::Unit,
         syn::ReturnType::Type(_, ty) => match &**ty {
             syn::Type::Tuple(t) if t.elems.is_empty() => RetCase::Unit,
@@ -154,32 +227,102 @@ pub struct MethodSpec {
     pub ident: syn::Ident,
     pub msg_ty: Type,
     pub wants_ctx: bool,
+    pub envelope: bool, // 消息参数为 `&Envelope<T>`
     pub ret_case: RetCase,
+    pub published: Option<Type>,
+    pub on_error: OnError,
+    pub latest: bool,
+    pub pace: Option<Pace>,
+    pub sample: Option<Sample>,
+    pub local: bool,
+    pub in_order: bool,
+    pub weak: bool,
+    pub weight: u32,
+    pub window: Option<WindowSpec>, // 消息参数为 `&Window<T>`：按窗口批量调用
+    pub emit: Option<Type>,         // `emit = ..` 且返回类型静态可知时，生成与返回类型的一致性检查
+    pub mut_self: bool,             // `&mut self`（仅信箱模式允许）
+}
+
+// handle 的消息参数形式
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MsgArg {
+    Ref,
+    Envelope,
+    Window,
 }
 pub struct ActiveSpec {
     pub ident: syn::Ident,
     pub wants_ctx: bool,
-    pub ret_case: RetCase,
+    pub ret_case: RetCase, // `flow` 时为 Continue 载荷的返回情形
+    pub published: Option<Type>,
     pub kind: ActiveKind,
+    pub flow: bool, // 返回 `ControlFlow<(), T>`：Break 结束本循环
+    pub idle_backoff: Option<(u64, u64)>,
 }
 pub struct InitSpec {
     pub ident: syn::Ident,
     pub wants_ctx: bool,
     pub ret_case: RetCase,
+    pub published: Option<Type>,
 }
 pub struct StopSpec {
     pub ident: syn::Ident,
     pub wants_ctx: bool,
     pub ret_case: RetCase,
+    pub published: Option<Type>,
 }
+pub struct SnapshotSpec {
+    pub save: syn::Ident,
+    pub restore: syn::Ident,
+}
+
+// 生命周期注解互斥：同一方法至多承担一种角色；冲突方法报错并从后续分析中剔除（不生成任何行为，也不再叠加签名诊断）
+const ROLE_ATTRS: [&str; 5] = ["handle", "active", "init", "stop", "snapshot"];
 
-pub fn collect_handles(item: &ItemImpl) -> (Vec<MethodSpec>, Vec<proc_macro2::TokenStream>) {
+pub fn strip_role_conflicts(item: &ItemImpl) -> (ItemImpl, Vec<proc_macro2::TokenStream>) {
+    let mut errs = Vec::new();
+    let mut scanned = item.clone();
+    scanned.items.retain(|it| {
+        let syn::ImplItem::Fn(m) = it else {
+            return true;
+        };
+        let mut first: Option<String> = None;
+        for a in &m.attrs {
+            let Some(role) = a
+                .path()
+                .segments
+                .last()
+                .map(|s| s.ident.to_string())
+                .filter(|r| ROLE_ATTRS.contains(&r.as_str()))
+            else {
+                continue;
+            };
+            match &first {
+                None => first = Some(role),
+                Some(f) if *f == role => {}
+                Some(_) => {
+                    errs.push(syn::Error::new_spanned(a, ERR_ROLE_CONFLICT).to_compile_error());
+                    return false;
+                }
+            }
+        }
+        true
+    });
+    (scanned, errs)
+}
+
+// `actor`：信箱模式（`#[component(actor)]`），允许 `&mut self`
+pub fn collect_handles(
+    item: &ItemImpl,
+    actor: bool,
+) -> (Vec<MethodSpec>, Vec<proc_macro2::TokenStream>) {
     let mut methods = Vec::new();
     let mut errs = Vec::new();
     for it in &item.items {
         if let syn::ImplItem::Fn(m) = it {
             let mut has_handle_attr = false;
             let mut handle_attr_count = 0usize;
+            let mut opts = HandleOpts::default();
             for a in &m.attrs {
                 let last = a
                     .path()
@@ -190,8 +333,9 @@ pub fn collect_handles(item: &ItemImpl) -> (Vec<MethodSpec>, Vec<proc_macro2::To
                 if last == "handle" {
                     has_handle_attr = true;
                     handle_attr_count += 1;
-                    if parse_handle_attr(a) {
-                        errs.push(quote! { compile_error!(#ERR_HANDLE_NO_ARGS); });
+                    match parse_handle_attr(a) {
+                        Ok(parsed) => opts = parsed,
+                        Err(e) => errs.push(e.to_compile_error()),
                     }
                 }
             }
@@ -199,17 +343,16 @@ pub fn collect_handles(item: &ItemImpl) -> (Vec<MethodSpec>, Vec<proc_macro2::To
                 errs.push(quote! { compile_error!(#ERR_HANDLE_MULTI_ATTR); });
             }
             if has_handle_attr {
-                if let Some(rcv) = m.sig.receiver() {
-                    if rcv.mutability.is_some() {
-                        errs.push(
-                            syn::Error::new_spanned(&m.sig, ERR_HANDLE_MUT_SELF).to_compile_error(),
-                        );
-                        continue;
-                    }
+                let mut_self = m.sig.receiver().is_some_and(|r| r.mutability.is_some());
+                if mut_self && !actor {
+                    errs.push(
+                        syn::Error::new_spanned(&m.sig, ERR_HANDLE_MUT_SELF).to_compile_error(),
+                    );
+                    continue;
                 }
                 let mut wants_ctx = false;
                 let mut duplicate_ctx = false;
-                let mut candidates: Vec<Type> = Vec::new();
+                let mut candidates: Vec<(Type, MsgArg)> = Vec::new();
                 for arg in &m.sig.inputs {
                     if let syn::FnArg::Typed(pat_ty) = arg {
                         if is_ctx_type(&pat_ty.ty) {
@@ -219,29 +362,67 @@ pub fn collect_handles(item: &ItemImpl) -> (Vec<MethodSpec>, Vec<proc_macro2::To
                             wants_ctx = true;
                             continue;
                         }
-                        if let Some(t) = parse_msg_arg_ref(&pat_ty.ty) {
-                            candidates.push(t);
+                        if let Some(t) = parse_envelope_arg(&pat_ty.ty) {
+                            candidates.push((t, MsgArg::Envelope));
+                        } else if let Some(t) = parse_window_arg(&pat_ty.ty) {
+                            candidates.push((t, MsgArg::Window));
+                        } else if let Some(t) = parse_msg_arg_ref(&pat_ty.ty) {
+                            candidates.push((t, MsgArg::Ref));
                         }
                     }
                 }
                 if duplicate_ctx {
-                    errs.push(quote! { compile_error!(#ERR_HANDLE_CTX_DUP) });
+                    errs.push(quote! { compile_error!(#ERR_HANDLE_CTX_DUP); });
                 }
                 let chosen = if candidates.len() == 1 {
                     Some(candidates[0].clone())
                 } else if candidates.is_empty() {
-                    errs.push(quote! { compile_error!(#ERR_HANDLE_NEED_ONE_T) });
+                    errs.push(quote! { compile_error!(#ERR_HANDLE_NEED_ONE_T); });
                     None
                 } else {
-                    errs.push(quote! { compile_error!(#ERR_HANDLE_ONLY_ONE_T) });
+                    errs.push(quote! { compile_error!(#ERR_HANDLE_ONLY_ONE_T); });
                     None
                 };
-                if let Some(msg_ty) = chosen {
+                let ret_case = analyze_return(&m.sig);
+                if opts.on_error != OnError::Ignore && !ret_case.is_result() {
+                    errs.push(
+                        syn::Error::new_spanned(&m.sig, ERR_HANDLE_ON_ERROR_RESULT)
+                            .to_compile_error(),
+                    );
+                }
+                if let Some((_, arg)) = &chosen {
+                    if (*arg == MsgArg::Window) != opts.window.is_some() {
+                        errs.push(
+                            syn::Error::new_spanned(&m.sig, ERR_HANDLE_WINDOW_ARG)
+                                .to_compile_error(),
+                        );
+                        continue;
+                    }
+                }
+                if let Some((msg_ty, arg)) = chosen {
+                    // 动态返回时 `emit` 即装配清单中的发布类型；静态可知时保留以生成一致性检查
+                    let (published, emit) = match published_type(&m.sig.output, &ret_case) {
+                        None => (opts.emit, None),
+                        published => (published, opts.emit),
+                    };
                     methods.push(MethodSpec {
                         ident: m.sig.ident.clone(),
                         msg_ty,
                         wants_ctx,
-                        ret_case: analyze_return(&m.sig),
+                        envelope: arg == MsgArg::Envelope,
+                        published,
+                        ret_case,
+                        on_error: opts.on_error,
+                        latest: opts.latest,
+                        pace: opts.pace,
+                        sample: opts.sample,
+                        local: opts.local,
+                        in_order: opts.in_order,
+                        weak: opts.weak,
+                        weight: opts.weight,
+                        window: opts.window,
+                        emit,
+                        mut_self,
                     });
                 }
             }
@@ -250,25 +431,28 @@ pub fn collect_handles(item: &ItemImpl) -> (Vec<MethodSpec>, Vec<proc_macro2::To
     (methods, errs)
 }
 
-pub fn collect_actives(item: &ItemImpl) -> (Vec<ActiveSpec>, Vec<proc_macro2::TokenStream>) {
+pub fn collect_actives(
+    item: &ItemImpl,
+    actor: bool,
+) -> (Vec<ActiveSpec>, Vec<proc_macro2::TokenStream>) {
     let mut actives = Vec::new();
     let mut errs = Vec::new();
     for it in &item.items {
         if let syn::ImplItem::Fn(m) = it {
             let mut is_active = false;
-            let mut active_kind = None;
+            let mut active_opts = None;
             for a in &m.attrs {
-                if let Some(res) = parse_active_kind(a) {
+                if let Some(res) = parse_active_attr(a) {
                     is_active = true;
                     match res {
-                        Ok(k) => active_kind = Some(k),
+                        Ok(o) => active_opts = Some(o),
                         Err(e) => errs.push(e.to_compile_error()),
                     }
                 }
             }
             if is_active {
                 if let Some(rcv) = m.sig.receiver() {
-                    if rcv.mutability.is_some() {
+                    if rcv.mutability.is_some() && !actor {
                         errs.push(
                             syn::Error::new_spanned(&m.sig, ERR_ACTIVE_MUT_SELF).to_compile_error(),
                         );
@@ -305,11 +489,46 @@ pub fn collect_actives(item: &ItemImpl) -> (Vec<ActiveSpec>, Vec<proc_macro2::To
                     );
                     continue;
                 }
+                let ActiveOpts { kind, idle_backoff } = active_opts.unwrap_or(ActiveOpts {
+                    kind: ActiveKind::Loop,
+                    idle_backoff: None,
+                });
+                let (ret_case, flow, output) = match parse_control_flow(&m.sig.output) {
+                    None => (analyze_return(&m.sig), false, m.sig.output.clone()),
+                    Some(Err(msg)) => {
+                        errs.push(syn::Error::new_spanned(&m.sig.output, msg).to_compile_error());
+                        continue;
+                    }
+                    Some(Ok(_)) if kind == ActiveKind::Once => {
+                        errs.push(
+                            syn::Error::new_spanned(&m.sig.output, ERR_ACTIVE_FLOW_ONCE)
+                                .to_compile_error(),
+                        );
+                        continue;
+                    }
+                    Some(Ok(cont)) => (analyze_output(&cont), true, cont),
+                };
+                if idle_backoff.is_some() {
+                    let err = if kind == ActiveKind::Once {
+                        Some(ERR_ACTIVE_BACKOFF_ONCE)
+                    } else if !ret_case.is_option() {
+                        Some(ERR_ACTIVE_BACKOFF_RET)
+                    } else {
+                        None
+                    };
+                    if let Some(msg) = err {
+                        errs.push(syn::Error::new_spanned(&m.sig, msg).to_compile_error());
+                        continue;
+                    }
+                }
                 actives.push(ActiveSpec {
                     ident: m.sig.ident.clone(),
                     wants_ctx,
-                    ret_case: analyze_return(&m.sig),
-                    kind: active_kind.unwrap_or(super::parse::ActiveKind::Loop),
+                    published: published_type(&output, &ret_case),
+                    ret_case,
+                    kind,
+                    flow,
+                    idle_backoff,
                 });
             }
         }
@@ -317,6 +536,38 @@ pub fn collect_actives(item: &ItemImpl) -> (Vec<ActiveSpec>, Vec<proc_macro2::To
     (actives, errs)
 }
 
+// `ControlFlow<(), T>` / `ControlFlow<()>` 返回：取 Continue 载荷为新的返回类型；Break 须为 `()`
+fn parse_control_flow(output: &syn::ReturnType) -> Option<Result<syn::ReturnType, &'static str>> {
+    let syn::ReturnType::Type(arrow, ty) = output else {
+        return None;
+    };
+    let syn::Type::Path(tp) = &**ty else {
+        return None;
+    };
+    let seg = tp.path.segments.last()?;
+    if seg.ident != "ControlFlow" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(ab) = &seg.arguments else {
+        return Some(Err(ERR_ACTIVE_FLOW_BREAK));
+    };
+    let mut args = ab.args.iter();
+    let unit_break = matches!(
+        args.next(),
+        Some(syn::GenericArgument::Type(syn::Type::Tuple(t))) if t.elems.is_empty()
+    );
+    if !unit_break {
+        return Some(Err(ERR_ACTIVE_FLOW_BREAK));
+    }
+    match (args.next(), args.next()) {
+        (None, _) => Some(Ok(syn::ReturnType::Default)),
+        (Some(syn::GenericArgument::Type(cont)), None) => {
+            Some(Ok(syn::ReturnType::Type(*arrow, Box::new(cont.clone()))))
+        }
+        _ => Some(Err(ERR_ACTIVE_FLOW_BREAK)),
+    }
+}
+
 pub fn handle_init_fn(m: &syn::ImplItemFn) -> (Option<InitSpec>, Option<proc_macro2::TokenStream>) {
     let mut wants_ctx = false;
     let mut invalid_extra = false;
@@ -339,15 +590,20 @@ pub fn handle_init_fn(m: &syn::ImplItemFn) -> (Option<InitSpec>, Option<proc_mac
         let e = syn::Error::new_spanned(&m.sig, ERR_INIT_SIG).to_compile_error();
         return (None, Some(e));
     }
+    let ret_case = analyze_return(&m.sig);
     let spec = InitSpec {
         ident: m.sig.ident.clone(),
         wants_ctx,
-        ret_case: analyze_return(&m.sig),
+        published: published_type(&m.sig.output, &ret_case),
+        ret_case,
     };
     (Some(spec), None)
 }
 
-pub fn handle_stop_fn(m: &syn::ImplItemFn) -> (Option<StopSpec>, Vec<proc_macro2::TokenStream>) {
+pub fn handle_stop_fn(
+    m: &syn::ImplItemFn,
+    actor: bool,
+) -> (Option<StopSpec>, Vec<proc_macro2::TokenStream>) {
     let mut compile_errors = Vec::new();
     // Enforce: #[stop] must be synchronous (non-async)
     if m.sig.asyncness.is_some() {
@@ -355,7 +611,7 @@ pub fn handle_stop_fn(m: &syn::ImplItemFn) -> (Option<StopSpec>, Vec<proc_macro2
             .push(syn::Error::new_spanned(&m.sig, ERR_STOP_ASYNC_NOT_ALLOWED).to_compile_error());
     }
     if let Some(rcv) = m.sig.receiver() {
-        if rcv.mutability.is_some() {
+        if rcv.mutability.is_some() && !actor {
             compile_errors
                 .push(syn::Error::new_spanned(&m.sig, ERR_STOP_MUT_SELF).to_compile_error());
             return (None, compile_errors);
@@ -385,10 +641,12 @@ pub fn handle_stop_fn(m: &syn::ImplItemFn) -> (Option<StopSpec>, Vec<proc_macro2
     if duplicate_ctx || !extraneous.is_empty() {
         return (None, compile_errors);
     }
+    let ret_case = analyze_return(&m.sig);
     let spec = StopSpec {
         ident: m.sig.ident.clone(),
         wants_ctx,
-        ret_case: analyze_return(&m.sig),
+        published: published_type(&m.sig.output, &ret_case),
+        ret_case,
     };
     (Some(spec), compile_errors)
 }
@@ -416,7 +674,10 @@ pub fn collect_inits(item: &ItemImpl) -> (Vec<InitSpec>, Vec<proc_macro2::TokenS
     (inits, compile_errors)
 }
 
-pub fn collect_stops(item: &ItemImpl) -> (Vec<StopSpec>, Vec<proc_macro2::TokenStream>) {
+pub fn collect_stops(
+    item: &ItemImpl,
+    actor: bool,
+) -> (Vec<StopSpec>, Vec<proc_macro2::TokenStream>) {
     let mut stops = Vec::new();
     let mut compile_errors = Vec::new();
     for it in &item.items {
@@ -426,7 +687,7 @@ pub fn collect_stops(item: &ItemImpl) -> (Vec<StopSpec>, Vec<proc_macro2::TokenS
                 .iter()
                 .any(|a| a.path().segments.last().is_some_and(|s| s.ident == "stop"));
             if has_stop {
-                let (spec, mut errs) = handle_stop_fn(m);
+                let (spec, mut errs) = handle_stop_fn(m, actor);
                 if let Some(s) = spec {
                     stops.push(s);
                 }
@@ -436,3 +697,73 @@ pub fn collect_stops(item: &ItemImpl) -> (Vec<StopSpec>, Vec<proc_macro2::TokenS
     }
     (stops, compile_errors)
 }
+
+// #[snapshot]：同步 `&self -> S`；#[snapshot(restore)]：同步 `&mut self, S`，二者成对出现
+fn snapshot_sig_ok(m: &syn::ImplItemFn, kind: SnapshotKind) -> bool {
+    let sig = &m.sig;
+    let Some(rcv) = sig.receiver() else {
+        return false;
+    };
+    if sig.asyncness.is_some() || rcv.reference.is_none() {
+        return false;
+    }
+    let typed = sig
+        .inputs
+        .iter()
+        .filter(|a| matches!(a, syn::FnArg::Typed(_)))
+        .count();
+    match kind {
+        SnapshotKind::Save => {
+            rcv.mutability.is_none()
+                && typed == 0
+                && !matches!(sig.output, syn::ReturnType::Default)
+        }
+        SnapshotKind::Restore => {
+            rcv.mutability.is_some() && typed == 1 && matches!(sig.output, syn::ReturnType::Default)
+        }
+    }
+}
+
+pub fn collect_snapshot(item: &ItemImpl) -> (Option<SnapshotSpec>, Vec<proc_macro2::TokenStream>) {
+    let mut save = None;
+    let mut restore = None;
+    let mut errs = Vec::new();
+    for it in &item.items {
+        let syn::ImplItem::Fn(m) = it else {
+            continue;
+        };
+        for a in &m.attrs {
+            let kind = match parse_snapshot_kind(a) {
+                None => continue,
+                Some(Err(e)) => {
+                    errs.push(e.to_compile_error());
+                    continue;
+                }
+                Some(Ok(kind)) => kind,
+            };
+            if !snapshot_sig_ok(m, kind) {
+                let msg = match kind {
+                    SnapshotKind::Save => ERR_SNAPSHOT_SAVE_SIG,
+                    SnapshotKind::Restore => ERR_SNAPSHOT_RESTORE_SIG,
+                };
+                errs.push(syn::Error::new_spanned(&m.sig, msg).to_compile_error());
+                continue;
+            }
+            let slot = match kind {
+                SnapshotKind::Save => &mut save,
+                SnapshotKind::Restore => &mut restore,
+            };
+            if slot.replace(m.sig.ident.clone()).is_some() {
+                errs.push(syn::Error::new_spanned(&m.sig, ERR_SNAPSHOT_DUP).to_compile_error());
+            }
+        }
+    }
+    match (save, restore) {
+        (Some(save), Some(restore)) => (Some(SnapshotSpec { save, restore }), errs),
+        (None, None) => (None, errs),
+        (Some(ident), None) | (None, Some(ident)) => {
+            errs.push(syn::Error::new_spanned(ident, ERR_SNAPSHOT_PAIR).to_compile_error());
+            (None, errs)
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// `#[derive(BusMessage)]`：为具体类型实现 `codec::BusMessage` 并登记模式；
+// 名称默认取类型标识符，`#[bus_message(name = "..", version = N, compatible_from = M)]` 覆盖。
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, LitInt, LitStr};
+
+use super::msgs::{
+    ERR_BUS_MESSAGE_ARG, ERR_BUS_MESSAGE_COMPAT_RANGE, ERR_BUS_MESSAGE_GENERIC,
+    ERR_BUS_MESSAGE_NAME_EMPTY, ERR_BUS_MESSAGE_VERSION_ZERO,
+};
+
+struct MessageAttrs {
+    name: String,
+    version: u32,
+    compatible_from: Option<u32>,
+}
+
+pub fn expand_bus_message(input: TokenStream) -> TokenStream {
+    let input = match syn::parse2::<DeriveInput>(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+    let attrs = match message_attrs(&input) {
+        Ok(attrs) => attrs,
+        Err(e) => return e.to_compile_error(),
+    };
+    let ident = &input.ident;
+    let MessageAttrs {
+        name,
+        version,
+        compatible_from,
+    } = attrs;
+    let min_compatible = compatible_from.map(|m| quote! { const MIN_COMPATIBLE: u32 = #m; });
+    quote! {
+        impl ::mmg_microbus::codec::BusMessage for #ident {
+            const NAME: &'static str = #name;
+            const VERSION: u32 = #version;
+            #min_compatible
+        }
+        const _: () = {
+            fn __schema() -> ::mmg_microbus::codec::Schema {
+                ::mmg_microbus::codec::Schema::of::<#ident>()
+            }
+            ::mmg_microbus::__inventory::submit! {
+                ::mmg_microbus::codec::__SchemaEntry { schema: __schema }
+            }
+        };
+    }
+}
+
+fn message_attrs(input: &DeriveInput) -> syn::Result<MessageAttrs> {
+    // 泛型实例共用同一名称会在对端无法区分
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            ERR_BUS_MESSAGE_GENERIC,
+        ));
+    }
+    let mut out = MessageAttrs {
+        name: input.ident.to_string(),
+        version: 1,
+        compatible_from: None,
+    };
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("bus_message"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                let lit: LitStr = meta.value()?.parse()?;
+                if lit.value().is_empty() {
+                    return Err(syn::Error::new_spanned(&lit, ERR_BUS_MESSAGE_NAME_EMPTY));
+                }
+                out.name = lit.value();
+            } else if meta.path.is_ident("version") {
+                let lit: LitInt = meta.value()?.parse()?;
+                out.version = lit.base10_parse()?;
+                if out.version == 0 {
+                    return Err(syn::Error::new_spanned(&lit, ERR_BUS_MESSAGE_VERSION_ZERO));
+                }
+            } else if meta.path.is_ident("compatible_from") {
+                let lit: LitInt = meta.value()?.parse()?;
+                out.compatible_from = Some(lit.base10_parse()?);
+            } else {
+                return Err(meta.error(ERR_BUS_MESSAGE_ARG));
+            }
+            Ok(())
+        })?;
+    }
+    if out
+        .compatible_from
+        .is_some_and(|m| m == 0 || m > out.version)
+    {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            ERR_BUS_MESSAGE_COMPAT_RANGE,
+        ));
+    }
+    Ok(out)
+}
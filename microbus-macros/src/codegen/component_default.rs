@@ -0,0 +1,68 @@
+// `#[derive(ComponentDefault)]`：逐字段生成 `Default`，字段类型缺少 `Default` 时错误指向该字段；
+// `#[component_default(expr)]` 为单个字段指定初始值。
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields};
+
+use super::msgs::ERR_COMPONENT_DEFAULT_TARGET;
+
+pub fn expand_component_default(input: TokenStream) -> TokenStream {
+    let input = match syn::parse2::<DeriveInput>(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+    match body(&input) {
+        Ok(body) => {
+            let ident = &input.ident;
+            let (impl_g, ty_g, where_g) = input.generics.split_for_impl();
+            quote! {
+                impl #impl_g ::core::default::Default for #ident #ty_g #where_g {
+                    fn default() -> Self { #body }
+                }
+            }
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn body(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            ERR_COMPONENT_DEFAULT_TARGET,
+        ));
+    };
+    let values = data
+        .fields
+        .iter()
+        .map(field_value)
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(match &data.fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| &f.ident);
+            quote! { Self { #( #names: #values ),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self( #( #values ),* ) },
+        Fields::Unit => quote! { Self },
+    })
+}
+
+fn field_value(field: &syn::Field) -> syn::Result<TokenStream> {
+    let mut custom = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("component_default"))
+    {
+        custom = Some(attr.parse_args::<syn::Expr>()?);
+    }
+    let ty = &field.ty;
+    Ok(match custom {
+        Some(expr) => quote! { #expr },
+        // 经带 on_unimplemented 的辅助 trait 取值，错误落在字段类型上
+        None => quote_spanned! { ty.span()=>
+            <#ty as ::mmg_microbus::component::__FieldDefault>::__field_default()
+        },
+    })
+}
@@ -1,12 +1,14 @@
 use quote::quote;
 
-use super::analyze::ActiveSpec;
+use super::analyze::{ActiveSpec, RetCase};
+use super::emit_handles::mailbox_call;
 use super::emit_ret::gen_ret_case_tokens;
 use super::parse::ActiveKind;
 
-// active 方法（loop / once）生成
+// active 方法（loop / once）生成；`actor` 时每次调用经组件信箱（idle_backoff 退避等待不占信箱）
 pub fn build_active_parts(
     actives: &[ActiveSpec],
+    actor: bool,
 ) -> (Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>) {
     let mut active_spawns = Vec::new();
     let mut once_calls = Vec::new();
@@ -19,12 +21,14 @@ pub fn build_active_parts(
                 } else {
                     quote! { this.#ident() }
                 };
+                let core = if actor { mailbox_call(&core) } else { core };
                 let expr = gen_ret_case_tokens(
                     "active returned error",
                     &core,
                     &a.ret_case,
                     false,
                     &quote! {ctx},
+                    None,
                 );
                 once_calls.push(expr);
             }
@@ -34,21 +38,88 @@ pub fn build_active_parts(
                 } else {
                     quote! { this.#ident() }
                 };
-                let expr_spawn = gen_ret_case_tokens(
+                let core_spawn = if actor {
+                    mailbox_call(&core_spawn)
+                } else {
+                    core_spawn
+                };
+                // ControlFlow：Continue 载荷按常规返回值发布；Break 仅结束本循环（组件其余部分照常运行）并发布 ActiveCompleted
+                // idle_backoff：先取返回值判定是否空闲（None / Err），发布后按结果退避或复位
+                let call = if a.flow || a.idle_backoff.is_some() {
+                    quote! { std::future::ready(__v) }
+                } else {
+                    core_spawn.clone()
+                };
+                let publish = gen_ret_case_tokens(
                     "active returned error",
-                    &core_spawn,
+                    &call,
                     &a.ret_case,
                     false,
                     &quote! {ctx_c},
+                    None,
                 );
+                let (backoff_decl, expr_spawn) = match a.idle_backoff {
+                    None => (quote! {}, publish),
+                    Some((min, max)) => {
+                        let idle = if matches!(a.ret_case, RetCase::ResultOption) {
+                            quote! { !matches!(__v, Ok(Some(_))) }
+                        } else {
+                            quote! { __v.is_none() }
+                        };
+                        (
+                            quote! { let mut __backoff = mmg_microbus::component::__IdleBackoff::new(#min, #max); },
+                            quote! {
+                                let __idle = #idle;
+                                { #publish }
+                                __backoff.step(__idle).await;
+                            },
+                        )
+                    }
+                };
+                let expr_spawn = if a.flow || a.idle_backoff.is_none() {
+                    expr_spawn
+                } else {
+                    quote! { let __v = #core_spawn.await; #expr_spawn }
+                };
+                let run_arm = if a.flow {
+                    let method_name = ident.to_string();
+                    quote! {
+                        __done = async {
+                            mmg_microbus::component::__wait_resumed(&mut __ctl).await;
+                            let this=&this_c;
+                            match #core_spawn.await {
+                                std::ops::ControlFlow::Continue(__v) => { #expr_spawn false }
+                                std::ops::ControlFlow::Break(()) => true,
+                            }
+                        } => {
+                            if __done {
+                                mmg_microbus::component::__active_completed(&ctx_c, #method_name).await;
+                                break;
+                            }
+                        }
+                    }
+                } else {
+                    quote! {
+                        _ = async {
+                            mmg_microbus::component::__wait_resumed(&mut __ctl).await;
+                            let this=&this_c;
+                            { #expr_spawn }
+                        } => {}
+                    }
+                };
+                // 循环任务持有数据源句柄：Break 结束或停机时随任务释放
                 let spawn_token = quote! {
                     let this_c = this.clone();
                     let ctx_c = ctx.__fork();
-                    let __jh = tokio::spawn(async move {
+                    let __source_c = __source.clone();
+                    let __jh = mmg_microbus::component::__spawn_active(&ctx, async move {
+                        let _source = __source_c;
+                        let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+                        #backoff_decl
                         loop {
-                            tokio::select! {
+                            mmg_microbus::rt::select! {
                                 _ = mmg_microbus::component::__recv_stop(&ctx_c) => break,
-                                _ = async { let this=&this_c; { #expr_spawn } } => {}
+                                #run_arm
                             }
                         }
                     });
@@ -2,55 +2,330 @@ use quote::{format_ident, quote};
 
 use super::analyze::MethodSpec;
 use super::emit_ret::gen_ret_case_tokens;
+use super::parse::{OnError, Pace, Sample, WindowSpec};
 
-// handle 方法的订阅声明与 worker 生成
-pub fn build_handle_parts(
-    methods: &[MethodSpec],
-) -> (Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>) {
+pub struct HandleParts {
+    pub local_scope: proc_macro2::TokenStream,
+    pub sub_decls: Vec<proc_macro2::TokenStream>,
+    pub handle_spawns: Vec<proc_macro2::TokenStream>,
+    pub dispatch_arms: Vec<proc_macro2::TokenStream>,
+}
+
+// handle 方法的订阅声明、worker 与直接调度分支生成；`tracked` 时 worker 维护处理中计数（快照空闲判定）；
+// `actor` 时每次调用先取得组件信箱（`this` 为 `Arc<__Mailbox<Self>>`），返回值在释放信箱后发布
+pub fn build_handle_parts(methods: &[MethodSpec], tracked: bool, actor: bool) -> HandleParts {
     let mut sub_decls = Vec::new();
+    let mut local_decls = Vec::new();
     let mut handle_spawns = Vec::new();
+    let mut dispatch_arms = Vec::new();
     for (idx, ms) in methods.iter().enumerate() {
         let ty = &ms.msg_ty;
         let ident = &ms.ident;
         let sub_var = format_ident!("__sub_any_{}", idx);
-        // 订阅声明
-        sub_decls.push(quote! { let mut #sub_var = mmg_microbus::component::__subscribe_any_auto::<#ty>(&ctx); });
+        let method_name = ident.to_string();
+        // 订阅声明（`latest`：合并订阅；`local`：订阅组件私有总线；`weak`：弱订阅）
+        let subscribe = match (ms.local, ms.latest) {
+            (false, false) if ms.weak => quote! { __subscribe_weak_auto },
+            (false, true) if ms.weak => quote! { __subscribe_weak_latest_auto },
+            (false, false) if ms.in_order => quote! { __subscribe_ordered_auto },
+            (false, false) => quote! { __subscribe_any_auto },
+            (false, true) => quote! { __subscribe_latest_auto },
+            (true, false) => quote! { __subscribe_local_auto },
+            (true, true) => quote! { __subscribe_local_latest_auto },
+        };
+        let decl = quote! { let mut #sub_var = mmg_microbus::component::#subscribe::<#ty>(&ctx); };
+        if ms.local {
+            local_decls.push(decl);
+        } else {
+            sub_decls.push(decl);
+        }
+
+        // 核心调用表达式 (区分是否需要 ctx；`&Envelope<T>` 形式直接传信封)
+        let arg = if ms.envelope || ms.window.is_some() {
+            quote! { &env }
+        } else {
+            quote! { &*env }
+        };
+        // 以 ctx 为参数的 `Ask` handler：轮询期间 `ctx.request_token()` 取得本次请求的取消信号
+        let direct = if ms.wants_ctx && ms.window.is_none() {
+            quote! {{
+                use mmg_microbus::query::{__ViaAsk as _, __ViaOther as _};
+                (&*env).__request_scope(this.#ident(&ctx_c, #arg))
+            }}
+        } else if ms.wants_ctx {
+            quote! { this.#ident(&ctx_c, #arg) }
+        } else {
+            quote! { this.#ident(#arg) }
+        };
+        let expr = if actor {
+            handle_call_tokens(ms, &mailbox_call(&direct))
+        } else {
+            handle_call_tokens(ms, &direct)
+        };
 
-        // 核心调用表达式 (区分是否需要 ctx)
-        let core = if ms.wants_ctx {
-            quote! { this.#ident(&ctx_c, &*env) }
+        let (track_decl, track_begin, track_end) = if tracked {
+            (
+                quote! { let __activity_c = __activity.clone(); },
+                quote! { __activity_c.begin(); },
+                quote! { __activity_c.end(); },
+            )
         } else {
-            quote! { this.#ident(&*env) }
+            Default::default()
+        };
+
+        // debounce / throttle：消息先经节流状态，到期的暂存消息作为第三路 select 分支；
+        // window：消息先入窗口缓存，窗口关闭时的整批作为第三路分支
+        let due_body = quote! {
+            let this=&this_c;
+            #track_begin
+            let __t0 = mmg_microbus::component::__handler_begin(&ctx_c);
+            { #expr }
+            mmg_microbus::component::__handler_end(&ctx_c, #method_name, std::any::type_name::<#ty>(), __t0);
+            #track_end
+        };
+        let (pace_decl, offer, due_arm, tail) = match (ms.pace, ms.window) {
+            (Some(pace), _) => {
+                let (ctor, nanos) = match pace {
+                    Pace::Debounce(n) => (quote! { debounce }, n),
+                    Pace::Throttle(n) => (quote! { throttle }, n),
+                };
+                (
+                    quote! { let mut __pacer = mmg_microbus::component::__Pacer::#ctor(&ctx_c, #nanos); },
+                    quote! {
+                        let Some(env) = __pacer.offer(env) else { continue; };
+                    },
+                    quote! {
+                        env = __pacer.due() => { #due_body }
+                    },
+                    quote! {},
+                )
+            }
+            (None, Some(WindowSpec { len, every })) => (
+                quote! { let mut __window = mmg_microbus::window::__Windower::<#ty>::new(&ctx_c, #len, #every); },
+                quote! { __window.push(env); },
+                quote! {
+                    env = __window.close() => { #due_body }
+                },
+                // 停机或订阅关闭：进行中的窗口提前关闭并处理
+                quote! {
+                    if let Some(env) = __window.flush() { #due_body }
+                },
+            ),
+            (None, None) => Default::default(),
+        };
+
+        // 窗口 handler 的消息分支只入缓存，调用发生在窗口关闭分支
+        let msg_body = if ms.window.is_some() {
+            quote! {}
+        } else {
+            quote! {
+                let this=&this_c;
+                #track_begin
+                let __t0 = mmg_microbus::component::__handler_begin(&ctx_c);
+                { #expr }
+                mmg_microbus::component::__handler_end(&ctx_c, #method_name, std::any::type_name::<#ty>(), __t0);
+                #track_end
+                __budget.spend().await;
+            }
+        };
+
+        // `emit = ..`：窗口 handler 声明的发布类型须与返回类型一致
+        let emit_check = match (&ms.emit, &ms.published) {
+            (Some(emit), Some(published)) => quote! { let _: fn(#published) -> #emit = |__x| __x; },
+            _ => quote! {},
+        };
+
+        // 采样先于节流：未被抽中的消息直接跳过并计数
+        let (sample_decl, sample_check) = match ms.sample {
+            None => Default::default(),
+            Some(sample) => {
+                let ctor = match sample {
+                    Sample::Every(n) => {
+                        quote! { every(&ctx_c, #method_name, std::any::type_name::<#ty>(), #n) }
+                    }
+                    Sample::Ratio(p) => {
+                        quote! { ratio(&ctx_c, #method_name, std::any::type_name::<#ty>(), #p) }
+                    }
+                };
+                (
+                    quote! { let mut __sampler = mmg_microbus::component::__Sampler::#ctor; },
+                    quote! { if !__sampler.admit() { continue; } },
+                )
+            }
         };
-        let expr = gen_ret_case_tokens(
-            "handle returned error",
-            &core,
-            &ms.ret_case,
-            false,
-            &quote! {ctx_c},
-        );
 
-        // 通用 worker 模板：停机 select + 消息循环
+        // 公平调度：连续处理满预算后让出执行权（`Topology::handler_budget` 未配置时不生效）
+        let weight = ms.weight;
+
+        // 通用 worker 模板：停机 select + 控制优先的消息循环（暂停期间不取消息）
+        // 每个 handler 一个 worker、逐条串行处理；`in_order` handler 依赖这一点，并发 / 批处理类优化须将其排除
         let spawn_token = quote! {
+            #emit_check
             let this_c = this.clone();
             let ctx_c = ctx.__fork();
+            #track_decl
             let mut sub = #sub_var;
-            let __jh = tokio::spawn(async move {
+            let __jh = mmg_microbus::rt::spawn(async move {
+                let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+                let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, #weight);
+                #sample_decl
+                #pace_decl
                 loop {
-                    tokio::select! {
+                    mmg_microbus::rt::select! {
                         _ = mmg_microbus::component::__recv_stop(&ctx_c) => { break; }
-                        msg = sub.recv() => {
+                        msg = mmg_microbus::component::__recv_controlled(&mut __ctl, &mut sub) => {
                             match msg {
-                                Some(env) => { let this=&this_c; { #expr } }
+                                Some(env) => {
+                                    #sample_check
+                                    #offer
+                                    #msg_body
+                                }
                                 None => break,
                             }
                         }
+                        #due_arm
                     }
                 }
+                #tail
             });
             __workers.push(__jh);
         };
         handle_spawns.push(spawn_token);
+
+        // 直接调度（__dispatch）：与 worker 相同的调用与返回值发布，按声明顺序依次匹配
+        let detach = if ms.envelope {
+            quote! { let env = mmg_microbus::bus::Envelope::__detached(env); }
+        } else if ms.window.is_some() {
+            quote! { let env = mmg_microbus::window::Window::__single(env); }
+        } else {
+            quote! {}
+        };
+        // 信箱模式下 `&mut self` handler 无法经 `&self` 直接调度
+        if actor && ms.mut_self {
+            continue;
+        }
+        let expr = if actor {
+            handle_call_tokens(ms, &direct)
+        } else {
+            expr
+        };
+        dispatch_arms.push(quote! {
+            if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<#ty>() {
+                #detach
+                let this = self;
+                let ctx_c = ctx;
+                { #expr }
+                __handled = true;
+            }
+        });
+    }
+    // 局部作用域先于 init 建立：init 返回的局部类型同样只到达本组件
+    let local_tys = methods.iter().filter(|m| m.local).map(|m| &m.msg_ty);
+    let local_scope = if local_decls.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            mmg_microbus::component::__local_scope(&mut ctx, vec![ #( std::any::TypeId::of::<#local_tys>() ),* ]);
+            #( #local_decls )*
+            mmg_microbus::component::__seal_local(&ctx);
+        }
+    };
+    HandleParts {
+        local_scope,
+        sub_decls,
+        handle_spawns,
+        dispatch_arms,
     }
-    (sub_decls, handle_spawns)
+}
+
+// 信箱模式的单次调用：持有信箱期间独占 `&mut Self`，调用结束即释放（返回值发布不占信箱）
+pub fn mailbox_call(direct: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        async {
+            let mut __mail = this.lock().await;
+            let this = &mut *__mail;
+            #direct.await
+        }
+    }
+}
+
+// 单次调用：panic 捕获、重试、出错策略与返回值发布；`call` 为调用 future 表达式
+fn handle_call_tokens(
+    ms: &MethodSpec,
+    call: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let ty = &ms.msg_ty;
+    let method_name = ms.ident.to_string();
+    // 出错 / panic 上报的消息：窗口 handler 为整批
+    let (report_ty, report_msg) = if ms.window.is_some() {
+        (
+            quote! { mmg_microbus::window::Window<#ty> },
+            quote! { &env },
+        )
+    } else {
+        (quote! { #ty }, quote! { &*env })
+    };
+    // panic 捕获：结果为 Result<方法返回值, panic 载荷>；重试时同一消息再调用至多 n 次（返回 Err 或 panic 均计入），仍失败时按 ignore 处理
+    let guarded = if let OnError::Retry(n) = ms.on_error {
+        quote! {
+            (async {
+                let mut __attempt = 0u32;
+                loop {
+                    match mmg_microbus::component::__invoke::<#ty, _>(&ctx_c, #call).await {
+                        Ok(Err(e)) if __attempt < #n => {
+                            __attempt += 1;
+                            Self::__microbus_log_retry(__attempt, Some(&e));
+                        }
+                        Err(_) if __attempt < #n => {
+                            __attempt += 1;
+                            Self::__microbus_log_retry(__attempt, None);
+                        }
+                        __r => break __r,
+                    }
+                }
+            })
+        }
+    } else {
+        quote! { mmg_microbus::component::__invoke::<#ty, _>(&ctx_c, #call) }
+    };
+    let policy = match ms.on_error {
+        OnError::Ignore | OnError::Retry(_) => quote! {},
+        OnError::StopComponent => {
+            quote! { mmg_microbus::component::__stop_component(&ctx_c, #method_name); }
+        }
+        OnError::StopApp => {
+            quote! { mmg_microbus::component::__stop_app(&ctx_c, #method_name); }
+        }
+    };
+    let report = quote! {
+        if let Some(__ev) = mmg_microbus::component::__handler_error::<#report_ty>(&ctx_c, #method_name, #report_msg, &e) {
+            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+        }
+        #policy
+    };
+    let on_output = gen_ret_case_tokens(
+        "handle returned error",
+        &quote! { std::future::ready(__out) },
+        &ms.ret_case,
+        false,
+        &quote! {ctx_c},
+        Some(&report),
+    );
+    // 并发许可只覆盖方法调用：返回值发布可能因背压等待，持有许可会与下游 handler 互等
+    let expr = quote! {
+        let __res = {
+            let _permit = mmg_microbus::component::__handler_permit(&ctx_c).await;
+            #guarded.await
+        };
+        match __res {
+            Ok(__out) => { #on_output }
+            Err(__panic) => {
+                if let Some(__ev) = mmg_microbus::component::__handler_panicked::<#report_ty>(&ctx_c, #method_name, #report_msg, &*__panic) {
+                    mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                }
+                #policy
+            }
+        }
+    };
+    expr
 }
@@ -2,21 +2,25 @@ use quote::quote;
 
 use super::analyze::RetCase;
 
-// 单一职责：根据返回值分类生成处理 token
+// 单一职责：根据返回值分类生成处理 token；`report` 在不中止时追加于 warn 之后（错误绑定为 `e`，按 `Display` 记录）
 pub fn gen_ret_case_tokens(
     phase: &str,
     call_core: &proc_macro2::TokenStream,
     rc: &RetCase,
     abort_on_error: bool,
     ctx_ident: &proc_macro2::TokenStream,
+    report: Option<&proc_macro2::TokenStream>,
 ) -> proc_macro2::TokenStream {
+    // 出错分支只生成一次函数调用（tracing 宏在每个组件生成的日志函数中展开一次）
+    let warn = quote! { Self::__microbus_log_error(#phase, &e); #report };
+    let abort = quote! { return Err(Self::__microbus_init_failed(&#ctx_ident, #phase, e)); };
     match rc {
         RetCase::Unit => quote! { let _ = #call_core.await; },
         RetCase::ResultUnit => {
             if abort_on_error {
-                quote! { if let Err(e)=#call_core.await { tracing::error!(error=?e,#phase); mmg_microbus::component::__startup_mark_failed(&#ctx_ident); return Err(e);} }
+                quote! { if let Err(e)=#call_core.await { #abort } }
             } else {
-                quote! { if let Err(e)=#call_core.await { tracing::warn!(error=?e,#phase); } }
+                quote! { if let Err(e)=#call_core.await { #warn } }
             }
         }
         RetCase::Some => {
@@ -27,16 +31,16 @@ pub fn gen_ret_case_tokens(
         }
         RetCase::ResultSome => {
             if abort_on_error {
-                quote! { match #call_core.await { Ok(v)=> mmg_microbus::component::__publish_auto(&#ctx_ident,v).await, Err(e)=>{tracing::error!(error=?e,#phase); mmg_microbus::component::__startup_mark_failed(&#ctx_ident); return Err(e);} } }
+                quote! { match #call_core.await { Ok(v)=> mmg_microbus::component::__publish_auto(&#ctx_ident,v).await, Err(e)=>{ #abort } } }
             } else {
-                quote! { match #call_core.await { Ok(v)=> mmg_microbus::component::__publish_auto(&#ctx_ident,v).await, Err(e)=>{tracing::warn!(error=?e,#phase);} } }
+                quote! { match #call_core.await { Ok(v)=> mmg_microbus::component::__publish_auto(&#ctx_ident,v).await, Err(e)=>{ #warn } } }
             }
         }
         RetCase::ResultOption => {
             if abort_on_error {
-                quote! { match #call_core.await { Ok(opt)=> if let Some(v)=opt { mmg_microbus::component::__publish_auto(&#ctx_ident,v).await }, Err(e)=>{tracing::error!(error=?e,#phase); mmg_microbus::component::__startup_mark_failed(&#ctx_ident); return Err(e);} } }
+                quote! { match #call_core.await { Ok(opt)=> if let Some(v)=opt { mmg_microbus::component::__publish_auto(&#ctx_ident,v).await }, Err(e)=>{ #abort } } }
             } else {
-                quote! { match #call_core.await { Ok(opt)=> if let Some(v)=opt { mmg_microbus::component::__publish_auto(&#ctx_ident,v).await }, Err(e)=>{tracing::warn!(error=?e,#phase);} } }
+                quote! { match #call_core.await { Ok(opt)=> if let Some(v)=opt { mmg_microbus::component::__publish_auto(&#ctx_ident,v).await }, Err(e)=>{ #warn } } }
             }
         }
         RetCase::Erased => {
@@ -63,16 +67,16 @@ pub fn gen_ret_case_tokens(
         }
         RetCase::ResultAnyBox => {
             if abort_on_error {
-                quote! { match #call_core.await { Ok(__b)=> mmg_microbus::component::__publish_any_box(&#ctx_ident,__b).await, Err(e)=>{tracing::error!(error=?e,#phase); mmg_microbus::component::__startup_mark_failed(&#ctx_ident); return Err(e);} } }
+                quote! { match #call_core.await { Ok(__b)=> mmg_microbus::component::__publish_any_box(&#ctx_ident,__b).await, Err(e)=>{ #abort } } }
             } else {
-                quote! { match #call_core.await { Ok(__b)=> mmg_microbus::component::__publish_any_box(&#ctx_ident,__b).await, Err(e)=>{tracing::warn!(error=?e,#phase);} } }
+                quote! { match #call_core.await { Ok(__b)=> mmg_microbus::component::__publish_any_box(&#ctx_ident,__b).await, Err(e)=>{ #warn } } }
             }
         }
         RetCase::ResultAnyArc => {
             if abort_on_error {
-                quote! { match #call_core.await { Ok(__a)=> mmg_microbus::component::__publish_any_arc(&#ctx_ident,__a).await, Err(e)=>{tracing::error!(error=?e,#phase); mmg_microbus::component::__startup_mark_failed(&#ctx_ident); return Err(e);} } }
+                quote! { match #call_core.await { Ok(__a)=> mmg_microbus::component::__publish_any_arc(&#ctx_ident,__a).await, Err(e)=>{ #abort } } }
             } else {
-                quote! { match #call_core.await { Ok(__a)=> mmg_microbus::component::__publish_any_arc(&#ctx_ident,__a).await, Err(e)=>{tracing::warn!(error=?e,#phase);} } }
+                quote! { match #call_core.await { Ok(__a)=> mmg_microbus::component::__publish_any_arc(&#ctx_ident,__a).await, Err(e)=>{ #warn } } }
             }
         }
     }
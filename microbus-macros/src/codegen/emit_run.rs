@@ -1,4 +1,4 @@
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use syn::{ItemImpl, ItemStruct};
 
 use super::analyze::{InitSpec, StopSpec};
@@ -23,10 +23,12 @@ pub fn build_init_stop_calls(
             &i.ret_case,
             true,
             &quote! {ctx},
+            None,
         );
         init_calls.push(quote! { { #expr } });
     }
     let mut stop_calls = Vec::new();
+    let warn = quote! { Self::__microbus_log_stop_error(&e); };
     for s in stops {
         let ident = &s.ident;
         let core = if s.wants_ctx {
@@ -37,7 +39,7 @@ pub fn build_init_stop_calls(
         let expr = match &s.ret_case {
             super::analyze::RetCase::Unit => quote! { let _ = #core; },
             super::analyze::RetCase::ResultUnit => {
-                quote! { match #core { Ok(()) => {}, Err(e) => { tracing::warn!(error=?e, "stop returned error"); } } }
+                quote! { match #core { Ok(()) => {}, Err(e) => { #warn } } }
             }
             super::analyze::RetCase::Some => {
                 quote! { { let __v = #core; mmg_microbus::component::__publish_auto(&ctx, __v).await; } }
@@ -46,10 +48,10 @@ pub fn build_init_stop_calls(
                 quote! { { if let Some(__v) = #core { mmg_microbus::component::__publish_auto(&ctx, __v).await; } } }
             }
             super::analyze::RetCase::ResultSome => {
-                quote! { match #core { Ok(v) => { mmg_microbus::component::__publish_auto(&ctx, v).await; }, Err(e) => { tracing::warn!(error=?e, "stop returned error"); } } }
+                quote! { match #core { Ok(v) => { mmg_microbus::component::__publish_auto(&ctx, v).await; }, Err(e) => { #warn } } }
             }
             super::analyze::RetCase::ResultOption => {
-                quote! { match #core { Ok(opt) => { if let Some(v) = opt { mmg_microbus::component::__publish_auto(&ctx, v).await; } }, Err(e) => { tracing::warn!(error=?e, "stop returned error"); } } }
+                quote! { match #core { Ok(opt) => { if let Some(v) = opt { mmg_microbus::component::__publish_auto(&ctx, v).await; } }, Err(e) => { #warn } } }
             }
             super::analyze::RetCase::Erased => {
                 quote! { { let __e = #core; mmg_microbus::component::__publish_erased(&ctx,__e).await; } }
@@ -73,10 +75,10 @@ pub fn build_init_stop_calls(
                 quote! { { if let Some(__a)=#core { mmg_microbus::component::__publish_any_arc(&ctx,__a).await; } } }
             }
             super::analyze::RetCase::ResultAnyBox => {
-                quote! { match #core { Ok(__b)=> mmg_microbus::component::__publish_any_box(&ctx,__b).await, Err(e)=>{ tracing::warn!(error=?e, "stop returned error"); } } }
+                quote! { match #core { Ok(__b)=> mmg_microbus::component::__publish_any_box(&ctx,__b).await, Err(e)=>{ #warn } } }
             }
             super::analyze::RetCase::ResultAnyArc => {
-                quote! { match #core { Ok(__a)=> mmg_microbus::component::__publish_any_arc(&ctx,__a).await, Err(e)=>{ tracing::warn!(error=?e, "stop returned error"); } } }
+                quote! { match #core { Ok(__a)=> mmg_microbus::component::__publish_any_arc(&ctx,__a).await, Err(e)=>{ #warn } } }
             }
         };
         stop_calls.push(quote! { { #expr } });
@@ -85,12 +87,18 @@ pub fn build_init_stop_calls(
 }
 
 pub struct RunParts {
+    pub actor: bool, // 信箱模式：`this` 为 `Arc<__Mailbox<Self>>`
     pub init_calls: Vec<proc_macro2::TokenStream>,
     pub stop_calls: Vec<proc_macro2::TokenStream>,
+    pub local_scope: proc_macro2::TokenStream,
     pub sub_decls: Vec<proc_macro2::TokenStream>,
     pub handle_spawns: Vec<proc_macro2::TokenStream>,
+    pub dispatch_arms: Vec<proc_macro2::TokenStream>,
     pub active_spawns: Vec<proc_macro2::TokenStream>,
     pub once_calls: Vec<proc_macro2::TokenStream>,
+    pub snapshot: super::emit_snapshot::SnapshotParts,
+    pub wiring: proc_macro2::TokenStream,
+    pub contract: syn::Attribute,
     pub compile_errors: Vec<proc_macro2::TokenStream>,
 }
 
@@ -101,56 +109,158 @@ pub fn gen_component_run(
     item: &ItemImpl,
 ) -> proc_macro2::TokenStream {
     let RunParts {
+        actor,
         init_calls,
         stop_calls,
+        local_scope,
         sub_decls,
         handle_spawns,
+        dispatch_arms,
         active_spawns,
         once_calls,
+        snapshot,
+        wiring,
+        contract,
         compile_errors,
     } = parts;
-    // run 本体：阶段顺序：init -> 订阅声明 -> startup barrier -> once -> workers -> 等待 stop -> 立刻调用 stop 钩子（不等待 worker）
+    let super::emit_snapshot::SnapshotParts {
+        restore_call,
+        activity_decl,
+        saver_spawn,
+        final_save,
+    } = snapshot;
+    // run 本体：阶段顺序：局部作用域 -> init -> 快照恢复 -> 订阅声明 -> startup barrier -> once -> workers -> 等待 stop -> 最终快照 -> 立刻调用 stop 钩子（不等待 worker）
+    let (wrap, stop_lock) = if *actor {
+        (
+            quote! { mmg_microbus::component::__Mailbox::new(this) },
+            quote! { let mut __mail = this.lock().await; let this = &mut *__mail; },
+        )
+    } else {
+        (quote! { this }, quote! {})
+    };
+    let stop_lock = if stop_calls.is_empty() {
+        quote! {}
+    } else {
+        stop_lock
+    };
+    // 含 active 的组件为数据源（自然完成检测）：屏障前登记，once 执行完毕、循环任务派生后释放本地句柄
+    let (source_decl, source_release) = if active_spawns.is_empty() && once_calls.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        (
+            quote! { let __source = mmg_microbus::component::__register_source(&ctx); },
+            quote! { drop(__source); },
+        )
+    };
     let run_impl = quote! {
         #[async_trait::async_trait]
         impl mmg_microbus::component::Component for #self_ty {
             async fn run(self: Box<Self>, mut ctx: mmg_microbus::component::ComponentContext) -> mmg_microbus::error::Result<()> {
-                let mut this=*self; #( #init_calls )* let this=std::sync::Arc::new(this);
+                #local_scope
+                let mut this=*self; #( #init_calls )* #restore_call let this=std::sync::Arc::new(#wrap);
+                #activity_decl
                 #( #sub_decls )*
+                #source_decl
                 mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
                 { #( #once_calls )* }
-                let mut __workers:Vec<tokio::task::JoinHandle<()>>=Vec::new();
+                let mut __workers:Vec<mmg_microbus::rt::JoinHandle<()>>=Vec::new();
                 #( #handle_spawns )*
                 #( #active_spawns )*
+                #source_release
+                #saver_spawn
                 mmg_microbus::component::__recv_stop(&ctx).await;
+                #final_save
                 // 同步停机契约：收到 stop 后立即执行 stop 钩子，不等待任何 worker 结束
+                #stop_lock
                 #( #stop_calls )*
                 Ok(())
             }
+            #[doc(hidden)]
+            async fn __dispatch(&self, ctx: &mmg_microbus::component::ComponentContext, msg: std::sync::Arc<dyn std::any::Any + Send + Sync>) -> bool {
+                let mut __handled = false;
+                #( #dispatch_arms )*
+                __handled
+            }
+            #[doc(hidden)]
+            #wiring
         }
     };
+    let log_fns = gen_log_fns(self_ty);
     let mut errs_ts = proc_macro2::TokenStream::new();
     for e in compile_errors {
         errs_ts.extend(e.clone());
     }
-    quote! { #item #run_impl #errs_ts }
+    // 原 impl 块的 doc 注释末尾追加消息契约（已有文档时空行分段）
+    let mut item = item.clone();
+    if item.attrs.iter().any(|a| a.path().is_ident("doc")) {
+        item.attrs.push(syn::parse_quote!(#[doc = ""]));
+    }
+    item.attrs.push(contract.clone());
+    quote! { #item #run_impl #log_fns #errs_ts }
+}
+
+// 出错日志：每个组件展开一份 tracing 调用点，各出错分支只生成函数调用；
+// 调用点位于组件所在模块，日志 target 仍为该模块路径（按业务模块过滤照常生效）
+fn gen_log_fns(self_ty: &syn::Type) -> proc_macro2::TokenStream {
+    quote! {
+        #[allow(dead_code)]
+        impl #self_ty {
+            fn __microbus_log_error(phase: &'static str, e: &dyn std::fmt::Display) {
+                mmg_microbus::__tracing::warn!(error=%e, "{phase}");
+            }
+            fn __microbus_init_failed<E: std::fmt::Debug + Into<mmg_microbus::error::BoxError>>(
+                ctx: &mmg_microbus::component::ComponentContext,
+                phase: &'static str,
+                e: E,
+            ) -> mmg_microbus::error::MicrobusError {
+                mmg_microbus::__tracing::error!(error=?e, "{phase}");
+                mmg_microbus::component::__init_failed(ctx, e)
+            }
+            fn __microbus_log_stop_error(e: &dyn std::fmt::Debug) {
+                mmg_microbus::__tracing::warn!(error=?e, "stop returned error");
+            }
+            fn __microbus_log_retry(attempt: u32, e: Option<&dyn std::fmt::Display>) {
+                match e {
+                    Some(e) => mmg_microbus::__tracing::warn!(error=%e, attempt, "handle returned error; retrying"),
+                    None => mmg_microbus::__tracing::warn!(attempt, "handle panicked; retrying"),
+                }
+            }
+        }
+    }
 }
 
 // struct 派生入口（维持原始语义）
 pub fn component_for_struct(
     item: &ItemStruct,
-    _args: proc_macro2::TokenStream,
+    args: proc_macro2::TokenStream,
 ) -> proc_macro2::TokenStream {
+    let requires = match super::parse::parse_component_args(args) {
+        Ok(r) => r,
+        Err(e) => return e.to_compile_error(),
+    };
+    // 依赖经 `__requires::<C>()` 限定为组件类型；无依赖时不生成，沿用 trait 默认实现
+    let requires_fn = (!requires.is_empty()).then(|| {
+        quote! {
+            fn requires(&self) -> Vec<&'static str> {
+                vec![ #( mmg_microbus::component::__requires::<#requires>() ),* ]
+            }
+        }
+    });
     let struct_ident = &item.ident;
     let factory_ident = format_ident!("__{}Factory", struct_ident);
-    let default_assert_ident = format_ident!("__AssertDefaultFor{}", struct_ident);
+    // 构造经带 on_unimplemented 的辅助 trait，缺少 Default 时错误落在结构体名上并给出修复提示
+    let construct = quote_spanned! { struct_ident.span()=>
+        <#struct_ident as mmg_microbus::component::__ComponentDefault>::__component_default()
+    };
     quote! {
         #item
-        trait #default_assert_ident { fn __assert_default(){ let _ = <#struct_ident as Default>::default(); } }
         #[doc(hidden)] #[derive(Default)] struct #factory_ident;
         #[async_trait::async_trait]
         impl mmg_microbus::component::ComponentFactory for #factory_ident {
             fn type_name(&self)->&'static str { std::any::type_name::<#struct_ident>() }
-            async fn build(&self,_bus: mmg_microbus::bus::BusHandle)-> mmg_microbus::error::Result<Box<dyn mmg_microbus::component::Component>> { Ok(Box::new(<#struct_ident as Default>::default())) }
+            fn wiring(&self) -> Option<mmg_microbus::wiring::Wiring> { <#struct_ident as mmg_microbus::component::Component>::__wiring() }
+            #requires_fn
+            async fn build(&self,_bus: mmg_microbus::bus::BusHandle)-> mmg_microbus::error::Result<Box<dyn mmg_microbus::component::Component>> { Ok(Box::new(#construct)) }
         }
         #[doc(hidden)] const _: () = {
             fn __create_factory_for() -> Box<dyn mmg_microbus::component::ComponentFactory> { Box::new(#factory_ident::default()) }
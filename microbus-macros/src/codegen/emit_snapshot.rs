@@ -0,0 +1,39 @@
+use quote::quote;
+
+use super::analyze::SnapshotSpec;
+
+#[derive(Default)]
+pub struct SnapshotParts {
+    pub restore_call: proc_macro2::TokenStream,
+    pub activity_decl: proc_macro2::TokenStream,
+    pub saver_spawn: proc_macro2::TokenStream,
+    pub final_save: proc_macro2::TokenStream,
+}
+
+// 快照钩子：init 之后恢复、屏障之后派生周期保存、stop 钩子之前最终保存
+pub fn build_snapshot_parts(spec: Option<&SnapshotSpec>) -> SnapshotParts {
+    let Some(SnapshotSpec { save, restore }) = spec else {
+        return SnapshotParts::default();
+    };
+    SnapshotParts {
+        restore_call: quote! {
+            match mmg_microbus::snapshot::__restore(&ctx) {
+                Ok(Some(__s)) => this.#restore(__s),
+                Ok(None) => {}
+                Err(e) => return Err(Self::__microbus_init_failed(&ctx, "snapshot restore failed", e)),
+            }
+        },
+        activity_decl: quote! { let __activity = mmg_microbus::snapshot::__Activity::new(); },
+        saver_spawn: quote! {
+            let this_c = this.clone();
+            let ctx_c = ctx.__fork();
+            let __activity_c = __activity.clone();
+            __workers.push(mmg_microbus::rt::spawn(async move {
+                mmg_microbus::snapshot::__save_periodically(&ctx_c, &__activity_c, || this_c.#save()).await;
+            }));
+        },
+        final_save: quote! {
+            mmg_microbus::snapshot::__save_final(&ctx, &__activity, || this.#save()).await;
+        },
+    }
+}
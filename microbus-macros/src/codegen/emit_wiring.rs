@@ -0,0 +1,143 @@
+use quote::quote;
+
+use super::analyze::{ActiveSpec, InitSpec, MethodSpec, StopSpec};
+
+// 装配清单（`Component::__wiring`）：订阅类型与静态可知的发布类型，供 `App::validate` 检查无人发布的订阅
+pub fn build_wiring(
+    methods: &[MethodSpec],
+    actives: &[ActiveSpec],
+    inits: &[InitSpec],
+    stops: &[StopSpec],
+) -> proc_macro2::TokenStream {
+    // `Ask<Q, A>` 由查询方在方法体内发布（`ctx.query`），无法静态判定，不列入订阅清单；
+    // `scope = local` 的订阅与对应类型的发布只在组件内部流转；弱订阅不计为消费者，也不要求有发布方
+    let subscribes = methods
+        .iter()
+        .filter(|m| !m.local && !m.weak && !is_ask(&m.msg_ty))
+        .map(|m| &m.msg_ty);
+    let locals: Vec<_> = methods
+        .iter()
+        .filter(|m| m.local)
+        .map(|m| &m.msg_ty)
+        .collect();
+    let outputs = methods
+        .iter()
+        .map(|m| (&m.published, &m.ret_case))
+        .chain(actives.iter().map(|a| (&a.published, &a.ret_case)))
+        .chain(inits.iter().map(|i| (&i.published, &i.ret_case)))
+        .chain(stops.iter().map(|s| (&s.published, &s.ret_case)));
+    let mut publishes = Vec::new();
+    let mut dynamic = false;
+    for (published, ret_case) in outputs {
+        dynamic |= ret_case.is_dynamic();
+        publishes.extend(published.iter());
+    }
+    let publishes_decl = if locals.is_empty() {
+        quote! { let publishes = vec![ #( mmg_microbus::wiring::MessageType::of::<#publishes>() ),* ]; }
+    } else {
+        quote! {
+            let __local = [ #( std::any::TypeId::of::<#locals>() ),* ];
+            let mut publishes = vec![ #( mmg_microbus::wiring::MessageType::of::<#publishes>() ),* ];
+            publishes.retain(|t| !__local.contains(&t.id));
+        }
+    };
+    quote! {
+        fn __wiring() -> Option<mmg_microbus::wiring::Wiring> where Self: Sized {
+            #publishes_decl
+            Some(mmg_microbus::wiring::Wiring {
+                subscribes: vec![ #( mmg_microbus::wiring::MessageType::of::<#subscribes>() ),* ],
+                publishes,
+                dynamic: #dynamic,
+            })
+        }
+    }
+}
+
+// 消息契约文档：追加到组件 impl 块的 doc 注释，rustdoc 中即为装配参考
+pub fn contract_docs(
+    methods: &[MethodSpec],
+    actives: &[ActiveSpec],
+    inits: &[InitSpec],
+    stops: &[StopSpec],
+) -> syn::Attribute {
+    let mut subscribes = Vec::new();
+    for m in methods {
+        let label = if m.local {
+            format!("`{}` (local)", type_label(&m.msg_ty))
+        } else if m.weak {
+            format!("`{}` (weak)", type_label(&m.msg_ty))
+        } else {
+            format!("`{}`", type_label(&m.msg_ty))
+        };
+        if !subscribes.contains(&label) {
+            subscribes.push(label);
+        }
+    }
+    let locals: Vec<_> = methods
+        .iter()
+        .filter(|m| m.local)
+        .map(|m| type_label(&m.msg_ty))
+        .collect();
+    let mut publishes = Vec::new();
+    let mut dynamic = false;
+    let outputs = methods
+        .iter()
+        .map(|m| (&m.published, &m.ret_case))
+        .chain(actives.iter().map(|a| (&a.published, &a.ret_case)))
+        .chain(inits.iter().map(|i| (&i.published, &i.ret_case)))
+        .chain(stops.iter().map(|s| (&s.published, &s.ret_case)));
+    for (published, ret_case) in outputs {
+        dynamic |= ret_case.is_dynamic();
+        if let Some(ty) = published {
+            let ty = type_label(ty);
+            let label = if locals.contains(&ty) {
+                format!("`{ty}` (local)")
+            } else {
+                format!("`{ty}`")
+            };
+            if !publishes.contains(&label) {
+                publishes.push(label);
+            }
+        }
+    }
+    if dynamic {
+        publishes.push("dynamic types".to_string());
+    }
+    let list = |v: Vec<String>| {
+        if v.is_empty() {
+            "none".to_string()
+        } else {
+            v.join(", ")
+        }
+    };
+    let line = format!(
+        " Subscribes: {}; Publishes: {}",
+        list(subscribes),
+        list(publishes)
+    );
+    syn::parse_quote!(#[doc = #line])
+}
+
+// 类型的紧凑书写：`Vec < u8 >` → `Vec<u8>`
+fn type_label(ty: &syn::Type) -> String {
+    let s = quote!(#ty).to_string();
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ' ' {
+            let prev = out.chars().last();
+            let next = chars.peek().copied();
+            let glue = matches!(prev, Some('<' | ':' | '&' | '('))
+                || matches!(next, Some('<' | '>' | ':' | ',' | ')'));
+            if glue {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn is_ask(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(tp) if tp.path.segments.last().is_some_and(|s| s.ident == "Ask"))
+}
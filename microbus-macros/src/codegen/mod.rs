@@ -1,53 +1,83 @@
 mod analyze;
+pub mod bus_message;
+pub mod component_default;
 mod emit_actives;
 mod emit_handles;
 mod emit_ret;
 mod emit_run;
+mod emit_snapshot;
+mod emit_wiring;
 mod msgs;
 mod parse;
 
-use proc_macro::TokenStream;
-use syn::{parse_macro_input, Item};
+use proc_macro2::TokenStream;
+use syn::Item;
 
-use analyze::{collect_actives, collect_handles, collect_inits, collect_stops};
+use analyze::{
+    collect_actives, collect_handles, collect_inits, collect_snapshot, collect_stops,
+    strip_role_conflicts,
+};
 use emit_actives::build_active_parts;
 use emit_handles::build_handle_parts;
 use emit_run::{build_init_stop_calls, component_for_struct, gen_component_run, RunParts};
-use msgs::ERR_COMPONENT_TARGET;
+use emit_snapshot::build_snapshot_parts;
+use emit_wiring::{build_wiring, contract_docs};
+use msgs::{ERR_ACTOR_SNAPSHOT, ERR_COMPONENT_TARGET};
 
-pub fn entrypoint(args: TokenStream, input: TokenStream) -> TokenStream {
-    let args_ts = proc_macro2::TokenStream::from(args);
-    let item_any = parse_macro_input!(input as Item);
+// 仅依赖 proc_macro2：宏入口之外（fuzz / 展开快照）亦可直接调用
+pub fn expand(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item_any = match syn::parse2::<Item>(input) {
+        Ok(item) => item,
+        Err(e) => return e.to_compile_error(),
+    };
     match item_any {
-        Item::Struct(item) => component_for_struct(&item, args_ts).into(),
+        Item::Struct(item) => component_for_struct(&item, args),
         Item::Impl(item) => {
+            let actor = match parse::parse_impl_args(args) {
+                Ok(a) => a,
+                Err(e) => return e.to_compile_error(),
+            };
             let self_ty = item.self_ty.clone();
-            let (methods, mut errs_h) = collect_handles(&item);
-            let (actives, mut errs_a) = collect_actives(&item);
-            let (inits, mut errs_i) = collect_inits(&item);
-            let (stops, mut errs_s) = collect_stops(&item);
-            let mut compile_errors = Vec::new();
+            let (scanned, errs_r) = strip_role_conflicts(&item);
+            let (methods, mut errs_h) = collect_handles(&scanned, actor);
+            let (actives, mut errs_a) = collect_actives(&scanned, actor);
+            let (inits, mut errs_i) = collect_inits(&scanned);
+            let (stops, mut errs_s) = collect_stops(&scanned, actor);
+            let (snapshot, mut errs_p) = collect_snapshot(&scanned);
+            let mut compile_errors = errs_r;
+            if actor && snapshot.is_some() {
+                compile_errors.push(
+                    syn::Error::new_spanned(&item.self_ty, ERR_ACTOR_SNAPSHOT).to_compile_error(),
+                );
+            }
             compile_errors.append(&mut errs_h);
             compile_errors.append(&mut errs_a);
             compile_errors.append(&mut errs_i);
             compile_errors.append(&mut errs_s);
+            compile_errors.append(&mut errs_p);
+            let wiring = build_wiring(&methods, &actives, &inits, &stops);
+            let contract = contract_docs(&methods, &actives, &inits, &stops);
             let (init_calls, stop_calls) = build_init_stop_calls(&inits, &stops);
-            let (sub_decls, handle_spawns) = build_handle_parts(&methods);
-            let (active_spawns, once_calls) = build_active_parts(&actives);
+            let handles = build_handle_parts(&methods, snapshot.is_some(), actor);
+            let (active_spawns, once_calls) = build_active_parts(&actives, actor);
             let parts = RunParts {
+                actor,
                 init_calls,
                 stop_calls,
-                sub_decls,
-                handle_spawns,
+                local_scope: handles.local_scope,
+                sub_decls: handles.sub_decls,
+                handle_spawns: handles.handle_spawns,
+                dispatch_arms: handles.dispatch_arms,
                 active_spawns,
                 once_calls,
+                snapshot: build_snapshot_parts(snapshot.as_ref()),
+                wiring,
+                contract,
                 compile_errors,
             };
-            gen_component_run(&self_ty, &parts, &item).into()
+            gen_component_run(&self_ty, &parts, &item)
         }
-        other => syn::Error::new_spanned(other, ERR_COMPONENT_TARGET)
-            .to_compile_error()
-            .into(),
+        other => syn::Error::new_spanned(other, ERR_COMPONENT_TARGET).to_compile_error(),
     }
 }
 // end of layered codegen module
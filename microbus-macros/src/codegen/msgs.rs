@@ -1,30 +1,69 @@
 // Centralized compile-time diagnostic & error string constants for the macro codegen layer.
 // Behavior-neutral refactor: only moves literal strings into named constants.
 
-pub(super) const ERR_HANDLE_NO_ARGS: &str = "#[handle] does not accept any arguments in this model";
+pub(super) const ERR_HANDLE_ARGS: &str =
+    "#[handle] only accepts: on_error = <policy>, latest, debounce = \"<duration>\", throttle = \"<n>/<unit>\", sample = <n | p>, local, scope = <local | global>, in_order, weak, weight = <n>, window = \"<duration>\", every = \"<duration>\", emit = <Type>";
+pub(super) const ERR_HANDLE_IN_ORDER: &str =
+    "#[handle(in_order)] guarantees every message in publish order; it cannot be combined with latest, debounce or throttle, which coalesce messages";
+pub(super) const ERR_HANDLE_WEAK: &str =
+    "#[handle(weak)] cannot be combined with in_order or local scope";
+pub(super) const ERR_HANDLE_SCOPE: &str = "scope must be one of: local, global";
+pub(super) const ERR_HANDLE_SAMPLE: &str =
+    "sample expects an integer N >= 1 (every Nth message) or a probability in (0, 1]";
+pub(super) const ERR_HANDLE_DEBOUNCE: &str =
+    "debounce expects a positive duration such as \"50ms\" (units: us, ms, s, min)";
+pub(super) const ERR_HANDLE_THROTTLE: &str =
+    "throttle expects a positive rate such as \"10/s\" (units: us, ms, s, min)";
+pub(super) const ERR_HANDLE_WEIGHT: &str =
+    "weight expects an integer N >= 1 (multiplies the handler's share of Topology::handler_budget)";
+pub(super) const ERR_HANDLE_WINDOW: &str =
+    "window expects a positive duration such as \"1s\" (units: us, ms, s, min)";
+pub(super) const ERR_HANDLE_WINDOW_EVERY: &str =
+    "every expects a positive duration no longer than window, such as \"200ms\" (sliding window hop)";
+pub(super) const ERR_HANDLE_WINDOW_OPTS: &str =
+    "every and emit only apply to #[handle(window = ..)]; window cannot be combined with latest, debounce, throttle or in_order";
+pub(super) const ERR_HANDLE_WINDOW_ARG: &str =
+    "#[handle(window = ..)] takes the batch as &Window<T>, and &Window<T> requires window = \"<duration>\"";
+pub(super) const ERR_HANDLE_PACE_BOTH: &str = "#[handle] accepts only one of debounce or throttle";
+pub(super) const ERR_HANDLE_ON_ERROR: &str =
+    "on_error must be one of: ignore, retry, retry(n), stop_component, stop_app";
+pub(super) const ERR_HANDLE_ON_ERROR_RESULT: &str =
+    "on_error other than ignore requires the #[handle] method to return Result";
 pub(super) const ERR_HANDLE_MULTI_ATTR: &str =
     "a method can only have one #[handle(...)] attribute";
 pub(super) const ERR_HANDLE_CTX_DUP: &str =
     "#[handle] allows at most one &ComponentContext parameter";
 pub(super) const ERR_HANDLE_NEED_ONE_T: &str =
-    "#[handle] requires exactly one &T parameter (message payload)";
+    "#[handle] requires exactly one &T, &Envelope<T> or &Window<T> parameter (message payload)";
 pub(super) const ERR_HANDLE_ONLY_ONE_T: &str =
-    "#[handle] allows only one &T parameter; remove extras";
+    "#[handle] allows only one &T, &Envelope<T> or &Window<T> parameter; remove extras";
 
 pub(super) const ERR_HANDLE_MUT_SELF: &str =
-    "#[handle] method cannot take &mut self under spawned worker model; use interior mutability";
+    "#[handle] method cannot take &mut self under spawned worker model; use interior mutability or #[component(actor)]";
 
 pub(super) const ERR_ACTIVE_MUT_SELF: &str =
-    "#[active] method cannot take &mut self; use interior mutability if needed";
+    "#[active] method cannot take &mut self; use interior mutability or #[component(actor)]";
 pub(super) const ERR_ACTIVE_CTX_DUP: &str =
     "#[active] allows at most one &ComponentContext parameter";
 pub(super) const ERR_ACTIVE_ONLY_CTX: &str = "#[active] method can only take &ComponentContext as parameter; other &T parameters are not allowed";
-pub(super) const ERR_ACTIVE_LIST_ONCE_ONLY: &str = "#[active] only supports (once)";
+pub(super) const ERR_ACTIVE_FLOW_BREAK: &str =
+    "#[active] ControlFlow return must be ControlFlow<(), T> (Break carries no value)";
+pub(super) const ERR_ACTIVE_FLOW_ONCE: &str =
+    "#[active(once)] cannot return ControlFlow; it already runs a single time";
+pub(super) const ERR_ACTIVE_ARGS: &str =
+    "#[active] only accepts: once, idle_backoff = \"<min>..<max>\"";
+pub(super) const ERR_ACTIVE_IDLE_BACKOFF: &str =
+    "idle_backoff expects a duration range such as \"1ms..100ms\" with min <= max (units: us, ms, s, min)";
+pub(super) const ERR_ACTIVE_BACKOFF_ONCE: &str =
+    "idle_backoff only applies to looping #[active], not #[active(once)]";
+pub(super) const ERR_ACTIVE_BACKOFF_RET: &str =
+    "idle_backoff requires the #[active] method to return Option<T> or Result<Option<T>> (None counts as idle)";
 pub(super) const ERR_ACTIVE_NO_NV: &str = "#[active] does not take name-value arguments";
 
 pub(super) const ERR_INIT_SIG: &str = "#[init] only allows optional &ComponentContext";
 
-pub(super) const ERR_STOP_MUT_SELF: &str = "#[stop] cannot take &mut self; use interior mutability";
+pub(super) const ERR_STOP_MUT_SELF: &str =
+    "#[stop] cannot take &mut self; use interior mutability or #[component(actor)]";
 pub(super) const ERR_STOP_CTX_DUP: &str = "#[stop] allows at most one &ComponentContext parameter";
 pub(super) const ERR_STOP_SIG: &str =
     "#[stop] method must take only self or optionally &self plus &ComponentContext";
@@ -32,4 +71,34 @@ pub(super) const ERR_STOP_SIG: &str =
 pub(super) const ERR_STOP_ASYNC_NOT_ALLOWED: &str =
     "#[stop] must be a synchronous function (do not mark it async)";
 
+pub(super) const ERR_SNAPSHOT_ARG: &str = "#[snapshot] only supports (restore)";
+pub(super) const ERR_SNAPSHOT_SAVE_SIG: &str =
+    "#[snapshot] method must be synchronous, take only &self and return the state";
+pub(super) const ERR_SNAPSHOT_RESTORE_SIG: &str = "#[snapshot(restore)] method must be synchronous, take &mut self plus the state by value and return nothing";
+pub(super) const ERR_SNAPSHOT_DUP: &str =
+    "a component can have only one #[snapshot] and one #[snapshot(restore)] method";
+pub(super) const ERR_SNAPSHOT_PAIR: &str =
+    "#[snapshot] and #[snapshot(restore)] must be declared together";
+
+pub(super) const ERR_ROLE_CONFLICT: &str =
+    "a method can carry only one of #[handle], #[active], #[init], #[stop], #[snapshot]; split it into separate methods";
+
 pub(super) const ERR_COMPONENT_TARGET: &str = "#[component] only supports struct or impl blocks";
+pub(super) const ERR_COMPONENT_ARGS: &str =
+    "#[component] on a struct only accepts: requires(<Component>, ...)";
+pub(super) const ERR_COMPONENT_IMPL_ARGS: &str =
+    "#[component] on an impl block only accepts: actor (put requires(...) on the struct)";
+pub(super) const ERR_ACTOR_SNAPSHOT: &str =
+    "#[component(actor)] does not support #[snapshot]; save state from an #[active] or #[stop] instead";
+
+pub(super) const ERR_COMPONENT_DEFAULT_TARGET: &str =
+    "#[derive(ComponentDefault)] only supports structs";
+
+pub(super) const ERR_BUS_MESSAGE_GENERIC: &str =
+    "#[derive(BusMessage)] requires a concrete type; wrap generic instantiations in a newtype";
+pub(super) const ERR_BUS_MESSAGE_ARG: &str =
+    "#[bus_message] only supports name = \"...\", version = N, compatible_from = M";
+pub(super) const ERR_BUS_MESSAGE_NAME_EMPTY: &str = "#[bus_message(name)] must not be empty";
+pub(super) const ERR_BUS_MESSAGE_VERSION_ZERO: &str = "#[bus_message(version)] must be at least 1";
+pub(super) const ERR_BUS_MESSAGE_COMPAT_RANGE: &str =
+    "#[bus_message(compatible_from)] must be between 1 and version";
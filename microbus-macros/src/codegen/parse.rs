@@ -1,4 +1,10 @@
-use super::msgs::{ERR_ACTIVE_LIST_ONCE_ONLY, ERR_ACTIVE_NO_NV};
+use super::msgs::{
+    ERR_ACTIVE_ARGS, ERR_ACTIVE_IDLE_BACKOFF, ERR_ACTIVE_NO_NV, ERR_COMPONENT_ARGS,
+    ERR_COMPONENT_IMPL_ARGS, ERR_HANDLE_ARGS, ERR_HANDLE_DEBOUNCE, ERR_HANDLE_IN_ORDER,
+    ERR_HANDLE_ON_ERROR, ERR_HANDLE_PACE_BOTH, ERR_HANDLE_SAMPLE, ERR_HANDLE_SCOPE,
+    ERR_HANDLE_THROTTLE, ERR_HANDLE_WEAK, ERR_HANDLE_WEIGHT, ERR_HANDLE_WINDOW,
+    ERR_HANDLE_WINDOW_EVERY, ERR_HANDLE_WINDOW_OPTS, ERR_SNAPSHOT_ARG,
+};
 use syn::{Attribute, Type};
 
 // 低层解析与判别辅助
@@ -27,10 +33,311 @@ pub fn parse_msg_arg_ref(ty: &syn::Type) -> Option<Type> {
     None
 }
 
-// #[handle] 不允许任何参数（保持当前模型简单）
+// `&Envelope<T>` 形式的消息参数：返回 T
 #[inline]
-pub fn parse_handle_attr(a: &Attribute) -> bool {
-    a.meta.require_path_only().is_err()
+pub fn parse_envelope_arg(ty: &syn::Type) -> Option<Type> {
+    parse_wrapped_arg(ty, "Envelope")
+}
+
+// `&Window<T>` 形式的批量参数（`#[handle(window = ..)]`）：返回 T
+#[inline]
+pub fn parse_window_arg(ty: &syn::Type) -> Option<Type> {
+    parse_wrapped_arg(ty, "Window")
+}
+
+fn parse_wrapped_arg(ty: &syn::Type, wrapper: &str) -> Option<Type> {
+    let syn::Type::Reference(r) = ty else {
+        return None;
+    };
+    let syn::Type::Path(tp) = &*r.elem else {
+        return None;
+    };
+    let seg = tp.path.segments.last()?;
+    if seg.ident != wrapper {
+        return None;
+    }
+    match &seg.arguments {
+        syn::PathArguments::AngleBracketed(ab) if ab.args.len() == 1 => match ab.args.first() {
+            Some(syn::GenericArgument::Type(t)) => Some(t.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// #[handle] 出错策略：`on_error = ignore | retry | retry(n) | stop_component | stop_app`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OnError {
+    Ignore,
+    Retry(u32),
+    StopComponent,
+    StopApp,
+}
+
+const DEFAULT_RETRIES: u32 = 3;
+
+// worker 节流：`debounce = "50ms"` 或 `throttle = "10/s"`（均归一为纳秒间隔）
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Pace {
+    Debounce(u64),
+    Throttle(u64),
+}
+
+// 窗口聚合：`window = "1s"`（滚动窗口）或另加 `every = "200ms"`（滑动窗口的步长），均为纳秒
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct WindowSpec {
+    pub len: u64,
+    pub every: u64,
+}
+
+// 采样：`sample = N`（每 N 条处理一条）或 `sample = 0.01`（按概率）
+#[derive(Clone, Copy)]
+pub enum Sample {
+    Every(u64),
+    Ratio(f64),
+}
+
+// #[handle(...)] 选项：逗号分隔，顺序不限
+#[derive(Clone)]
+pub struct HandleOpts {
+    pub on_error: OnError,
+    pub latest: bool, // 合并订阅：消费落后时只保留最新一条
+    pub pace: Option<Pace>,
+    pub sample: Option<Sample>,
+    pub local: bool,    // `local` / `scope = local`：只接收本组件自身的发布
+    pub weak: bool,     // 弱订阅：不计为消费者、从不施加背压
+    pub in_order: bool, // 顺序契约：逐条、按发布方 FIFO 处理，排除合并类选项
+    pub weight: u32,    // 公平调度权重：连续处理 `handler_budget × weight` 条后让出
+    pub window: Option<WindowSpec>,
+    pub emit: Option<Type>, // 窗口关闭时发布的类型（与返回值核对；动态返回时登记到装配清单）
+}
+
+impl Default for HandleOpts {
+    fn default() -> Self {
+        Self {
+            on_error: OnError::Ignore,
+            latest: false,
+            pace: None,
+            sample: None,
+            local: false,
+            weak: false,
+            in_order: false,
+            weight: 1,
+            window: None,
+            emit: None,
+        }
+    }
+}
+
+// 结构体上 `#[component(requires(A, B))]` 的参数：须先于本组件启动的组件类型
+pub fn parse_component_args(args: proc_macro2::TokenStream) -> syn::Result<Vec<Type>> {
+    let mut requires = Vec::new();
+    if args.is_empty() {
+        return Ok(requires);
+    }
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("requires") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            requires.extend(
+                content.parse_terminated(<Type as syn::parse::Parse>::parse, syn::Token![,])?,
+            );
+            Ok(())
+        } else {
+            Err(meta.error(ERR_COMPONENT_ARGS))
+        }
+    });
+    syn::parse::Parser::parse2(parser, args)?;
+    Ok(requires)
+}
+
+// impl 块上 `#[component(actor)]`：启用信箱模式
+pub fn parse_impl_args(args: proc_macro2::TokenStream) -> syn::Result<bool> {
+    let mut actor = false;
+    if args.is_empty() {
+        return Ok(actor);
+    }
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("actor") {
+            actor = true;
+            Ok(())
+        } else {
+            Err(meta.error(ERR_COMPONENT_IMPL_ARGS))
+        }
+    });
+    syn::parse::Parser::parse2(parser, args)?;
+    Ok(actor)
+}
+
+pub fn parse_handle_attr(a: &Attribute) -> syn::Result<HandleOpts> {
+    let mut opts = HandleOpts::default();
+    if matches!(a.meta, syn::Meta::Path(_)) {
+        return Ok(opts);
+    }
+    if let syn::Meta::NameValue(nv) = &a.meta {
+        return Err(syn::Error::new_spanned(nv, ERR_HANDLE_ARGS));
+    }
+    let (mut window_len, mut every) = (None, None);
+    a.parse_nested_meta(|meta| {
+        if meta.path.is_ident("on_error") {
+            let value: syn::Expr = meta.value()?.parse()?;
+            opts.on_error = parse_on_error(&value)
+                .ok_or_else(|| syn::Error::new_spanned(&value, ERR_HANDLE_ON_ERROR))?;
+        } else if meta.path.is_ident("latest") {
+            opts.latest = true;
+        } else if meta.path.is_ident("debounce") || meta.path.is_ident("throttle") {
+            let debounce = meta.path.is_ident("debounce");
+            let lit: syn::LitStr = meta.value()?.parse()?;
+            let pace = if debounce {
+                parse_duration_nanos(&lit.value())
+                    .map(Pace::Debounce)
+                    .ok_or_else(|| syn::Error::new_spanned(&lit, ERR_HANDLE_DEBOUNCE))?
+            } else {
+                parse_rate_interval_nanos(&lit.value())
+                    .map(Pace::Throttle)
+                    .ok_or_else(|| syn::Error::new_spanned(&lit, ERR_HANDLE_THROTTLE))?
+            };
+            if opts.pace.is_some() {
+                return Err(meta.error(ERR_HANDLE_PACE_BOTH));
+            }
+            opts.pace = Some(pace);
+        } else if meta.path.is_ident("sample") {
+            let lit: syn::Lit = meta.value()?.parse()?;
+            let sample = match &lit {
+                syn::Lit::Int(n) => n
+                    .base10_parse::<u64>()
+                    .ok()
+                    .filter(|&n| n >= 1)
+                    .map(Sample::Every),
+                syn::Lit::Float(p) => p
+                    .base10_parse::<f64>()
+                    .ok()
+                    .filter(|&p| p > 0.0 && p <= 1.0)
+                    .map(Sample::Ratio),
+                _ => None,
+            };
+            opts.sample =
+                Some(sample.ok_or_else(|| syn::Error::new_spanned(&lit, ERR_HANDLE_SAMPLE))?);
+        } else if meta.path.is_ident("weight") {
+            let lit: syn::LitInt = meta.value()?.parse()?;
+            opts.weight = lit
+                .base10_parse::<u32>()
+                .ok()
+                .filter(|&n| n >= 1)
+                .ok_or_else(|| syn::Error::new_spanned(&lit, ERR_HANDLE_WEIGHT))?;
+        } else if meta.path.is_ident("window") || meta.path.is_ident("every") {
+            let window = meta.path.is_ident("window");
+            let lit: syn::LitStr = meta.value()?.parse()?;
+            let nanos = parse_duration_nanos(&lit.value()).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &lit,
+                    if window {
+                        ERR_HANDLE_WINDOW
+                    } else {
+                        ERR_HANDLE_WINDOW_EVERY
+                    },
+                )
+            })?;
+            if window {
+                window_len = Some(nanos);
+            } else {
+                every = Some((nanos, lit));
+            }
+        } else if meta.path.is_ident("emit") {
+            opts.emit = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("in_order") {
+            opts.in_order = true;
+        } else if meta.path.is_ident("weak") {
+            opts.weak = true;
+        } else if meta.path.is_ident("local") {
+            opts.local = true;
+        } else if meta.path.is_ident("scope") {
+            let value: syn::Path = meta.value()?.parse()?;
+            opts.local = if value.is_ident("local") {
+                true
+            } else if value.is_ident("global") {
+                false
+            } else {
+                return Err(syn::Error::new_spanned(&value, ERR_HANDLE_SCOPE));
+            };
+        } else {
+            return Err(meta.error(ERR_HANDLE_ARGS));
+        }
+        Ok(())
+    })?;
+    if opts.in_order && (opts.latest || opts.pace.is_some()) {
+        return Err(syn::Error::new_spanned(a, ERR_HANDLE_IN_ORDER));
+    }
+    if opts.weak && (opts.in_order || opts.local) {
+        return Err(syn::Error::new_spanned(a, ERR_HANDLE_WEAK));
+    }
+    match (window_len, every) {
+        (Some(len), every) => {
+            let every = match every {
+                Some((n, lit)) if n > len => {
+                    return Err(syn::Error::new_spanned(lit, ERR_HANDLE_WINDOW_EVERY))
+                }
+                Some((n, _)) => n,
+                None => len,
+            };
+            if opts.latest || opts.pace.is_some() || opts.in_order {
+                return Err(syn::Error::new_spanned(a, ERR_HANDLE_WINDOW_OPTS));
+            }
+            opts.window = Some(WindowSpec { len, every });
+        }
+        (None, every) if every.is_some() || opts.emit.is_some() => {
+            return Err(syn::Error::new_spanned(a, ERR_HANDLE_WINDOW_OPTS));
+        }
+        (None, _) => {}
+    }
+    Ok(opts)
+}
+
+fn unit_nanos(unit: &str) -> Option<u64> {
+    Some(match unit {
+        "us" => 1_000,
+        "ms" => 1_000_000,
+        "s" => 1_000_000_000,
+        "min" => 60_000_000_000,
+        _ => return None,
+    })
+}
+
+// "50ms" / "2s" / "250us" / "1min" → 纳秒；须为正
+fn parse_duration_nanos(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit())?;
+    let n: u64 = s[..split].parse().ok()?;
+    let nanos = n.checked_mul(unit_nanos(s[split..].trim())?)?;
+    (nanos > 0).then_some(nanos)
+}
+
+// "10/s" / "100/min" → 相邻两次处理的最小间隔（纳秒）；须为正
+fn parse_rate_interval_nanos(s: &str) -> Option<u64> {
+    let (count, unit) = s.split_once('/')?;
+    let count: u64 = count.trim().parse().ok().filter(|&n| n > 0)?;
+    let per = unit_nanos(unit.trim())?;
+    (per >= count).then(|| per / count)
+}
+
+fn parse_on_error(value: &syn::Expr) -> Option<OnError> {
+    match value {
+        syn::Expr::Path(p) if p.path.is_ident("ignore") => Some(OnError::Ignore),
+        syn::Expr::Path(p) if p.path.is_ident("retry") => Some(OnError::Retry(DEFAULT_RETRIES)),
+        syn::Expr::Path(p) if p.path.is_ident("stop_component") => Some(OnError::StopComponent),
+        syn::Expr::Path(p) if p.path.is_ident("stop_app") => Some(OnError::StopApp),
+        syn::Expr::Call(call) if call.args.len() == 1 => match (&*call.func, &call.args[0]) {
+            (
+                syn::Expr::Path(p),
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(n),
+                    ..
+                }),
+            ) if p.path.is_ident("retry") => n.base10_parse().ok().map(OnError::Retry),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -39,7 +346,14 @@ pub enum ActiveKind {
     Once,
 }
 
-pub fn parse_active_kind(a: &Attribute) -> Option<syn::Result<ActiveKind>> {
+// #[active(...)] 选项：`once`、`idle_backoff = "1ms..100ms"`（空闲退避区间，纳秒）
+#[derive(Clone, Copy)]
+pub struct ActiveOpts {
+    pub kind: ActiveKind,
+    pub idle_backoff: Option<(u64, u64)>,
+}
+
+pub fn parse_active_attr(a: &Attribute) -> Option<syn::Result<ActiveOpts>> {
     let last = a
         .path()
         .segments
@@ -49,22 +363,64 @@ pub fn parse_active_kind(a: &Attribute) -> Option<syn::Result<ActiveKind>> {
     if last.as_str() != "active" {
         return None;
     }
+    let mut opts = ActiveOpts {
+        kind: ActiveKind::Loop,
+        idle_backoff: None,
+    };
     match &a.meta {
-        syn::Meta::Path(_) => Some(Ok(ActiveKind::Loop)),
-        syn::Meta::List(list_meta) => {
-            if list_meta.tokens.is_empty() {
-                return Some(Ok(ActiveKind::Loop));
-            }
-            let content = list_meta.tokens.to_string();
-            if content.trim() == "once" {
-                Some(Ok(ActiveKind::Once))
-            } else {
-                Some(Err(syn::Error::new_spanned(
-                    &list_meta.tokens,
-                    ERR_ACTIVE_LIST_ONCE_ONLY,
-                )))
-            }
-        }
+        syn::Meta::Path(_) => Some(Ok(opts)),
+        syn::Meta::List(list_meta) if list_meta.tokens.is_empty() => Some(Ok(opts)),
+        syn::Meta::List(_) => Some(
+            a.parse_nested_meta(|meta| {
+                if meta.path.is_ident("once") {
+                    opts.kind = ActiveKind::Once;
+                } else if meta.path.is_ident("idle_backoff") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    opts.idle_backoff =
+                        Some(parse_backoff_range(&lit.value()).ok_or_else(|| {
+                            syn::Error::new_spanned(&lit, ERR_ACTIVE_IDLE_BACKOFF)
+                        })?);
+                } else {
+                    return Err(meta.error(ERR_ACTIVE_ARGS));
+                }
+                Ok(())
+            })
+            .map(|()| opts),
+        ),
         syn::Meta::NameValue(nv) => Some(Err(syn::Error::new_spanned(nv, ERR_ACTIVE_NO_NV))),
     }
 }
+
+// "1ms..100ms" → (下限, 上限)；单个时长表示固定间隔
+fn parse_backoff_range(s: &str) -> Option<(u64, u64)> {
+    match s.split_once("..") {
+        Some((min, max)) => {
+            let (min, max) = (parse_duration_nanos(min)?, parse_duration_nanos(max)?);
+            (min <= max).then_some((min, max))
+        }
+        None => parse_duration_nanos(s).map(|d| (d, d)),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotKind {
+    Save,
+    Restore,
+}
+
+pub fn parse_snapshot_kind(a: &Attribute) -> Option<syn::Result<SnapshotKind>> {
+    if a.path()
+        .segments
+        .last()
+        .is_none_or(|s| s.ident != "snapshot")
+    {
+        return None;
+    }
+    match &a.meta {
+        syn::Meta::Path(_) => Some(Ok(SnapshotKind::Save)),
+        syn::Meta::List(list_meta) if list_meta.tokens.to_string().trim() == "restore" => {
+            Some(Ok(SnapshotKind::Restore))
+        }
+        other => Some(Err(syn::Error::new_spanned(other, ERR_SNAPSHOT_ARG))),
+    }
+}
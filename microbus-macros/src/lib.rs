@@ -1,20 +1,23 @@
 //! microbus-macros 宏入口（接口层）。
 //!
-//! 仅声明属性宏并把展开逻辑转发到 `gen.rs`；本文件不包含任何实现，以符合“接口与实现分离”约束。
+//! 仅声明属性宏并把展开逻辑转发到 `codegen`；本文件不包含任何实现，以符合“接口与实现分离”约束。
 //!
 //! 属性简述：
 //! - #[component] : struct => 工厂注册；impl => 生成 `Component::run`
-//! - #[handle]    : `(&ComponentContext? , &T)` -> 六类返回之一，自动发布
+//! - #[handle]    : `(&ComponentContext? , &T)` -> 六类返回之一，自动发布；`#[handle(on_error = ..)]` 出错策略
 //! - #[active]    : 主动逻辑；可 `#[active(once)]` 一次执行
 //! - #[init]      : 主循环前一次调用（无外部配置注入）
 //! - #[stop]      : 退出前一次调用
+//! - #[snapshot]  : 状态快照 `&self -> S`，配 `#[snapshot(restore)]` `(&mut self, S)`（特性 `snapshot`）
+//! - #[derive(BusMessage)] : 稳定消息名（`#[bus_message(name = "..")]` 覆盖，默认类型名）
+//! - #[derive(ComponentDefault)] : 逐字段 `Default`（`#[component_default(expr)]` 指定单个字段初值）
 
 use proc_macro::TokenStream;
 mod codegen; // 分层实现：parse / analyze / emit
 
 #[proc_macro_attribute]
 pub fn component(args: TokenStream, input: TokenStream) -> TokenStream {
-    codegen::entrypoint(args, input)
+    codegen::expand(args.into(), input.into()).into()
 }
 
 #[proc_macro_attribute]
@@ -32,7 +35,22 @@ pub fn stop(_args: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
 
+#[proc_macro_attribute]
+pub fn snapshot(_args: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
 #[proc_macro_attribute]
 pub fn active(_args: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
+
+#[proc_macro_derive(BusMessage, attributes(bus_message))]
+pub fn bus_message(input: TokenStream) -> TokenStream {
+    codegen::bus_message::expand_bus_message(input.into()).into()
+}
+
+#[proc_macro_derive(ComponentDefault, attributes(component_default))]
+pub fn component_default(input: TokenStream) -> TokenStream {
+    codegen::component_default::expand_component_default(input.into()).into()
+}
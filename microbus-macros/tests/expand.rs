@@ -0,0 +1,54 @@
+//! 宏展开快照：`tests/expand/<name>.rs` 为输入条目，`<name>.expanded.rs` 为 `#[component]` 展开结果（prettyplease 格式化）。
+//!
+//! 生成代码有意变更时以 `EXPAND_BLESS=1 cargo test -p microbus-macros --test expand` 重写快照并审阅 diff。
+
+#[allow(dead_code)]
+#[path = "../src/codegen/mod.rs"]
+mod codegen;
+
+use std::path::Path;
+
+fn expand_file(input: &Path) -> String {
+    let src = std::fs::read_to_string(input).unwrap();
+    let tokens = src.parse().unwrap();
+    let out = codegen::expand(proc_macro2::TokenStream::new(), tokens);
+    let file = syn::parse2::<syn::File>(out).expect("expansion is not a valid item list");
+    prettyplease::unparse(&file)
+}
+
+#[test]
+fn expansion_matches_snapshots() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/expand");
+    let bless = std::env::var_os("EXPAND_BLESS").is_some();
+    let mut inputs: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| {
+            p.extension().is_some_and(|e| e == "rs")
+                && !p.to_string_lossy().ends_with(".expanded.rs")
+        })
+        .collect();
+    inputs.sort();
+    assert!(!inputs.is_empty(), "no inputs in {}", dir.display());
+    let mut mismatched = Vec::new();
+    for input in &inputs {
+        let actual = expand_file(input);
+        let golden = input.with_extension("expanded.rs");
+        match std::fs::read_to_string(&golden) {
+            Ok(expected) if expected == actual => {}
+            Ok(_) | Err(_) if bless => std::fs::write(&golden, &actual).unwrap(),
+            Ok(expected) => {
+                eprintln!(
+                    "--- {} (expected)\n{expected}\n+++ actual\n{actual}",
+                    golden.display()
+                );
+                mismatched.push(golden);
+            }
+            Err(_) => mismatched.push(golden),
+        }
+    }
+    assert!(
+        mismatched.is_empty(),
+        "expansion differs from snapshot (set EXPAND_BLESS=1 to update): {mismatched:?}"
+    );
+}
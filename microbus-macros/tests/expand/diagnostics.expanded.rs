@@ -0,0 +1,504 @@
+/// Subscribes: `Tick`; Publishes: `Tick`
+impl Broken {
+    #[handle(x)]
+    async fn with_args(&self, tick: &Tick) {}
+    #[handle(on_error = sometimes)]
+    async fn bad_policy(&self, tick: &Tick) -> Result<()> {
+        Ok(())
+    }
+    #[handle(on_error = stop_component)]
+    async fn policy_without_result(&self, tick: &Tick) {}
+    #[handle(in_order, latest)]
+    async fn ordered_latest(&self, tick: &Tick) {}
+    #[handle(window = "1s")]
+    async fn window_without_batch(&self, tick: &Tick) {}
+    #[handle(every = "100ms")]
+    async fn every_without_window(&self, tick: &Tick) {}
+    #[handle]
+    async fn no_payload(&self) {}
+    #[handle]
+    async fn mut_self(&mut self, tick: &Tick) {}
+    #[active(sometimes)]
+    async fn bad_active(&self) {}
+    #[active(idle_backoff = "10ms..1ms")]
+    async fn bad_backoff(&self) -> Option<Tick> {
+        None
+    }
+    #[active(idle_backoff = "1ms")]
+    async fn backoff_without_option(&self) -> Tick {
+        Tick
+    }
+    #[active]
+    async fn bad_flow(&self) -> ControlFlow<String, Tick> {
+        ControlFlow::Break(String::new())
+    }
+    #[active(once)]
+    async fn once_flow(&self) -> ControlFlow<()> {
+        ControlFlow::Break(())
+    }
+    #[init]
+    #[handle]
+    async fn init_and_handle(&self, tick: &Tick) {}
+    #[snapshot]
+    async fn unpaired(&self) -> u64 {
+        0
+    }
+}
+#[async_trait::async_trait]
+impl mmg_microbus::component::Component for Broken {
+    async fn run(
+        self: Box<Self>,
+        mut ctx: mmg_microbus::component::ComponentContext,
+    ) -> mmg_microbus::error::Result<()> {
+        let mut this = *self;
+        let this = std::sync::Arc::new(this);
+        let mut __sub_any_0 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_1 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_2 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_3 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_4 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let __source = mmg_microbus::component::__register_source(&ctx);
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        {}
+        let mut __workers: Vec<mmg_microbus::rt::JoinHandle<()>> = Vec::new();
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_0;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .with_args(& * env)). await }; match __res { Ok(__out) => { let _ =
+                    std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick >
+                    (& ctx_c, "with_args", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "with_args",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_1;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .bad_policy(& * env)). await }; match __res { Ok(__out) => { if let
+                    Err(e) = std::future::ready(__out). await {
+                    Self::__microbus_log_error("handle returned error", & e); if let
+                    Some(__ev) = mmg_microbus::component::__handler_error:: < Tick > (&
+                    ctx_c, "bad_policy", & * env, & e) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "bad_policy", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "bad_policy",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_2;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .policy_without_result(& * env)). await }; match __res { Ok(__out) =>
+                    { let _ = std::future::ready(__out). await; } Err(__panic) => { if
+                    let Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick
+                    > (& ctx_c, "policy_without_result", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; }
+                    mmg_microbus::component::__stop_component(& ctx_c,
+                    "policy_without_result"); } } }
+                    mmg_microbus::component::__handler_end(& ctx_c,
+                    "policy_without_result", std::any::type_name:: < Tick > (), __t0);
+                    __budget.spend(). await; } None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_3;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .ordered_latest(& * env)). await }; match __res { Ok(__out) => { let
+                    _ = std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick >
+                    (& ctx_c, "ordered_latest", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "ordered_latest",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_4;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .every_without_window(& * env)). await }; match __res { Ok(__out) =>
+                    { let _ = std::future::ready(__out). await; } Err(__panic) => { if
+                    let Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick
+                    > (& ctx_c, "every_without_window", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c,
+                    "every_without_window", std::any::type_name:: < Tick > (), __t0);
+                    __budget.spend(). await; } None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let __source_c = __source.clone();
+        let __jh = mmg_microbus::component::__spawn_active(
+            &ctx,
+            async move {
+                let _source = __source_c;
+                let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+                loop {
+                    mmg_microbus::rt::select! {
+                        _ = mmg_microbus::component::__recv_stop(& ctx_c) => break, _ =
+                        async { mmg_microbus::component::__wait_resumed(& mut __ctl).
+                        await; let this = & this_c; { let _ = this.bad_active(). await; }
+                        } => {}
+                    }
+                }
+            },
+        );
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let __source_c = __source.clone();
+        let __jh = mmg_microbus::component::__spawn_active(
+            &ctx,
+            async move {
+                let _source = __source_c;
+                let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+                loop {
+                    mmg_microbus::rt::select! {
+                        _ = mmg_microbus::component::__recv_stop(& ctx_c) => break, _ =
+                        async { mmg_microbus::component::__wait_resumed(& mut __ctl).
+                        await; let this = & this_c; { { if let Some(__v) = this
+                        .bad_backoff(). await { mmg_microbus::component::__publish_auto(&
+                        ctx_c, __v). await; } } } } => {}
+                    }
+                }
+            },
+        );
+        __workers.push(__jh);
+        drop(__source);
+        mmg_microbus::component::__recv_stop(&ctx).await;
+        Ok(())
+    }
+    #[doc(hidden)]
+    async fn __dispatch(
+        &self,
+        ctx: &mmg_microbus::component::ComponentContext,
+        msg: std::sync::Arc<dyn std::any::Any + Send + Sync>,
+    ) -> bool {
+        let mut __handled = false;
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.with_args(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "with_args", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.bad_policy(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        if let Err(e) = std::future::ready(__out).await {
+                            Self::__microbus_log_error("handle returned error", &e);
+                            if let Some(__ev) = mmg_microbus::component::__handler_error::<
+                                Tick,
+                            >(&ctx_c, "bad_policy", &*env, &e) {
+                                mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                            }
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "bad_policy", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.policy_without_result(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "policy_without_result", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                        mmg_microbus::component::__stop_component(
+                            &ctx_c,
+                            "policy_without_result",
+                        );
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.ordered_latest(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "ordered_latest", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.every_without_window(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "every_without_window", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        __handled
+    }
+    #[doc(hidden)]
+    fn __wiring() -> Option<mmg_microbus::wiring::Wiring>
+    where
+        Self: Sized,
+    {
+        let publishes = vec![mmg_microbus::wiring::MessageType::of:: < Tick > ()];
+        Some(mmg_microbus::wiring::Wiring {
+            subscribes: vec![
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > ()
+            ],
+            publishes,
+            dynamic: false,
+        })
+    }
+}
+#[allow(dead_code)]
+impl Broken {
+    fn __microbus_log_error(phase: &'static str, e: &dyn std::fmt::Display) {
+        mmg_microbus::__tracing::warn!(error = % e, "{phase}");
+    }
+    fn __microbus_init_failed<E: std::fmt::Debug + Into<mmg_microbus::error::BoxError>>(
+        ctx: &mmg_microbus::component::ComponentContext,
+        phase: &'static str,
+        e: E,
+    ) -> mmg_microbus::error::MicrobusError {
+        mmg_microbus::__tracing::error!(error = ? e, "{phase}");
+        mmg_microbus::component::__init_failed(ctx, e)
+    }
+    fn __microbus_log_stop_error(e: &dyn std::fmt::Debug) {
+        mmg_microbus::__tracing::warn!(error = ? e, "stop returned error");
+    }
+    fn __microbus_log_retry(attempt: u32, e: Option<&dyn std::fmt::Display>) {
+        match e {
+            Some(e) => {
+                mmg_microbus::__tracing::warn!(
+                    error = % e, attempt, "handle returned error; retrying"
+                )
+            }
+            None => mmg_microbus::__tracing::warn!(attempt, "handle panicked; retrying"),
+        }
+    }
+}
+::core::compile_error! {
+    "a method can carry only one of #[handle], #[active], #[init], #[stop], #[snapshot]; split it into separate methods"
+}
+::core::compile_error! {
+    "#[handle] only accepts: on_error = <policy>, latest, debounce = \"<duration>\", throttle = \"<n>/<unit>\", sample = <n | p>, local, scope = <local | global>, in_order, weak, weight = <n>, window = \"<duration>\", every = \"<duration>\", emit = <Type>"
+}
+::core::compile_error! {
+    "on_error must be one of: ignore, retry, retry(n), stop_component, stop_app"
+}
+::core::compile_error! {
+    "on_error other than ignore requires the #[handle] method to return Result"
+}
+::core::compile_error! {
+    "#[handle(in_order)] guarantees every message in publish order; it cannot be combined with latest, debounce or throttle, which coalesce messages"
+}
+::core::compile_error! {
+    "#[handle(window = ..)] takes the batch as &Window<T>, and &Window<T> requires window = \"<duration>\""
+}
+::core::compile_error! {
+    "every and emit only apply to #[handle(window = ..)]; window cannot be combined with latest, debounce, throttle or in_order"
+}
+compile_error!(
+    "#[handle] requires exactly one &T, &Envelope<T> or &Window<T> parameter (message payload)"
+);
+::core::compile_error! {
+    "#[handle] method cannot take &mut self under spawned worker model; use interior mutability or #[component(actor)]"
+}
+::core::compile_error! {
+    "#[active] only accepts: once, idle_backoff = \"<min>..<max>\""
+}
+::core::compile_error! {
+    "idle_backoff expects a duration range such as \"1ms..100ms\" with min <= max (units: us, ms, s, min)"
+}
+::core::compile_error! {
+    "idle_backoff requires the #[active] method to return Option<T> or Result<Option<T>> (None counts as idle)"
+}
+::core::compile_error! {
+    "#[active] ControlFlow return must be ControlFlow<(), T> (Break carries no value)"
+}
+::core::compile_error! {
+    "#[active(once)] cannot return ControlFlow; it already runs a single time"
+}
+::core::compile_error! {
+    "#[snapshot] method must be synchronous, take only &self and return the state"
+}
@@ -0,0 +1,59 @@
+impl Broken {
+    #[handle(x)]
+    async fn with_args(&self, tick: &Tick) {}
+
+    #[handle(on_error = sometimes)]
+    async fn bad_policy(&self, tick: &Tick) -> Result<()> {
+        Ok(())
+    }
+
+    #[handle(on_error = stop_component)]
+    async fn policy_without_result(&self, tick: &Tick) {}
+
+    #[handle(in_order, latest)]
+    async fn ordered_latest(&self, tick: &Tick) {}
+
+    #[handle(window = "1s")]
+    async fn window_without_batch(&self, tick: &Tick) {}
+
+    #[handle(every = "100ms")]
+    async fn every_without_window(&self, tick: &Tick) {}
+
+    #[handle]
+    async fn no_payload(&self) {}
+
+    #[handle]
+    async fn mut_self(&mut self, tick: &Tick) {}
+
+    #[active(sometimes)]
+    async fn bad_active(&self) {}
+
+    #[active(idle_backoff = "10ms..1ms")]
+    async fn bad_backoff(&self) -> Option<Tick> {
+        None
+    }
+
+    #[active(idle_backoff = "1ms")]
+    async fn backoff_without_option(&self) -> Tick {
+        Tick
+    }
+
+    #[active]
+    async fn bad_flow(&self) -> ControlFlow<String, Tick> {
+        ControlFlow::Break(String::new())
+    }
+
+    #[active(once)]
+    async fn once_flow(&self) -> ControlFlow<()> {
+        ControlFlow::Break(())
+    }
+
+    #[init]
+    #[handle]
+    async fn init_and_handle(&self, tick: &Tick) {}
+
+    #[snapshot]
+    async fn unpaired(&self) -> u64 {
+        0
+    }
+}
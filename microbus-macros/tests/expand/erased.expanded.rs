@@ -0,0 +1,326 @@
+/// Subscribes: `Raw`, `Batch`; Publishes: dynamic types
+impl Router {
+    #[handle]
+    async fn one(&self, raw: &Raw) -> ErasedEvent {
+        ErasedEvent::new(Parsed)
+    }
+    #[handle]
+    async fn many(&self, batch: &Batch) -> Vec<ErasedEvent> {
+        Vec::new()
+    }
+    #[handle]
+    async fn boxed(&self, raw: &Raw) -> Box<dyn std::any::Any + Send + Sync> {
+        Box::new(Parsed)
+    }
+    #[handle]
+    async fn shared(
+        &self,
+        raw: &Raw,
+    ) -> Option<std::sync::Arc<dyn std::any::Any + Send + Sync>> {
+        None
+    }
+}
+#[async_trait::async_trait]
+impl mmg_microbus::component::Component for Router {
+    async fn run(
+        self: Box<Self>,
+        mut ctx: mmg_microbus::component::ComponentContext,
+    ) -> mmg_microbus::error::Result<()> {
+        let mut this = *self;
+        let this = std::sync::Arc::new(this);
+        let mut __sub_any_0 = mmg_microbus::component::__subscribe_any_auto::<Raw>(&ctx);
+        let mut __sub_any_1 = mmg_microbus::component::__subscribe_any_auto::<
+            Batch,
+        >(&ctx);
+        let mut __sub_any_2 = mmg_microbus::component::__subscribe_any_auto::<Raw>(&ctx);
+        let mut __sub_any_3 = mmg_microbus::component::__subscribe_any_auto::<Raw>(&ctx);
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        {}
+        let mut __workers: Vec<mmg_microbus::rt::JoinHandle<()>> = Vec::new();
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_0;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Raw, _ > (& ctx_c, this
+                    .one(& * env)). await }; match __res { Ok(__out) => { { let __ev =
+                    std::future::ready(__out). await;
+                    mmg_microbus::component::__publish_erased(& ctx_c, __ev). await; } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Raw > (& ctx_c,
+                    "one", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "one",
+                    std::any::type_name:: < Raw > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_1;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Batch, _ > (& ctx_c,
+                    this.many(& * env)). await }; match __res { Ok(__out) => { { let
+                    __vec = std::future::ready(__out). await; for __ev in __vec {
+                    mmg_microbus::component::__publish_erased(& ctx_c, __ev). await; } }
+                    } Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Batch > (& ctx_c,
+                    "many", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "many",
+                    std::any::type_name:: < Batch > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_2;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Raw, _ > (& ctx_c, this
+                    .boxed(& * env)). await }; match __res { Ok(__out) => { { let __b =
+                    std::future::ready(__out). await;
+                    mmg_microbus::component::__publish_any_box(& ctx_c, __b). await; } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Raw > (& ctx_c,
+                    "boxed", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "boxed",
+                    std::any::type_name:: < Raw > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_3;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Raw, _ > (& ctx_c, this
+                    .shared(& * env)). await }; match __res { Ok(__out) => { { if let
+                    Some(__a) = std::future::ready(__out). await {
+                    mmg_microbus::component::__publish_any_arc(& ctx_c, __a). await; } }
+                    } Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Raw > (& ctx_c,
+                    "shared", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "shared",
+                    std::any::type_name:: < Raw > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        mmg_microbus::component::__recv_stop(&ctx).await;
+        Ok(())
+    }
+    #[doc(hidden)]
+    async fn __dispatch(
+        &self,
+        ctx: &mmg_microbus::component::ComponentContext,
+        msg: std::sync::Arc<dyn std::any::Any + Send + Sync>,
+    ) -> bool {
+        let mut __handled = false;
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Raw>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<Raw, _>(&ctx_c, this.one(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let __ev = std::future::ready(__out).await;
+                        mmg_microbus::component::__publish_erased(&ctx_c, __ev).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Raw,
+                        >(&ctx_c, "one", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Batch>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Batch,
+                        _,
+                    >(&ctx_c, this.many(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let __vec = std::future::ready(__out).await;
+                        for __ev in __vec {
+                            mmg_microbus::component::__publish_erased(&ctx_c, __ev)
+                                .await;
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Batch,
+                        >(&ctx_c, "many", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Raw>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Raw,
+                        _,
+                    >(&ctx_c, this.boxed(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let __b = std::future::ready(__out).await;
+                        mmg_microbus::component::__publish_any_box(&ctx_c, __b).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Raw,
+                        >(&ctx_c, "boxed", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Raw>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Raw,
+                        _,
+                    >(&ctx_c, this.shared(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        if let Some(__a) = std::future::ready(__out).await {
+                            mmg_microbus::component::__publish_any_arc(&ctx_c, __a)
+                                .await;
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Raw,
+                        >(&ctx_c, "shared", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        __handled
+    }
+    #[doc(hidden)]
+    fn __wiring() -> Option<mmg_microbus::wiring::Wiring>
+    where
+        Self: Sized,
+    {
+        let publishes = vec![];
+        Some(mmg_microbus::wiring::Wiring {
+            subscribes: vec![
+                mmg_microbus::wiring::MessageType::of:: < Raw > (),
+                mmg_microbus::wiring::MessageType::of:: < Batch > (),
+                mmg_microbus::wiring::MessageType::of:: < Raw > (),
+                mmg_microbus::wiring::MessageType::of:: < Raw > ()
+            ],
+            publishes,
+            dynamic: true,
+        })
+    }
+}
+#[allow(dead_code)]
+impl Router {
+    fn __microbus_log_error(phase: &'static str, e: &dyn std::fmt::Display) {
+        mmg_microbus::__tracing::warn!(error = % e, "{phase}");
+    }
+    fn __microbus_init_failed<E: std::fmt::Debug + Into<mmg_microbus::error::BoxError>>(
+        ctx: &mmg_microbus::component::ComponentContext,
+        phase: &'static str,
+        e: E,
+    ) -> mmg_microbus::error::MicrobusError {
+        mmg_microbus::__tracing::error!(error = ? e, "{phase}");
+        mmg_microbus::component::__init_failed(ctx, e)
+    }
+    fn __microbus_log_stop_error(e: &dyn std::fmt::Debug) {
+        mmg_microbus::__tracing::warn!(error = ? e, "stop returned error");
+    }
+    fn __microbus_log_retry(attempt: u32, e: Option<&dyn std::fmt::Display>) {
+        match e {
+            Some(e) => {
+                mmg_microbus::__tracing::warn!(
+                    error = % e, attempt, "handle returned error; retrying"
+                )
+            }
+            None => mmg_microbus::__tracing::warn!(attempt, "handle panicked; retrying"),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+impl Router {
+    #[handle]
+    async fn one(&self, raw: &Raw) -> ErasedEvent {
+        ErasedEvent::new(Parsed)
+    }
+
+    #[handle]
+    async fn many(&self, batch: &Batch) -> Vec<ErasedEvent> {
+        Vec::new()
+    }
+
+    #[handle]
+    async fn boxed(&self, raw: &Raw) -> Box<dyn std::any::Any + Send + Sync> {
+        Box::new(Parsed)
+    }
+
+    #[handle]
+    async fn shared(&self, raw: &Raw) -> Option<std::sync::Arc<dyn std::any::Any + Send + Sync>> {
+        None
+    }
+}
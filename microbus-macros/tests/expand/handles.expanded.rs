@@ -0,0 +1,1283 @@
+/// Subscribes: `Tick`, `Price` (local), `Tick` (weak); Publishes: `Price` (local)
+impl Pricer {
+    #[handle]
+    async fn on_unit(&self, tick: &Tick) {}
+    #[handle]
+    async fn on_value(&self, ctx: &ComponentContext, tick: &Tick) -> Price {
+        Price(tick.0)
+    }
+    #[handle]
+    async fn on_option(&self, tick: &Tick) -> Option<Price> {
+        None
+    }
+    #[handle]
+    async fn on_result_unit(&self, tick: &Tick) -> Result<()> {
+        Ok(())
+    }
+    #[handle]
+    async fn on_result_value(&self, tick: &Tick) -> Result<Price> {
+        Ok(Price(tick.0))
+    }
+    #[handle]
+    async fn on_result_option(&self, tick: &Tick) -> Result<Option<Price>> {
+        Ok(None)
+    }
+    #[handle(on_error = retry(2))]
+    async fn on_retry(&self, tick: &Tick) -> Result<Price> {
+        Ok(Price(tick.0))
+    }
+    #[handle(on_error = stop_app)]
+    async fn on_risk(&self, tick: &Tick) -> Result<()> {
+        Ok(())
+    }
+    #[handle(latest)]
+    async fn on_latest(&self, tick: &Tick) {}
+    #[handle(throttle = "10/s")]
+    async fn on_paced(&self, tick: &Tick) {}
+    #[handle(sample = 100)]
+    async fn on_sampled(&self, tick: &Tick) {}
+    #[handle]
+    async fn on_envelope(&self, env: &Envelope<Tick>) -> Option<Price> {
+        env.seq().map(Price)
+    }
+    #[handle(scope = local)]
+    async fn on_local(&self, price: &Price) {}
+    #[handle(weak)]
+    async fn on_observed(&self, tick: &Tick) {}
+    #[handle(window = "1s", emit = Price)]
+    async fn on_window(&self, w: &Window<Tick>) -> Price {
+        Price(w.len() as u64)
+    }
+    #[handle(in_order, on_error = retry(1))]
+    async fn on_ordered(&self, tick: &Tick) -> Result<()> {
+        Ok(())
+    }
+}
+#[async_trait::async_trait]
+impl mmg_microbus::component::Component for Pricer {
+    async fn run(
+        self: Box<Self>,
+        mut ctx: mmg_microbus::component::ComponentContext,
+    ) -> mmg_microbus::error::Result<()> {
+        mmg_microbus::component::__local_scope(
+            &mut ctx,
+            vec![std::any::TypeId::of:: < Price > ()],
+        );
+        let mut __sub_any_12 = mmg_microbus::component::__subscribe_local_auto::<
+            Price,
+        >(&ctx);
+        mmg_microbus::component::__seal_local(&ctx);
+        let mut this = *self;
+        let this = std::sync::Arc::new(this);
+        let mut __sub_any_0 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_1 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_2 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_3 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_4 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_5 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_6 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_7 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_8 = mmg_microbus::component::__subscribe_latest_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_9 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_10 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_11 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_13 = mmg_microbus::component::__subscribe_weak_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_14 = mmg_microbus::component::__subscribe_any_auto::<
+            Tick,
+        >(&ctx);
+        let mut __sub_any_15 = mmg_microbus::component::__subscribe_ordered_auto::<
+            Tick,
+        >(&ctx);
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        {}
+        let mut __workers: Vec<mmg_microbus::rt::JoinHandle<()>> = Vec::new();
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_0;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_unit(& * env)). await }; match __res { Ok(__out) => { let _ =
+                    std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick >
+                    (& ctx_c, "on_unit", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_unit",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_1;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, {
+                    use mmg_microbus::query:: { __ViaAsk as _, __ViaOther as _ }; (& *
+                    env).__request_scope(this.on_value(& ctx_c, & * env)) }). await };
+                    match __res { Ok(__out) => { { let __v = std::future::ready(__out).
+                    await; mmg_microbus::component::__publish_auto(& ctx_c, __v). await;
+                    } } Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_value", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_value",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_2;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_option(& * env)). await }; match __res { Ok(__out) => { { if let
+                    Some(__v) = std::future::ready(__out). await {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __v). await; } } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_option", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_option",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_3;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_result_unit(& * env)). await }; match __res { Ok(__out) => { if
+                    let Err(e) = std::future::ready(__out). await {
+                    Self::__microbus_log_error("handle returned error", & e); if let
+                    Some(__ev) = mmg_microbus::component::__handler_error:: < Tick > (&
+                    ctx_c, "on_result_unit", & * env, & e) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_result_unit", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_result_unit",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_4;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_result_value(& * env)). await }; match __res { Ok(__out) => {
+                    match std::future::ready(__out). await { Ok(v) =>
+                    mmg_microbus::component::__publish_auto(& ctx_c, v). await, Err(e) =>
+                    { Self::__microbus_log_error("handle returned error", & e); if let
+                    Some(__ev) = mmg_microbus::component::__handler_error:: < Tick > (&
+                    ctx_c, "on_result_value", & * env, & e) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_result_value", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_result_value",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_5;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_result_option(& * env)). await }; match __res { Ok(__out) => {
+                    match std::future::ready(__out). await { Ok(opt) => if let Some(v) =
+                    opt { mmg_microbus::component::__publish_auto(& ctx_c, v). await },
+                    Err(e) => { Self::__microbus_log_error("handle returned error", & e);
+                    if let Some(__ev) = mmg_microbus::component::__handler_error:: < Tick
+                    > (& ctx_c, "on_result_option", & * env, & e) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_result_option", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_result_option",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_6;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; (async { let mut __attempt = 0u32; loop { match
+                    mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_retry(& * env)). await { Ok(Err(e)) if __attempt < 2u32 => {
+                    __attempt += 1; Self::__microbus_log_retry(__attempt, Some(& e)); }
+                    Err(_) if __attempt < 2u32 => { __attempt += 1;
+                    Self::__microbus_log_retry(__attempt, None); } __r => break __r, } }
+                    }). await }; match __res { Ok(__out) => { match
+                    std::future::ready(__out). await { Ok(v) =>
+                    mmg_microbus::component::__publish_auto(& ctx_c, v). await, Err(e) =>
+                    { Self::__microbus_log_error("handle returned error", & e); if let
+                    Some(__ev) = mmg_microbus::component::__handler_error:: < Tick > (&
+                    ctx_c, "on_retry", & * env, & e) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_retry", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_retry",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_7;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_risk(& * env)). await }; match __res { Ok(__out) => { if let
+                    Err(e) = std::future::ready(__out). await {
+                    Self::__microbus_log_error("handle returned error", & e); if let
+                    Some(__ev) = mmg_microbus::component::__handler_error:: < Tick > (&
+                    ctx_c, "on_risk", & * env, & e) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; }
+                    mmg_microbus::component::__stop_app(& ctx_c, "on_risk"); } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_risk", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; }
+                    mmg_microbus::component::__stop_app(& ctx_c, "on_risk"); } } }
+                    mmg_microbus::component::__handler_end(& ctx_c, "on_risk",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_8;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_latest(& * env)). await }; match __res { Ok(__out) => { let _ =
+                    std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick >
+                    (& ctx_c, "on_latest", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_latest",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_9;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            let mut __pacer = mmg_microbus::component::__Pacer::throttle(
+                &ctx_c,
+                100000000u64,
+            );
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let Some(env) = __pacer.offer(env) else
+                    { continue; }; let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_paced(& * env)). await }; match __res { Ok(__out) => { let _ =
+                    std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick >
+                    (& ctx_c, "on_paced", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_paced",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } } env = __pacer.due() => { let this = & this_c; let
+                    __t0 = mmg_microbus::component::__handler_begin(& ctx_c); { let __res
+                    = { let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_paced(& * env)). await }; match __res { Ok(__out) => { let _ =
+                    std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick >
+                    (& ctx_c, "on_paced", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_paced",
+                    std::any::type_name:: < Tick > (), __t0); }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_10;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            let mut __sampler = mmg_microbus::component::__Sampler::every(
+                &ctx_c,
+                "on_sampled",
+                std::any::type_name::<Tick>(),
+                100u64,
+            );
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { if ! __sampler.admit() { continue; } let
+                    this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_sampled(& * env)). await }; match __res { Ok(__out) => { let _ =
+                    std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick >
+                    (& ctx_c, "on_sampled", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_sampled",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_11;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_envelope(& env)). await }; match __res { Ok(__out) => { { if let
+                    Some(__v) = std::future::ready(__out). await {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __v). await; } } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_envelope", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_envelope",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_12;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Price, _ > (& ctx_c,
+                    this.on_local(& * env)). await }; match __res { Ok(__out) => { let _
+                    = std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Price >
+                    (& ctx_c, "on_local", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_local",
+                    std::any::type_name:: < Price > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_13;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_observed(& * env)). await }; match __res { Ok(__out) => { let _ =
+                    std::future::ready(__out). await; } Err(__panic) => { if let
+                    Some(__ev) = mmg_microbus::component::__handler_panicked:: < Tick >
+                    (& ctx_c, "on_observed", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_observed",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let _: fn(Price) -> Price = |__x| __x;
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_14;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            let mut __window = mmg_microbus::window::__Windower::<
+                Tick,
+            >::new(&ctx_c, 1000000000u64, 1000000000u64);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { __window.push(env); } None => break, } }
+                    env = __window.close() => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_window(& env)). await }; match __res { Ok(__out) => { { let __v =
+                    std::future::ready(__out). await;
+                    mmg_microbus::component::__publish_auto(& ctx_c, __v). await; } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: <
+                    mmg_microbus::window::Window < Tick > > (& ctx_c, "on_window", & env,
+                    & * __panic) { mmg_microbus::component::__publish_auto(& ctx_c, __ev)
+                    . await; } } } } mmg_microbus::component::__handler_end(& ctx_c,
+                    "on_window", std::any::type_name:: < Tick > (), __t0); }
+                }
+            }
+            if let Some(env) = __window.flush() {
+                let this = &this_c;
+                let __t0 = mmg_microbus::component::__handler_begin(&ctx_c);
+                {
+                    let __res = {
+                        let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                            .await;
+                        mmg_microbus::component::__invoke::<
+                            Tick,
+                            _,
+                        >(&ctx_c, this.on_window(&env))
+                            .await
+                    };
+                    match __res {
+                        Ok(__out) => {
+                            let __v = std::future::ready(__out).await;
+                            mmg_microbus::component::__publish_auto(&ctx_c, __v).await;
+                        }
+                        Err(__panic) => {
+                            if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                                mmg_microbus::window::Window<Tick>,
+                            >(&ctx_c, "on_window", &env, &*__panic) {
+                                mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                            }
+                        }
+                    }
+                }
+                mmg_microbus::component::__handler_end(
+                    &ctx_c,
+                    "on_window",
+                    std::any::type_name::<Tick>(),
+                    __t0,
+                );
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let mut sub = __sub_any_15;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; let __t0 =
+                    mmg_microbus::component::__handler_begin(& ctx_c); { let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(& ctx_c).
+                    await; (async { let mut __attempt = 0u32; loop { match
+                    mmg_microbus::component::__invoke:: < Tick, _ > (& ctx_c, this
+                    .on_ordered(& * env)). await { Ok(Err(e)) if __attempt < 1u32 => {
+                    __attempt += 1; Self::__microbus_log_retry(__attempt, Some(& e)); }
+                    Err(_) if __attempt < 1u32 => { __attempt += 1;
+                    Self::__microbus_log_retry(__attempt, None); } __r => break __r, } }
+                    }). await }; match __res { Ok(__out) => { if let Err(e) =
+                    std::future::ready(__out). await {
+                    Self::__microbus_log_error("handle returned error", & e); if let
+                    Some(__ev) = mmg_microbus::component::__handler_error:: < Tick > (&
+                    ctx_c, "on_ordered", & * env, & e) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Tick > (& ctx_c,
+                    "on_ordered", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_ordered",
+                    std::any::type_name:: < Tick > (), __t0); __budget.spend(). await; }
+                    None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        mmg_microbus::component::__recv_stop(&ctx).await;
+        Ok(())
+    }
+    #[doc(hidden)]
+    async fn __dispatch(
+        &self,
+        ctx: &mmg_microbus::component::ComponentContext,
+        msg: std::sync::Arc<dyn std::any::Any + Send + Sync>,
+    ) -> bool {
+        let mut __handled = false;
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_unit(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_unit", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(
+                            &ctx_c,
+                            {
+                                use mmg_microbus::query::{__ViaAsk as _, __ViaOther as _};
+                                (&*env).__request_scope(this.on_value(&ctx_c, &*env))
+                            },
+                        )
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let __v = std::future::ready(__out).await;
+                        mmg_microbus::component::__publish_auto(&ctx_c, __v).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_value", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_option(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        if let Some(__v) = std::future::ready(__out).await {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __v).await;
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_option", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_result_unit(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        if let Err(e) = std::future::ready(__out).await {
+                            Self::__microbus_log_error("handle returned error", &e);
+                            if let Some(__ev) = mmg_microbus::component::__handler_error::<
+                                Tick,
+                            >(&ctx_c, "on_result_unit", &*env, &e) {
+                                mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                            }
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_result_unit", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_result_value(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        match std::future::ready(__out).await {
+                            Ok(v) => {
+                                mmg_microbus::component::__publish_auto(&ctx_c, v).await
+                            }
+                            Err(e) => {
+                                Self::__microbus_log_error("handle returned error", &e);
+                                if let Some(__ev) = mmg_microbus::component::__handler_error::<
+                                    Tick,
+                                >(&ctx_c, "on_result_value", &*env, &e) {
+                                    mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                                }
+                            }
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_result_value", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_result_option(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        match std::future::ready(__out).await {
+                            Ok(opt) => {
+                                if let Some(v) = opt {
+                                    mmg_microbus::component::__publish_auto(&ctx_c, v).await
+                                }
+                            }
+                            Err(e) => {
+                                Self::__microbus_log_error("handle returned error", &e);
+                                if let Some(__ev) = mmg_microbus::component::__handler_error::<
+                                    Tick,
+                                >(&ctx_c, "on_result_option", &*env, &e) {
+                                    mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                                }
+                            }
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_result_option", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    (async {
+                        let mut __attempt = 0u32;
+                        loop {
+                            match mmg_microbus::component::__invoke::<
+                                Tick,
+                                _,
+                            >(&ctx_c, this.on_retry(&*env))
+                                .await
+                            {
+                                Ok(Err(e)) if __attempt < 2u32 => {
+                                    __attempt += 1;
+                                    Self::__microbus_log_retry(__attempt, Some(&e));
+                                }
+                                Err(_) if __attempt < 2u32 => {
+                                    __attempt += 1;
+                                    Self::__microbus_log_retry(__attempt, None);
+                                }
+                                __r => break __r,
+                            }
+                        }
+                    })
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        match std::future::ready(__out).await {
+                            Ok(v) => {
+                                mmg_microbus::component::__publish_auto(&ctx_c, v).await
+                            }
+                            Err(e) => {
+                                Self::__microbus_log_error("handle returned error", &e);
+                                if let Some(__ev) = mmg_microbus::component::__handler_error::<
+                                    Tick,
+                                >(&ctx_c, "on_retry", &*env, &e) {
+                                    mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                                }
+                            }
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_retry", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_risk(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        if let Err(e) = std::future::ready(__out).await {
+                            Self::__microbus_log_error("handle returned error", &e);
+                            if let Some(__ev) = mmg_microbus::component::__handler_error::<
+                                Tick,
+                            >(&ctx_c, "on_risk", &*env, &e) {
+                                mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                            }
+                            mmg_microbus::component::__stop_app(&ctx_c, "on_risk");
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_risk", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                        mmg_microbus::component::__stop_app(&ctx_c, "on_risk");
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_latest(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_latest", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_paced(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_paced", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_sampled(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_sampled", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let env = mmg_microbus::bus::Envelope::__detached(env);
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_envelope(&env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        if let Some(__v) = std::future::ready(__out).await {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __v).await;
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_envelope", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Price>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Price,
+                        _,
+                    >(&ctx_c, this.on_local(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Price,
+                        >(&ctx_c, "on_local", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_observed(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let _ = std::future::ready(__out).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_observed", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let env = mmg_microbus::window::Window::__single(env);
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Tick,
+                        _,
+                    >(&ctx_c, this.on_window(&env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let __v = std::future::ready(__out).await;
+                        mmg_microbus::component::__publish_auto(&ctx_c, __v).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            mmg_microbus::window::Window<Tick>,
+                        >(&ctx_c, "on_window", &env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Tick>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    (async {
+                        let mut __attempt = 0u32;
+                        loop {
+                            match mmg_microbus::component::__invoke::<
+                                Tick,
+                                _,
+                            >(&ctx_c, this.on_ordered(&*env))
+                                .await
+                            {
+                                Ok(Err(e)) if __attempt < 1u32 => {
+                                    __attempt += 1;
+                                    Self::__microbus_log_retry(__attempt, Some(&e));
+                                }
+                                Err(_) if __attempt < 1u32 => {
+                                    __attempt += 1;
+                                    Self::__microbus_log_retry(__attempt, None);
+                                }
+                                __r => break __r,
+                            }
+                        }
+                    })
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        if let Err(e) = std::future::ready(__out).await {
+                            Self::__microbus_log_error("handle returned error", &e);
+                            if let Some(__ev) = mmg_microbus::component::__handler_error::<
+                                Tick,
+                            >(&ctx_c, "on_ordered", &*env, &e) {
+                                mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                            }
+                        }
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Tick,
+                        >(&ctx_c, "on_ordered", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        __handled
+    }
+    #[doc(hidden)]
+    fn __wiring() -> Option<mmg_microbus::wiring::Wiring>
+    where
+        Self: Sized,
+    {
+        let __local = [std::any::TypeId::of::<Price>()];
+        let mut publishes = vec![
+            mmg_microbus::wiring::MessageType::of:: < Price > (),
+            mmg_microbus::wiring::MessageType::of:: < Price > (),
+            mmg_microbus::wiring::MessageType::of:: < Price > (),
+            mmg_microbus::wiring::MessageType::of:: < Price > (),
+            mmg_microbus::wiring::MessageType::of:: < Price > (),
+            mmg_microbus::wiring::MessageType::of:: < Price > (),
+            mmg_microbus::wiring::MessageType::of:: < Price > ()
+        ];
+        publishes.retain(|t| !__local.contains(&t.id));
+        Some(mmg_microbus::wiring::Wiring {
+            subscribes: vec![
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > (),
+                mmg_microbus::wiring::MessageType::of:: < Tick > ()
+            ],
+            publishes,
+            dynamic: false,
+        })
+    }
+}
+#[allow(dead_code)]
+impl Pricer {
+    fn __microbus_log_error(phase: &'static str, e: &dyn std::fmt::Display) {
+        mmg_microbus::__tracing::warn!(error = % e, "{phase}");
+    }
+    fn __microbus_init_failed<E: std::fmt::Debug + Into<mmg_microbus::error::BoxError>>(
+        ctx: &mmg_microbus::component::ComponentContext,
+        phase: &'static str,
+        e: E,
+    ) -> mmg_microbus::error::MicrobusError {
+        mmg_microbus::__tracing::error!(error = ? e, "{phase}");
+        mmg_microbus::component::__init_failed(ctx, e)
+    }
+    fn __microbus_log_stop_error(e: &dyn std::fmt::Debug) {
+        mmg_microbus::__tracing::warn!(error = ? e, "stop returned error");
+    }
+    fn __microbus_log_retry(attempt: u32, e: Option<&dyn std::fmt::Display>) {
+        match e {
+            Some(e) => {
+                mmg_microbus::__tracing::warn!(
+                    error = % e, attempt, "handle returned error; retrying"
+                )
+            }
+            None => mmg_microbus::__tracing::warn!(attempt, "handle panicked; retrying"),
+        }
+    }
+}
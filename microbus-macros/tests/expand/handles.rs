@@ -0,0 +1,69 @@
+impl Pricer {
+    #[handle]
+    async fn on_unit(&self, tick: &Tick) {}
+
+    #[handle]
+    async fn on_value(&self, ctx: &ComponentContext, tick: &Tick) -> Price {
+        Price(tick.0)
+    }
+
+    #[handle]
+    async fn on_option(&self, tick: &Tick) -> Option<Price> {
+        None
+    }
+
+    #[handle]
+    async fn on_result_unit(&self, tick: &Tick) -> Result<()> {
+        Ok(())
+    }
+
+    #[handle]
+    async fn on_result_value(&self, tick: &Tick) -> Result<Price> {
+        Ok(Price(tick.0))
+    }
+
+    #[handle]
+    async fn on_result_option(&self, tick: &Tick) -> Result<Option<Price>> {
+        Ok(None)
+    }
+
+    #[handle(on_error = retry(2))]
+    async fn on_retry(&self, tick: &Tick) -> Result<Price> {
+        Ok(Price(tick.0))
+    }
+
+    #[handle(on_error = stop_app)]
+    async fn on_risk(&self, tick: &Tick) -> Result<()> {
+        Ok(())
+    }
+
+    #[handle(latest)]
+    async fn on_latest(&self, tick: &Tick) {}
+
+    #[handle(throttle = "10/s")]
+    async fn on_paced(&self, tick: &Tick) {}
+
+    #[handle(sample = 100)]
+    async fn on_sampled(&self, tick: &Tick) {}
+
+    #[handle]
+    async fn on_envelope(&self, env: &Envelope<Tick>) -> Option<Price> {
+        env.seq().map(Price)
+    }
+
+    #[handle(scope = local)]
+    async fn on_local(&self, price: &Price) {}
+
+    #[handle(weak)]
+    async fn on_observed(&self, tick: &Tick) {}
+
+    #[handle(window = "1s", emit = Price)]
+    async fn on_window(&self, w: &Window<Tick>) -> Price {
+        Price(w.len() as u64)
+    }
+
+    #[handle(in_order, on_error = retry(1))]
+    async fn on_ordered(&self, tick: &Tick) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+/// Emits the opening tick and a greeting.
+///
+/// Subscribes: none; Publishes: `Hello`, `Tick`
+impl Feeder {
+    #[init]
+    async fn init(&self, ctx: &ComponentContext) -> Result<()> {
+        Ok(())
+    }
+    #[active(once)]
+    async fn announce(&self) -> Hello {
+        Hello
+    }
+    #[active]
+    async fn poll(&self, ctx: &ComponentContext) -> Option<Tick> {
+        None
+    }
+    #[active(idle_backoff = "1ms..100ms")]
+    async fn drain(&self) -> Result<Option<Tick>> {
+        Ok(None)
+    }
+    #[active]
+    async fn replay(&self) -> ControlFlow<(), Option<Tick>> {
+        ControlFlow::Break(())
+    }
+    #[stop]
+    fn stop(&self) {}
+}
+#[async_trait::async_trait]
+impl mmg_microbus::component::Component for Feeder {
+    async fn run(
+        self: Box<Self>,
+        mut ctx: mmg_microbus::component::ComponentContext,
+    ) -> mmg_microbus::error::Result<()> {
+        let mut this = *self;
+        {
+            if let Err(e) = this.init(&ctx).await {
+                return Err(Self::__microbus_init_failed(&ctx, "init returned error", e));
+            }
+        }
+        let this = std::sync::Arc::new(this);
+        let __source = mmg_microbus::component::__register_source(&ctx);
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        {
+            {
+                let __v = this.announce().await;
+                mmg_microbus::component::__publish_auto(&ctx, __v).await;
+            }
+        }
+        let mut __workers: Vec<mmg_microbus::rt::JoinHandle<()>> = Vec::new();
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let __source_c = __source.clone();
+        let __jh = mmg_microbus::component::__spawn_active(
+            &ctx,
+            async move {
+                let _source = __source_c;
+                let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+                loop {
+                    mmg_microbus::rt::select! {
+                        _ = mmg_microbus::component::__recv_stop(& ctx_c) => break, _ =
+                        async { mmg_microbus::component::__wait_resumed(& mut __ctl).
+                        await; let this = & this_c; { { if let Some(__v) = this.poll(&
+                        ctx_c). await { mmg_microbus::component::__publish_auto(& ctx_c,
+                        __v). await; } } } } => {}
+                    }
+                }
+            },
+        );
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let __source_c = __source.clone();
+        let __jh = mmg_microbus::component::__spawn_active(
+            &ctx,
+            async move {
+                let _source = __source_c;
+                let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+                let mut __backoff = mmg_microbus::component::__IdleBackoff::new(
+                    1000000u64,
+                    100000000u64,
+                );
+                loop {
+                    mmg_microbus::rt::select! {
+                        _ = mmg_microbus::component::__recv_stop(& ctx_c) => break, _ =
+                        async { mmg_microbus::component::__wait_resumed(& mut __ctl).
+                        await; let this = & this_c; { let __v = this.drain(). await; let
+                        __idle = ! matches!(__v, Ok(Some(_))); { match
+                        std::future::ready(__v). await { Ok(opt) => if let Some(v) = opt
+                        { mmg_microbus::component::__publish_auto(& ctx_c, v). await },
+                        Err(e) => { Self::__microbus_log_error("active returned error", &
+                        e); } } } __backoff.step(__idle). await; } } => {}
+                    }
+                }
+            },
+        );
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let __source_c = __source.clone();
+        let __jh = mmg_microbus::component::__spawn_active(
+            &ctx,
+            async move {
+                let _source = __source_c;
+                let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+                loop {
+                    mmg_microbus::rt::select! {
+                        _ = mmg_microbus::component::__recv_stop(& ctx_c) => break,
+                        __done = async { mmg_microbus::component::__wait_resumed(& mut
+                        __ctl). await; let this = & this_c; match this.replay(). await {
+                        std::ops::ControlFlow::Continue(__v) => { { if let Some(__v) =
+                        std::future::ready(__v). await {
+                        mmg_microbus::component::__publish_auto(& ctx_c, __v). await; } }
+                        false } std::ops::ControlFlow::Break(()) => true, } } => { if
+                        __done { mmg_microbus::component::__active_completed(& ctx_c,
+                        "replay"). await; break; } }
+                    }
+                }
+            },
+        );
+        __workers.push(__jh);
+        drop(__source);
+        mmg_microbus::component::__recv_stop(&ctx).await;
+        {
+            let _ = this.stop();
+        }
+        Ok(())
+    }
+    #[doc(hidden)]
+    async fn __dispatch(
+        &self,
+        ctx: &mmg_microbus::component::ComponentContext,
+        msg: std::sync::Arc<dyn std::any::Any + Send + Sync>,
+    ) -> bool {
+        let mut __handled = false;
+        __handled
+    }
+    #[doc(hidden)]
+    fn __wiring() -> Option<mmg_microbus::wiring::Wiring>
+    where
+        Self: Sized,
+    {
+        let publishes = vec![
+            mmg_microbus::wiring::MessageType::of:: < Hello > (),
+            mmg_microbus::wiring::MessageType::of:: < Tick > (),
+            mmg_microbus::wiring::MessageType::of:: < Tick > (),
+            mmg_microbus::wiring::MessageType::of:: < Tick > ()
+        ];
+        Some(mmg_microbus::wiring::Wiring {
+            subscribes: vec![],
+            publishes,
+            dynamic: false,
+        })
+    }
+}
+#[allow(dead_code)]
+impl Feeder {
+    fn __microbus_log_error(phase: &'static str, e: &dyn std::fmt::Display) {
+        mmg_microbus::__tracing::warn!(error = % e, "{phase}");
+    }
+    fn __microbus_init_failed<E: std::fmt::Debug + Into<mmg_microbus::error::BoxError>>(
+        ctx: &mmg_microbus::component::ComponentContext,
+        phase: &'static str,
+        e: E,
+    ) -> mmg_microbus::error::MicrobusError {
+        mmg_microbus::__tracing::error!(error = ? e, "{phase}");
+        mmg_microbus::component::__init_failed(ctx, e)
+    }
+    fn __microbus_log_stop_error(e: &dyn std::fmt::Debug) {
+        mmg_microbus::__tracing::warn!(error = ? e, "stop returned error");
+    }
+    fn __microbus_log_retry(attempt: u32, e: Option<&dyn std::fmt::Display>) {
+        match e {
+            Some(e) => {
+                mmg_microbus::__tracing::warn!(
+                    error = % e, attempt, "handle returned error; retrying"
+                )
+            }
+            None => mmg_microbus::__tracing::warn!(attempt, "handle panicked; retrying"),
+        }
+    }
+}
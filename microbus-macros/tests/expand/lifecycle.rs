@@ -0,0 +1,30 @@
+/// Emits the opening tick and a greeting.
+impl Feeder {
+    #[init]
+    async fn init(&self, ctx: &ComponentContext) -> Result<()> {
+        Ok(())
+    }
+
+    #[active(once)]
+    async fn announce(&self) -> Hello {
+        Hello
+    }
+
+    #[active]
+    async fn poll(&self, ctx: &ComponentContext) -> Option<Tick> {
+        None
+    }
+
+    #[active(idle_backoff = "1ms..100ms")]
+    async fn drain(&self) -> Result<Option<Tick>> {
+        Ok(None)
+    }
+
+    #[active]
+    async fn replay(&self) -> ControlFlow<(), Option<Tick>> {
+        ControlFlow::Break(())
+    }
+
+    #[stop]
+    fn stop(&self) {}
+}
@@ -0,0 +1,163 @@
+/// Subscribes: `Deposit`; Publishes: `Balance`
+impl Account {
+    #[handle]
+    async fn on_deposit(&self, d: &Deposit) -> Balance {
+        Balance(d.0)
+    }
+    #[snapshot]
+    fn save(&self) -> u64 {
+        0
+    }
+    #[snapshot(restore)]
+    fn load(&mut self, total: u64) {}
+}
+#[async_trait::async_trait]
+impl mmg_microbus::component::Component for Account {
+    async fn run(
+        self: Box<Self>,
+        mut ctx: mmg_microbus::component::ComponentContext,
+    ) -> mmg_microbus::error::Result<()> {
+        let mut this = *self;
+        match mmg_microbus::snapshot::__restore(&ctx) {
+            Ok(Some(__s)) => this.load(__s),
+            Ok(None) => {}
+            Err(e) => {
+                return Err(
+                    Self::__microbus_init_failed(&ctx, "snapshot restore failed", e),
+                );
+            }
+        }
+        let this = std::sync::Arc::new(this);
+        let __activity = mmg_microbus::snapshot::__Activity::new();
+        let mut __sub_any_0 = mmg_microbus::component::__subscribe_any_auto::<
+            Deposit,
+        >(&ctx);
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        {}
+        let mut __workers: Vec<mmg_microbus::rt::JoinHandle<()>> = Vec::new();
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let __activity_c = __activity.clone();
+        let mut sub = __sub_any_0;
+        let __jh = mmg_microbus::rt::spawn(async move {
+            let mut __ctl = mmg_microbus::component::__control_rx(&ctx_c);
+            let mut __budget = mmg_microbus::component::__Budget::new(&ctx_c, 1u32);
+            loop {
+                mmg_microbus::rt::select! {
+                    _ = mmg_microbus::component::__recv_stop(& ctx_c) => { break; } msg =
+                    mmg_microbus::component::__recv_controlled(& mut __ctl, & mut sub) =>
+                    { match msg { Some(env) => { let this = & this_c; __activity_c
+                    .begin(); let __t0 = mmg_microbus::component::__handler_begin(&
+                    ctx_c); { let __res = { let _permit =
+                    mmg_microbus::component::__handler_permit(& ctx_c). await;
+                    mmg_microbus::component::__invoke:: < Deposit, _ > (& ctx_c, this
+                    .on_deposit(& * env)). await }; match __res { Ok(__out) => { { let
+                    __v = std::future::ready(__out). await;
+                    mmg_microbus::component::__publish_auto(& ctx_c, __v). await; } }
+                    Err(__panic) => { if let Some(__ev) =
+                    mmg_microbus::component::__handler_panicked:: < Deposit > (& ctx_c,
+                    "on_deposit", & * env, & * __panic) {
+                    mmg_microbus::component::__publish_auto(& ctx_c, __ev). await; } } }
+                    } mmg_microbus::component::__handler_end(& ctx_c, "on_deposit",
+                    std::any::type_name:: < Deposit > (), __t0); __activity_c.end();
+                    __budget.spend(). await; } None => break, } }
+                }
+            }
+        });
+        __workers.push(__jh);
+        let this_c = this.clone();
+        let ctx_c = ctx.__fork();
+        let __activity_c = __activity.clone();
+        __workers
+            .push(
+                mmg_microbus::rt::spawn(async move {
+                    mmg_microbus::snapshot::__save_periodically(
+                            &ctx_c,
+                            &__activity_c,
+                            || this_c.save(),
+                        )
+                        .await;
+                }),
+            );
+        mmg_microbus::component::__recv_stop(&ctx).await;
+        mmg_microbus::snapshot::__save_final(&ctx, &__activity, || this.save()).await;
+        Ok(())
+    }
+    #[doc(hidden)]
+    async fn __dispatch(
+        &self,
+        ctx: &mmg_microbus::component::ComponentContext,
+        msg: std::sync::Arc<dyn std::any::Any + Send + Sync>,
+    ) -> bool {
+        let mut __handled = false;
+        if let Ok(env) = std::sync::Arc::clone(&msg).downcast::<Deposit>() {
+            let this = self;
+            let ctx_c = ctx;
+            {
+                let __res = {
+                    let _permit = mmg_microbus::component::__handler_permit(&ctx_c)
+                        .await;
+                    mmg_microbus::component::__invoke::<
+                        Deposit,
+                        _,
+                    >(&ctx_c, this.on_deposit(&*env))
+                        .await
+                };
+                match __res {
+                    Ok(__out) => {
+                        let __v = std::future::ready(__out).await;
+                        mmg_microbus::component::__publish_auto(&ctx_c, __v).await;
+                    }
+                    Err(__panic) => {
+                        if let Some(__ev) = mmg_microbus::component::__handler_panicked::<
+                            Deposit,
+                        >(&ctx_c, "on_deposit", &*env, &*__panic) {
+                            mmg_microbus::component::__publish_auto(&ctx_c, __ev).await;
+                        }
+                    }
+                }
+            }
+            __handled = true;
+        }
+        __handled
+    }
+    #[doc(hidden)]
+    fn __wiring() -> Option<mmg_microbus::wiring::Wiring>
+    where
+        Self: Sized,
+    {
+        let publishes = vec![mmg_microbus::wiring::MessageType::of:: < Balance > ()];
+        Some(mmg_microbus::wiring::Wiring {
+            subscribes: vec![mmg_microbus::wiring::MessageType::of:: < Deposit > ()],
+            publishes,
+            dynamic: false,
+        })
+    }
+}
+#[allow(dead_code)]
+impl Account {
+    fn __microbus_log_error(phase: &'static str, e: &dyn std::fmt::Display) {
+        mmg_microbus::__tracing::warn!(error = % e, "{phase}");
+    }
+    fn __microbus_init_failed<E: std::fmt::Debug + Into<mmg_microbus::error::BoxError>>(
+        ctx: &mmg_microbus::component::ComponentContext,
+        phase: &'static str,
+        e: E,
+    ) -> mmg_microbus::error::MicrobusError {
+        mmg_microbus::__tracing::error!(error = ? e, "{phase}");
+        mmg_microbus::component::__init_failed(ctx, e)
+    }
+    fn __microbus_log_stop_error(e: &dyn std::fmt::Debug) {
+        mmg_microbus::__tracing::warn!(error = ? e, "stop returned error");
+    }
+    fn __microbus_log_retry(attempt: u32, e: Option<&dyn std::fmt::Display>) {
+        match e {
+            Some(e) => {
+                mmg_microbus::__tracing::warn!(
+                    error = % e, attempt, "handle returned error; retrying"
+                )
+            }
+            None => mmg_microbus::__tracing::warn!(attempt, "handle panicked; retrying"),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+impl Account {
+    #[handle]
+    async fn on_deposit(&self, d: &Deposit) -> Balance {
+        Balance(d.0)
+    }
+
+    #[snapshot]
+    fn save(&self) -> u64 {
+        0
+    }
+
+    #[snapshot(restore)]
+    fn load(&mut self, total: u64) {}
+}
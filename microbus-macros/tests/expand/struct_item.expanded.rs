@@ -0,0 +1,35 @@
+#[derive(Default)]
+struct Pricer {
+    last: std::sync::atomic::AtomicU64,
+}
+#[doc(hidden)]
+#[derive(Default)]
+struct __PricerFactory;
+#[async_trait::async_trait]
+impl mmg_microbus::component::ComponentFactory for __PricerFactory {
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Pricer>()
+    }
+    fn wiring(&self) -> Option<mmg_microbus::wiring::Wiring> {
+        <Pricer as mmg_microbus::component::Component>::__wiring()
+    }
+    async fn build(
+        &self,
+        _bus: mmg_microbus::bus::BusHandle,
+    ) -> mmg_microbus::error::Result<Box<dyn mmg_microbus::component::Component>> {
+        Ok(
+            Box::new(
+                <Pricer as mmg_microbus::component::__ComponentDefault>::__component_default(),
+            ),
+        )
+    }
+}
+#[doc(hidden)]
+const _: () = {
+    fn __create_factory_for() -> Box<dyn mmg_microbus::component::ComponentFactory> {
+        Box::new(__PricerFactory::default())
+    }
+    inventory::submit! {
+        mmg_microbus::component::__RegisteredFactory { create : __create_factory_for }
+    };
+};
@@ -0,0 +1,4 @@
+#[derive(Default)]
+struct Pricer {
+    last: std::sync::atomic::AtomicU64,
+}
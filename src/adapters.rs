@@ -0,0 +1,172 @@
+//! 通道适配：把既有 `tokio::sync::mpsc` 通道接入总线，便于以通道组织的旧代码逐步迁移为组件。
+//!
+//! - [`ChannelSource`]：从 `Receiver<T>` 取出的每条消息以本组件为来源发布；发送端全部关闭后保持空闲直至停机；
+//! - [`ChannelSink`]：订阅 `T` 并克隆转发到 `Sender<T>`；接收端满时等待（背压沿订阅队列传回发布方），接收端关闭后丢弃并计数；
+//! - [`SyncPublisher`]：供非 async 线程（FFI 回调、硬件轮询线程）发布消息的可克隆句柄，经配套的 [`ChannelSource`] 进入总线。
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+
+use crate::component::{Component, ComponentContext};
+use crate::error::Result;
+
+/// 适配器计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct AdapterHandle {
+    forwarded: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl AdapterHandle {
+    /// 已转发的消息数。
+    #[must_use]
+    pub fn forwarded(&self) -> u64 {
+        self.forwarded.load(Ordering::Relaxed)
+    }
+    /// 因对端关闭而丢弃的消息数。
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// 通道输入：`app.add_component(ChannelSource::new(rx))`。
+pub struct ChannelSource<T> {
+    rx: mpsc::Receiver<T>,
+    handle: AdapterHandle,
+}
+
+impl<T: Send + Sync + 'static> ChannelSource<T> {
+    #[must_use]
+    pub fn new(rx: mpsc::Receiver<T>) -> Self {
+        Self {
+            rx,
+            handle: AdapterHandle::default(),
+        }
+    }
+    #[must_use]
+    pub fn handle(&self) -> AdapterHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync + 'static> Component for ChannelSource<T> {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self { mut rx, handle } = *self;
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            crate::rt::select! {
+                () = crate::component::__recv_stop(&ctx) => return Ok(()),
+                msg = rx.recv() => match msg {
+                    Some(msg) => {
+                        handle.forwarded.fetch_add(1, Ordering::Relaxed);
+                        crate::component::__publish_auto(&ctx, msg).await;
+                    }
+                    None => break,
+                },
+            }
+        }
+        crate::component::__recv_stop(&ctx).await;
+        Ok(())
+    }
+}
+
+/// 同步发布句柄：`let (publisher, source) = SyncPublisher::channel(64); app.add_component(source);`。
+///
+/// 队列满时 [`publish`](Self::publish) 阻塞当前线程（背压传回生产方）；在运行时线程内调用时退化为不阻塞的
+/// [`try_publish`](Self::try_publish)。返回 `Err(msg)` 表示未送达：队列已满（仅非阻塞路径）或 App 已停止。
+pub struct SyncPublisher<T> {
+    tx: mpsc::Sender<T>,
+}
+
+impl<T> Clone for SyncPublisher<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> SyncPublisher<T> {
+    /// 创建容量为 `capacity` 的句柄及其输入组件（组件须加入 App 后消息才会被发布）。
+    ///
+    /// # Panics
+    /// `capacity` 为 0。
+    #[must_use]
+    pub fn channel(capacity: usize) -> (Self, ChannelSource<T>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        (Self { tx }, ChannelSource::new(rx))
+    }
+    /// 发布一条消息；队列满时阻塞等待（运行时线程内不阻塞）。
+    ///
+    /// # Errors
+    /// 未送达时原样返回消息。
+    pub fn publish(&self, msg: T) -> std::result::Result<(), T> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return self.try_publish(msg);
+        }
+        self.tx.blocking_send(msg).map_err(|e| e.0)
+    }
+    /// 不阻塞地发布一条消息。
+    ///
+    /// # Errors
+    /// 队列已满或 App 已停止时原样返回消息。
+    pub fn try_publish(&self, msg: T) -> std::result::Result<(), T> {
+        self.tx.try_send(msg).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(m) | mpsc::error::TrySendError::Closed(m) => m,
+        })
+    }
+    /// App 已停止（输入组件已退出）。
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+}
+
+/// 通道输出：`app.add_component(ChannelSink::new(tx))`。
+pub struct ChannelSink<T> {
+    tx: mpsc::Sender<T>,
+    handle: AdapterHandle,
+}
+
+impl<T: Clone + Send + Sync + 'static> ChannelSink<T> {
+    #[must_use]
+    pub fn new(tx: mpsc::Sender<T>) -> Self {
+        Self {
+            tx,
+            handle: AdapterHandle::default(),
+        }
+    }
+    #[must_use]
+    pub fn handle(&self) -> AdapterHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl<T: Clone + Send + Sync + 'static> Component for ChannelSink<T> {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self { tx, handle } = *self;
+        let mut sub = crate::component::__subscribe_any_auto::<T>(&ctx);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            crate::rt::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                msg = sub.recv() => {
+                    let Some(msg) = msg else { break };
+                    // 先取得容量再计数，接收方看到消息时计数已更新
+                    if let Ok(permit) = tx.reserve().await {
+                        handle.forwarded.fetch_add(1, Ordering::Relaxed);
+                        permit.send((*msg).clone());
+                    } else {
+                        handle.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
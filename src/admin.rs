@@ -0,0 +1,273 @@
+//! 内置运维 HTTP 端点（特性 `admin-http`）：基于自省快照提供 `/health` `/topology` `/metrics` `/components`。
+//!
+//! 极简 HTTP/1.x 实现：仅处理 GET，每个连接一次请求一次响应；通过 `App::add_component` 显式启用。
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+use crate::introspect::Snapshot;
+
+const MAX_REQUEST: usize = 8 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct AdminServer {
+    listener: std::net::TcpListener,
+    local_addr: SocketAddr,
+}
+
+impl AdminServer {
+    /// 立即绑定监听地址（端口 0 由系统分配，可经 `local_addr()` 取得）。
+    ///
+    /// # Errors
+    /// 地址不可绑定时返回 IO 错误。
+    pub fn bind(addr: impl Into<SocketAddr>) -> std::io::Result<Self> {
+        let listener = std::net::TcpListener::bind(addr.into())?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        Ok(Self {
+            listener,
+            local_addr,
+        })
+    }
+    #[must_use]
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+#[async_trait]
+impl Component for AdminServer {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let listener = TcpListener::from_std(self.listener).map_err(|e| {
+            crate::component::__startup_mark_failed(&ctx);
+            MicrobusError::Dynamic(format!("admin-http: listener setup failed: {e}"))
+        })?;
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        tracing::info!(addr = %self.local_addr, "admin http endpoint listening");
+        loop {
+            tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        let ctx_c = ctx.__fork();
+                        tokio::spawn(async move { serve_conn(stream, &ctx_c).await });
+                    }
+                    Err(e) => tracing::warn!(error = %e, "admin http accept failed"),
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn serve_conn(mut stream: TcpStream, ctx: &ComponentContext) {
+    let mut buf = Vec::with_capacity(512);
+    let mut chunk = [0u8; 512];
+    // 读到请求头结束（空行）或上限
+    let read = tokio::time::timeout(READ_TIMEOUT, async {
+        while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST {
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            }
+        }
+    })
+    .await;
+    if read.is_err() {
+        return;
+    }
+    let head = String::from_utf8_lossy(&buf);
+    let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+    let (method, path) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or("/"),
+    );
+    let (status, content_type, body) = if method == "GET" {
+        route(path, &ctx.introspect())
+    } else {
+        (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_string(),
+        )
+    };
+    let resp = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(resp.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn route(path: &str, snap: &Snapshot) -> (&'static str, &'static str, String) {
+    const JSON: &str = "application/json";
+    match path.split('?').next().unwrap_or_default() {
+        "/health" => {
+            let healthy = snap.is_healthy();
+            let status = if healthy {
+                "200 OK"
+            } else {
+                "503 Service Unavailable"
+            };
+            (
+                status,
+                JSON,
+                json!({ "healthy": healthy, "sealed": snap.sealed }).to_string(),
+            )
+        }
+        "/components" => ("200 OK", JSON, json!(snap.components).to_string()),
+        "/topology" => ("200 OK", JSON, topology_json(snap)),
+        "/metrics" => ("200 OK", "text/plain; version=0.0.4", metrics_text(snap)),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    }
+}
+
+// 按消息类型聚合订阅者
+fn topology_json(snap: &Snapshot) -> String {
+    let mut types: Vec<&'static str> = snap.subscriptions.iter().map(|s| s.type_name).collect();
+    types.sort_unstable();
+    types.dedup();
+    let body: Vec<_> = types
+        .into_iter()
+        .map(|t| {
+            let subs: Vec<_> = snap
+                .subscriptions
+                .iter()
+                .filter(|s| s.type_name == t)
+                .map(|s| {
+                    json!({
+                        "component": s.component,
+                        "depth": s.depth,
+                        "capacity": s.capacity,
+                        "closed": s.closed,
+                    })
+                })
+                .collect();
+            json!({ "type_name": t, "subscribers": subs })
+        })
+        .collect();
+    json!(body).to_string()
+}
+
+// Prometheus 文本格式
+fn metrics_text(snap: &Snapshot) -> String {
+    use std::fmt::Write as _;
+    let mut out = String::new();
+    out.push_str("# TYPE microbus_queue_depth gauge\n");
+    for s in &snap.subscriptions {
+        let _ = writeln!(
+            out,
+            "microbus_queue_depth{{component=\"{}\",type=\"{}\"}} {}",
+            escape_label(s.component),
+            escape_label(s.type_name),
+            s.depth
+        );
+    }
+    out.push_str("# TYPE microbus_queue_capacity gauge\n");
+    for s in &snap.subscriptions {
+        let _ = writeln!(
+            out,
+            "microbus_queue_capacity{{component=\"{}\",type=\"{}\"}} {}",
+            escape_label(s.component),
+            escape_label(s.type_name),
+            s.capacity
+        );
+    }
+    out.push_str("# TYPE microbus_published_total counter\n");
+    for t in &snap.types {
+        let _ = writeln!(
+            out,
+            "microbus_published_total{{type=\"{}\"}} {}",
+            escape_label(t.type_name),
+            t.published
+        );
+    }
+    out.push_str("# TYPE microbus_published_bytes_total counter\n");
+    for t in &snap.types {
+        let _ = writeln!(
+            out,
+            "microbus_published_bytes_total{{type=\"{}\"}} {}",
+            escape_label(t.type_name),
+            t.bytes
+        );
+    }
+    out.push_str("# TYPE microbus_closed_drops_total counter\n");
+    for d in snap.drops.iter().filter(|d| d.closed > 0) {
+        let _ = writeln!(
+            out,
+            "microbus_closed_drops_total{{type=\"{}\"}} {}",
+            escape_label(d.type_name),
+            d.closed
+        );
+    }
+    out.push_str("# TYPE microbus_full_drops_total counter\n");
+    for d in snap.drops.iter().filter(|d| d.full > 0) {
+        let _ = writeln!(
+            out,
+            "microbus_full_drops_total{{type=\"{}\"}} {}",
+            escape_label(d.type_name),
+            d.full
+        );
+    }
+    out.push_str("# TYPE microbus_blocked_sends_total counter\n");
+    for b in &snap.backpressure {
+        let _ = writeln!(
+            out,
+            "microbus_blocked_sends_total{{type=\"{}\"}} {}",
+            escape_label(b.type_name),
+            b.blocked_sends
+        );
+    }
+    out.push_str("# TYPE microbus_sampled_skipped_total counter\n");
+    for s in &snap.sampling {
+        let _ = writeln!(
+            out,
+            "microbus_sampled_skipped_total{{component=\"{}\",method=\"{}\",type=\"{}\"}} {}",
+            escape_label(s.component),
+            escape_label(s.method),
+            escape_label(s.type_name),
+            s.skipped
+        );
+    }
+    out.push_str("# TYPE microbus_rate_limited_total counter\n");
+    for r in &snap.rate_limits {
+        for (action, n) in [("delayed", r.delayed), ("dropped", r.dropped)] {
+            let _ = writeln!(
+                out,
+                "microbus_rate_limited_total{{type=\"{}\",action=\"{action}\"}} {n}",
+                escape_label(r.type_name)
+            );
+        }
+    }
+    out.push_str("# TYPE microbus_dedup_suppressed_total counter\n");
+    for d in &snap.dedup {
+        let _ = writeln!(
+            out,
+            "microbus_dedup_suppressed_total{{type=\"{}\"}} {}",
+            escape_label(d.type_name),
+            d.suppressed
+        );
+    }
+    out.push_str("# TYPE microbus_blocked_seconds_total counter\n");
+    for b in &snap.backpressure {
+        let _ = writeln!(
+            out,
+            "microbus_blocked_seconds_total{{type=\"{}\"}} {}",
+            escape_label(b.type_name),
+            b.blocked_total.as_secs_f64()
+        );
+    }
+    out
+}
+
+fn escape_label(v: &str) -> String {
+    v.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
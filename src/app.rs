@@ -1,37 +1,223 @@
-use crate::error::{MicrobusError, Result};
-use tokio::task::JoinHandle;
+use crate::error::{MicrobusError, Result, StartupFailure, Trace};
+use crate::rt::JoinHandle;
 
 use crate::{
     bus::{Bus, BusHandle},
     component::{
-        ComponentContext, __RegisteredFactory, __new_startup_barrier, __new_stop_flag,
-        __trigger_stop_flag,
+        __RegisteredFactory, __new_startup_barrier, __new_stop_flag, __trigger_stop_flag,
+        Component, ComponentContext, ComponentFactory, InstanceFactory,
     },
     config::AppConfig,
+    events::{AppSealed, ComponentFailed, ComponentStopped, FailurePhase},
+    introspect::{ComponentRegistry, ComponentStatus, Snapshot, StartupProgress},
 };
 
+// App 与各组件上下文共享的运行期状态
+pub(crate) struct AppShared {
+    pub(crate) cfg: AppConfig,
+    pub(crate) components: ComponentRegistry,
+    pub(crate) sampling: crate::introspect::SamplingRegistry,
+    pub(crate) controls: crate::introspect::ControlRegistry,
+    #[cfg(feature = "snapshot")]
+    pub(crate) snapshots: parking_lot::RwLock<Option<std::sync::Arc<crate::snapshot::Snapshots>>>,
+    #[cfg(feature = "chaos")]
+    pub(crate) chaos: parking_lot::RwLock<Option<std::sync::Arc<crate::chaos::Chaos>>>,
+    #[cfg(feature = "crash-bundle")]
+    pub(crate) crash_bundles:
+        parking_lot::RwLock<Option<std::sync::Arc<crate::crash::CrashBundles>>>,
+    // 启动阶段各失败组件的错误，供 start() 汇总返回
+    pub(crate) start_errors: parking_lot::Mutex<Vec<StartupFailure>>,
+    // 组件标记启动失败时捕获的调用栈，记录失败时按名称取回
+    pub(crate) start_traces: parking_lot::Mutex<Vec<(&'static str, Trace)>>,
+    pub(crate) start_error_ready: tokio::sync::Notify,
+    // `Topology::max_concurrent_handlers`：全部 handler 共享的调用许可
+    pub(crate) handler_permits: Option<std::sync::Arc<tokio::sync::Semaphore>>,
+    // `AppConfig::stop_on_completion`：数据源与进行中 handler 计数
+    pub(crate) completion: std::sync::Arc<crate::monitor::Completion>,
+}
+
+impl AppShared {
+    pub(crate) fn new(cfg: AppConfig) -> Self {
+        Self {
+            components: ComponentRegistry::default(),
+            sampling: crate::introspect::SamplingRegistry::default(),
+            controls: crate::introspect::ControlRegistry::default(),
+            #[cfg(feature = "snapshot")]
+            snapshots: parking_lot::RwLock::default(),
+            #[cfg(feature = "chaos")]
+            chaos: parking_lot::RwLock::default(),
+            #[cfg(feature = "crash-bundle")]
+            crash_bundles: parking_lot::RwLock::default(),
+            start_errors: parking_lot::Mutex::default(),
+            start_traces: parking_lot::Mutex::default(),
+            start_error_ready: tokio::sync::Notify::new(),
+            handler_permits: cfg
+                .topology
+                .max_concurrent_handlers
+                .map(|n| std::sync::Arc::new(tokio::sync::Semaphore::new(n))),
+            completion: std::sync::Arc::new(crate::monitor::Completion::new(
+                cfg.stop_on_completion,
+            )),
+            cfg,
+        }
+    }
+    // 须先于 set_status(Failed) 调用：start() 以“无组件仍在启动”判定报告已完整
+    fn record_start_error(
+        &self,
+        component: &'static str,
+        phase: FailurePhase,
+        error: MicrobusError,
+    ) {
+        let trace = {
+            let mut traces = self.start_traces.lock();
+            traces
+                .iter()
+                .position(|(name, _)| *name == component)
+                .map_or_else(Trace::capture, |i| traces.swap_remove(i).1)
+        };
+        self.start_errors.lock().push(StartupFailure {
+            component,
+            phase,
+            error,
+            trace,
+        });
+        self.start_error_ready.notify_one();
+    }
+}
+
+// App 自建的多线程运行时（`Topology::worker_threads`）；App 可能在异步上下文中丢弃，故不阻塞关闭
+struct OwnedRuntime(Option<tokio::runtime::Runtime>);
+
+impl OwnedRuntime {
+    #[cfg(target_arch = "wasm32")]
+    fn build(_threads: usize) -> Option<Self> {
+        tracing::warn!("worker_threads is unsupported on wasm32; using the caller's runtime");
+        None
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build(threads: usize) -> Option<Self> {
+        match tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(threads)
+            .thread_name("microbus-worker")
+            .enable_all()
+            .build()
+        {
+            Ok(rt) => Some(Self(Some(rt))),
+            Err(e) => {
+                tracing::error!(error = %e, "worker runtime setup failed; using the caller's runtime");
+                None
+            }
+        }
+    }
+    fn handle(&self) -> Option<&tokio::runtime::Handle> {
+        self.0.as_ref().map(tokio::runtime::Runtime::handle)
+    }
+}
+
+impl Drop for OwnedRuntime {
+    fn drop(&mut self) {
+        if let Some(rt) = self.0.take() {
+            rt.shutdown_background();
+        }
+    }
+}
+
+// 组件任务结束（正常返回、出错或被 abort）时置位 `exited`，供 `stop_component` 等待
+struct ExitGuard(std::sync::Arc<crate::component::ControlPlane>);
+
+impl Drop for ExitGuard {
+    fn drop(&mut self) {
+        self.0.exited.trigger();
+    }
+}
+
+// 按 `requires` 排定启动阶段（组件下标按 `factories` 顺序）
+fn dependency_phases<'a>(
+    factories: impl Iterator<Item = &'a Box<dyn ComponentFactory>>,
+) -> Result<Vec<Vec<usize>>> {
+    let deps: Vec<_> = factories.map(|f| (f.type_name(), f.requires())).collect();
+    crate::wiring::startup_phases(&deps)
+}
+
+// 组件名称模式：完整类型名或末段类型名
+fn component_matches(type_name: &str, pattern: &str) -> bool {
+    type_name == pattern || type_name.rsplit("::").next() == Some(pattern)
+}
+
+// 启动失败后等待其余组件完成 init（成功到达或同样失败）的最长时间
+const START_ERROR_GRACE: std::time::Duration = std::time::Duration::from_millis(500);
+
 pub struct App {
-    _cfg: AppConfig,
+    shared: std::sync::Arc<AppShared>,
     bus: Bus,
     tasks: Vec<JoinHandle<()>>,
+    extra: Vec<Box<dyn ComponentFactory>>, // 显式添加的组件（内置可选组件等）
+    only: Option<Vec<&'static str>>,       // 仅启动指定的自动发现组件（测试工具使用）
+    declared: crate::wiring::Declared,     // 组件之外的发布方 / 订阅方登记的类型（装配校验使用）
+    enabled: Option<Vec<String>>,          // 运维选择：仅启动这些自动发现组件（名称模式）
+    disabled: Vec<String>,                 // 运维选择：不启动这些自动发现组件
     started: bool,
     stop_flag: std::sync::Arc<crate::component::StopFlag>,
     startup_barrier: Option<std::sync::Arc<crate::component::StartupBarrier>>, // 协调启动失败与等待
+    runtime: Option<OwnedRuntime>, // 组件任务所在的自建运行时（未配置时为调用方运行时）
+    message_types: Vec<crate::introspect::MessageTypeInfo>, // 启动成功时生成的消息类型登记表
 }
 
 impl App {
+    /// 以 `cfg` 构造应用。
+    ///
+    /// # Panics
+    /// 配置无效（见 [`AppConfig::validate`]）；需要以错误处理时用 [`try_new`](Self::try_new)。
     #[must_use]
     pub fn new(cfg: AppConfig) -> Self {
+        match Self::try_new(cfg) {
+            Ok(app) => app,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// 以运行档 `profile` 构造：配置覆盖作用于 `cfg` 后构造，再登记该档的组件选择。
+    ///
+    /// # Errors
+    /// 覆盖后的配置无效（见 [`AppConfig::validate`]）。
+    pub fn with_profile(cfg: AppConfig, profile: &crate::profile::Profile) -> Result<Self> {
+        let mut app = Self::try_new(profile.configure(cfg))?;
+        profile.select_components(&mut app);
+        tracing::info!(profile = profile.name(), "profile selected");
+        Ok(app)
+    }
+
+    /// 同 [`new`](Self::new)，配置无效时返回错误。
+    ///
+    /// # Errors
+    /// [`AppConfig::validate`] 的错误。
+    pub fn try_new(cfg: AppConfig) -> Result<Self> {
+        cfg.validate()?;
         let bus = Bus::new(cfg.queue_capacity);
+        bus.handle().set_sequence_numbers(cfg.sequence_numbers);
+        bus.handle().set_buffer_pre_seal(cfg.buffer_pre_seal);
+        bus.handle().set_rate_limits(&cfg.rate_limits);
+        bus.handle().set_dedup(&cfg.dedup);
+        if let Some(every) = wire_debug_from_env() {
+            bus.handle().set_wire_debug(every);
+        }
         let stop_flag = __new_stop_flag();
-        Self {
-            _cfg: cfg,
+        let runtime = cfg.topology.worker_threads.and_then(OwnedRuntime::build);
+        Ok(Self {
+            shared: std::sync::Arc::new(AppShared::new(cfg)),
             bus,
             tasks: Vec::new(),
+            extra: Vec::new(),
+            only: None,
+            declared: crate::wiring::Declared::default(),
+            enabled: None,
+            disabled: Vec::new(),
             started: false,
             stop_flag,
             startup_barrier: None,
-        }
+            runtime,
+            message_types: Vec::new(),
+        })
     }
 
     // 框架配置仅能在 new() 时提供；运行期不支持修改。
@@ -40,65 +226,470 @@ impl App {
         inventory::iter::<__RegisteredFactory>.into_iter().collect()
     }
 
+    // 自动发现的工厂（按 only 过滤）
+    fn auto_factories(&self) -> Vec<Box<dyn ComponentFactory>> {
+        Self::discover_factories()
+            .into_iter()
+            .map(|reg| (reg.create)())
+            .filter(|f| {
+                self.only
+                    .as_ref()
+                    .is_none_or(|only| only.contains(&f.type_name()))
+            })
+            .filter(|f| self.selected(f.type_name()))
+            .collect()
+    }
+
+    fn selected(&self, name: &str) -> bool {
+        self.enabled
+            .as_ref()
+            .is_none_or(|en| en.iter().any(|p| component_matches(name, p)))
+            && !self.disabled.iter().any(|p| component_matches(name, p))
+    }
+
+    // 未匹配任何自动发现组件的名称多为拼写错误
+    fn warn_unmatched_selection(&self) {
+        let names: Vec<_> = Self::discover_factories()
+            .into_iter()
+            .map(|reg| (reg.create)().type_name())
+            .collect();
+        let patterns = self.enabled.iter().flatten().chain(&self.disabled);
+        for p in patterns {
+            if !names.iter().any(|n| component_matches(n, p)) {
+                tracing::warn!(component = %p, "component selection matches no discovered component");
+            }
+        }
+    }
+
+    /// 不启动自动发现的组件 `name`（完整类型名，或末段类型名如 `Pricer`）；显式添加的组件不受影响。须在 `start()` 之前调用。
+    pub fn disable_component(&mut self, name: impl Into<String>) -> &mut Self {
+        self.disabled.push(name.into());
+        self
+    }
+
+    /// 仅启动列出的自动发现组件（名称规则同 [`disable_component`](Self::disable_component)，可多次调用累加）。
+    pub fn enable_component(&mut self, name: impl Into<String>) -> &mut Self {
+        self.enabled.get_or_insert_with(Vec::new).push(name.into());
+        self
+    }
+
+    /// 显式添加一个组件实例（不经 inventory 自动发现），用于框架内置的可选组件。
+    ///
+    /// 必须在 `start()` 之前调用；启动后调用将被忽略并记录 warn。
+    pub fn add_component<C: Component>(&mut self, component: C) -> &mut Self {
+        if self.started {
+            tracing::warn!(
+                component = std::any::type_name::<C>(),
+                "add_component after start ignored"
+            );
+            return self;
+        }
+        self.add_factory(Box::new(InstanceFactory::new(component)));
+        self
+    }
+
+    /// 向组件 `C` 发送控制指令（不经数据队列）；组件尚未启动、不存在或意图队列已满时返回 `false`。
+    pub fn control<C: Component>(&self, c: crate::component::Control) -> bool {
+        self.control_by_name(std::any::type_name::<C>(), c)
+    }
+
+    /// 同 [`control`](Self::control)，按组件名（`type_name`，与自省快照一致）寻址，供运维入口使用。
+    pub fn control_by_name(&self, component: &str, c: crate::component::Control) -> bool {
+        self.shared.controls.send(component, c)
+    }
+
+    /// 仅停止组件 `C`：发出该组件的停机信号（同 `#[handle(on_error = stop_component)]`），其 handler / active 结束、
+    /// `#[stop]` 照常执行，其余组件继续运行。返回时该组件任务已完全退出；已退出的组件直接返回 `Ok`。
+    ///
+    /// # Errors
+    /// 组件不存在（未启动或未被选择）时返回 [`MicrobusError::Config`]。
+    pub async fn stop_component<C: Component>(&self) -> Result<()> {
+        self.stop_component_by_name(std::any::type_name::<C>())
+            .await
+    }
+
+    /// 同 [`stop_component`](Self::stop_component)，按组件名（`type_name`，与自省快照一致）寻址，供运维入口使用。
+    ///
+    /// # Errors
+    /// 组件不存在时返回 [`MicrobusError::Config`]。
+    pub async fn stop_component_by_name(&self, component: &str) -> Result<()> {
+        let plane = self
+            .shared
+            .controls
+            .get(component)
+            .ok_or_else(|| MicrobusError::Config(format!("unknown component `{component}`")))?;
+        tracing::info!(component, "stopping component");
+        plane.halt.trigger();
+        plane.exited.wait().await;
+        Ok(())
+    }
+
+    /// 注册异步闭包 handler：总线上的每条 `T` 依次交给 `f` 处理（上一条的 future 完成后才取下一条）。
+    ///
+    /// 适用于脚本、测试与顶层胶水代码，无需定义组件结构体；闭包装配为独立组件，与 `add_component` 相同须在 `start()` 之前调用。
+    /// 需要发布时在闭包中持有 [`bus_handle`](Self::bus_handle)。
+    pub fn on<T: Send + Sync + 'static>(&mut self, f: impl crate::pipeline::OnFn<T>) -> &mut Self {
+        self.add_component(crate::pipeline::OnHandler::new(f))
+    }
+
+    /// 添加一条声明式流水线（见 [`crate::pipeline`]）；与 `add_component` 相同，须在 `start()` 之前调用。
+    pub fn add_pipeline(&mut self, pipeline: crate::pipeline::Assembled) -> &mut Self {
+        if self.started {
+            tracing::warn!("add_pipeline after start ignored");
+            return self;
+        }
+        self.extra.extend(pipeline.factories);
+        self
+    }
+
+    pub(crate) fn add_factory(&mut self, factory: Box<dyn ComponentFactory>) {
+        self.extra.push(factory);
+    }
+
+    /// 为类型 `T` 登记附加尺寸估算（如 `Vec` / `String` 的堆上容量），计入按类型字节统计。
+    ///
+    /// 仅 `bus-metrics` 特性下可用；估算在发布路径同步执行，须保持廉价。
+    #[cfg(feature = "bus-metrics")]
+    pub fn message_size_hint<T: Send + Sync + 'static>(&mut self, f: fn(&T) -> usize) -> &mut Self {
+        self.bus.handle().set_size_hint::<T>(f);
+        self
+    }
+
+    /// 把小型 `Copy` 类型 `T`（行情 tick、计数器等）登记为内联消息：按值经订阅队列复制投递，
+    /// 发布时不再分配 `Arc`。handler 收到的仍是 `&T`；经 `Subscription::recv` 取 `Arc<T>` 时在接收方装箱。
+    ///
+    /// 须在 `start()` 之前调用；启用 tap（录制、测试探针等）或封印前缓冲时对应发布仍走共享路径。
+    pub fn inline_message<T: Copy + Send + Sync + 'static>(&mut self) -> &mut Self {
+        if self.started {
+            tracing::warn!(
+                message_type = std::any::type_name::<T>(),
+                "inline_message after start ignored"
+            );
+            return self;
+        }
+        self.bus.handle().set_inline::<T>();
+        self
+    }
+
+    /// 启用组件状态快照（`#[snapshot]` 钩子）；须在 `start()` 之前调用。
+    #[cfg(feature = "snapshot")]
+    pub fn snapshots(&mut self, snapshots: crate::snapshot::Snapshots) -> &mut Self {
+        *self.shared.snapshots.write() = Some(std::sync::Arc::new(snapshots));
+        self
+    }
+
+    /// 启用故障注入（见 [`chaos`](crate::chaos)）；须在 `start()` 之前调用。
+    #[cfg(feature = "chaos")]
+    pub fn inject_faults(&mut self, chaos: crate::chaos::Chaos) -> &mut Self {
+        if self.started {
+            tracing::warn!("inject_faults after start ignored");
+            return self;
+        }
+        *self.shared.chaos.write() = Some(std::sync::Arc::new(chaos));
+        self
+    }
+
+    /// handler 失败时写入故障现场包（见 [`crash`](crate::crash)）；须在 `start()` 之前调用。
+    #[cfg(feature = "crash-bundle")]
+    pub fn crash_bundles(&mut self, bundles: crate::crash::CrashBundles) -> &mut Self {
+        *self.shared.crash_bundles.write() = Some(std::sync::Arc::new(bundles));
+        self
+    }
+
+    /// 任何线程 panic 时（含被捕获的 handler panic），先把本 App 的自省快照（组件状态、有积压的订阅队列）
+    /// 以 error 级写入日志（target `mmg_microbus::panic`），再交由原有 panic 钩子处理。
+    /// 进程级钩子在首次调用时安装并串接原钩子；App 释放后对应输出自动停止。
+    pub fn dump_on_panic(&mut self) -> &mut Self {
+        crate::postmortem::register(&self.shared, &self.bus.handle());
+        self
+    }
+
+    /// 运行期切换线路调试：每 `sample_every` 次组件发布以 trace 级（target `mmg_microbus::wire`）
+    /// 记录一次类型名、订阅者数与来源组件；`0` 关闭。初始值取自环境变量 `MICROBUS_WIRE_DEBUG`。
+    pub fn set_wire_debug(&self, sample_every: u32) {
+        self.bus.handle().set_wire_debug(sample_every);
+    }
+
+    /// 登记由组件之外（宿主代码经 `BusHandle`、桥的对端等）发布的类型 `T`，装配校验视其为已有发布方。
+    pub fn declare_publisher<T: 'static>(&mut self) -> &mut Self {
+        self.declared
+            .publishers
+            .push(crate::wiring::MessageType::of::<T>());
+        self
+    }
+
+    /// 登记由组件之外（宿主代码经 `BusHandle` 订阅、桥转发到对端等）订阅的类型 `T`，装配校验视其为已有订阅方。
+    pub fn declare_subscriber<T: 'static>(&mut self) -> &mut Self {
+        self.declared
+            .subscribers
+            .push(crate::wiring::MessageType::of::<T>());
+        self
+    }
+
+    /// 装配校验：找出被 `#[handle]` 订阅、却没有任何组件发布（亦非框架事件、未经
+    /// [`declare_publisher`](Self::declare_publisher) 登记）的消息类型。
+    ///
+    /// 存在无法静态判定发布类型的组件（手写 `Component`、返回 `ErasedEvent` 等）时缺口只记录 warn 并返回 `Ok`；
+    /// 组件发布却无人订阅（未经 [`declare_subscriber`](Self::declare_subscriber) 登记）的类型同样只记录 warn。
+    /// 需一律报错时用 [`validate_strict`](Self::validate_strict)。
+    ///
+    /// # Errors
+    /// 存在确定无人发布的订阅类型时返回 [`MicrobusError::Config`](crate::error::MicrobusError::Config)。
+    pub fn validate(&self) -> Result<()> {
+        self.check_wiring(false)
+    }
+
+    /// 同 [`validate`](Self::validate)，但无法判定的发布方不再豁免缺口，无人订阅的发布类型同样报错。
+    /// `AppConfig::strict_wiring` 开启时 `start()` 先执行本校验。
+    ///
+    /// # Errors
+    /// 存在无人发布的订阅类型或无人订阅的发布类型时返回 [`MicrobusError::Config`](crate::error::MicrobusError::Config)。
+    pub fn validate_strict(&self) -> Result<()> {
+        self.check_wiring(true)
+    }
+
+    fn check_wiring(&self, strict: bool) -> Result<()> {
+        crate::wiring::check(&self.component_wirings(), &self.declared, strict)
+    }
+
+    // 将要启动的组件（自动发现 + 显式添加）及其装配清单；只创建工厂，不构建组件
+    fn component_wirings(&self) -> Vec<(&'static str, Option<crate::wiring::Wiring>)> {
+        self.auto_factories()
+            .iter()
+            .chain(self.extra.iter())
+            .map(|f| (f.type_name(), f.wiring()))
+            .collect()
+    }
+
+    /// 试运行：执行组件发现、组件选择、依赖排序与装配校验（`strict_wiring` 开启时为严格校验），返回启动计划而不构建组件、
+    /// 不派生任何任务。用于 CI 与部署流水线中的 `--check` 式启动检查；须在 `start()` 之前调用。
+    ///
+    /// # Errors
+    /// 组件依赖缺失或成环，或装配校验失败（见 [`validate`](Self::validate) / [`validate_strict`](Self::validate_strict)）。
+    pub fn plan(&self) -> Result<crate::wiring::Plan> {
+        self.warn_unmatched_selection();
+        let auto = self.auto_factories();
+        let phases = dependency_phases(auto.iter().chain(self.extra.iter()))?;
+        let components = self.component_wirings();
+        crate::wiring::check(&components, &self.declared, self.shared.cfg.strict_wiring)?;
+        Ok(crate::wiring::Plan::new(
+            components,
+            phases,
+            self.shared.cfg.clone(),
+        ))
+    }
+
+    // 限定自动发现组件集合（按类型名）；显式 add_component 的组件不受影响
+    #[cfg(feature = "testing")]
+    pub(crate) fn select_components(&mut self, names: Vec<&'static str>) {
+        self.only = Some(names);
+    }
+
     async fn await_startup_and_seal(
         &self,
         barrier_ref: &std::sync::Arc<crate::component::StartupBarrier>,
     ) {
         // Wait until all components arrived OR startup is marked failed.
-        crate::component::__startup_wait_all(barrier_ref).await;
+        self.wait_reporting(crate::component::__startup_wait_all(barrier_ref))
+            .await;
         // Only seal the bus when startup succeeded. If startup failed, components may not have
         // finished pre-barrier subscription steps; sealing here would cause panics on subscribe.
-        if !crate::component::__startup_failed(barrier_ref) {
+        if crate::component::__startup_failed(barrier_ref) {
+            let dropped = self.bus.handle().discard_pre_seal();
+            if dropped > 0 {
+                tracing::warn!(
+                    dropped,
+                    "startup failed; buffered pre-seal publishes discarded"
+                );
+            }
+        } else {
             self.bus.handle().seal();
+            let replayed = self.bus.handle().replay_pre_seal().await;
+            if replayed > 0 {
+                tracing::debug!(replayed, "buffered pre-seal publishes delivered");
+            }
+            self.bus
+                .handle()
+                .publish_type_nowait(AppSealed {
+                    components: barrier_ref.total(),
+                })
+                .await;
+        }
+    }
+
+    // 等待启动屏障上的条件；期间按配置周期报告未到达屏障的组件
+    async fn wait_reporting(&self, wait: impl std::future::Future<Output = ()>) {
+        if let Some(every) = self.shared.cfg.startup_progress_interval {
+            // 等待期间周期性报告未到达屏障的组件，便于定位卡住的 init
+            tokio::pin!(wait);
+            let t0 = crate::rt::Instant::now();
+            loop {
+                tokio::select! {
+                    () = &mut wait => break,
+                    () = crate::rt::sleep(every) => {
+                        let p = self.shared.components.startup_progress();
+                        tracing::warn!(
+                            elapsed_ms = t0.elapsed().as_millis(),
+                            arrived = ?p.arrived,
+                            pending = ?p.pending,
+                            "startup barrier still waiting"
+                        );
+                    }
+                }
+            }
+        } else {
+            wait.await;
         }
     }
 
     fn spawn_components(
         &mut self,
-        factories: &[&__RegisteredFactory],
+        factories: Vec<Box<dyn ComponentFactory>>,
         bus_handle: &BusHandle,
         startup_barrier: &std::sync::Arc<crate::component::StartupBarrier>,
     ) {
-        for reg in factories {
-            let factory = (reg.create)();
-            let name = factory.type_name().to_string();
+        for factory in factories {
+            let name = factory.type_name();
+            self.shared.components.register(name);
+            let shared_clone = self.shared.clone();
             let stop_clone = self.stop_flag.clone();
             let bus_clone = bus_handle.clone();
             let barrier_clone = startup_barrier.clone();
+            let exit = ExitGuard(self.shared.controls.register(name));
             let fut = async move {
+                let _exit = exit;
                 match factory.build(bus_clone.clone()).await {
                     Ok(comp) => {
                         // 注意：ComponentContext::new_with_service 仅在 crate 内部可见，
                         // 组件上下文的构造必须走 App 流程以确保启动屏障与总线 seal 顺序正确。
                         let ctx = ComponentContext::new_with_service(
+                            name,
+                            shared_clone.clone(),
                             bus_clone.clone(),
                             stop_clone.clone(),
                             barrier_clone.clone(),
                         );
-                        if let Err(e) = comp.run(ctx).await {
-                            tracing::error!(component = %name, kind = %factory.type_name(), error = %e, "component exited with error");
+                        match comp.run(ctx).await {
+                            Ok(()) => {
+                                shared_clone
+                                    .components
+                                    .set_status(name, ComponentStatus::Stopped);
+                                bus_clone
+                                    .publish_type_nowait(ComponentStopped { component: name })
+                                    .await;
+                            }
+                            Err(e) => {
+                                tracing::error!(component = %name, kind = %factory.type_name(), error = %e, "component exited with error");
+                                let error = e.to_string();
+                                // 仅计入尚未到达屏障的失败；已到达者的错误多为连带停机
+                                if shared_clone.components.is_starting(name)
+                                    && crate::component::__startup_failed(&barrier_clone)
+                                {
+                                    shared_clone.record_start_error(name, FailurePhase::Run, e);
+                                }
+                                shared_clone
+                                    .components
+                                    .set_status(name, ComponentStatus::Failed(error.clone()));
+                                bus_clone
+                                    .publish_type_nowait(ComponentFailed {
+                                        component: name,
+                                        phase: FailurePhase::Run,
+                                        error,
+                                    })
+                                    .await;
+                            }
                         }
                     }
                     Err(e) => {
                         tracing::error!(component = %name, kind = %factory.type_name(), error = %e, "failed to build component");
                         // 构建失败视为启动失败
                         crate::component::__startup_mark_failed_barrier(&barrier_clone);
+                        let error = e.to_string();
+                        shared_clone.record_start_error(name, FailurePhase::Build, e);
+                        shared_clone
+                            .components
+                            .set_status(name, ComponentStatus::Failed(error.clone()));
+                        bus_clone
+                            .publish_type_nowait(ComponentFailed {
+                                component: name,
+                                phase: FailurePhase::Build,
+                                error,
+                            })
+                            .await;
                     }
                 }
             };
-            let h = tokio::spawn(fut);
+            let h = match self.runtime.as_ref().and_then(OwnedRuntime::handle) {
+                Some(rt) => crate::rt::spawn_on(rt, fut),
+                None => crate::rt::spawn(fut),
+            };
             self.tasks.push(h);
         }
     }
 
+    // 封印后生成消息类型登记表，并以 debug 级逐类型输出（尺寸降序，便于发现按值发布的大结构体）
+    fn build_message_types(&mut self, wirings: &[(&'static str, Option<crate::wiring::Wiring>)]) {
+        self.message_types = crate::wiring::message_types(
+            wirings,
+            &self.declared,
+            self.bus.handle().subscription_types(),
+        );
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            for t in &self.message_types {
+                tracing::debug!(
+                    message_type = t.type_name,
+                    size = t.size,
+                    publishers = ?t.publishers,
+                    subscribers = ?t.subscribers,
+                    capacity = t.capacity,
+                    "message type registry"
+                );
+            }
+        }
+    }
+
+    // 封印后的运行期监控任务（按配置启用）
+    fn spawn_monitors(&mut self) {
+        if let Some(lag) = self.shared.cfg.subscriber_lag.clone() {
+            let fut =
+                crate::monitor::run_lag_monitor(self.bus.handle(), lag, self.stop_flag.clone());
+            self.tasks.push(crate::rt::spawn(fut));
+        }
+        if self.shared.cfg.stop_on_completion {
+            let completion = self.shared.completion.clone();
+            if completion.has_sources() {
+                let fut = crate::monitor::run_completion_monitor(
+                    self.bus.handle(),
+                    completion,
+                    self.stop_flag.clone(),
+                );
+                self.tasks.push(crate::rt::spawn(fut));
+            } else {
+                tracing::warn!("stop_on_completion enabled but no source component registered; completion is never detected");
+            }
+        }
+    }
+
     async fn handle_start_failure(
         &mut self,
         barrier: std::sync::Arc<crate::component::StartupBarrier>,
     ) -> Result<()> {
         if crate::component::__startup_failed(&barrier) {
+            // 失败组件先标记屏障再返回错误：等到无组件仍在启动（或超时），一次汇总全部失败
+            let deadline = crate::rt::Instant::now() + START_ERROR_GRACE;
+            while !self.shared.components.startup_progress().pending.is_empty() {
+                tokio::select! {
+                    () = self.shared.start_error_ready.notified() => {}
+                    () = crate::rt::sleep_until(deadline) => break,
+                }
+            }
             self.stop();
             self.started = false;
-            return Err(MicrobusError::Other("app start aborted: init/build failed"));
+            let failures = std::mem::take(&mut *self.shared.start_errors.lock());
+            if failures.is_empty() {
+                return Err(MicrobusError::Other("app start aborted: init/build failed"));
+            }
+            return Err(MicrobusError::Startup { failures });
         }
         Ok(())
     }
@@ -114,19 +705,52 @@ impl App {
         if self.started {
             return Ok(());
         }
+        if self.shared.cfg.strict_wiring {
+            self.validate_strict()?;
+        }
         // 自动发现：inventory 收集的所有工厂；按 kind 去重（单例模式）。
+        self.warn_unmatched_selection();
         let bus_handle = self.bus.handle();
-        let factories: Vec<&__RegisteredFactory> = Self::discover_factories();
+        let mut factories = self.auto_factories();
+        let phases = dependency_phases(factories.iter().chain(self.extra.iter()))?;
+        factories.append(&mut self.extra);
+        let wirings: Vec<_> = factories
+            .iter()
+            .map(|f| (f.type_name(), f.wiring()))
+            .collect();
+        if phases.len() > 1 {
+            let names: Vec<Vec<_>> = phases
+                .iter()
+                .map(|p| p.iter().map(|&i| factories[i].type_name()).collect())
+                .collect();
+            tracing::info!(phases = ?names, "starting components in dependency order");
+        }
         let total = factories.len();
         let startup_barrier = __new_startup_barrier(total);
         self.startup_barrier = Some(startup_barrier.clone());
-        self.spawn_components(&factories, &bus_handle, &startup_barrier);
+        // 按依赖阶段派生：后一阶段等前一阶段全部到达屏障（完成 init 与订阅装配）后才开始构建
+        let mut slots: Vec<_> = factories.into_iter().map(Some).collect();
+        let mut spawned = 0;
+        for phase in phases {
+            if spawned > 0 {
+                self.wait_reporting(startup_barrier.wait_arrived(spawned))
+                    .await;
+                if crate::component::__startup_failed(&startup_barrier) {
+                    break;
+                }
+            }
+            spawned += phase.len();
+            let batch = phase.iter().filter_map(|&i| slots[i].take()).collect();
+            self.spawn_components(batch, &bus_handle, &startup_barrier);
+        }
         let barrier_ref = self
             .startup_barrier
             .as_ref()
             .expect("startup_barrier must be set before waiting");
         self.await_startup_and_seal(barrier_ref).await; // 阶段：等待并封印
         self.handle_start_failure(barrier_ref.clone()).await?; // 阶段：失败分支
+        self.build_message_types(&wirings);
+        self.spawn_monitors();
         self.started = true;
         Ok(())
     }
@@ -139,17 +763,32 @@ impl App {
         rest.append(&mut self.tasks);
         // 安排后台收割：给予极短宽限以便组件在其任务内执行同步 stop 钩子，然后强制 abort 并回收 JoinHandle
         for h in rest {
-            tokio::spawn(async move {
-                let grace = std::time::Duration::from_millis(50);
-                let _ = tokio::time::sleep(grace).await;
-                if !h.is_finished() {
-                    h.abort();
-                }
-                let _ = h.await;
+            crate::rt::spawn(async move {
+                crate::rt::sleep(std::time::Duration::from_millis(50)).await;
+                h.abort().await;
             });
         }
         self.started = false;
     }
+    /// 运行期快照：组件状态、订阅拓扑与队列深度、按类型计数（`bus-metrics`）。
+    #[must_use]
+    pub fn introspect(&self) -> Snapshot {
+        crate::introspect::snapshot(&self.shared, &self.bus.handle())
+    }
+    /// 消息类型登记表：装配清单与实际订阅中出现的每个类型的 `size_of`、发布方、订阅方与队列容量，按尺寸降序。
+    ///
+    /// `start()` 成功后可用（之前为空）；启动时同一内容以 debug 级输出。
+    #[must_use]
+    pub fn message_types(&self) -> &[crate::introspect::MessageTypeInfo] {
+        &self.message_types
+    }
+    /// 启动屏障进度：哪些组件已到达、哪些仍未到达。
+    ///
+    /// 可在 `start()` 超时（如外层 `tokio::time::timeout` 取消）后调用以定位卡住的组件。
+    #[must_use]
+    pub fn startup_progress(&self) -> StartupProgress {
+        self.shared.components.startup_progress()
+    }
     #[must_use]
     pub fn bus_handle(&self) -> BusHandle {
         self.bus.handle()
@@ -158,6 +797,29 @@ impl App {
     pub const fn is_started(&self) -> bool {
         self.started
     }
+    /// 等待停止信号：`stop()`、`#[handle(on_error = stop_app)]` 的 handler 出错或自然完成（`stop_on_completion`）；
+    /// 随后仍需调用 `stop()` 回收任务。
+    pub async fn wait_for_stop(&self) {
+        self.stop_flag.wait().await;
+    }
+    /// 是否已检测到自然完成（见 `AppConfig::stop_on_completion`）。
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        self.shared.completion.is_completed()
+    }
+}
+
+// MICROBUS_WIRE_DEBUG：未设置 / 0 / false 关闭；1 或 true 记录全部；N 表示每 N 次采样一次
+fn wire_debug_from_env() -> Option<u32> {
+    let v = std::env::var("MICROBUS_WIRE_DEBUG").ok()?;
+    match v.trim() {
+        "" | "0" | "false" | "off" => None,
+        "true" | "on" => Some(1),
+        n => n.parse().ok().or_else(|| {
+            tracing::warn!(value = n, "invalid MICROBUS_WIRE_DEBUG ignored");
+            None
+        }),
+    }
 }
 
 // tests are covered in integration suite; unit tests omitted here
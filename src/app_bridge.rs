@@ -0,0 +1,112 @@
+//! 进程内 App 间桥：把源 App 总线上的选定类型转发到另一 App 的总线，供各自独立启停的子系统交换消息。
+//!
+//! 作为组件加入源 App（`app_a.add_component(bridge)`），随源 App 启停；目标 App 未启动或已停止时，
+//! 转发按目标总线的常规路由处理（无订阅者即丢弃，已关闭订阅计入其丢弃统计）。消息以 `Arc` 共享，不复制。
+//! 同一类型只应单向转发，双向转发会在两 App 间往复回环。
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::app::App;
+use crate::bus::BusHandle;
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+use crate::rt::JoinHandle;
+
+type SpawnRelay =
+    Box<dyn FnOnce(&ComponentContext, BusHandle, Arc<AtomicU64>) -> JoinHandle<()> + Send + Sync>;
+
+/// App 间桥计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct AppBridgeHandle {
+    relayed: Arc<AtomicU64>,
+}
+
+impl AppBridgeHandle {
+    /// 已转发到目标总线的消息数。
+    #[must_use]
+    pub fn relayed(&self) -> u64 {
+        self.relayed.load(Ordering::Relaxed)
+    }
+}
+
+pub struct AppBridge {
+    source: BusHandle,
+    target: BusHandle,
+    relays: Vec<SpawnRelay>,
+    handle: AppBridgeHandle,
+}
+
+impl AppBridge {
+    /// 建立 `source` → `target` 的桥；须随后加入 `source`（加入其他 App 时启动失败）。
+    #[must_use]
+    pub fn connect(source: &App, target: &App) -> Self {
+        Self {
+            source: source.bus_handle(),
+            target: target.bus_handle(),
+            relays: Vec::new(),
+            handle: AppBridgeHandle::default(),
+        }
+    }
+    /// 转发类型 `T`：订阅源总线上的全部 `T` 并在目标总线发布。
+    #[must_use]
+    pub fn forward<T: Send + Sync + 'static>(mut self) -> Self {
+        self.relays.push(Box::new(|ctx, target, relayed| {
+            let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+            let ctx = ctx.__fork();
+            crate::rt::spawn(async move {
+                loop {
+                    tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => break,
+                        msg = sub.recv() => {
+                            let Some(msg) = msg else { break };
+                            relayed.fetch_add(1, Ordering::Relaxed);
+                            target.publish_any_arc(msg).await;
+                        }
+                    }
+                }
+            })
+        }));
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> AppBridgeHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for AppBridge {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            source,
+            target,
+            relays,
+            handle,
+        } = *self;
+        if !ctx.bus().same_bus(&source) {
+            crate::component::__startup_mark_failed(&ctx);
+            return Err(MicrobusError::Config(
+                "app bridge must be added to its source app".to_owned(),
+            ));
+        }
+        if target.same_bus(&source) {
+            crate::component::__startup_mark_failed(&ctx);
+            return Err(MicrobusError::Config(
+                "app bridge source and target are the same app".to_owned(),
+            ));
+        }
+        // 订阅须在启动屏障（总线封印）之前登记
+        let workers: Vec<_> = relays
+            .into_iter()
+            .map(|f| f(&ctx, target.clone(), handle.relayed.clone()))
+            .collect();
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        crate::component::__recv_stop(&ctx).await;
+        for w in workers {
+            w.abort().await;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,580 @@
+//! 跨进程总线桥（特性 `bridge-tcp` / `bridge-ipc`）：把选定类型经编解码器转发到另一进程的总线。
+//!
+//! 传输：TCP（跨主机）；同机 sidecar 可用 IPC（unix 上为 Unix 域套接字，Windows 上为命名管道），
+//! 类型转发配置与 TCP 完全一致，仅端点构造不同。
+//!
+//! - [`BridgeOut`]：订阅登记的类型，编码后写往远端；未连接时丢弃并计数，按间隔自动重连。
+//! - [`BridgeIn`]：监听连接，解码后在本地总线重新发布（走常规路由与背压）。
+//!
+//! 两端均通过 `App::add_component` 显式启用，编解码器复用 [`MessageCodec`]（字节接口），默认按类型名（`std::any::type_name`）匹配，
+//! 两端须使用同一版本的类型定义；[`BusMessage`] 类型可经 `forward_message` / `receive_message` 改按稳定名称匹配，
+//! 帧内类型名为 `NAME@VERSION`，接收端按本地模式检查版本兼容性（不兼容的帧丢弃并计数）。
+//! 同一类型只应单向转发，双向转发会在两进程间往复回环。
+//!
+//! 帧格式：`u32 BE 帧长 | u16 BE 类型名长 | 类型名 | 编码字节`。
+use std::collections::HashMap;
+use std::net::SocketAddr;
+#[cfg(all(feature = "bridge-ipc", unix))]
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::bus::ErasedEvent;
+use crate::codec::{BusMessage, MessageCodec, Schema, VersionGate};
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+
+// 单帧上限：超出视为协议错误并断开连接
+const MAX_FRAME: usize = 16 * 1024 * 1024;
+// 待发送帧队列：满时订阅侧 await，背压传回本地发布方
+const OUT_QUEUE: usize = 1024;
+#[cfg(feature = "bridge-tcp")]
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+type SpawnForward =
+    Box<dyn FnOnce(&ComponentContext, mpsc::Sender<Vec<u8>>) -> JoinHandle<()> + Send + Sync>;
+type DecodeFn = Box<dyn Fn(&[u8]) -> std::result::Result<ErasedEvent, String> + Send + Sync>;
+type BoxWrite = Box<dyn AsyncWrite + Unpin + Send>;
+type BoxRead = Box<dyn AsyncRead + Unpin + Send>;
+
+/// 桥接计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct BridgeHandle {
+    frames: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl BridgeHandle {
+    /// 出站：已写出的帧数；入站：已在本地发布的消息数。
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+    /// 出站：因未连接或写失败而丢弃的帧数；入站：类型未登记或解码失败的帧数。
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+enum Target {
+    #[cfg(feature = "bridge-tcp")]
+    Tcp(String),
+    #[cfg(all(feature = "bridge-ipc", unix))]
+    Unix(PathBuf),
+    #[cfg(all(feature = "bridge-ipc", windows))]
+    Pipe(String),
+}
+
+impl Target {
+    async fn connect(&self) -> std::io::Result<BoxWrite> {
+        match self {
+            #[cfg(feature = "bridge-tcp")]
+            Self::Tcp(addr) => {
+                let stream =
+                    tokio::time::timeout(CONNECT_TIMEOUT, tokio::net::TcpStream::connect(addr))
+                        .await
+                        .map_err(|_| {
+                            std::io::Error::new(std::io::ErrorKind::TimedOut, "connect timed out")
+                        })??;
+                stream.set_nodelay(true)?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(all(feature = "bridge-ipc", unix))]
+            Self::Unix(path) => Ok(Box::new(tokio::net::UnixStream::connect(path).await?)),
+            // 管道实例全忙（ERROR_PIPE_BUSY）时返回错误，按重连间隔重试
+            #[cfg(all(feature = "bridge-ipc", windows))]
+            Self::Pipe(name) => Ok(Box::new(
+                tokio::net::windows::named_pipe::ClientOptions::new().open(name)?,
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "bridge-tcp")]
+            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+            #[cfg(all(feature = "bridge-ipc", unix))]
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+            #[cfg(all(feature = "bridge-ipc", windows))]
+            Self::Pipe(name) => write!(f, "pipe://{name}"),
+        }
+    }
+}
+
+/// 出站桥：把登记类型转发到远端 [`BridgeIn`]。
+pub struct BridgeOut {
+    target: Target,
+    forwards: Vec<SpawnForward>,
+    handle: BridgeHandle,
+}
+
+impl BridgeOut {
+    fn new(target: Target) -> Self {
+        Self {
+            target,
+            forwards: Vec::new(),
+            handle: BridgeHandle::default(),
+        }
+    }
+    /// 连接远端 TCP 地址（`host:port`）；连接在启动后建立，失败不影响本地启动。
+    #[cfg(feature = "bridge-tcp")]
+    #[must_use]
+    pub fn connect_tcp(addr: impl Into<String>) -> Self {
+        Self::new(Target::Tcp(addr.into()))
+    }
+    /// 连接同机 [`BridgeIn::bind_ipc`] 端点：unix 上为套接字文件路径。
+    #[cfg(all(feature = "bridge-ipc", unix))]
+    #[must_use]
+    pub fn connect_ipc(path: impl Into<PathBuf>) -> Self {
+        Self::new(Target::Unix(path.into()))
+    }
+    /// 连接同机 [`BridgeIn::bind_ipc`] 端点：Windows 上为管道名（`\\.\pipe\<name>`）。
+    #[cfg(all(feature = "bridge-ipc", windows))]
+    #[must_use]
+    pub fn connect_ipc(name: impl Into<String>) -> Self {
+        Self::new(Target::Pipe(name.into()))
+    }
+    /// 转发类型 `T`：订阅本地总线上的全部 `T`，经 `codec` 编码后发送。
+    #[must_use]
+    pub fn forward<T: Send + Sync + 'static>(self, codec: impl MessageCodec<T>) -> Self {
+        self.push_forward(std::any::type_name::<T>().to_owned(), codec)
+    }
+    /// 同 `forward`，但帧内以 [`BusMessage::NAME`] 标识类型（对端用 `receive_message`）。
+    #[must_use]
+    pub fn forward_message<T: BusMessage>(self, codec: impl MessageCodec<T>) -> Self {
+        self.push_forward(Schema::of::<T>().wire_name(), codec)
+    }
+    fn push_forward<T: Send + Sync + 'static>(
+        mut self,
+        name: String,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.forwards.push(Box::new(move |ctx, tx| {
+            let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+            let ctx = ctx.__fork();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => break,
+                        msg = sub.recv() => {
+                            let Some(msg) = msg else { break };
+                            let frame = encode_frame(&name, &codec.encode_bytes(&msg));
+                            if tx.send(frame).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            })
+        }));
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> BridgeHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for BridgeOut {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            target,
+            forwards,
+            handle,
+        } = *self;
+        // 订阅须在启动屏障（总线封印）之前登记
+        let (tx, mut rx) = mpsc::channel(OUT_QUEUE);
+        let workers: Vec<_> = forwards.into_iter().map(|f| f(&ctx, tx.clone())).collect();
+        drop(tx);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        let mut conn = connect_logged(&target).await;
+        let mut retry_at = Instant::now() + RECONNECT_INTERVAL;
+        loop {
+            tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                frame = rx.recv() => {
+                    let Some(frame) = frame else { break };
+                    if conn.is_none() && Instant::now() >= retry_at {
+                        conn = connect_logged(&target).await;
+                        retry_at = Instant::now() + RECONNECT_INTERVAL;
+                    }
+                    let Some(w) = conn.as_mut() else {
+                        handle.dropped.fetch_add(1, Ordering::Relaxed);
+                        continue;
+                    };
+                    match w.write_all(&frame).await {
+                        Ok(()) => {
+                            handle.frames.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(e) => {
+                            tracing::warn!(target = %target, error = %e, "bridge write failed; reconnecting");
+                            handle.dropped.fetch_add(1, Ordering::Relaxed);
+                            conn = None;
+                        }
+                    }
+                }
+            }
+        }
+        for w in workers {
+            w.abort();
+        }
+        Ok(())
+    }
+}
+
+async fn connect_logged(target: &Target) -> Option<BoxWrite> {
+    match target.connect().await {
+        Ok(w) => {
+            tracing::info!(target = %target, "bridge connected");
+            Some(w)
+        }
+        Err(e) => {
+            tracing::warn!(target = %target, error = %e, "bridge connect failed");
+            None
+        }
+    }
+}
+
+fn encode_frame(type_name: &str, payload: &[u8]) -> Vec<u8> {
+    let name = type_name.as_bytes();
+    let name_len = u16::try_from(name.len()).unwrap_or(u16::MAX);
+    let name = &name[..usize::from(name_len)];
+    let len = 2 + name.len() + payload.len();
+    let mut frame = Vec::with_capacity(4 + len);
+    frame.extend_from_slice(&u32::try_from(len).unwrap_or(u32::MAX).to_be_bytes());
+    frame.extend_from_slice(&name_len.to_be_bytes());
+    frame.extend_from_slice(name);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+// 读取一帧：`Ok(None)` 表示对端正常关闭
+async fn read_frame(r: &mut BoxRead) -> std::io::Result<Option<(String, Vec<u8>)>> {
+    let mut len = [0u8; 4];
+    match r.read_exact(&mut len).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if !(2..=MAX_FRAME).contains(&len) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("bad frame length {len}"),
+        ));
+    }
+    let mut body = vec![0u8; len];
+    r.read_exact(&mut body).await?;
+    let name_len = usize::from(u16::from_be_bytes([body[0], body[1]]));
+    let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed frame");
+    let name = body.get(2..2 + name_len).ok_or_else(invalid)?;
+    let name = std::str::from_utf8(name).map_err(|_| invalid())?.to_owned();
+    let payload = body.split_off(2 + name_len);
+    Ok(Some((name, payload)))
+}
+
+enum Listener {
+    #[cfg(feature = "bridge-tcp")]
+    Tcp(std::net::TcpListener),
+    #[cfg(all(feature = "bridge-ipc", unix))]
+    Unix(std::os::unix::net::UnixListener, PathBuf),
+    // 管道实例须在运行时内创建，绑定时仅记录名称
+    #[cfg(all(feature = "bridge-ipc", windows))]
+    Pipe(String),
+}
+
+// 运行期监听端（已注册到 tokio）
+enum Acceptor {
+    #[cfg(feature = "bridge-tcp")]
+    Tcp(tokio::net::TcpListener),
+    #[cfg(all(feature = "bridge-ipc", unix))]
+    Unix(tokio::net::UnixListener, PathBuf),
+    #[cfg(all(feature = "bridge-ipc", windows))]
+    Pipe(String, tokio::net::windows::named_pipe::NamedPipeServer),
+}
+
+impl Listener {
+    fn endpoint(&self) -> String {
+        match self {
+            #[cfg(feature = "bridge-tcp")]
+            Self::Tcp(l) => l
+                .local_addr()
+                .map_or_else(|_| "tcp".to_owned(), |a| a.to_string()),
+            #[cfg(all(feature = "bridge-ipc", unix))]
+            Self::Unix(_, path) => path.display().to_string(),
+            #[cfg(all(feature = "bridge-ipc", windows))]
+            Self::Pipe(name) => name.clone(),
+        }
+    }
+    fn into_acceptor(self) -> std::io::Result<Acceptor> {
+        match self {
+            #[cfg(feature = "bridge-tcp")]
+            Self::Tcp(l) => Ok(Acceptor::Tcp(tokio::net::TcpListener::from_std(l)?)),
+            #[cfg(all(feature = "bridge-ipc", unix))]
+            Self::Unix(l, path) => Ok(Acceptor::Unix(tokio::net::UnixListener::from_std(l)?, path)),
+            #[cfg(all(feature = "bridge-ipc", windows))]
+            Self::Pipe(name) => {
+                let server = tokio::net::windows::named_pipe::ServerOptions::new()
+                    .first_pipe_instance(true)
+                    .create(&name)?;
+                Ok(Acceptor::Pipe(name, server))
+            }
+        }
+    }
+}
+
+impl Acceptor {
+    // 返回（读端, 对端描述）
+    async fn accept(&mut self) -> std::io::Result<(BoxRead, String)> {
+        match self {
+            #[cfg(feature = "bridge-tcp")]
+            Self::Tcp(l) => {
+                let (stream, peer) = l.accept().await?;
+                let _ = stream.set_nodelay(true);
+                Ok((Box::new(stream), peer.to_string()))
+            }
+            #[cfg(all(feature = "bridge-ipc", unix))]
+            Self::Unix(l, path) => {
+                let (stream, _) = l.accept().await?;
+                Ok((Box::new(stream), path.display().to_string()))
+            }
+            // 已连接实例交给读任务，随即创建下一实例继续等待
+            #[cfg(all(feature = "bridge-ipc", windows))]
+            Self::Pipe(name, server) => {
+                server.connect().await?;
+                let next = tokio::net::windows::named_pipe::ServerOptions::new().create(&*name)?;
+                Ok((Box::new(std::mem::replace(server, next)), name.clone()))
+            }
+        }
+    }
+    // 停机清理：删除自身创建的套接字文件
+    fn cleanup(&self) {
+        match self {
+            #[cfg(all(feature = "bridge-ipc", unix))]
+            Self::Unix(_, path) => {
+                let _ = std::fs::remove_file(path);
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+    }
+}
+
+#[cfg(all(feature = "bridge-ipc", unix))]
+fn remove_stale_socket(path: &std::path::Path) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    // 仅清理遗留的套接字文件，其它类型的同名文件保持原样并让绑定失败
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+/// 入站桥：接收远端 [`BridgeOut`] 的帧并在本地发布。
+pub struct BridgeIn {
+    listener: Listener,
+    local_addr: Option<SocketAddr>,
+    decoders: HashMap<&'static str, Decoder>,
+    handle: BridgeHandle,
+    finite: bool,
+}
+
+struct Decoder {
+    decode: DecodeFn,
+    // 仅 `receive_message` 登记的类型携带模式，用于版本检查
+    schema: Option<Schema>,
+}
+
+struct Inbound {
+    decoders: HashMap<&'static str, Decoder>,
+    gate: VersionGate,
+}
+
+impl BridgeIn {
+    fn new(listener: Listener, local_addr: Option<SocketAddr>) -> Self {
+        Self {
+            listener,
+            local_addr,
+            decoders: HashMap::new(),
+            handle: BridgeHandle::default(),
+            finite: false,
+        }
+    }
+    /// 作为有限数据源参与自然完成检测（`AppConfig::stop_on_completion`）：首个对端接入后，
+    /// 全部已接入的对端断开（EOF）即视为数据源结束（之后仍接受新连接）。
+    #[must_use]
+    pub const fn finite(mut self) -> Self {
+        self.finite = true;
+        self
+    }
+    /// 立即绑定 TCP 监听地址（端口 0 由系统分配，可经 `local_addr()` 取得）。
+    ///
+    /// # Errors
+    /// 地址不可绑定时返回 IO 错误。
+    #[cfg(feature = "bridge-tcp")]
+    pub fn bind_tcp(addr: impl Into<SocketAddr>) -> std::io::Result<Self> {
+        let listener = std::net::TcpListener::bind(addr.into())?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        Ok(Self::new(Listener::Tcp(listener), Some(local_addr)))
+    }
+    /// 立即绑定 Unix 域套接字（遗留的同名套接字文件会先被清理）；停机时删除套接字文件。
+    ///
+    /// # Errors
+    /// 路径不可绑定时返回 IO 错误。
+    #[cfg(all(feature = "bridge-ipc", unix))]
+    pub fn bind_ipc(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path = path.into();
+        remove_stale_socket(&path)?;
+        let listener = std::os::unix::net::UnixListener::bind(&path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(Listener::Unix(listener, path), None))
+    }
+    /// 登记命名管道（`\\.\pipe\<name>`）；管道在组件启动时创建，名称被占用按启动失败处理。
+    ///
+    /// # Errors
+    /// 当前实现不会失败（与 unix 版本签名保持一致）。
+    #[cfg(all(feature = "bridge-ipc", windows))]
+    pub fn bind_ipc(name: impl Into<String>) -> std::io::Result<Self> {
+        Ok(Self::new(Listener::Pipe(name.into()), None))
+    }
+    /// TCP 端点的实际监听地址。
+    #[must_use]
+    pub const fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+    /// 接收类型 `T`（须与对端 `forward::<T>` 的编解码器对应）；未登记类型的帧被丢弃并计数。
+    #[must_use]
+    pub fn receive<T: Send + Sync + 'static>(self, codec: impl MessageCodec<T>) -> Self {
+        self.push_decoder(std::any::type_name::<T>(), None, codec)
+    }
+    /// 同 `receive`，按 [`BusMessage::NAME`] 匹配（对端用 `forward_message`）。
+    #[must_use]
+    pub fn receive_message<T: BusMessage>(self, codec: impl MessageCodec<T>) -> Self {
+        self.push_decoder(T::NAME, Some(Schema::of::<T>()), codec)
+    }
+    fn push_decoder<T: Send + Sync + 'static>(
+        mut self,
+        name: &'static str,
+        schema: Option<Schema>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.decoders.insert(
+            name,
+            Decoder {
+                decode: Box::new(move |bytes| codec.decode_bytes(bytes).map(ErasedEvent::new)),
+                schema,
+            },
+        );
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> BridgeHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for BridgeIn {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            listener,
+            decoders,
+            handle,
+            finite,
+            ..
+        } = *self;
+        let endpoint = listener.endpoint();
+        let mut acceptor = listener.into_acceptor().map_err(|e| {
+            crate::component::__startup_mark_failed(&ctx);
+            MicrobusError::Bridge {
+                endpoint,
+                source: Box::new(e),
+            }
+        })?;
+        let inbound = Arc::new(Inbound {
+            decoders,
+            gate: VersionGate::default(),
+        });
+        // 有限模式：对端任务各持有数据源句柄，首个对端接入后释放本地句柄
+        let mut source = finite.then(|| ctx.register_source());
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                accepted = acceptor.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        tracing::info!(%peer, "bridge peer connected");
+                        let (ctx, inbound, handle) = (ctx.__fork(), inbound.clone(), handle.clone());
+                        let peer_source = source.take();
+                        tokio::spawn(async move {
+                            serve_peer(stream, &ctx, &inbound, &handle).await;
+                            drop(peer_source);
+                        });
+                    }
+                    Err(e) => tracing::warn!(error = %e, "bridge accept failed"),
+                },
+            }
+        }
+        acceptor.cleanup();
+        Ok(())
+    }
+}
+
+async fn serve_peer(
+    mut r: BoxRead,
+    ctx: &ComponentContext,
+    inbound: &Inbound,
+    handle: &BridgeHandle,
+) {
+    loop {
+        let frame = tokio::select! {
+            () = crate::component::__recv_stop(ctx) => return,
+            frame = read_frame(&mut r) => frame,
+        };
+        let (type_name, payload) = match frame {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(error = %e, "bridge peer read failed; closing");
+                return;
+            }
+        };
+        let (name, remote_version) = crate::codec::split_wire_name(&type_name);
+        let decoded = match inbound.decoders.get(name) {
+            Some(d) => match (d.schema, remote_version) {
+                (Some(schema), Some(remote)) => {
+                    if inbound.gate.check(ctx, &schema, remote).await.is_accepted() {
+                        (d.decode)(&payload)
+                    } else {
+                        Err(format!("incompatible schema version {remote}"))
+                    }
+                }
+                _ => (d.decode)(&payload),
+            },
+            None => Err("type not registered".to_owned()),
+        };
+        match decoded {
+            Ok(ev) => {
+                handle.frames.fetch_add(1, Ordering::Relaxed);
+                crate::component::__publish_erased(ctx, ev).await;
+            }
+            Err(e) => {
+                tracing::warn!(type_name = %type_name, error = %e, "bridge frame rejected");
+                handle.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
@@ -5,40 +5,442 @@ use std::{
     any::{Any, TypeId},
     collections::HashMap,
     fmt,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
     sync::Arc,
+    time::Duration,
 };
 use tokio::sync::mpsc;
 
+use crate::bus_core::{Clock, Delivery, Outbox, Route, TryDeliver};
+use crate::error::{PublishError, SubscribeError};
+use crate::rt::Instant;
+
 // Small helper alias used across functions
-type SenderVec<T> = SmallVec<[mpsc::Sender<Arc<T>>; 8]>;
+type SenderVec<T> = SmallVec<[Sink<T>; 8]>;
+type FrozenSenders<T> = Arc<[Sink<T>]>;
 
 // 类型级 fanout 路由（按消息类型广播，不做拓扑/主题分层）
 
+/// 投递信封：消息本体（各订阅者共享同一 `Arc`，登记为内联的 `Copy` 类型按值复制）与投递元数据。
+///
+/// `#[handle]` 方法可用 `&Envelope<T>` 代替 `&T` 作为消息参数以读取元数据。
+pub struct Envelope<T> {
+    msg: Payload<T>,
+    seq: u64, // 0 = 未编号
+    stamp: Stamp,
+}
+
+// 发布时刻与发布方：每次发布取一次，同一消息的全部副本共享
+#[derive(Clone, Copy)]
+struct Stamp {
+    origin: Option<&'static str>,
+    at: Instant,
+}
+
+// 内联载荷携带复制函数：泛型路径无法得知 T: Copy，登记时（已知 Copy）取得
+enum Payload<T> {
+    Shared(Arc<T>),
+    Inline { value: T, copy: fn(&T) -> T },
+}
+
+// 发布入口交给路由的消息：已是 Arc 的保持共享，按值发布的由类型索引决定是否内联
+enum Outgoing<T> {
+    Shared(Arc<T>),
+    Value(T),
+}
+
+impl<T> Outgoing<T> {
+    #[cfg(feature = "bus-metrics")]
+    fn get(&self) -> &T {
+        match self {
+            Self::Shared(a) => a,
+            Self::Value(v) => v,
+        }
+    }
+}
+
+impl<T> Clone for Envelope<T> {
+    fn clone(&self) -> Self {
+        let msg = match &self.msg {
+            Payload::Shared(a) => Payload::Shared(a.clone()),
+            Payload::Inline { value, copy } => Payload::Inline {
+                value: copy(value),
+                copy: *copy,
+            },
+        };
+        Self {
+            msg,
+            seq: self.seq,
+            stamp: self.stamp,
+        }
+    }
+}
+
+impl<T> Envelope<T> {
+    /// 取出消息；内联载荷在此处装入 `Arc`（需要共享所有权时才付出分配）。
+    #[must_use]
+    pub fn into_message(self) -> Arc<T> {
+        match self.msg {
+            Payload::Shared(a) => a,
+            Payload::Inline { value, .. } => Arc::new(value),
+        }
+    }
+    /// 按类型单调递增的发布序号（从 1 开始）；未启用 `AppConfig::sequence_numbers` 时为 `None`。
+    #[must_use]
+    pub const fn seq(&self) -> Option<u64> {
+        if self.seq == 0 {
+            None
+        } else {
+            Some(self.seq)
+        }
+    }
+    /// 发布入队时刻（封印前缓冲的消息保留原发布时刻）。
+    #[must_use]
+    pub const fn enqueued_at(&self) -> Instant {
+        self.stamp.at
+    }
+    /// 发布方组件的类型名；经 `App` / `BusHandle` 在组件之外发布时为 `None`。
+    #[must_use]
+    pub const fn origin(&self) -> Option<&'static str> {
+        self.stamp.origin
+    }
+    // 宏生成的直接调度（`__dispatch`）使用：为不经总线的消息补一个未编号、无发布方的信封
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __detached(msg: Arc<T>) -> Self {
+        Self {
+            msg: Payload::Shared(msg),
+            seq: 0,
+            stamp: Stamp {
+                origin: None,
+                at: Instant::now(),
+            },
+        }
+    }
+}
+
+impl<T> std::ops::Deref for Envelope<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        match &self.msg {
+            Payload::Shared(a) => a,
+            Payload::Inline { value, .. } => value,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Envelope<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Envelope")
+            .field("seq", &self.seq())
+            .field("origin", &self.stamp.origin)
+            .field("msg", &**self)
+            .finish()
+    }
+}
+
+/// 序号检查结果，见 [`GapDetector::observe`]。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeqCheck {
+    /// 恰为期望的下一个序号（或首条消息）。
+    InOrder,
+    /// 跳号：`missing` 区间内的序号未收到。
+    Gap { missing: std::ops::Range<u64> },
+    /// 小于期望值：重复，或并发发布方之间的乱序到达。
+    Stale(u64),
+    /// 消息未编号（未启用序号）。
+    Unsequenced,
+}
+
+/// 消费侧跳号检测：按到达顺序喂入同一类型的序号，累计缺失条数。
+///
+/// 单一发布方时序号严格递增；同类型多个发布方并发时，入队顺序可能与编号顺序不同，
+/// 先到的较大序号会先报 `Gap`，迟到者随后以 `Stale` 出现。
+#[derive(Debug, Default)]
+pub struct GapDetector {
+    next: Option<u64>,
+    missing: u64,
+}
+
+impl GapDetector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn observe<T>(&mut self, env: &Envelope<T>) -> SeqCheck {
+        self.observe_seq(env.seq())
+    }
+    pub fn observe_seq(&mut self, seq: Option<u64>) -> SeqCheck {
+        let Some(seq) = seq else {
+            return SeqCheck::Unsequenced;
+        };
+        let expected = self.next.unwrap_or(seq);
+        if seq < expected {
+            return SeqCheck::Stale(seq);
+        }
+        self.next = Some(seq + 1);
+        if seq == expected {
+            SeqCheck::InOrder
+        } else {
+            self.missing += seq - expected;
+            SeqCheck::Gap {
+                missing: expected..seq,
+            }
+        }
+    }
+    /// 累计缺失（跳过）的序号条数。
+    #[must_use]
+    pub const fn missing(&self) -> u64 {
+        self.missing
+    }
+}
+
 pub struct Subscription<T> {
-    rx: mpsc::Receiver<Arc<T>>,
+    rx: Source<T>,
+    overflow: Option<Overflow>,
+}
+
+// 订阅端投递目标：普通队列（满时发布方等待）、合并槽（只保留最新一条，发布方从不等待），
+// 或弱订阅队列（满时丢弃本条并计数，发布方从不等待）
+enum Sink<T> {
+    Queue(mpsc::Sender<Envelope<T>>),
+    Latest {
+        slot: LatestSlot<T>,
+        wake: mpsc::Sender<()>, // 容量 1：有未取的新值时恰有一个唤醒令牌
+        weak: bool,
+    },
+    Weak {
+        tx: mpsc::Sender<Envelope<T>>,
+        dropped: Arc<AtomicU64>,
+    },
+}
+type LatestSlot<T> = Arc<parking_lot::Mutex<Option<Envelope<T>>>>;
+
+impl<T> Clone for Sink<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Queue(tx) => Self::Queue(tx.clone()),
+            Self::Latest { slot, wake, weak } => Self::Latest {
+                slot: slot.clone(),
+                wake: wake.clone(),
+                weak: *weak,
+            },
+            Self::Weak { tx, dropped } => Self::Weak {
+                tx: tx.clone(),
+                dropped: dropped.clone(),
+            },
+        }
+    }
+}
+
+impl<T> Sink<T> {
+    fn try_send(&self, env: Envelope<T>) -> Result<(), mpsc::error::TrySendError<Envelope<T>>> {
+        match self {
+            Self::Queue(tx) => tx.try_send(env),
+            Self::Weak { tx, dropped } => match tx.try_send(env) {
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::dropped(std::any::type_name::<T>(), "weak", 1);
+                    Ok(())
+                }
+                other => other,
+            },
+            Self::Latest { slot, wake, .. } => {
+                if wake.is_closed() {
+                    return Err(mpsc::error::TrySendError::Closed(env));
+                }
+                // 覆盖未取的旧值；令牌已在途时 Full 可忽略
+                *slot.lock() = Some(env);
+                let _ = wake.try_send(());
+                Ok(())
+            }
+        }
+    }
+    async fn send(&self, env: Envelope<T>) -> Result<(), ()> {
+        match self {
+            Self::Queue(tx) => tx.send(env).await.map_err(drop),
+            Self::Latest { .. } | Self::Weak { .. } => self.try_send(env).map_err(drop),
+        }
+    }
+    fn is_closed(&self) -> bool {
+        match self {
+            Self::Queue(tx) | Self::Weak { tx, .. } => tx.is_closed(),
+            Self::Latest { wake, .. } => wake.is_closed(),
+        }
+    }
+    // 弱订阅不计为消费者
+    const fn is_weak(&self) -> bool {
+        matches!(self, Self::Weak { .. } | Self::Latest { weak: true, .. })
+    }
+    fn dropped(&self) -> u64 {
+        match self {
+            Self::Weak { dropped, .. } => dropped.load(Ordering::Relaxed),
+            _ => 0,
+        }
+    }
+    // （排队深度, 容量）；合并槽按 0 / 1 计
+    fn depth(&self) -> Option<(usize, usize)> {
+        if self.is_closed() {
+            return None;
+        }
+        Some(match self {
+            Self::Queue(tx) | Self::Weak { tx, .. } => {
+                (tx.max_capacity() - tx.capacity(), tx.max_capacity())
+            }
+            Self::Latest { slot, .. } => (usize::from(slot.lock().is_some()), 1),
+        })
+    }
+}
+
+// tokio 集成层：核心 fanout 经此投递
+impl<T: Send + Sync + 'static> Outbox<Envelope<T>> for Sink<T> {
+    fn try_deliver(&self, env: Envelope<T>) -> TryDeliver<Envelope<T>> {
+        match self.try_send(env) {
+            Ok(()) => TryDeliver::Delivered,
+            Err(mpsc::error::TrySendError::Full(env)) => TryDeliver::Full(env),
+            Err(mpsc::error::TrySendError::Closed(_)) => TryDeliver::Closed,
+        }
+    }
+    async fn deliver(&self, env: Envelope<T>) -> bool {
+        self.send(env).await.is_ok()
+    }
+    fn is_closed(&self) -> bool {
+        Self::is_closed(self)
+    }
 }
+
+// 背压等待计时用 `rt::Instant`：原生目标为 tokio 时钟（虚拟时间下同样推进）
+struct RtClock;
+
+impl Clock for RtClock {
+    type Instant = Instant;
+    fn now() -> Instant {
+        Instant::now()
+    }
+    fn elapsed(since: Instant) -> Duration {
+        since.elapsed()
+    }
+}
+
+enum Source<T> {
+    Queue(mpsc::Receiver<Envelope<T>>),
+    Latest {
+        slot: LatestSlot<T>,
+        wake: mpsc::Receiver<()>,
+    },
+}
+
+impl<T> Source<T> {
+    fn poll_recv(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Envelope<T>>> {
+        match self {
+            Self::Queue(rx) => rx.poll_recv(cx),
+            Self::Latest { slot, wake } => loop {
+                // 令牌先于取值：取走的总是令牌之后最新写入的值；值已被前一次取走时继续等待
+                match wake.poll_recv(cx) {
+                    std::task::Poll::Ready(Some(())) => {
+                        if let Some(env) = slot.lock().take() {
+                            return std::task::Poll::Ready(Some(env));
+                        }
+                    }
+                    other => return other.map(|_| None),
+                }
+            },
+        }
+    }
+}
+
+// 溢出回调：一轮“满 -> 排空”内只触发一次，避免在容量边界反复回调
+struct Overflow {
+    hook: Box<dyn FnMut(usize) + Send>,
+    fired: bool,
+}
+
 impl<T> Subscription<T> {
     pub async fn recv(&mut self) -> Option<Arc<T>>
     where
         T: Send + Sync + 'static,
     {
-        self.rx.recv().await
+        self.recv_envelope().await.map(Envelope::into_message)
+    }
+    /// 同 [`recv`](Self::recv)，附带投递元数据（序号等）。
+    pub async fn recv_envelope(&mut self) -> Option<Envelope<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let env = std::future::poll_fn(|cx| self.rx.poll_recv(cx)).await;
+        self.observe_depth();
+        env
+    }
+    // 非 async 的取出入口（`Stream` 实现与 `merge::OrderedMerge` 使用）
+    pub(crate) fn poll_recv(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Arc<T>>> {
+        let polled = self.rx.poll_recv(cx);
+        if polled.is_ready() {
+            self.observe_depth();
+        }
+        polled.map(|env| env.map(Envelope::into_message))
+    }
+    /// 订阅队列达到容量（消费落后、发布方开始等待）时回调，参数为队列容量。
+    ///
+    /// 在取出消息时于接收方任务内同步检测与调用，发布路径无额外开销；队列排空后重新计轮。
+    /// 总线按背压投递，满队列不丢消息；回调用于切换到更廉价的降级处理。
+    #[must_use]
+    pub fn on_overflow(mut self, hook: impl FnMut(usize) + Send + 'static) -> Self {
+        self.overflow = Some(Overflow {
+            hook: Box::new(hook),
+            fired: false,
+        });
+        self
+    }
+    fn observe_depth(&mut self) {
+        // 合并订阅不会积压
+        let (Some(of), Source::Queue(rx)) = (self.overflow.as_mut(), &self.rx) else {
+            return;
+        };
+        // 取出一条后剩余 cap - 1：取出前队列已满
+        let remaining = rx.len();
+        let cap = rx.max_capacity();
+        if remaining + 1 >= cap {
+            if !of.fired {
+                of.fired = true;
+                (of.hook)(cap);
+            }
+        } else if remaining == 0 {
+            of.fired = false;
+        }
+    }
+}
+
+// 特性 `stream`：订阅可直接配合 `StreamExt` 组合子使用；发送端全部关闭后结束
+#[cfg(feature = "stream")]
+impl<T> futures_core::Stream for Subscription<T> {
+    type Item = Arc<T>;
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Arc<T>>> {
+        self.get_mut().poll_recv(cx)
     }
 }
 
-// 订阅索引：类型级。
-// - 启动阶段（未封印）：累积订阅到 `any`。
-// - 封印后：惰性构建不可变快照 `frozen_any`，发布阶段直接使用该快照，避免每次发布克隆 sender 与小分配。
+// 订阅索引：类型级。订阅登记与封印快照见 `bus_core::Route`。
 struct TypeIndex<T: Send + Sync + 'static> {
-    any: SmallVec<[mpsc::Sender<Arc<T>>; 4]>,
-    frozen_any: Option<std::sync::Arc<[mpsc::Sender<Arc<T>>]>>,
+    route: Route<Sink<T>>,
+    seq: AtomicU64,              // 本类型已编号的发布数
+    inline: Option<fn(&T) -> T>, // 已登记为内联（Copy）类型：按值投递，不分配 Arc
 }
 impl<T: Send + Sync + 'static> Default for TypeIndex<T> {
     fn default() -> Self {
         Self {
-            any: SmallVec::new(),
-            frozen_any: None,
+            route: Route::default(),
+            seq: AtomicU64::new(0),
+            inline: None,
         }
     }
 }
@@ -48,16 +450,19 @@ trait TypeIndexEntry: Any + Send + Sync {
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn freeze(&mut self);
+    fn type_name(&self) -> &'static str;
+    fn open_subscribers(&self) -> usize;
+    // 动态路径发布；future 输出投递结果
     fn publish_box_dyn(
         &self,
-        sealed: bool,
+        mode: RouteMode,
         msg: Box<dyn Any + Send + Sync>,
-    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+    ) -> Result<DynPublishFuture, PublishError>;
     fn publish_arc_dyn(
         &self,
-        sealed: bool,
+        mode: RouteMode,
         msg: std::sync::Arc<dyn Any + Send + Sync>,
-    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+    ) -> Result<DynPublishFuture, PublishError>;
 }
 impl<T: Send + Sync + 'static> TypeIndexEntry for TypeIndex<T> {
     fn as_any(&self) -> &dyn Any {
@@ -66,61 +471,78 @@ impl<T: Send + Sync + 'static> TypeIndexEntry for TypeIndex<T> {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+    fn open_subscribers(&self) -> usize {
+        self.route
+            .all()
+            .iter()
+            .filter(|tx| !tx.is_closed() && !tx.is_weak())
+            .count()
+    }
     fn freeze(&mut self) {
-        if self.frozen_any.is_none() {
-            let small = std::mem::take(&mut self.any);
-            let vec = small.into_vec();
-            self.frozen_any = Some(Arc::<[mpsc::Sender<Arc<T>>]>::from(vec));
-        }
+        self.route.freeze();
     }
     fn publish_box_dyn(
         &self,
-        sealed: bool,
+        mode: RouteMode,
         msg: Box<dyn Any + Send + Sync>,
-    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
-        let val = *msg.downcast::<T>().expect("dynamic box downcast mismatch");
-        let arc = Arc::new(val);
-        if sealed {
-            if let Some(frozen) = self.frozen_any.clone() {
-                Box::pin(async move { publish_to_senders_static::<T>(&frozen, arc).await })
-            } else {
-                Box::pin(async {})
-            }
-        } else {
-            // 未封印：过滤关闭的 sender
-            let mut senders: SenderVec<T> = SmallVec::new();
-            for tx in &self.any {
-                if !tx.is_closed() {
-                    senders.push(tx.clone());
-                }
-            }
-            Box::pin(async move { publish_to_senders_static::<T>(&senders, arc).await })
-        }
+    ) -> Result<DynPublishFuture, PublishError> {
+        let val = *msg.downcast::<T>().map_err(|_| Self::mismatch())?;
+        Ok(self.route_dyn(mode, Outgoing::Value(val)))
     }
     fn publish_arc_dyn(
         &self,
-        sealed: bool,
+        mode: RouteMode,
         msg: std::sync::Arc<dyn Any + Send + Sync>,
-    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
+    ) -> Result<DynPublishFuture, PublishError> {
         // 尝试 Arc<dyn Any> -> Arc<T>
-        let arc_t: Arc<T> = match msg.downcast() {
-            Ok(v) => v,
-            Err(_) => panic!("dynamic arc downcast mismatch"),
+        let arc_t: Arc<T> = msg.downcast().map_err(|_| Self::mismatch())?;
+        Ok(self.route_dyn(mode, Outgoing::Shared(arc_t)))
+    }
+}
+impl<T: Send + Sync + 'static> TypeIndex<T> {
+    // 编号与取 sender 在同一次索引读取内完成
+    fn envelope(&self, mode: RouteMode, msg: Outgoing<T>) -> Envelope<T> {
+        let seq = if mode.sequenced {
+            self.seq.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+            0
+        };
+        let msg = match (msg, self.inline) {
+            (Outgoing::Value(value), Some(copy)) => Payload::Inline { value, copy },
+            (Outgoing::Value(value), None) => Payload::Shared(Arc::new(value)),
+            (Outgoing::Shared(a), _) => Payload::Shared(a),
         };
-        if sealed {
-            if let Some(frozen) = self.frozen_any.clone() {
-                Box::pin(async move { publish_to_senders_static::<T>(&frozen, arc_t).await })
+        Envelope {
+            msg,
+            seq,
+            stamp: mode.stamp,
+        }
+    }
+    // 封印后用冻结快照；未封印时过滤关闭的 sender
+    fn open_senders(&self) -> SenderVec<T> {
+        self.route.open::<Envelope<T>>()
+    }
+    fn route_dyn(&self, mode: RouteMode, msg: Outgoing<T>) -> DynPublishFuture {
+        let env = self.envelope(mode, msg);
+        if mode.sealed {
+            if let Some(frozen) = self.route.frozen() {
+                Box::pin(async move { BusHandle::publish_to_senders(&frozen, env).await })
             } else {
-                Box::pin(async {})
+                Box::pin(async { Delivery::default() })
             }
         } else {
-            let mut senders: SenderVec<T> = SmallVec::new();
-            for tx in &self.any {
-                if !tx.is_closed() {
-                    senders.push(tx.clone());
-                }
-            }
-            Box::pin(async move { publish_to_senders_static::<T>(&senders, arc_t).await })
+            let senders = self.open_senders();
+            Box::pin(async move { BusHandle::publish_to_senders(&senders, env).await })
+        }
+    }
+    // 按 TypeId 检索到的条目不应出现不一致；出现即视为路由表损坏，按错误返回而非 panic
+    fn mismatch() -> PublishError {
+        PublishError::TypeMismatch {
+            expected: std::any::type_name::<T>(),
+            found: UNKNOWN_DYN_TYPE,
         }
     }
 }
@@ -128,6 +550,7 @@ impl<T: Send + Sync + 'static> TypeIndexEntry for TypeIndex<T> {
 #[derive(Clone)]
 pub struct BusHandle {
     inner: Arc<BusInner>,
+    origin: Option<&'static str>, // 经此句柄发布的消息的发布方（组件上下文持有的句柄）
 }
 
 // ================= 动态事件发布支持（ErasedEvent + Any 弱类型） =================
@@ -137,8 +560,8 @@ pub struct BusHandle {
 // - Any（Box/Arc<dyn Any + Send + Sync>）弱类型：用于快速实验/临时 PoC；运行期直接按 TypeId 查订阅者并 downcast，一次性投递。
 // 契约（与文档 FULL_GUIDE.md 保持一致）：所有动态/弱类型路径最终“归约到 T 或 ()”。
 // 安全与失败处理：
-//   * ErasedEvent 内部 downcast mismatch -> panic：编程期逻辑错误（publish_fn 与携带数据不匹配），不隐藏以便尽早暴露。
-//   * Any 弱类型：若 TypeId 无订阅者 -> 静默丢弃；若内部 downcast 失败（不应发生，因为以 TypeId 精确检索）-> panic。
+//   * ErasedEvent 内部 downcast mismatch -> `PublishError::TypeMismatch`（publish_fn 与携带数据不匹配）：`try_*` 接口返回，其余路径记 error 日志后丢弃。
+//   * Any 弱类型：若 TypeId 无订阅者 -> 静默丢弃；若内部 downcast 失败（不应发生，因为以 TypeId 精确检索）-> 同上。
 // 性能：
 //   * Sealed 后：ErasedEvent / Any 动态路径均避免构建订阅快照；仅一次 HashMap 读 + downcast。
 //   * 未 sealed：动态路径每次过滤已关闭 sender，保持与静态路径一致的背压策略。
@@ -148,10 +571,13 @@ use std::pin::Pin;
 // === 类型别名（降低复杂度，满足 clippy::type-complexity） ===
 type PublishData = Box<dyn Any + Send + Sync>;
 type PublishFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
-type PublishFn = fn(&BusHandle, PublishData) -> PublishFuture;
+type PublishFn = fn(&BusHandle, PublishData) -> Result<PublishFuture, PublishError>;
+type DynPublishFuture = Pin<Box<dyn Future<Output = Delivery> + Send + 'static>>;
+type PreSealQueue = std::collections::VecDeque<(Stamp, Arc<dyn Any + Send + Sync>)>;
 
 pub struct ErasedEvent {
     pub(crate) publish_fn: PublishFn,
+    pub(crate) type_name: &'static str,
     pub(crate) data: PublishData,
 }
 impl ErasedEvent {
@@ -159,15 +585,16 @@ impl ErasedEvent {
         fn publish_impl<T: Send + Sync + 'static>(
             bus: &BusHandle,
             data: PublishData,
-        ) -> PublishFuture {
+        ) -> Result<PublishFuture, PublishError> {
             let handle = bus.clone();
             let inner = *data
                 .downcast::<T>()
-                .expect("ErasedEvent type downcast mismatch");
-            Box::pin(async move { handle.publish_type(inner).await })
+                .map_err(|_| TypeIndex::<T>::mismatch())?;
+            Ok(Box::pin(async move { handle.publish_type(inner).await }))
         }
         Self {
             publish_fn: publish_impl::<T>,
+            type_name: std::any::type_name::<T>(),
             data: Box::new(value),
         }
     }
@@ -184,8 +611,156 @@ impl<T: Send + Sync + 'static> IntoErasedEvent for T {
 
 struct BusInner {
     subs: RwLock<HashMap<TypeId, Box<dyn TypeIndexEntry>>>,
+    probes: RwLock<Vec<Arc<SubscriberProbe>>>,
+    taps: RwLock<Vec<Arc<dyn PublishTap>>>,
+    has_taps: AtomicBool, // 无 tap 时发布路径仅多一次原子读
+    #[cfg(feature = "bus-metrics")]
+    stats: RwLock<HashMap<TypeId, crate::introspect::TypeStats>>,
+    flow: RwLock<HashMap<TypeId, FlowStats>>, // 投递异常（关闭丢弃 / 背压等待），按类型
+    wire_debug: AtomicU32,                    // 0 关闭；N = 每 N 次发布采样一次
+    wire_seq: AtomicU64,
     default_capacity: usize,
-    sealed: AtomicBool, // 一旦置 true，订阅结构视为只读
+    sealed: AtomicBool,    // 一旦置 true，订阅结构视为只读
+    sequenced: AtomicBool, // 按类型为发布编号（AppConfig::sequence_numbers）
+    // 封印前发布缓冲（AppConfig::buffer_pre_seal）：Some 期间发布只入队，封印后由 App 依序重放
+    buffering: AtomicBool,
+    pre_seal: parking_lot::Mutex<Option<PreSealQueue>>,
+    // 按类型的发布限速（AppConfig::rate_limits）：启动前设置一次，未配置时发布路径只多一次读取
+    limits: std::sync::OnceLock<HashMap<TypeId, crate::rate_limit::RateLimiter>>,
+    // 按类型的发布去重（AppConfig::dedup）：同上，启动前设置一次
+    dedup: std::sync::OnceLock<HashMap<TypeId, Box<dyn crate::dedup::Filter>>>,
+}
+
+/// 发布旁路观察（tap）：消息进入订阅队列前同步回调，供录制/调试等诊断设施使用。
+///
+/// 回调运行在发布方任务内，实现必须轻量且不可阻塞；需要保留消息时克隆 `msg` 即可（仅引用计数）。
+/// 动态 Any 路径若该类型从未被订阅，`type_name` 为 `"<dyn Any>"`。
+pub(crate) trait PublishTap: Send + Sync + 'static {
+    fn on_publish(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        msg: &Arc<dyn Any + Send + Sync>,
+    );
+    // 同一次发布完成入队（或因无订阅者丢弃）之后调用
+    fn on_routed(&self, _type_id: TypeId) {}
+}
+
+const UNKNOWN_DYN_TYPE: &str = "<dyn Any>";
+
+// 单次发布的路由方式：是否已封印（用冻结快照）、是否按类型编号，以及写入信封的发布戳
+#[derive(Clone, Copy)]
+struct RouteMode {
+    sealed: bool,
+    sequenced: bool,
+    stamp: Stamp,
+}
+// 经 `BusHandle::try_subscribe` 在组件之外建立的订阅的归属名
+pub(crate) const EXTERNAL_OWNER: &str = "<external>";
+const CLOSED_DROP_WARN_INTERVAL: Duration = Duration::from_secs(5);
+
+// 按类型的投递异常统计（关闭丢弃、不等待发布的满队列丢弃、背压等待）。关闭丢弃的 warn 每类型每 CLOSED_DROP_WARN_INTERVAL 至多一次。
+struct FlowStats {
+    type_name: &'static str,
+    closed: AtomicU64,
+    full: AtomicU64,
+    blocked_sends: AtomicU64,
+    blocked_nanos: AtomicU64,
+    blocked_max_nanos: AtomicU64,
+    last_closed_warn: parking_lot::Mutex<Option<Instant>>,
+}
+impl FlowStats {
+    const fn new(type_name: &'static str) -> Self {
+        Self {
+            type_name,
+            closed: AtomicU64::new(0),
+            full: AtomicU64::new(0),
+            blocked_sends: AtomicU64::new(0),
+            blocked_nanos: AtomicU64::new(0),
+            blocked_max_nanos: AtomicU64::new(0),
+            last_closed_warn: parking_lot::Mutex::new(None),
+        }
+    }
+    fn record(&self, d: &Delivery) {
+        #[cfg(feature = "metrics")]
+        {
+            if let Some(blocked) = d.blocked {
+                crate::metrics::backpressure_wait(self.type_name, blocked);
+            }
+            if d.closed > 0 {
+                crate::metrics::dropped(self.type_name, "closed", d.closed as u64);
+            }
+        }
+        if let Some(blocked) = d.blocked {
+            let nanos = u64::try_from(blocked.as_nanos()).unwrap_or(u64::MAX);
+            self.blocked_sends.fetch_add(1, Ordering::Relaxed);
+            self.blocked_nanos.fetch_add(nanos, Ordering::Relaxed);
+            self.blocked_max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        }
+        if d.closed > 0 {
+            let n = d.closed as u64;
+            let total = self.closed.fetch_add(n, Ordering::Relaxed) + n;
+            let mut last = self.last_closed_warn.lock();
+            if last.is_none_or(|t| t.elapsed() >= CLOSED_DROP_WARN_INTERVAL) {
+                *last = Some(Instant::now());
+                tracing::warn!(
+                    message_type = self.type_name,
+                    total,
+                    "message dropped: subscriber closed (component exited)"
+                );
+            }
+        }
+    }
+    // 不等待发布（生命周期事件）遇满队列的丢弃；此类事件低频，逐次输出 warn
+    fn record_full(&self, n: usize) {
+        let n = n as u64;
+        #[cfg(feature = "metrics")]
+        crate::metrics::dropped(self.type_name, "full", n);
+        let total = self.full.fetch_add(n, Ordering::Relaxed) + n;
+        tracing::warn!(
+            message_type = self.type_name,
+            total,
+            "lifecycle event dropped: subscriber queue full"
+        );
+    }
+}
+
+// 订阅探针：记录订阅归属（组件 + 消息类型）与队列深度读取方式，供运行期监控使用。
+pub(crate) struct SubscriberProbe {
+    pub(crate) component: &'static str,
+    pub(crate) type_name: &'static str,
+    message: crate::wiring::MessageType,
+    pub(crate) ordered: bool,
+    pub(crate) weak: bool, // 弱订阅：不参与滞后监控与快照静止判定
+    depth: Box<dyn Fn() -> Option<(usize, usize)> + Send + Sync>,
+    dropped: Box<dyn Fn() -> u64 + Send + Sync>,
+}
+impl SubscriberProbe {
+    fn new<T: Send + Sync + 'static>(
+        component: &'static str,
+        sink: Sink<T>,
+        ordered: bool,
+    ) -> Self {
+        let weak = sink.is_weak();
+        let counted = sink.clone();
+        Self {
+            component,
+            type_name: std::any::type_name::<T>(),
+            message: crate::wiring::MessageType::of::<T>(),
+            ordered,
+            weak,
+            depth: Box::new(move || sink.depth()),
+            dropped: Box::new(move || counted.dropped()),
+        }
+    }
+    /// 当前（排队深度, 容量）；订阅端已关闭时返回 `None`。
+    pub(crate) fn depth(&self) -> Option<(usize, usize)> {
+        (self.depth)()
+    }
+    // 弱订阅因队列满丢弃的消息数
+    pub(crate) fn dropped(&self) -> u64 {
+        (self.dropped)()
+    }
 }
 
 impl fmt::Debug for BusHandle {
@@ -196,6 +771,16 @@ impl fmt::Debug for BusHandle {
 
 // 路由索引：类型级（any-of-type）
 
+// 订阅投递方式
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SubMode {
+    Queue,
+    Latest,
+    Ordered,
+    Weak,
+    WeakLatest,
+}
+
 pub struct Bus {
     handle: BusHandle,
 }
@@ -204,12 +789,26 @@ impl Bus {
     pub fn new(default_capacity: usize) -> Self {
         let inner = BusInner {
             subs: RwLock::new(HashMap::new()),
+            probes: RwLock::new(Vec::new()),
+            taps: RwLock::new(Vec::new()),
+            has_taps: AtomicBool::new(false),
+            #[cfg(feature = "bus-metrics")]
+            stats: RwLock::new(HashMap::new()),
+            flow: RwLock::new(HashMap::new()),
+            wire_debug: AtomicU32::new(0),
+            wire_seq: AtomicU64::new(0),
             default_capacity,
             sealed: AtomicBool::new(false),
+            sequenced: AtomicBool::new(false),
+            buffering: AtomicBool::new(false),
+            pre_seal: parking_lot::Mutex::new(None),
+            limits: std::sync::OnceLock::new(),
+            dedup: std::sync::OnceLock::new(),
         };
         Self {
             handle: BusHandle {
                 inner: Arc::new(inner),
+                origin: None,
             },
         }
     }
@@ -219,72 +818,207 @@ impl Bus {
     }
 }
 
+// 不延长总线生命周期的句柄（进程级 panic 钩子持有）
+#[derive(Clone)]
+pub(crate) struct WeakBusHandle(std::sync::Weak<BusInner>);
+
+impl WeakBusHandle {
+    pub(crate) fn upgrade(&self) -> Option<BusHandle> {
+        self.0.upgrade().map(|inner| BusHandle {
+            inner,
+            origin: None,
+        })
+    }
+}
+
 impl BusHandle {
     #[inline]
-    fn is_sealed(&self) -> bool {
+    pub(crate) fn is_sealed(&self) -> bool {
         self.inner.sealed.load(Ordering::Acquire)
     }
-    #[inline]
-    async fn send_one<T: Send + Sync + 'static>(&self, tx: &mpsc::Sender<Arc<T>>, arc: Arc<T>) {
-        match tx.try_send(arc.clone()) {
-            Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => {
-                let _ = tx.send(arc).await;
-            }
-            Ok(()) | Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => {}
+    pub(crate) fn downgrade(&self) -> WeakBusHandle {
+        WeakBusHandle(Arc::downgrade(&self.inner))
+    }
+    // 同一总线、以 `origin` 为发布方的句柄（组件上下文持有）
+    pub(crate) fn with_origin(&self, origin: &'static str) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            origin: Some(origin),
         }
     }
 
     #[inline]
-    async fn send_pending_by_index<T: Send + Sync + 'static>(
-        senders: &[mpsc::Sender<Arc<T>>],
-        pending_idx: &[usize],
-        arc: Arc<T>,
-    ) {
-        if pending_idx.is_empty() {
-            return;
+    fn stamp(&self) -> Stamp {
+        Stamp {
+            origin: self.origin,
+            at: Instant::now(),
         }
-        let last = pending_idx.len() - 1;
-        for &i in &pending_idx[..last] {
-            let _ = senders[i].send(arc.clone()).await;
+    }
+
+    #[inline]
+    fn route_mode(&self, stamp: Stamp) -> RouteMode {
+        RouteMode {
+            sealed: self.is_sealed(),
+            sequenced: self.inner.sequenced.load(Ordering::Relaxed),
+            stamp,
         }
-        let _ = senders[pending_idx[last]].send(arc).await;
     }
 
     #[inline]
     fn get_frozen_senders<T: Send + Sync + 'static>(
         &self,
         type_id: TypeId,
-    ) -> Option<Arc<[mpsc::Sender<Arc<T>>]>> {
+        mode: RouteMode,
+        msg: Outgoing<T>,
+    ) -> Option<(FrozenSenders<T>, Envelope<T>)> {
         let subs = self.inner.subs.read();
-        subs.get(&type_id)
-            .and_then(|entry| entry.as_any().downcast_ref::<TypeIndex<T>>())
-            .and_then(|idx| idx.frozen_any.clone())
+        let idx = subs
+            .get(&type_id)
+            .and_then(|entry| entry.as_any().downcast_ref::<TypeIndex<T>>())?;
+        let frozen = idx.route.frozen()?;
+        Some((frozen, idx.envelope(mode, msg)))
     }
 
     #[inline]
-    fn get_open_senders_unsealed<T: Send + Sync + 'static>(&self, type_id: TypeId) -> SenderVec<T> {
-        let mut opened: SenderVec<T> = SmallVec::new();
-        if let Some(entry) = self.inner.subs.read().get(&type_id) {
-            if let Some(idx) = entry.as_any().downcast_ref::<TypeIndex<T>>() {
-                for tx in &idx.any {
-                    if !tx.is_closed() {
-                        opened.push(tx.clone());
-                    }
-                }
-            } else {
-                tracing::error!("type mismatch in type index for this type");
-            }
+    fn get_open_senders_unsealed<T: Send + Sync + 'static>(
+        &self,
+        type_id: TypeId,
+        mode: RouteMode,
+        msg: Outgoing<T>,
+    ) -> Option<(SenderVec<T>, Envelope<T>)> {
+        let subs = self.inner.subs.read();
+        let entry = subs.get(&type_id)?;
+        let Some(idx) = entry.as_any().downcast_ref::<TypeIndex<T>>() else {
+            tracing::error!("type mismatch in type index for this type");
+            return None;
+        };
+        Some((idx.open_senders(), idx.envelope(mode, msg)))
+    }
+    // 框架内部订阅均发生在封印前；封印后调用属编程错误
+    pub(crate) fn subscribe_type<T: Send + Sync + 'static>(
+        &self,
+        owner: &'static str,
+    ) -> Subscription<T> {
+        match self.try_subscribe_type(owner) {
+            Ok(sub) => sub,
+            Err(e) => panic!("{e}: subscription graph is immutable after startup"),
         }
-        opened
     }
-    pub(crate) fn subscribe_type<T: Send + Sync + 'static>(&self) -> Subscription<T> {
-        assert!(
-            !self.inner.sealed.load(Ordering::Acquire),
-            "subscribe_type called after bus sealed: subscription graph is immutable after startup"
-        );
-        let cap = self.inner.default_capacity;
+    /// 在组件之外订阅 `T`（须在 `start()` 封印总线之前）。
+    ///
+    /// # Errors
+    /// 总线已封印时返回 [`SubscribeError::Sealed`]。
+    pub fn try_subscribe<T: Send + Sync + 'static>(
+        &self,
+    ) -> Result<Subscription<T>, SubscribeError> {
+        self.try_subscribe_type(EXTERNAL_OWNER)
+    }
+    /// 合并订阅：消费落后时只保留最新一条（中间值被覆盖），发布方从不因本订阅等待；适用于持仓、最新盘口等状态型消息。
+    ///
+    /// # Errors
+    /// 总线已封印时返回 [`SubscribeError::Sealed`]。
+    pub fn try_subscribe_latest<T: Send + Sync + 'static>(
+        &self,
+    ) -> Result<Subscription<T>, SubscribeError> {
+        self.try_subscribe_with(EXTERNAL_OWNER, SubMode::Latest)
+    }
+    /// 弱订阅：只观察、不计为消费者。队列满时丢弃新消息（计入 `introspect().subscriptions` 的 `dropped`），
+    /// 发布方从不因本订阅等待；不计入查询应答方、滞后监控与快照静止判定。适用于调试录制、看板等纯观察方。
+    ///
+    /// # Errors
+    /// 总线已封印时返回 [`SubscribeError::Sealed`]。
+    pub fn try_subscribe_weak<T: Send + Sync + 'static>(
+        &self,
+    ) -> Result<Subscription<T>, SubscribeError> {
+        self.try_subscribe_with(EXTERNAL_OWNER, SubMode::Weak)
+    }
+    pub(crate) fn try_subscribe_type<T: Send + Sync + 'static>(
+        &self,
+        owner: &'static str,
+    ) -> Result<Subscription<T>, SubscribeError> {
+        self.try_subscribe_with(owner, SubMode::Queue)
+    }
+    // `#[handle(weak)]` / `#[handle(weak, latest)]`
+    pub(crate) fn subscribe_weak_type<T: Send + Sync + 'static>(
+        &self,
+        owner: &'static str,
+        latest: bool,
+    ) -> Subscription<T> {
+        let mode = if latest {
+            SubMode::WeakLatest
+        } else {
+            SubMode::Weak
+        };
+        match self.try_subscribe_with(owner, mode) {
+            Ok(sub) => sub,
+            Err(e) => panic!("{e}: subscription graph is immutable after startup"),
+        }
+    }
+    pub(crate) fn subscribe_latest_type<T: Send + Sync + 'static>(
+        &self,
+        owner: &'static str,
+    ) -> Subscription<T> {
+        match self.try_subscribe_with(owner, SubMode::Latest) {
+            Ok(sub) => sub,
+            Err(e) => panic!("{e}: subscription graph is immutable after startup"),
+        }
+    }
+    // `#[handle(in_order)]`：有界 FIFO 队列，按发布方入队顺序逐条交付（不合并、不丢弃），在自省中标记为有序
+    pub(crate) fn subscribe_ordered_type<T: Send + Sync + 'static>(
+        &self,
+        owner: &'static str,
+    ) -> Subscription<T> {
+        match self.try_subscribe_with(owner, SubMode::Ordered) {
+            Ok(sub) => sub,
+            Err(e) => panic!("{e}: subscription graph is immutable after startup"),
+        }
+    }
+    fn try_subscribe_with<T: Send + Sync + 'static>(
+        &self,
+        owner: &'static str,
+        mode: SubMode,
+    ) -> Result<Subscription<T>, SubscribeError> {
+        if self.is_sealed() {
+            return Err(SubscribeError::Sealed {
+                type_name: std::any::type_name::<T>(),
+            });
+        }
         let type_id = TypeId::of::<T>();
-        let (tx_local, rx) = mpsc::channel::<Arc<T>>(cap);
+        // 有序订阅只能落在 FIFO 队列上：合并槽位等会改变交付序列的投递方式在此处排除
+        let (tx_local, rx) = if matches!(mode, SubMode::Latest | SubMode::WeakLatest) {
+            let slot = LatestSlot::default();
+            let (wake_tx, wake_rx) = mpsc::channel(1);
+            (
+                Sink::Latest {
+                    slot: slot.clone(),
+                    wake: wake_tx,
+                    weak: mode == SubMode::WeakLatest,
+                },
+                Source::Latest {
+                    slot,
+                    wake: wake_rx,
+                },
+            )
+        } else {
+            let (tx, rx) = mpsc::channel::<Envelope<T>>(self.inner.default_capacity);
+            let sink = if mode == SubMode::Weak {
+                Sink::Weak {
+                    tx,
+                    dropped: Arc::default(),
+                }
+            } else {
+                Sink::Queue(tx)
+            };
+            (sink, Source::Queue(rx))
+        };
+        self.inner
+            .probes
+            .write()
+            .push(Arc::new(SubscriberProbe::new(
+                owner,
+                tx_local.clone(),
+                mode == SubMode::Ordered,
+            )));
         if let Some(entry) = self
             .inner
             .subs
@@ -294,68 +1028,474 @@ impl BusHandle {
             .as_any_mut()
             .downcast_mut::<TypeIndex<T>>()
         {
-            entry.any.push(tx_local);
+            entry.route.push(tx_local);
         } else {
             tracing::error!("type index downcast failed; subscription ignored");
         }
-        Subscription { rx }
+        Ok(Subscription { rx, overflow: None })
     }
     // 内部发送实现（统一入口）
     pub(crate) async fn publish_type<T: Send + Sync + 'static>(&self, msg: T) {
         // 顺序语义：同一类型的消息进入每个订阅者通道的顺序=各 publish 调用实际完成入队的顺序；无全局跨组件开播时间排序保证。
         let type_id = TypeId::of::<T>();
-        let arc = Arc::new(msg);
-        if self.is_sealed() {
-            self.publish_type_sealed::<T>(type_id, arc).await;
+        let Some((msg, tapped)) = self.prepare_publish(msg).await else {
+            return;
+        };
+        let mode = self.route_mode(self.stamp());
+        let delivery = if mode.sealed {
+            self.publish_type_sealed::<T>(type_id, mode, msg).await
         } else {
-            self.publish_type_unsealed::<T>(type_id, arc).await;
+            self.publish_type_unsealed::<T>(type_id, mode, msg).await
+        };
+        if tapped {
+            self.notify_routed(type_id);
+        }
+        if !delivery.is_clean() {
+            self.record_flow(type_id, std::any::type_name::<T>(), &delivery);
         }
     }
 
-    async fn publish_type_sealed<T: Send + Sync + 'static>(&self, type_id: TypeId, arc: Arc<T>) {
-        if let Some(frozen) = self.get_frozen_senders::<T>(type_id) {
-            self.publish_to_senders(&frozen, arc).await;
+    // 不等待的发布（框架生命周期事件）：队列已满的订阅者丢弃该份并按类型计数，绝不反压发布方
+    pub(crate) async fn publish_type_nowait<T: Send + Sync + 'static>(&self, msg: T) {
+        let type_id = TypeId::of::<T>();
+        let Some((msg, tapped)) = self.prepare_publish(msg).await else {
+            return;
+        };
+        let mode = self.route_mode(self.stamp());
+        let delivery = if mode.sealed {
+            self.get_frozen_senders::<T>(type_id, mode, msg)
+                .map(|(senders, env)| crate::bus_core::try_fanout(&senders, env))
+        } else {
+            self.get_open_senders_unsealed::<T>(type_id, mode, msg)
+                .map(|(senders, env)| crate::bus_core::try_fanout(&senders, env))
+        }
+        .unwrap_or_default();
+        if tapped {
+            self.notify_routed(type_id);
+        }
+        if delivery.closed > 0 {
+            let closed = Delivery {
+                closed: delivery.closed,
+                blocked: None,
+            };
+            self.record_flow(type_id, std::any::type_name::<T>(), &closed);
+        }
+        if delivery.full > 0 {
+            self.flow_stats(type_id, std::any::type_name::<T>(), |st| {
+                st.record_full(delivery.full);
+            });
         }
     }
 
-    async fn publish_type_unsealed<T: Send + Sync + 'static>(&self, type_id: TypeId, arc: Arc<T>) {
-        let senders = self.get_open_senders_unsealed::<T>(type_id);
-        self.publish_to_senders(&senders, arc).await;
+    // 发布前置：去重 / 限速、封印前缓冲与 tap 通知；返回 None 表示该条已被抑制或缓冲
+    async fn prepare_publish<T: Send + Sync + 'static>(
+        &self,
+        msg: T,
+    ) -> Option<(Outgoing<T>, bool)> {
+        let type_id = TypeId::of::<T>();
+        if !self.admit(&msg).await {
+            return None;
+        }
+        let tapped = self.inner.has_taps.load(Ordering::Acquire);
+        // 缓冲与 tap 需要共享所有权；其余按值交给路由，由类型索引决定内联或装入 Arc
+        let msg = if tapped || self.inner.buffering.load(Ordering::Acquire) {
+            let arc = Arc::new(msg);
+            if self.buffer_pre_seal(|| arc.clone()) {
+                return None;
+            }
+            if tapped {
+                let shared: Arc<dyn Any + Send + Sync> = arc.clone();
+                self.notify_taps(type_id, std::any::type_name::<T>(), &shared);
+            }
+            Outgoing::Shared(arc)
+        } else {
+            Outgoing::Value(msg)
+        };
+        #[cfg(feature = "metrics")]
+        crate::metrics::published(std::any::type_name::<T>());
+        #[cfg(feature = "bus-metrics")]
+        self.with_stats(type_id, std::any::type_name::<T>(), |st| {
+            st.record(msg.get(), std::mem::size_of::<T>());
+        });
+        Some((msg, tapped))
     }
 
-    #[inline]
-    fn try_send_collect_pending<T: Send + Sync + 'static>(
-        senders: &[mpsc::Sender<Arc<T>>],
-        arc: &Arc<T>,
-    ) -> SmallVec<[usize; 8]> {
-        let mut pending_idx: SmallVec<[usize; 8]> = SmallVec::new();
-        for (i, tx) in senders.iter().enumerate() {
-            match tx.try_send(arc.clone()) {
-                Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => pending_idx.push(i),
-                Ok(()) | Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => {}
-            }
+    async fn publish_type_sealed<T: Send + Sync + 'static>(
+        &self,
+        type_id: TypeId,
+        mode: RouteMode,
+        msg: Outgoing<T>,
+    ) -> Delivery {
+        match self.get_frozen_senders::<T>(type_id, mode, msg) {
+            Some((frozen, env)) => Self::publish_to_senders(&frozen, env).await,
+            None => Delivery::default(),
+        }
+    }
+
+    async fn publish_type_unsealed<T: Send + Sync + 'static>(
+        &self,
+        type_id: TypeId,
+        mode: RouteMode,
+        msg: Outgoing<T>,
+    ) -> Delivery {
+        match self.get_open_senders_unsealed::<T>(type_id, mode, msg) {
+            Some((senders, env)) => Self::publish_to_senders(&senders, env).await,
+            None => Delivery::default(),
         }
-        pending_idx
     }
 
     #[inline]
     async fn publish_to_senders<T: Send + Sync + 'static>(
+        senders: &[Sink<T>],
+        env: Envelope<T>,
+    ) -> Delivery {
+        crate::bus_core::fanout::<_, _, RtClock>(senders, env).await
+    }
+
+    // 投递异常（关闭丢弃 / 背压等待）按类型累计；正常投递不进入此路径
+    #[cold]
+    fn record_flow(&self, type_id: TypeId, type_name: &'static str, d: &Delivery) {
+        self.flow_stats(type_id, type_name, |st| st.record(d));
+    }
+
+    fn flow_stats(&self, type_id: TypeId, type_name: &'static str, f: impl FnOnce(&FlowStats)) {
+        if let Some(st) = self.inner.flow.read().get(&type_id) {
+            f(st);
+            return;
+        }
+        f(self
+            .inner
+            .flow
+            .write()
+            .entry(type_id)
+            .or_insert_with(|| FlowStats::new(type_name)));
+    }
+
+    pub(crate) fn closed_drops(&self) -> Vec<crate::introspect::DropMetrics> {
+        let mut v: Vec<_> = self
+            .inner
+            .flow
+            .read()
+            .values()
+            .filter(|st| {
+                st.closed.load(Ordering::Relaxed) > 0 || st.full.load(Ordering::Relaxed) > 0
+            })
+            .map(|st| crate::introspect::DropMetrics {
+                type_name: st.type_name,
+                closed: st.closed.load(Ordering::Relaxed),
+                full: st.full.load(Ordering::Relaxed),
+            })
+            .collect();
+        v.sort_by_key(|m| m.type_name);
+        v
+    }
+    pub(crate) fn dedup(&self) -> Vec<crate::introspect::DedupMetrics> {
+        let mut v: Vec<_> = self
+            .inner
+            .dedup
+            .get()
+            .map(|m| m.values().map(|f| f.metrics()).collect())
+            .unwrap_or_default();
+        v.sort_by_key(|m| m.type_name);
+        v
+    }
+
+    pub(crate) fn backpressure(&self) -> Vec<crate::introspect::BackpressureMetrics> {
+        let mut v: Vec<_> = self
+            .inner
+            .flow
+            .read()
+            .values()
+            .filter(|st| st.blocked_sends.load(Ordering::Relaxed) > 0)
+            .map(|st| crate::introspect::BackpressureMetrics {
+                type_name: st.type_name,
+                blocked_sends: st.blocked_sends.load(Ordering::Relaxed),
+                blocked_total: Duration::from_nanos(st.blocked_nanos.load(Ordering::Relaxed)),
+                blocked_max: Duration::from_nanos(st.blocked_max_nanos.load(Ordering::Relaxed)),
+            })
+            .collect();
+        v.sort_by_key(|m| m.type_name);
+        v
+    }
+    // 发布接口：仅供宏生成代码内部使用
+
+    pub(crate) fn subscriber_probes(&self) -> Vec<Arc<SubscriberProbe>> {
+        self.inner.probes.read().clone()
+    }
+
+    pub(crate) fn subscriptions_snapshot(&self) -> Vec<crate::introspect::SubscriptionInfo> {
+        self.inner
+            .probes
+            .read()
+            .iter()
+            .map(|p| {
+                let (depth, capacity, closed) = p
+                    .depth()
+                    .map_or((0, self.inner.default_capacity, true), |(d, c)| {
+                        (d, c, false)
+                    });
+                crate::introspect::SubscriptionInfo {
+                    component: p.component,
+                    type_name: p.type_name,
+                    depth,
+                    capacity,
+                    closed,
+                    ordered: p.ordered,
+                    weak: p.weak,
+                    dropped: p.dropped(),
+                }
+            })
+            .collect()
+    }
+
+    // 封印时的实际订阅：（类型, 归属组件, 队列容量），供消息类型登记表使用
+    pub(crate) fn subscription_types(
         &self,
-        senders: &[mpsc::Sender<Arc<T>>],
-        arc: Arc<T>,
+    ) -> Vec<(crate::wiring::MessageType, &'static str, usize)> {
+        self.inner
+            .probes
+            .read()
+            .iter()
+            .map(|p| {
+                let capacity = p.depth().map_or(self.inner.default_capacity, |(_, c)| c);
+                (p.message, p.component, capacity)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "bus-metrics")]
+    #[inline]
+    fn with_stats(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        f: impl FnOnce(&crate::introspect::TypeStats),
     ) {
-        match senders.len() {
-            0 => {}
-            1 => {
-                self.send_one(&senders[0], arc).await;
+        if let Some(st) = self.inner.stats.read().get(&type_id) {
+            f(st);
+            return;
+        }
+        let mut stats = self.inner.stats.write();
+        f(stats
+            .entry(type_id)
+            .or_insert_with(|| crate::introspect::TypeStats::new(type_name)));
+    }
+
+    // 登记类型的附加尺寸估算（如堆上载荷），计入 `TypeMetrics::bytes`
+    #[cfg(feature = "bus-metrics")]
+    pub(crate) fn set_size_hint<T: Send + Sync + 'static>(&self, f: fn(&T) -> usize) {
+        self.inner
+            .stats
+            .write()
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| crate::introspect::TypeStats::new(std::any::type_name::<T>()))
+            .set_size_hint(Box::new(move |msg| msg.downcast_ref::<T>().map_or(0, f)));
+    }
+
+    pub(crate) fn type_metrics(&self) -> Vec<crate::introspect::TypeMetrics> {
+        #[cfg(feature = "bus-metrics")]
+        {
+            let mut v: Vec<_> = self
+                .inner
+                .stats
+                .read()
+                .values()
+                .map(|s| s.snapshot())
+                .collect();
+            v.sort_by_key(|m| m.type_name);
+            v
+        }
+        #[cfg(not(feature = "bus-metrics"))]
+        Vec::new()
+    }
+
+    // 开启后投递信封携带逐类型序号（App 构造时按配置设置）
+    pub(crate) fn set_sequence_numbers(&self, on: bool) {
+        self.inner.sequenced.store(on, Ordering::Relaxed);
+    }
+    // 登记内联类型：按值经通道复制投递，静态发布路径不再分配 Arc（须在封印前，与订阅一同冻结）
+    pub(crate) fn set_inline<T: Copy + Send + Sync + 'static>(&self) {
+        if let Some(idx) = self
+            .inner
+            .subs
+            .write()
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::<TypeIndex<T>>::default() as Box<dyn TypeIndexEntry>)
+            .as_any_mut()
+            .downcast_mut::<TypeIndex<T>>()
+        {
+            idx.inline = Some(|v| *v);
+        }
+    }
+    pub(crate) fn set_rate_limits(&self, limits: &[crate::config::RateLimit]) {
+        if limits.is_empty() {
+            return;
+        }
+        let map = limits
+            .iter()
+            .map(|l| (l.type_id, crate::rate_limit::RateLimiter::new(l)))
+            .collect();
+        if self.inner.limits.set(map).is_err() {
+            tracing::warn!("rate limits already set; ignored");
+        }
+    }
+    pub(crate) fn set_dedup(&self, dedup: &[crate::config::Dedup]) {
+        if dedup.is_empty() {
+            return;
+        }
+        let map = dedup.iter().map(|d| (d.type_id, (d.build)())).collect();
+        if self.inner.dedup.set(map).is_err() {
+            tracing::warn!("dedup windows already set; ignored");
+        }
+    }
+    // 发布入口判定：先去重、再限速，未配置的类型直接放行；返回 false 表示该条发布被抑制或丢弃
+    #[inline]
+    async fn admit(&self, msg: &(dyn Any + Send + Sync)) -> bool {
+        let type_id = (*msg).type_id();
+        if let Some(filter) = self.inner.dedup.get().and_then(|m| m.get(&type_id)) {
+            if !filter.admit(msg) {
+                return false;
+            }
+        }
+        match self.inner.limits.get().and_then(|m| m.get(&type_id)) {
+            Some(limiter) => limiter.admit().await,
+            None => true,
+        }
+    }
+    pub(crate) fn rate_limits(&self) -> Vec<crate::introspect::RateLimitMetrics> {
+        let mut v: Vec<_> = self
+            .inner
+            .limits
+            .get()
+            .map(|m| {
+                m.values()
+                    .map(crate::rate_limit::RateLimiter::metrics)
+                    .collect()
+            })
+            .unwrap_or_default();
+        v.sort_by_key(|m| m.type_name);
+        v
+    }
+    pub(crate) fn set_buffer_pre_seal(&self, on: bool) {
+        if on && !self.is_sealed() {
+            *self.inner.pre_seal.lock() = Some(std::collections::VecDeque::new());
+            self.inner.buffering.store(true, Ordering::Release);
+        }
+    }
+    // 缓冲开启时消息入队并返回 true；未开启时仅一次原子读
+    #[inline]
+    fn buffer_pre_seal(&self, msg: impl FnOnce() -> Arc<dyn Any + Send + Sync>) -> bool {
+        if !self.inner.buffering.load(Ordering::Acquire) {
+            return false;
+        }
+        match self.inner.pre_seal.lock().as_mut() {
+            Some(q) => {
+                q.push_back((self.stamp(), msg()));
+                true
             }
-            _ => {
-                let pending_idx = Self::try_send_collect_pending(senders, &arc);
-                Self::send_pending_by_index::<T>(senders, &pending_idx, arc).await;
+            None => false,
+        }
+    }
+    // 封印后依序投递缓冲的消息；重放期间的新发布排在队尾，队列取空时（同一把锁内）关闭缓冲，保证不丢不乱序
+    pub(crate) async fn replay_pre_seal(&self) -> usize {
+        let mut replayed = 0;
+        loop {
+            let (stamp, next) = {
+                let mut q = self.inner.pre_seal.lock();
+                match q.as_mut().and_then(std::collections::VecDeque::pop_front) {
+                    Some(msg) => msg,
+                    None => {
+                        *q = None;
+                        self.inner.buffering.store(false, Ordering::Release);
+                        break;
+                    }
+                }
+            };
+            if let Err(e) = self.route_any_arc(next, stamp).await {
+                tracing::error!(error = %e, "pre-seal publish dropped");
             }
+            replayed += 1;
+        }
+        replayed
+    }
+    // 启动失败：丢弃缓冲，返回丢弃条数
+    pub(crate) fn discard_pre_seal(&self) -> usize {
+        let mut q = self.inner.pre_seal.lock();
+        self.inner.buffering.store(false, Ordering::Release);
+        q.take().map_or(0, |q| q.len())
+    }
+    // 线路调试：运行期可切换的采样 trace（target `mmg_microbus::wire`）。
+    // type_name 为 None（动态 Any 路径）时按 TypeId 反查，仅在采样命中时发生。
+    pub(crate) fn set_wire_debug(&self, sample_every: u32) {
+        self.inner.wire_debug.store(sample_every, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn wire_trace(
+        &self,
+        origin: &'static str,
+        type_id: TypeId,
+        type_name: Option<&'static str>,
+    ) {
+        let every = self.inner.wire_debug.load(Ordering::Relaxed);
+        if every == 0 {
+            return;
+        }
+        let seq = self.inner.wire_seq.fetch_add(1, Ordering::Relaxed);
+        if !seq.is_multiple_of(u64::from(every)) {
+            return;
+        }
+        let subscribers = self.open_subscribers(type_id);
+        tracing::trace!(
+            target: "mmg_microbus::wire",
+            origin,
+            message_type = type_name.unwrap_or_else(|| self.dyn_type_name(type_id)),
+            subscribers,
+            seq,
+            "publish"
+        );
+    }
+
+    // tap 不属于订阅图，封印前后均可登记
+    pub(crate) fn add_tap(&self, tap: Arc<dyn PublishTap>) {
+        self.inner.taps.write().push(tap);
+        self.inner.has_taps.store(true, Ordering::Release);
+    }
+
+    // 调用方先检查 has_taps，避免无 tap 时构造 Arc<dyn Any>
+    fn notify_taps(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        msg: &Arc<dyn Any + Send + Sync>,
+    ) {
+        for tap in self.inner.taps.read().iter() {
+            tap.on_publish(type_id, type_name, msg);
         }
     }
-    // 发布接口：仅供宏生成代码内部使用
+
+    fn notify_routed(&self, type_id: TypeId) {
+        for tap in self.inner.taps.read().iter() {
+            tap.on_routed(type_id);
+        }
+    }
+
+    fn dyn_type_name(&self, type_id: TypeId) -> &'static str {
+        self.inner
+            .subs
+            .read()
+            .get(&type_id)
+            .map_or(UNKNOWN_DYN_TYPE, |entry| entry.type_name())
+    }
+
+    // 当前未关闭的订阅数（所属组件仍在运行）
+    pub(crate) fn open_subscribers(&self, type_id: TypeId) -> usize {
+        self.inner
+            .subs
+            .read()
+            .get(&type_id)
+            .map_or(0, |entry| entry.open_subscribers())
+    }
 
     #[cfg(test)]
     #[must_use]
@@ -364,75 +1504,149 @@ impl BusHandle {
         let subs = self.inner.subs.read();
         subs.get(&type_id)
             .and_then(|entry| entry.as_any().downcast_ref::<TypeIndex<T>>())
-            .map_or(0, |idx| idx.any.iter().filter(|tx| !tx.is_closed()).count())
+            .map_or(0, |idx| {
+                idx.route.all().iter().filter(|tx| !tx.is_closed()).count()
+            })
     }
 
     // 动态消息发布：接收 Box<dyn Any>（业务返回值弱类型），按照其实际运行时 TypeId 精确投递。
     pub async fn publish_any_box(&self, msg: Box<dyn Any + Send + Sync>) {
+        if let Err(e) = self.try_publish_any_box(msg).await {
+            tracing::error!(error = %e, "dynamic publish dropped");
+        }
+    }
+    /// 同 [`publish_any_box`](Self::publish_any_box)，类型不一致时返回错误而非仅记录日志。
+    ///
+    /// # Errors
+    /// 路由表中该 `TypeId` 的条目与消息实际类型不一致时返回 [`PublishError::TypeMismatch`]。
+    pub async fn try_publish_any_box(
+        &self,
+        msg: Box<dyn Any + Send + Sync>,
+    ) -> Result<(), PublishError> {
+        if self.inner.has_taps.load(Ordering::Acquire)
+            || self.inner.buffering.load(Ordering::Acquire)
+        {
+            // tap 可能保留消息、缓冲需持有消息：转为 Arc 走共享路径（投递语义一致）
+            return self.try_publish_any_arc(Arc::from(msg)).await;
+        }
         let type_id = (*msg).type_id();
-        let sealed = self.is_sealed();
+        if !self.admit(&*msg).await {
+            return Ok(());
+        }
+        #[cfg(feature = "metrics")]
+        crate::metrics::published(self.dyn_type_name(type_id));
+        #[cfg(feature = "bus-metrics")]
+        self.with_stats(type_id, self.dyn_type_name(type_id), |st| {
+            st.record(&*msg, std::mem::size_of_val(&*msg));
+        });
+        let mode = self.route_mode(self.stamp());
         let fut = {
             let subs = self.inner.subs.read();
             if let Some(entry) = subs.get(&type_id) {
-                entry.publish_box_dyn(sealed, msg)
+                entry.publish_box_dyn(mode, msg)?
             } else {
                 // 无订阅者：静默丢弃
-                Box::pin(async {})
+                return Ok(());
             }
         };
-        fut.await;
+        let delivery = fut.await;
+        if !delivery.is_clean() {
+            self.record_flow(type_id, self.dyn_type_name(type_id), &delivery);
+        }
+        Ok(())
+    }
+    /// 逐项发布 `stream` 直至其结束，返回发布条数；每项与单独发布的投递语义相同（含背压等待）。
+    #[cfg(feature = "stream")]
+    pub async fn publish_stream<S>(&self, stream: S) -> u64
+    where
+        S: futures_core::Stream,
+        S::Item: Send + Sync + 'static,
+    {
+        let mut stream = std::pin::pin!(stream);
+        let mut published = 0;
+        while let Some(msg) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            self.publish_type(msg).await;
+            published += 1;
+        }
+        published
     }
     pub async fn publish_any_arc(&self, msg: Arc<dyn Any + Send + Sync>) {
+        if let Err(e) = self.try_publish_any_arc(msg).await {
+            tracing::error!(error = %e, "dynamic publish dropped");
+        }
+    }
+    /// 同 [`publish_any_arc`](Self::publish_any_arc)，类型不一致时返回错误而非仅记录日志。
+    ///
+    /// # Errors
+    /// 路由表中该 `TypeId` 的条目与消息实际类型不一致时返回 [`PublishError::TypeMismatch`]。
+    pub async fn try_publish_any_arc(
+        &self,
+        msg: Arc<dyn Any + Send + Sync>,
+    ) -> Result<(), PublishError> {
+        if !self.admit(&*msg).await {
+            return Ok(());
+        }
+        if self.buffer_pre_seal(|| msg.clone()) {
+            return Ok(());
+        }
+        self.route_any_arc(msg, self.stamp()).await
+    }
+    async fn route_any_arc(
+        &self,
+        msg: Arc<dyn Any + Send + Sync>,
+        stamp: Stamp,
+    ) -> Result<(), PublishError> {
         let type_id = (*msg).type_id();
-        let sealed = self.is_sealed();
+        let tapped = self.inner.has_taps.load(Ordering::Acquire);
+        if tapped {
+            self.notify_taps(type_id, self.dyn_type_name(type_id), &msg);
+        }
+        #[cfg(feature = "metrics")]
+        crate::metrics::published(self.dyn_type_name(type_id));
+        #[cfg(feature = "bus-metrics")]
+        self.with_stats(type_id, self.dyn_type_name(type_id), |st| {
+            st.record(&*msg, std::mem::size_of_val(&*msg));
+        });
+        let mode = self.route_mode(stamp);
         let fut = {
             let subs = self.inner.subs.read();
-            if let Some(entry) = subs.get(&type_id) {
-                entry.publish_arc_dyn(sealed, msg)
-            } else {
-                Box::pin(async {})
-            }
-        };
-        fut.await;
-    }
-}
-
-// 提取一个静态泛型帮助函数，供动态路径重用。
-async fn publish_to_senders_static<T: Send + Sync + 'static>(
-    senders: &[mpsc::Sender<Arc<T>>],
-    arc: Arc<T>,
-) {
-    match senders.len() {
-        0 => {}
-        1 => match senders[0].try_send(arc.clone()) {
-            Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => {
-                let _ = senders[0].send(arc).await;
-            }
-            Ok(()) | Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => {}
-        },
-        _ => {
-            let pending_idx = {
-                let mut pending: SmallVec<[usize; 8]> = SmallVec::new();
-                for (i, tx) in senders.iter().enumerate() {
-                    match tx.try_send(arc.clone()) {
-                        Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => pending.push(i),
-                        Ok(()) | Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => {}
+            match subs
+                .get(&type_id)
+                .map(|entry| entry.publish_arc_dyn(mode, msg))
+            {
+                Some(Ok(fut)) => fut,
+                routed => {
+                    if tapped {
+                        self.notify_routed(type_id);
                     }
+                    // 无订阅者：静默丢弃
+                    return routed.map_or(Ok(()), |r| r.map(|_| ()));
                 }
-                pending
-            };
-            if !pending_idx.is_empty() {
-                let last = pending_idx.len() - 1;
-                for &i in &pending_idx[..last] {
-                    let _ = senders[i].send(arc.clone()).await;
-                }
-                let _ = senders[pending_idx[last]].send(arc).await;
             }
+        };
+        let delivery = fut.await;
+        if tapped {
+            self.notify_routed(type_id);
         }
+        if !delivery.is_clean() {
+            self.record_flow(type_id, self.dyn_type_name(type_id), &delivery);
+        }
+        Ok(())
+    }
+    /// 发布类型擦除事件（编解码桥接的统一出口）。
+    ///
+    /// # Errors
+    /// 事件携带的数据与其记录的类型不一致时返回 [`PublishError::TypeMismatch`]。
+    pub async fn try_publish_erased(&self, ev: ErasedEvent) -> Result<(), PublishError> {
+        (ev.publish_fn)(self, ev.data)?.await;
+        Ok(())
     }
 }
 
 impl BusHandle {
+    pub(crate) fn same_bus(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
     pub(crate) fn seal(&self) {
         // 在封印前冻结所有已知类型的订阅快照，确保运行期发布路径无需惰性构建。
         let mut subs = self.inner.subs.write();
@@ -448,7 +1662,7 @@ impl BusHandle {
 
 // 背压策略：有界 mpsc + try_send 优先，必要时 await；单订阅者快路径；SmallVec 降低分配成本。
 
-// 实现保持最小化（无内部指标采集）
+// 内部统计：投递异常（关闭 / 满队列丢弃、背压等待）始终按类型累计；发布计数与近似字节量仅在特性 `bus-metrics` 下采集
 
 // 内部单元测试省略：由集成测试覆盖
 
@@ -467,7 +1681,7 @@ mod perf_tests {
         // 订阅者：每个订阅者消费 msgs 条消息
         let mut join = JoinSet::new();
         for _ in 0..n_subs {
-            let mut sub = handle.subscribe_type::<Msg>();
+            let mut sub = handle.subscribe_type::<Msg>("perf");
             join.spawn(async move {
                 let mut c = 0u64;
                 while c < msgs {
@@ -518,3 +1732,24 @@ mod perf_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod error_tests {
+    use super::{Bus, ErasedEvent};
+    use crate::error::PublishError;
+
+    #[tokio::test]
+    async fn erased_payload_mismatch_is_returned_not_panicked() {
+        let handle = Bus::new(8).handle();
+        let mut ev = ErasedEvent::new(1u32);
+        ev.data = Box::new("not a u32");
+        let err = handle.try_publish_erased(ev).await.unwrap_err();
+        assert!(matches!(
+            err,
+            PublishError::TypeMismatch {
+                expected: "u32",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+//! 运行时无关的总线核心：订阅登记表（[`Route`]）与 fanout 投递（[`fanout`]）。
+//!
+//! 本模块不依赖 tokio：通道经 [`Outbox`] 注入，计时经 [`Clock`] 注入。[`bus`](crate::bus) 是默认的 tokio 集成层
+//! （`tokio::sync::mpsc` 队列 / 合并槽 / 弱订阅实现 `Outbox`，`tokio::time::Instant` 实现 `Clock`）；
+//! 受限环境或其它运行时可提供自己的通道与时钟，复用相同的路由与背压语义。
+use smallvec::SmallVec;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// [`Outbox::try_deliver`] 的结果。
+pub enum TryDeliver<M> {
+    /// 已入队（或按投递方式的约定已处理，如弱订阅满时丢弃）。
+    Delivered,
+    /// 队列已满：消息原样退回，由调用方决定是否等待。
+    Full(M),
+    /// 接收端已关闭。
+    Closed,
+}
+
+/// 订阅端投递目标（一个订阅者的发送端）。
+pub trait Outbox<M> {
+    /// 不等待地投递。
+    fn try_deliver(&self, msg: M) -> TryDeliver<M>;
+    /// 等待至有空位后投递；接收端已关闭时输出 `false`。
+    fn deliver(&self, msg: M) -> impl Future<Output = bool> + Send;
+    fn is_closed(&self) -> bool;
+}
+
+/// 单调时钟：只用于度量发布方的背压等待时长。
+pub trait Clock {
+    type Instant: Copy + Send;
+    fn now() -> Self::Instant;
+    fn elapsed(since: Self::Instant) -> Duration;
+}
+
+/// 标准库单调时钟。
+pub struct StdClock;
+
+impl Clock for StdClock {
+    type Instant = std::time::Instant;
+    fn now() -> Self::Instant {
+        std::time::Instant::now()
+    }
+    fn elapsed(since: Self::Instant) -> Duration {
+        since.elapsed()
+    }
+}
+
+/// 单次 fanout 的投递结果。
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[must_use]
+pub struct Delivery {
+    /// 因订阅端关闭丢弃的份数。
+    pub closed: usize,
+    /// 存在满队列时发布方的等待时长（整段等待计一次）。
+    pub blocked: Option<Duration>,
+}
+
+impl Delivery {
+    pub(crate) const CLOSED: Self = Self {
+        closed: 1,
+        blocked: None,
+    };
+    /// 全部入队且未等待。
+    #[inline]
+    #[must_use]
+    pub const fn is_clean(&self) -> bool {
+        self.closed == 0 && self.blocked.is_none()
+    }
+}
+
+/// [`try_fanout`] 的投递结果：满队列的份数直接丢弃，发布方不等待。
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[must_use]
+pub struct TryDelivery {
+    /// 因订阅端关闭丢弃的份数。
+    pub closed: usize,
+    /// 因队列已满丢弃的份数。
+    pub full: usize,
+}
+
+/// 单个消息类型的订阅登记表。
+///
+/// 启动阶段（未封印）累积订阅，发布时过滤已关闭的发送端；[`freeze`](Self::freeze) 后构建不可变快照，
+/// 发布阶段直接共享该快照，不再克隆发送端。
+pub struct Route<O> {
+    open: SmallVec<[O; 4]>,
+    frozen: Option<Arc<[O]>>,
+}
+
+impl<O> Default for Route<O> {
+    fn default() -> Self {
+        Self {
+            open: SmallVec::new(),
+            frozen: None,
+        }
+    }
+}
+
+impl<O> Route<O> {
+    pub fn push(&mut self, outbox: O) {
+        self.open.push(outbox);
+    }
+    /// 构建快照；重复调用无效果。
+    pub fn freeze(&mut self) {
+        if self.frozen.is_none() {
+            self.frozen = Some(Arc::from(std::mem::take(&mut self.open).into_vec()));
+        }
+    }
+    /// 封印后的快照（未冻结时为 `None`）。
+    #[must_use]
+    pub fn frozen(&self) -> Option<Arc<[O]>> {
+        self.frozen.clone()
+    }
+    /// 全部登记的发送端（含已关闭的）。
+    #[must_use]
+    pub fn all(&self) -> &[O] {
+        self.frozen.as_deref().unwrap_or(&self.open)
+    }
+    /// 未冻结阶段仍开放的发送端副本。
+    #[must_use]
+    pub fn open<M>(&self) -> SmallVec<[O; 8]>
+    where
+        O: Outbox<M> + Clone,
+    {
+        self.open
+            .iter()
+            .filter(|o| !o.is_closed())
+            .cloned()
+            .collect()
+    }
+}
+
+/// 把 `msg` 投递给全部 `outboxes`。
+///
+/// 先对每个发送端做一次不等待投递，仅对满队列依次等待（最后一份移交原消息，其余克隆）；
+/// 单个订阅者时不克隆。
+pub async fn fanout<M, O, C>(outboxes: &[O], msg: M) -> Delivery
+where
+    M: Clone,
+    O: Outbox<M>,
+    C: Clock,
+{
+    match outboxes {
+        [] => Delivery::default(),
+        [one] => match one.try_deliver(msg) {
+            TryDeliver::Delivered => Delivery::default(),
+            TryDeliver::Closed => Delivery::CLOSED,
+            TryDeliver::Full(msg) => {
+                let t0 = C::now();
+                let closed = usize::from(!one.deliver(msg).await);
+                Delivery {
+                    closed,
+                    blocked: Some(C::elapsed(t0)),
+                }
+            }
+        },
+        _ => {
+            let mut pending: SmallVec<[usize; 8]> = SmallVec::new();
+            let mut closed = 0;
+            for (i, o) in outboxes.iter().enumerate() {
+                match o.try_deliver(msg.clone()) {
+                    TryDeliver::Delivered => {}
+                    TryDeliver::Full(_) => pending.push(i),
+                    TryDeliver::Closed => closed += 1,
+                }
+            }
+            let Some((&last, rest)) = pending.split_last() else {
+                return Delivery {
+                    closed,
+                    blocked: None,
+                };
+            };
+            let t0 = C::now();
+            for &i in rest {
+                closed += usize::from(!outboxes[i].deliver(msg.clone()).await);
+            }
+            closed += usize::from(!outboxes[last].deliver(msg).await);
+            Delivery {
+                closed,
+                blocked: Some(C::elapsed(t0)),
+            }
+        }
+    }
+}
+
+/// 不等待地把 `msg` 投递给全部 `outboxes`：满队列的订阅者丢弃该份（计入 `full`），从不反压发布方。
+pub fn try_fanout<M, O>(outboxes: &[O], msg: M) -> TryDelivery
+where
+    M: Clone,
+    O: Outbox<M>,
+{
+    let mut d = TryDelivery::default();
+    if let Some((last, rest)) = outboxes.split_last() {
+        for o in rest {
+            d.record(o.try_deliver(msg.clone()));
+        }
+        d.record(last.try_deliver(msg));
+    }
+    d
+}
+
+impl TryDelivery {
+    fn record<M>(&mut self, r: TryDeliver<M>) {
+        match r {
+            TryDeliver::Delivered => {}
+            TryDeliver::Full(_) => self.full += 1,
+            TryDeliver::Closed => self.closed += 1,
+        }
+    }
+}
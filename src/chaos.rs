@@ -0,0 +1,231 @@
+//! 故障注入（特性 `chaos`）：按消息类型对组件订阅随机延迟、丢弃、重排投递，或令 handler 调用失败，
+//! 用于在测试中验证组件对总线失效模式的假设。
+//!
+//! `app.inject_faults(Chaos::seeded(7).drop::<Quote>(0.1).delay::<Quote>(0.2, Duration::from_millis(5)))`，须在 `start()` 之前调用。
+//! - 只作用于组件的订阅（`#[handle]` 的投递与组件内手动订阅）；组件之外的 `try_subscribe` 与发布方不受影响；
+//! - 每个（组件, 类型）订阅各有独立的随机序列，由种子、组件名与类型名决定；同一种子下抽样结果可复现，实际交错仍取决于调度；
+//! - 重排：抽中的消息暂扣，待同一订阅的下一条先交付后再交付（至多暂扣 [`REORDER_WINDOW`]）；
+//! - 失败：不调用方法体，按 handler panic 处理（记录 error、按配置发布 `HandlerError`、执行 `on_error` 策略，`retry` 同样生效）。
+use std::any::TypeId;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::bus::{Envelope, Subscription};
+
+/// 重排暂扣的最长时间：期间没有下一条到达时按原序交付。
+pub const REORDER_WINDOW: Duration = Duration::from_millis(10);
+
+#[derive(Clone, Copy, Default)]
+struct Faults {
+    drop: f64,
+    delay: f64,
+    max_delay: Duration,
+    reorder: f64,
+    fail: f64,
+}
+
+/// 故障注入计划：按消息类型登记各类故障的概率（`0.0..=1.0`）。
+pub struct Chaos {
+    seed: u64,
+    faults: HashMap<TypeId, (&'static str, Faults)>,
+    // handler 失败的抽样状态：按类型共享（同一类型的多个 handler 依调用先后取号）
+    fail_rng: HashMap<TypeId, AtomicU64>,
+    handle: ChaosHandle,
+}
+
+/// 注入计数句柄（可在 `inject_faults` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct ChaosHandle {
+    dropped: Arc<AtomicU64>,
+    delayed: Arc<AtomicU64>,
+    reordered: Arc<AtomicU64>,
+    failed: Arc<AtomicU64>,
+}
+
+impl ChaosHandle {
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+    #[must_use]
+    pub fn delayed(&self) -> u64 {
+        self.delayed.load(Ordering::Relaxed)
+    }
+    #[must_use]
+    pub fn reordered(&self) -> u64 {
+        self.reordered.load(Ordering::Relaxed)
+    }
+    #[must_use]
+    pub fn failed(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+}
+
+impl Chaos {
+    #[must_use]
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seed,
+            faults: HashMap::new(),
+            fail_rng: HashMap::new(),
+            handle: ChaosHandle::default(),
+        }
+    }
+    /// 以概率 `p` 丢弃 `T` 的投递（订阅方收不到该条）。
+    #[must_use]
+    pub fn drop<T: 'static>(self, p: f64) -> Self {
+        self.with::<T>(|f| f.drop = p)
+    }
+    /// 以概率 `p` 把 `T` 的投递推迟 `0..=max` 之间的随机时长（后续消息随之排队，不越过被推迟者）。
+    #[must_use]
+    pub fn delay<T: 'static>(self, p: f64, max: Duration) -> Self {
+        self.with::<T>(|f| {
+            f.delay = p;
+            f.max_delay = max;
+        })
+    }
+    /// 以概率 `p` 把 `T` 的一条投递与其后一条交换顺序。
+    #[must_use]
+    pub fn reorder<T: 'static>(self, p: f64) -> Self {
+        self.with::<T>(|f| f.reorder = p)
+    }
+    /// 以概率 `p` 令处理 `T` 的 handler 调用失败。
+    #[must_use]
+    pub fn fail<T: 'static>(mut self, p: f64) -> Self {
+        let seed = self.seed ^ name_hash(std::any::type_name::<T>());
+        self.fail_rng
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| AtomicU64::new(seed));
+        self.with::<T>(|f| f.fail = p)
+    }
+    #[must_use]
+    pub fn handle(&self) -> ChaosHandle {
+        self.handle.clone()
+    }
+    fn with<T: 'static>(mut self, f: impl FnOnce(&mut Faults)) -> Self {
+        let entry = self
+            .faults
+            .entry(TypeId::of::<T>())
+            .or_insert((std::any::type_name::<T>(), Faults::default()));
+        f(&mut entry.1);
+        self
+    }
+
+    // 组件订阅的注入通道：该类型未登记投递故障时为 None
+    pub(crate) fn lane<T: 'static>(&self, component: &'static str) -> Option<Lane<T>> {
+        let (type_name, faults) = self.faults.get(&TypeId::of::<T>())?;
+        if faults.drop <= 0.0 && faults.delay <= 0.0 && faults.reorder <= 0.0 {
+            return None;
+        }
+        Some(Lane {
+            faults: *faults,
+            rng: self.seed ^ name_hash(component) ^ name_hash(type_name).rotate_left(32),
+            handle: self.handle.clone(),
+            ready: VecDeque::new(),
+            held: None,
+        })
+    }
+
+    // handler 调用前抽样：命中时该次调用按失败处理
+    pub(crate) fn should_fail<T: 'static>(&self) -> bool {
+        let id = TypeId::of::<T>();
+        let (Some((_, faults)), Some(state)) = (self.faults.get(&id), self.fail_rng.get(&id))
+        else {
+            return false;
+        };
+        let x = state.fetch_add(GOLDEN_GAMMA, Ordering::Relaxed);
+        let hit = chance(splitmix(x.wrapping_add(GOLDEN_GAMMA)), faults.fail);
+        if hit {
+            self.handle.failed.fetch_add(1, Ordering::Relaxed);
+        }
+        hit
+    }
+}
+
+/// 单个组件订阅的注入状态；所有暂存都留在此处，`recv` 被取消（停机 / 暂停竞争）时不丢消息。
+pub(crate) struct Lane<T> {
+    faults: Faults,
+    rng: u64,
+    handle: ChaosHandle,
+    ready: VecDeque<(Envelope<T>, Instant)>, // 待交付（及其最早交付时刻）
+    held: Option<(Envelope<T>, Instant)>,    // 重排暂扣（及其最晚交付时刻）
+}
+
+impl<T: Send + Sync + 'static> Lane<T> {
+    pub(crate) async fn recv(&mut self, sub: &mut Subscription<T>) -> Option<Envelope<T>> {
+        loop {
+            if let Some(&(_, at)) = self.ready.front() {
+                tokio::time::sleep_until(at).await;
+                return self.ready.pop_front().map(|(env, _)| env);
+            }
+            let next = match self.held.as_ref().map(|&(_, until)| until) {
+                Some(until) => crate::rt::select! {
+                    msg = sub.recv_envelope() => msg,
+                    () = tokio::time::sleep_until(until) => {
+                        return self.held.take().map(|(env, _)| env);
+                    }
+                },
+                None => sub.recv_envelope().await,
+            };
+            let Some(env) = next else {
+                return self.held.take().map(|(env, _)| env);
+            };
+            if self.roll(self.faults.drop) {
+                self.handle.dropped.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+            let now = Instant::now();
+            if self.held.is_none() && self.roll(self.faults.reorder) {
+                self.handle.reordered.fetch_add(1, Ordering::Relaxed);
+                self.held = Some((env, now + REORDER_WINDOW));
+                continue;
+            }
+            let at = if self.roll(self.faults.delay) {
+                self.handle.delayed.fetch_add(1, Ordering::Relaxed);
+                now + self.faults.max_delay.mul_f64(self.unit())
+            } else {
+                now
+            };
+            self.ready.push_back((env, at));
+            if let Some((held, _)) = self.held.take() {
+                self.ready.push_back((held, at));
+            }
+        }
+    }
+
+    fn roll(&mut self, p: f64) -> bool {
+        p > 0.0 && self.unit() < p
+    }
+    fn unit(&mut self) -> f64 {
+        self.rng = self.rng.wrapping_add(GOLDEN_GAMMA);
+        unit(splitmix(self.rng))
+    }
+}
+
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// splitmix64：注入抽样无需密码学随机，只需按种子可复现
+const fn splitmix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn unit(x: u64) -> f64 {
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+fn chance(x: u64, p: f64) -> bool {
+    p > 0.0 && unit(x) < p
+}
+
+// FNV-1a：名称到种子分量的稳定映射（不随进程变化）
+fn name_hash(name: &str) -> u64 {
+    name.bytes().fold(0xCBF2_9CE4_8422_2325, |h, b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01B3)
+    })
+}
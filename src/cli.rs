@@ -0,0 +1,147 @@
+//! 命令行参数（特性 `cli`）：为二进制提供一致的 `--queue-capacity`、`--disable-component`、`--profile`、`--log-level` 等参数。
+//!
+//! 可单独解析（`AppArgs::parse()`），或经 `#[command(flatten)]` 嵌入项目自身的 clap 解析器。
+use clap::Parser;
+
+use crate::app::App;
+use crate::config::{ActiveScheduling, AppConfig};
+use crate::error::{MicrobusError, Result};
+use crate::profile::Profiles;
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct AppArgs {
+    /// 订阅队列容量
+    #[arg(long, value_name = "N")]
+    pub queue_capacity: Option<usize>,
+    /// 慢 handler 告警阈值（毫秒）
+    #[arg(long, value_name = "MS")]
+    pub slow_handler_ms: Option<u64>,
+    /// 按类型为发布编号
+    #[arg(long)]
+    pub sequence_numbers: bool,
+    /// 缓冲封印前的发布
+    #[arg(long)]
+    pub buffer_pre_seal: bool,
+    /// handler 返回 Err 时发布 HandlerError
+    #[arg(long)]
+    pub publish_handler_errors: bool,
+    /// 同时执行中的 handler 调用上限
+    #[arg(long, value_name = "N")]
+    pub max_concurrent_handlers: Option<usize>,
+    /// handler 连续处理 N 条后让出（公平调度预算）
+    #[arg(long, value_name = "N")]
+    pub handler_budget: Option<u32>,
+    /// 组件运行在 N 个专用 worker 线程上
+    #[arg(long, value_name = "N")]
+    pub worker_threads: Option<usize>,
+    /// 每个 #[active] 循环独占一个线程
+    #[arg(long)]
+    pub dedicated_actives: bool,
+    /// 启动前执行严格装配校验
+    #[arg(long)]
+    pub strict_wiring: bool,
+    /// 数据源全部结束且队列排空后自动停止
+    #[arg(long)]
+    pub stop_on_completion: bool,
+    /// 只做试运行检查（由二进制调用 `App::plan()` 后退出）
+    #[arg(long)]
+    pub check: bool,
+    /// 仅启动该组件（类型名或末段类型名，可重复）
+    #[arg(long, value_name = "NAME")]
+    pub enable_component: Vec<String>,
+    /// 不启动该组件（类型名或末段类型名，可重复）
+    #[arg(long, value_name = "NAME")]
+    pub disable_component: Vec<String>,
+    /// 运行档名称（见 `build_with_profiles`，或由二进制映射为基础配置）
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+    /// 日志级别：trace / debug / info / warn / error
+    #[arg(long, value_name = "LEVEL")]
+    pub log_level: Option<tracing::Level>,
+}
+
+impl AppArgs {
+    /// 在 `base` 之上应用命令行给出的项（布尔开关只开启，不关闭基础配置中已开启的项）。
+    #[must_use]
+    pub fn apply(&self, mut base: AppConfig) -> AppConfig {
+        if let Some(n) = self.queue_capacity {
+            base.queue_capacity = n;
+        }
+        if let Some(ms) = self.slow_handler_ms {
+            base.slow_handler_threshold = Some(std::time::Duration::from_millis(ms));
+        }
+        base.sequence_numbers |= self.sequence_numbers;
+        base.buffer_pre_seal |= self.buffer_pre_seal;
+        base.publish_handler_errors |= self.publish_handler_errors;
+        base.strict_wiring |= self.strict_wiring;
+        base.stop_on_completion |= self.stop_on_completion;
+        if self.max_concurrent_handlers.is_some() {
+            base.topology.max_concurrent_handlers = self.max_concurrent_handlers;
+        }
+        if self.handler_budget.is_some() {
+            base.topology.handler_budget = self.handler_budget;
+        }
+        if self.worker_threads.is_some() {
+            base.topology.worker_threads = self.worker_threads;
+        }
+        if self.dedicated_actives {
+            base.topology.actives = ActiveScheduling::Dedicated;
+        }
+        base
+    }
+
+    /// 按 `--profile` 经 `profiles` 取基础配置（未给出时为 `AppConfig::default()`），再应用其余参数。
+    ///
+    /// # Errors
+    /// `profiles` 不认识该名称时返回 [`MicrobusError::Config`]。
+    pub fn config(&self, profiles: impl FnOnce(&str) -> Option<AppConfig>) -> Result<AppConfig> {
+        let base = match &self.profile {
+            Some(name) => profiles(name)
+                .ok_or_else(|| MicrobusError::Config(format!("unknown profile `{name}`")))?,
+            None => AppConfig::default(),
+        };
+        Ok(self.apply(base))
+    }
+
+    /// 以 `cfg` 构造 App 并应用组件选择（`--enable-component` / `--disable-component`）。
+    ///
+    /// # Errors
+    /// 配置无效（见 [`AppConfig::validate`]）。
+    pub fn build(&self, cfg: AppConfig) -> Result<App> {
+        let mut app = App::try_new(cfg)?;
+        self.select_components(&mut app);
+        Ok(app)
+    }
+
+    /// 按 `--profile` 从 `profiles` 选择运行档构造 App：基础配置依次经运行档覆盖、命令行覆盖，
+    /// 组件选择为运行档与命令行两者叠加。未给出 `--profile` 时同 [`build`](Self::build)。
+    ///
+    /// # Errors
+    /// 未登记的运行档名称，或最终配置无效。
+    pub fn build_with_profiles(&self, base: AppConfig, profiles: &Profiles) -> Result<App> {
+        let Some(name) = &self.profile else {
+            return self.build(self.apply(base));
+        };
+        let profile = profiles.select(name)?;
+        let mut app = App::try_new(self.apply(profile.configure(base)))?;
+        profile.select_components(&mut app);
+        self.select_components(&mut app);
+        Ok(app)
+    }
+
+    fn select_components(&self, app: &mut App) {
+        for name in &self.enable_component {
+            app.enable_component(name.clone());
+        }
+        for name in &self.disable_component {
+            app.disable_component(name.clone());
+        }
+    }
+
+    /// 按 `--log-level`（默认 info）安装 fmt 日志输出；已安装全局订阅者时不做任何事。
+    pub fn init_logging(&self) {
+        let _ = tracing_subscriber::fmt()
+            .with_max_level(self.log_level.unwrap_or(tracing::Level::INFO))
+            .try_init();
+    }
+}
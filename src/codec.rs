@@ -0,0 +1,296 @@
+//! 消息编解码层：录制 / 回放与各跨进程桥共用的 [`MessageCodec`]，以及带稳定名称的 [`BusMessage`]。
+//!
+//! - 文本接口（`encode` / `decode`）供录制文件等按行存储的场景；字节接口（`encode_bytes` / `decode_bytes`）供桥传输，
+//!   默认取文本的 UTF-8 字节，二进制编解码器覆盖为原始字节。
+//! - 内置 serde 编解码器按特性启用：[`Json`]（`codec-json`）、[`Bincode`]（`codec-bincode`）、[`Postcard`]（`codec-postcard`）；
+//!   二进制编解码器的文本形式为小写十六进制。
+//! - 模式版本：[`BusMessage`] 携带 `VERSION` / `MIN_COMPATIBLE`，派生类型登记到进程内模式表（[`schemas`]）；
+//!   桥与回放在边界处以 [`Schema::check`] 比对对端版本，不一致经 [`SchemaMismatch`](crate::events::SchemaMismatch) 事件上报。
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub use microbus_macros::BusMessage;
+
+/// 带稳定名称的总线消息：名称在重命名 / 移动模块后保持不变，跨进程与录制文件据此匹配类型。
+///
+/// 通常经 `#[derive(BusMessage)]` 实现（默认取类型名，`#[bus_message(name = "..", version = 2, compatible_from = 1)]` 覆盖）。
+pub trait BusMessage: Serialize + DeserializeOwned + Send + Sync + 'static {
+    const NAME: &'static str;
+    /// 当前模式版本（结构变更时递增）。
+    const VERSION: u32 = 1;
+    /// 可接受的最低对端版本：较旧版本缺失的字段须可缺省（如 `#[serde(default)]`）。
+    const MIN_COMPATIBLE: u32 = Self::VERSION;
+}
+
+/// 消息模式：稳定名称与版本区间。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schema {
+    pub name: &'static str,
+    pub version: u32,
+    pub min_compatible: u32,
+    pub type_name: &'static str,
+}
+
+/// 对端版本相对本地模式的兼容性。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    Exact,
+    /// 对端较旧且不低于 `min_compatible`。
+    Older,
+    /// 对端较新：仍尝试解码，依赖编解码器容忍未知字段（JSON 可以，位置编码的二进制格式通常不行）。
+    Newer,
+    /// 对端低于 `min_compatible`：拒绝。
+    Incompatible,
+}
+
+impl Compatibility {
+    #[must_use]
+    pub const fn is_accepted(self) -> bool {
+        !matches!(self, Self::Incompatible)
+    }
+}
+
+impl Schema {
+    #[must_use]
+    pub fn of<T: BusMessage>() -> Self {
+        Self {
+            name: T::NAME,
+            version: T::VERSION,
+            min_compatible: T::MIN_COMPATIBLE,
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+    #[must_use]
+    pub const fn check(&self, remote: u32) -> Compatibility {
+        if remote == self.version {
+            Compatibility::Exact
+        } else if remote > self.version {
+            Compatibility::Newer
+        } else if remote >= self.min_compatible {
+            Compatibility::Older
+        } else {
+            Compatibility::Incompatible
+        }
+    }
+    // 线上 / 录制文件中的类型标识：`NAME@VERSION`
+    pub(crate) fn wire_name(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+}
+
+// 拆分 `NAME@VERSION`；无版本后缀（按类型名转发的类型）时版本为 None
+pub(crate) fn split_wire_name(name: &str) -> (&str, Option<u32>) {
+    match name.rsplit_once('@') {
+        Some((bare, v)) => match v.parse() {
+            Ok(v) => (bare, Some(v)),
+            Err(_) => (name, None),
+        },
+        None => (name, None),
+    }
+}
+
+// 边界处的版本检查：每个（名称, 对端版本）组合首次不一致时记录日志并生成 `SchemaMismatch` 事件
+#[derive(Default)]
+pub(crate) struct VersionGate {
+    seen: parking_lot::Mutex<std::collections::HashSet<(&'static str, u32)>>,
+}
+
+impl VersionGate {
+    pub(crate) fn observe(
+        &self,
+        component: &'static str,
+        schema: &Schema,
+        remote: u32,
+    ) -> (Compatibility, Option<crate::events::SchemaMismatch>) {
+        let compat = schema.check(remote);
+        if compat == Compatibility::Exact || !self.seen.lock().insert((schema.name, remote)) {
+            return (compat, None);
+        }
+        tracing::warn!(
+            component,
+            name = schema.name,
+            local = schema.version,
+            remote,
+            ?compat,
+            "message schema version mismatch"
+        );
+        let ev = crate::events::SchemaMismatch {
+            component,
+            name: schema.name,
+            local_version: schema.version,
+            remote_version: remote,
+            compatibility: compat,
+        };
+        (compat, Some(ev))
+    }
+
+    #[cfg(any(feature = "bridge-tcp", feature = "bridge-ipc"))]
+    pub(crate) async fn check(
+        &self,
+        ctx: &crate::component::ComponentContext,
+        schema: &Schema,
+        remote: u32,
+    ) -> Compatibility {
+        let (compat, ev) = self.observe(ctx.component_name(), schema, remote);
+        if let Some(ev) = ev {
+            crate::component::__publish_auto(ctx, ev).await;
+        }
+        compat
+    }
+}
+
+#[doc(hidden)]
+pub struct __SchemaEntry {
+    pub schema: fn() -> Schema,
+}
+
+inventory::collect!(__SchemaEntry);
+
+/// 进程内已链接的全部 `#[derive(BusMessage)]` 类型模式（按名称、版本排序）。
+#[must_use]
+pub fn schemas() -> Vec<Schema> {
+    let mut all: Vec<Schema> = inventory::iter::<__SchemaEntry>
+        .into_iter()
+        .map(|e| (e.schema)())
+        .collect();
+    all.sort_by(|a, b| (a.name, a.version).cmp(&(b.name, b.version)));
+    all
+}
+
+/// 消息编解码：录制时编码为文本、回放时还原；桥传输时经字节接口收发。
+///
+/// 亦可直接使用 `(encode, decode)` 闭包二元组。
+pub trait MessageCodec<T>: Send + Sync + 'static {
+    fn encode(&self, msg: &T) -> String;
+    /// # Errors
+    /// 文本无法还原为 `T` 时返回原因。
+    fn decode(&self, text: &str) -> std::result::Result<T, String>;
+    /// 桥传输使用的字节形式；默认为 `encode` 文本的 UTF-8 字节。
+    fn encode_bytes(&self, msg: &T) -> Vec<u8> {
+        self.encode(msg).into_bytes()
+    }
+    /// # Errors
+    /// 字节无法还原为 `T` 时返回原因。
+    fn decode_bytes(&self, bytes: &[u8]) -> std::result::Result<T, String> {
+        std::str::from_utf8(bytes)
+            .map_err(|e| e.to_string())
+            .and_then(|text| self.decode(text))
+    }
+}
+
+impl<T, E, D> MessageCodec<T> for (E, D)
+where
+    E: Fn(&T) -> String + Send + Sync + 'static,
+    D: Fn(&str) -> std::result::Result<T, String> + Send + Sync + 'static,
+{
+    fn encode(&self, msg: &T) -> String {
+        (self.0)(msg)
+    }
+    fn decode(&self, text: &str) -> std::result::Result<T, String> {
+        (self.1)(text)
+    }
+}
+
+// 编码失败（如含非字符串键的 map）无从上报：记录并输出空内容，由对端解码失败计数
+#[cfg(any(
+    feature = "codec-json",
+    feature = "codec-bincode",
+    feature = "codec-postcard"
+))]
+fn encode_failed(codec: &str, e: &dyn std::fmt::Display) {
+    tracing::warn!(codec, error = %e, "message encode failed");
+}
+
+/// JSON 编解码（特性 `codec-json`）。
+#[cfg(feature = "codec-json")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Json;
+
+#[cfg(feature = "codec-json")]
+impl<T: Serialize + DeserializeOwned> MessageCodec<T> for Json {
+    fn encode(&self, msg: &T) -> String {
+        serde_json::to_string(msg).unwrap_or_else(|e| {
+            encode_failed("json", &e);
+            String::new()
+        })
+    }
+    fn decode(&self, text: &str) -> std::result::Result<T, String> {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    }
+    fn decode_bytes(&self, bytes: &[u8]) -> std::result::Result<T, String> {
+        serde_json::from_slice(bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// bincode 1.x 编解码（特性 `codec-bincode`），格式随字段顺序变化，两端须使用同一类型定义。
+#[cfg(feature = "codec-bincode")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bincode;
+
+#[cfg(feature = "codec-bincode")]
+impl<T: Serialize + DeserializeOwned> MessageCodec<T> for Bincode {
+    fn encode(&self, msg: &T) -> String {
+        hex_encode(&self.encode_bytes(msg))
+    }
+    fn decode(&self, text: &str) -> std::result::Result<T, String> {
+        self.decode_bytes(&hex_decode(text)?)
+    }
+    fn encode_bytes(&self, msg: &T) -> Vec<u8> {
+        bincode::serialize(msg).unwrap_or_else(|e| {
+            encode_failed("bincode", &e);
+            Vec::new()
+        })
+    }
+    fn decode_bytes(&self, bytes: &[u8]) -> std::result::Result<T, String> {
+        bincode::deserialize(bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// postcard 编解码（特性 `codec-postcard`），变长整数编码，体积最小；两端须使用同一类型定义。
+#[cfg(feature = "codec-postcard")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Postcard;
+
+#[cfg(feature = "codec-postcard")]
+impl<T: Serialize + DeserializeOwned> MessageCodec<T> for Postcard {
+    fn encode(&self, msg: &T) -> String {
+        hex_encode(&self.encode_bytes(msg))
+    }
+    fn decode(&self, text: &str) -> std::result::Result<T, String> {
+        self.decode_bytes(&hex_decode(text)?)
+    }
+    fn encode_bytes(&self, msg: &T) -> Vec<u8> {
+        postcard::to_allocvec(msg).unwrap_or_else(|e| {
+            encode_failed("postcard", &e);
+            Vec::new()
+        })
+    }
+    fn decode_bytes(&self, bytes: &[u8]) -> std::result::Result<T, String> {
+        postcard::from_bytes(bytes).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(any(feature = "codec-bincode", feature = "codec-postcard"))]
+fn hex_encode(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        let _ = write!(out, "{b:02x}");
+    }
+    out
+}
+
+#[cfg(any(feature = "codec-bincode", feature = "codec-postcard"))]
+fn hex_decode(text: &str) -> std::result::Result<Vec<u8>, String> {
+    if !text.len().is_multiple_of(2) {
+        return Err("odd-length hex payload".to_owned());
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            text.get(i..i + 2)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+                .ok_or_else(|| format!("invalid hex at offset {i}"))
+        })
+        .collect()
+}
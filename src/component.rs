@@ -1,13 +1,36 @@
+use crate::app::AppShared;
 use crate::bus::BusHandle;
 use crate::error::Result;
+use crate::rt::Instant;
 use async_trait::async_trait;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::{any::Any, fmt, sync::Arc};
+use std::time::Duration;
+use std::{
+    any::{Any, TypeId},
+    fmt,
+    sync::Arc,
+};
 use tokio::sync::Notify;
 
 #[async_trait]
 pub trait Component: Send + Sync + 'static + Any {
     async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()>;
+
+    // 单条消息直接调度到匹配的 #[handle]（由宏生成，不经订阅队列）；返回是否有 handler 接收。
+    // 仅供测试工具（MockBus）在无 App / 无运行时的场景下驱动组件。
+    #[doc(hidden)]
+    async fn __dispatch(&self, _ctx: &ComponentContext, _msg: Arc<dyn Any + Send + Sync>) -> bool {
+        false
+    }
+
+    // 装配清单（由宏生成）；手写组件为 `None`，装配校验视其为无法判定的发布方
+    #[doc(hidden)]
+    fn __wiring() -> Option<crate::wiring::Wiring>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 impl dyn Component {}
@@ -16,6 +39,14 @@ impl dyn Component {}
 #[async_trait]
 pub trait ComponentFactory: Send + Sync {
     fn type_name(&self) -> &'static str;
+    /// 所构造组件的装配清单（见 [`crate::wiring`]）；未知时为 `None`。
+    fn wiring(&self) -> Option<crate::wiring::Wiring> {
+        None
+    }
+    /// 须先于本组件启动的组件类型名（`#[component(requires(..))]`）。
+    fn requires(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
     async fn build(&self, bus: BusHandle) -> crate::error::Result<Box<dyn Component>>;
 }
 
@@ -27,6 +58,69 @@ impl fmt::Debug for dyn ComponentFactory {
 
 pub type DynFactory = Arc<dyn ComponentFactory>;
 
+// 显式实例注册（非 inventory 自动发现）：由 `App::add_component` 使用，实例仅能被构建一次。
+pub(crate) struct InstanceFactory {
+    name: &'static str,
+    wiring: Option<crate::wiring::Wiring>,
+    slot: parking_lot::Mutex<Option<Box<dyn Component>>>,
+}
+impl InstanceFactory {
+    pub(crate) fn new<C: Component>(component: C) -> Self {
+        Self {
+            name: std::any::type_name::<C>(),
+            wiring: C::__wiring(),
+            slot: parking_lot::Mutex::new(Some(Box::new(component))),
+        }
+    }
+}
+#[async_trait]
+impl ComponentFactory for InstanceFactory {
+    fn type_name(&self) -> &'static str {
+        self.name
+    }
+    fn wiring(&self) -> Option<crate::wiring::Wiring> {
+        self.wiring.clone()
+    }
+    async fn build(&self, _bus: BusHandle) -> crate::error::Result<Box<dyn Component>> {
+        self.slot
+            .lock()
+            .take()
+            .ok_or(crate::error::MicrobusError::Other(
+                "component instance already consumed",
+            ))
+    }
+}
+
+// `#[component]` 结构体的构造入口：缺少 Default 时给出指向组件类型的明确诊断
+#[diagnostic::on_unimplemented(
+    message = "component `{Self}` must implement Default",
+    label = "the framework constructs components with Default::default()",
+    note = "add #[derive(Default)], or #[derive(mmg_microbus::ComponentDefault)] to get per-field errors and #[component_default(expr)] initializers"
+)]
+pub trait __ComponentDefault: Sized {
+    fn __component_default() -> Self;
+}
+impl<T: Default> __ComponentDefault for T {
+    fn __component_default() -> Self {
+        T::default()
+    }
+}
+
+// `#[derive(ComponentDefault)]` 的逐字段取值：错误指向缺少 Default 的字段类型
+#[diagnostic::on_unimplemented(
+    message = "component field of type `{Self}` does not implement Default",
+    label = "this field has no default value",
+    note = "give the field #[component_default(expr)], or implement Default for `{Self}`"
+)]
+pub trait __FieldDefault: Sized {
+    fn __field_default() -> Self;
+}
+impl<T: Default> __FieldDefault for T {
+    fn __field_default() -> Self {
+        T::default()
+    }
+}
+
 pub struct __RegisteredFactory {
     pub create: fn() -> Box<dyn ComponentFactory>,
 }
@@ -51,22 +145,249 @@ impl StopFlag {
     pub(crate) fn is_set(&self) -> bool {
         self.set.load(Ordering::Acquire)
     }
+    pub(crate) async fn wait(&self) {
+        let notified = self.notify.notified();
+        if self.is_set() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// 控制面指令：经每组件独立的控制通道送达，不排在数据队列之后。
+#[derive(Clone)]
+pub enum Control {
+    /// 生成的 handle worker 暂停取消息（当前调用照常完成；队列积压照常向发布方施加背压），`#[active]` 循环在两次调用之间暂停
+    Pause,
+    /// 解除暂停
+    Resume,
+    /// 业务自定义意图（重新配置、刷新等），组件经 [`ComponentContext::next_intent`] 取出
+    Intent(Arc<dyn Any + Send + Sync>),
+}
+
+impl fmt::Debug for Control {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pause => f.write_str("Pause"),
+            Self::Resume => f.write_str("Resume"),
+            Self::Intent(_) => f.write_str("Intent(..)"),
+        }
+    }
+}
+
+const CONTROL_INTENT_QUEUE: usize = 16;
+
+// 每组件控制通道：暂停状态经 watch 广播给全部 worker，意图走独立的小容量队列；
+// `halt` 为仅本组件的停机信号，`exited` 在组件任务结束（含被 abort）时置位
+pub(crate) struct ControlPlane {
+    paused: tokio::sync::watch::Sender<bool>,
+    intents_tx: tokio::sync::mpsc::Sender<Arc<dyn Any + Send + Sync>>,
+    intents_rx: tokio::sync::Mutex<tokio::sync::mpsc::Receiver<Arc<dyn Any + Send + Sync>>>,
+    pub(crate) halt: Arc<StopFlag>,
+    pub(crate) exited: StopFlag,
+}
+
+impl ControlPlane {
+    pub(crate) fn new() -> Self {
+        let (intents_tx, intents_rx) = tokio::sync::mpsc::channel(CONTROL_INTENT_QUEUE);
+        Self {
+            paused: tokio::sync::watch::Sender::new(false),
+            intents_tx,
+            intents_rx: tokio::sync::Mutex::new(intents_rx),
+            halt: Arc::new(StopFlag::new()),
+            exited: StopFlag::new(),
+        }
+    }
+    // 意图队列满时返回 false
+    pub(crate) fn send(&self, c: Control) -> bool {
+        match c {
+            Control::Pause | Control::Resume => {
+                self.paused.send_replace(matches!(c, Control::Pause));
+                true
+            }
+            Control::Intent(i) => self.intents_tx.try_send(i).is_ok(),
+        }
+    }
 }
 
 pub struct ComponentContext {
+    name: &'static str,
+    shared: Arc<AppShared>,
     bus: BusHandle,
     stop: Arc<StopFlag>,
+    // 仅本组件的停机信号（`on_error = stop_component` / `App::stop_component`），与 App 停机等效地结束本组件
+    halt: Arc<StopFlag>,
     startup: Arc<StartupBarrier>,
+    control: Arc<ControlPlane>,
+    // `#[handle(local)]` 的组件私有总线；列出的类型由本组件发布时只投递到这里
+    local: Option<Arc<LocalScope>>,
+}
+
+struct LocalScope {
+    bus: BusHandle,
+    types: Vec<TypeId>,
 }
 
 impl ComponentContext {
     // 仅框架内部用于 App->Component 的构造路径，不对外暴露，以避免外部绕开 App 生命周期管理直接构造上下文。
-    pub(crate) const fn new_with_service(
+    pub(crate) fn new_with_service(
+        name: &'static str,
+        shared: Arc<AppShared>,
         bus: BusHandle,
         stop: Arc<StopFlag>,
         startup: Arc<StartupBarrier>,
     ) -> Self {
-        Self { bus, stop, startup }
+        let control = shared.controls.register(name);
+        Self {
+            name,
+            halt: control.halt.clone(),
+            control,
+            shared,
+            bus: bus.with_origin(name),
+            stop,
+            startup,
+            local: None,
+        }
+    }
+
+    pub(crate) const fn bus(&self) -> &BusHandle {
+        &self.bus
+    }
+
+    pub(crate) fn held(&self) -> crate::monitor::Held {
+        crate::monitor::Held::new(self.shared.completion.clone())
+    }
+
+    // 返回值发布的目标总线：局部类型走组件私有总线
+    fn route(&self, type_id: TypeId) -> &BusHandle {
+        match &self.local {
+            Some(local) if local.types.contains(&type_id) => &local.bus,
+            _ => &self.bus,
+        }
+    }
+
+    /// 向本组件的局部 handler（`#[handle(local)]`）发布 `msg`：只经组件私有总线，不进入全局路由表。
+    /// 本组件没有接收 `T` 的局部 handler 时丢弃并返回 `false`。
+    pub async fn local_publish<T: Send + Sync + 'static>(&self, msg: T) -> bool {
+        let type_id = TypeId::of::<T>();
+        match &self.local {
+            Some(local) if local.types.contains(&type_id) => {
+                local
+                    .bus
+                    .wire_trace(self.name, type_id, Some(std::any::type_name::<T>()));
+                local.bus.publish_type(msg).await;
+                true
+            }
+            _ => {
+                tracing::debug!(
+                    component = self.name,
+                    type_name = std::any::type_name::<T>(),
+                    "local_publish without local handler; dropped"
+                );
+                false
+            }
+        }
+    }
+
+    /// 把本组件登记为有限数据源（`AppConfig::stop_on_completion`），须在启动屏障之前调用。
+    ///
+    /// 返回的句柄及其全部克隆释放后即视为该数据源结束（读到 EOF、回放完毕等）；组件任务退出时随之释放。
+    #[must_use]
+    pub fn register_source(&self) -> SourceGuard {
+        if self.bus.is_sealed() {
+            tracing::warn!(
+                component = self.name,
+                "register_source after startup; completion may be detected early"
+            );
+        }
+        self.shared.completion.source_started();
+        SourceGuard {
+            _token: Arc::new(SourceToken {
+                completion: self.shared.completion.clone(),
+            }),
+        }
+    }
+
+    /// 应用运行期快照（组件状态、订阅拓扑与队列深度等），供运维类组件使用。
+    #[must_use]
+    pub fn introspect(&self) -> crate::introspect::Snapshot {
+        crate::introspect::snapshot(&self.shared, &self.bus)
+    }
+
+    /// 散发-汇集查询：向 `Ask<Q, A>` 的全部应答方投递 `q`，收齐全部应答或 `timeout` 到期后返回已收到的应答。
+    pub async fn query<Q, A>(&self, q: Q, timeout: Duration) -> Vec<A>
+    where
+        Q: Send + Sync + 'static,
+        A: Send + 'static,
+    {
+        crate::query::gather(&self.bus, q, crate::query::Gather::All, timeout).await
+    }
+
+    /// 同 [`query`](Self::query)，按 `gather` 提前结束（首个应答 / 达到法定数）。
+    pub async fn query_with<Q, A>(
+        &self,
+        q: Q,
+        gather: crate::query::Gather,
+        timeout: Duration,
+    ) -> Vec<A>
+    where
+        Q: Send + Sync + 'static,
+        A: Send + 'static,
+    {
+        crate::query::gather(&self.bus, q, gather, timeout).await
+    }
+
+    /// 单应答请求（见 [`crate::query::Request`]）：`ctx.request::<Q, A>(q).timeout(d).await` 取首个应答，
+    /// 超时返回 `MicrobusError::Timeout`，应答方经 `Ask::cancelled` 或 [`request_token`](Self::request_token) 感知放弃。
+    pub fn request<Q, A>(&self, q: Q) -> crate::query::Request<Q, A> {
+        crate::query::Request::new(self.bus.clone(), q)
+    }
+
+    /// 正在处理的请求的取消信号：在以 `ctx` 为参数、消息为 `Ask<Q, A>` 的 handler 内返回 `Some`，
+    /// 发起方超时、收齐或丢弃请求时触发；其余场合（非 `Ask` handler、派生的任务内）为 `None`，需要时先克隆带出。
+    #[must_use]
+    pub fn request_token(&self) -> Option<crate::query::CancelToken> {
+        crate::query::current_token()
+    }
+
+    /// 向组件 `C` 发送控制指令（组件不存在或意图队列已满时返回 `false`）。
+    pub fn control<C: Component>(&self, c: Control) -> bool {
+        self.shared.controls.send(std::any::type_name::<C>(), c)
+    }
+
+    /// 本组件是否处于暂停状态。
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        *self.control.paused.borrow()
+    }
+
+    /// 取出下一条发给本组件的 [`Control::Intent`]；通常在 `#[active]` 中循环等待。
+    pub async fn next_intent(&self) -> Arc<dyn Any + Send + Sync> {
+        match self.control.intents_rx.lock().await.recv().await {
+            Some(i) => i,
+            None => std::future::pending().await,
+        }
+    }
+
+    #[cfg(feature = "chaos")]
+    pub(crate) fn chaos(&self) -> Option<Arc<crate::chaos::Chaos>> {
+        self.shared.chaos.read().clone()
+    }
+
+    #[cfg(feature = "crash-bundle")]
+    pub(crate) fn crash_bundles(&self) -> Option<Arc<crate::crash::CrashBundles>> {
+        self.shared.crash_bundles.read().clone()
+    }
+
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn snapshots(&self) -> Option<Arc<crate::snapshot::Snapshots>> {
+        self.shared.snapshots.read().clone()
+    }
+
+    /// 所属组件的类型名（`std::any::type_name`），用于日志与诊断。
+    #[must_use]
+    pub const fn component_name(&self) -> &'static str {
+        self.name
     }
 
     // 仅保留单一构造路径，避免歧义；组件以 kind 进行类型化
@@ -78,22 +399,94 @@ impl ComponentContext {
     #[must_use]
     pub fn __fork(&self) -> Self {
         Self {
+            name: self.name,
+            shared: self.shared.clone(),
             bus: self.bus.clone(),
             stop: self.stop.clone(),
+            halt: self.halt.clone(),
             startup: self.startup.clone(),
+            control: self.control.clone(),
+            local: self.local.clone(),
         }
     }
 }
 
+/// 有限数据源句柄（见 [`ComponentContext::register_source`]）：最后一个克隆释放时该数据源结束。
+#[derive(Clone)]
+pub struct SourceGuard {
+    _token: Arc<SourceToken>,
+}
+
+struct SourceToken {
+    completion: Arc<crate::monitor::Completion>,
+}
+
+impl Drop for SourceToken {
+    fn drop(&mut self) {
+        self.completion.source_finished();
+    }
+}
+
+impl fmt::Debug for SourceGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SourceGuard(..)")
+    }
+}
+
 // 外部配置注入模型已移除：组件自管内部初始化，不支持 #[init](&Cfg)
 
 /// 订阅封装（不含协作停机）
 pub struct AutoSubscription<T> {
     inner: crate::bus::Subscription<T>,
+    // 故障注入（特性 `chaos`）：该类型登记了投递故障时经此通道交付；装箱使本结构保持 `Unpin`（`Stream` 实现需要）
+    #[cfg(feature = "chaos")]
+    chaos: Option<Box<crate::chaos::Lane<T>>>,
 }
 impl<T: Send + Sync + 'static> AutoSubscription<T> {
+    fn new(ctx: &ComponentContext, inner: crate::bus::Subscription<T>) -> Self {
+        #[cfg(not(feature = "chaos"))]
+        let _ = ctx;
+        Self {
+            inner,
+            #[cfg(feature = "chaos")]
+            chaos: ctx
+                .chaos()
+                .and_then(|c| c.lane::<T>(ctx.name))
+                .map(Box::new),
+        }
+    }
     pub async fn recv(&mut self) -> Option<std::sync::Arc<T>> {
-        self.inner.recv().await
+        self.recv_envelope()
+            .await
+            .map(crate::bus::Envelope::into_message)
+    }
+    /// 同 [`recv`](Self::recv)，附带投递元数据（序号等）。
+    pub async fn recv_envelope(&mut self) -> Option<crate::bus::Envelope<T>> {
+        #[cfg(feature = "chaos")]
+        if let Some(lane) = &mut self.chaos {
+            return lane.recv(&mut self.inner).await;
+        }
+        self.inner.recv_envelope().await
+    }
+    /// 见 [`Subscription::on_overflow`](crate::bus::Subscription::on_overflow)。
+    #[must_use]
+    pub fn on_overflow(self, hook: impl FnMut(usize) + Send + 'static) -> Self {
+        Self {
+            inner: self.inner.on_overflow(hook),
+            #[cfg(feature = "chaos")]
+            chaos: self.chaos,
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T> futures_core::Stream for AutoSubscription<T> {
+    type Item = std::sync::Arc<T>;
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        std::pin::Pin::new(&mut self.get_mut().inner).poll_next(cx)
     }
 }
 
@@ -106,39 +499,548 @@ impl<T: Send + Sync + 'static> AutoSubscription<T> {
 pub fn __subscribe_any_auto<T: Send + Sync + 'static>(
     ctx: &ComponentContext,
 ) -> AutoSubscription<T> {
-    let sub = ctx.bus.subscribe_type::<T>();
-    AutoSubscription { inner: sub }
+    let sub = ctx.bus.subscribe_type::<T>(ctx.name);
+    AutoSubscription::new(ctx, sub)
+}
+// 含 `#[active]` 的组件在启动屏障前登记为数据源；各循环任务持有克隆，全部结束后该数据源结束
+#[must_use]
+pub fn __register_source(ctx: &ComponentContext) -> SourceGuard {
+    ctx.register_source()
+}
+// `#[handle(in_order)]`：有序订阅
+#[must_use]
+pub fn __subscribe_ordered_auto<T: Send + Sync + 'static>(
+    ctx: &ComponentContext,
+) -> AutoSubscription<T> {
+    let sub = ctx.bus.subscribe_ordered_type::<T>(ctx.name);
+    AutoSubscription::new(ctx, sub)
+}
+// `#[handle(latest)]`：合并订阅
+#[must_use]
+pub fn __subscribe_latest_auto<T: Send + Sync + 'static>(
+    ctx: &ComponentContext,
+) -> AutoSubscription<T> {
+    let sub = ctx.bus.subscribe_latest_type::<T>(ctx.name);
+    AutoSubscription::new(ctx, sub)
+}
+// `#[handle(weak)]`：弱订阅（满时丢弃，不计为消费者）
+#[must_use]
+pub fn __subscribe_weak_auto<T: Send + Sync + 'static>(
+    ctx: &ComponentContext,
+) -> AutoSubscription<T> {
+    let sub = ctx.bus.subscribe_weak_type::<T>(ctx.name, false);
+    AutoSubscription::new(ctx, sub)
+}
+#[must_use]
+pub fn __subscribe_weak_latest_auto<T: Send + Sync + 'static>(
+    ctx: &ComponentContext,
+) -> AutoSubscription<T> {
+    let sub = ctx.bus.subscribe_weak_type::<T>(ctx.name, true);
+    AutoSubscription::new(ctx, sub)
+}
+// `#[handle(local)]`：建立组件私有总线（`types` 为局部消息类型），须先于局部订阅调用
+pub fn __local_scope(ctx: &mut ComponentContext, types: Vec<TypeId>) {
+    let bus = crate::bus::Bus::new(ctx.shared.cfg.queue_capacity)
+        .handle()
+        .with_origin(ctx.name);
+    ctx.shared.completion.register_local_bus(&bus);
+    ctx.local = Some(Arc::new(LocalScope { bus, types }));
+}
+fn local_bus(ctx: &ComponentContext) -> &BusHandle {
+    &ctx.local
+        .as_ref()
+        .expect("__local_scope must precede local subscriptions")
+        .bus
+}
+#[must_use]
+pub fn __subscribe_local_auto<T: Send + Sync + 'static>(
+    ctx: &ComponentContext,
+) -> AutoSubscription<T> {
+    let sub = local_bus(ctx).subscribe_type::<T>(ctx.name);
+    AutoSubscription::new(ctx, sub)
+}
+#[must_use]
+pub fn __subscribe_local_latest_auto<T: Send + Sync + 'static>(
+    ctx: &ComponentContext,
+) -> AutoSubscription<T> {
+    let sub = local_bus(ctx).subscribe_latest_type::<T>(ctx.name);
+    AutoSubscription::new(ctx, sub)
+}
+// 局部订阅装配完毕：私有总线直接封印，走冻结快照的发布快路径
+pub fn __seal_local(ctx: &ComponentContext) {
+    local_bus(ctx).seal();
+}
+// 手写组件在运行期按需订阅时使用：封印后返回错误而非 panic
+pub fn __try_subscribe_any_auto<T: Send + Sync + 'static>(
+    ctx: &ComponentContext,
+) -> std::result::Result<AutoSubscription<T>, crate::error::SubscribeError> {
+    let sub = ctx.bus.try_subscribe_type::<T>(ctx.name)?;
+    Ok(AutoSubscription::new(ctx, sub))
 }
 
 // 发布：仅由宏在返回值场景调用；不对业务暴露
 pub async fn __publish_auto<T: Send + Sync + 'static>(ctx: &ComponentContext, msg: T) {
-    ctx.bus.publish_type(msg).await;
+    let bus = ctx.route(TypeId::of::<T>());
+    bus.wire_trace(
+        ctx.name,
+        TypeId::of::<T>(),
+        Some(std::any::type_name::<T>()),
+    );
+    bus.publish_type(msg).await;
 }
 
 // 发布 ErasedEvent：供宏在返回值为 ErasedEvent/Option/Vec<ErasedEvent> 时使用
 pub async fn __publish_erased(ctx: &ComponentContext, ev: crate::bus::ErasedEvent) {
     // 直接调用存储在结构内的发布函数
-    (ev.publish_fn)(&ctx.bus, ev.data).await;
+    let bus = ctx.route((*ev.data).type_id());
+    bus.wire_trace(ctx.name, (*ev.data).type_id(), Some(ev.type_name));
+    if let Err(e) = bus.try_publish_erased(ev).await {
+        tracing::error!(component = %ctx.name, error = %e, "erased publish dropped");
+    }
 }
 
 // 动态 Any（Box）发布：框架内部宏会在检测到函数返回 Box<dyn Any> / Result<Box<dyn Any>> / Option<Box<dyn Any>> 时调用。
 pub async fn __publish_any_box(ctx: &ComponentContext, b: Box<dyn Any + Send + Sync>) {
-    ctx.bus.publish_any_box(b).await;
+    let bus = ctx.route((*b).type_id());
+    bus.wire_trace(ctx.name, (*b).type_id(), None);
+    bus.publish_any_box(b).await;
 }
 pub async fn __publish_any_arc(ctx: &ComponentContext, a: std::sync::Arc<dyn Any + Send + Sync>) {
-    ctx.bus.publish_any_arc(a).await;
+    let bus = ctx.route((*a).type_id());
+    bus.wire_trace(ctx.name, (*a).type_id(), None);
+    bus.publish_any_arc(a).await;
 }
 
 // 配置相关能力已移除：init 仅由组件自身内部逻辑决定，其它注入路径删除。
 
+// handler 返回 Err：按配置构造待发布的 HandlerError；HandlerError 自身的处理错误不再发布，避免循环
+#[must_use]
+pub fn __handler_error<T: 'static>(
+    ctx: &ComponentContext,
+    method: &'static str,
+    message: &T,
+    error: &dyn std::fmt::Display,
+) -> Option<crate::events::HandlerError> {
+    let message_type = std::any::type_name::<T>();
+    #[cfg(feature = "metrics")]
+    crate::metrics::handler_error(ctx.name, method);
+    #[cfg(feature = "crash-bundle")]
+    if let Some(bundles) = ctx.crash_bundles() {
+        bundles.capture(ctx.name, method, message, error);
+    }
+    #[cfg(not(feature = "crash-bundle"))]
+    let _ = message;
+    if !ctx.shared.cfg.publish_handler_errors
+        || message_type == std::any::type_name::<crate::events::HandlerError>()
+    {
+        return None;
+    }
+    Some(crate::events::HandlerError {
+        component: ctx.name,
+        method,
+        message_type,
+        error: error.to_string(),
+    })
+}
+
+// handler 调用的 panic 捕获：每次 poll 包裹 catch_unwind，panic 转为 Err(payload)，不拖垮 worker 任务
+pub async fn __catch_unwind<F: std::future::Future>(
+    fut: F,
+) -> std::result::Result<F::Output, Box<dyn Any + Send>> {
+    let mut fut = std::pin::pin!(fut);
+    std::future::poll_fn(|cx| {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| fut.as_mut().poll(cx))) {
+            Ok(std::task::Poll::Ready(v)) => std::task::Poll::Ready(Ok(v)),
+            Ok(std::task::Poll::Pending) => std::task::Poll::Pending,
+            Err(payload) => std::task::Poll::Ready(Err(payload)),
+        }
+    })
+    .await
+}
+
+// handler 单次调用：故障注入命中时不调用方法体，按 panic 处理；否则同 `__catch_unwind`
+pub async fn __invoke<T: 'static, F: std::future::Future>(
+    ctx: &ComponentContext,
+    fut: F,
+) -> std::result::Result<F::Output, Box<dyn Any + Send>> {
+    #[cfg(feature = "chaos")]
+    if ctx.chaos().is_some_and(|c| c.should_fail::<T>()) {
+        return Err(Box::new("injected handler fault"));
+    }
+    #[cfg(not(feature = "chaos"))]
+    let _ = ctx;
+    __catch_unwind(fut).await
+}
+
+// handler panic：记录 error 并按 handler 错误同样的路径构造 HandlerError
+#[must_use]
+pub fn __handler_panicked<T: 'static>(
+    ctx: &ComponentContext,
+    method: &'static str,
+    message: &T,
+    payload: &(dyn Any + Send),
+) -> Option<crate::events::HandlerError> {
+    let msg = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic payload");
+    #[cfg(feature = "metrics")]
+    crate::metrics::handler_panic(ctx.name, method);
+    tracing::error!(
+        component = ctx.name,
+        method,
+        message_type = std::any::type_name::<T>(),
+        panic = msg,
+        "handle panicked"
+    );
+    __handler_error(ctx, method, message, &format_args!("panicked: {msg}"))
+}
+
+// 公平调度预算（`Topology::handler_budget`）：worker 每处理一条消息 `spend` 一次，满 `budget × weight` 条后让出；未配置时不让出
+pub struct __Budget {
+    left: u32,
+    limit: u32, // 0：未启用
+}
+
+impl __Budget {
+    pub fn new(ctx: &ComponentContext, weight: u32) -> Self {
+        let limit = ctx
+            .shared
+            .cfg
+            .topology
+            .handler_budget
+            .map_or(0, |n| n.saturating_mul(weight));
+        Self { left: limit, limit }
+    }
+    pub async fn spend(&mut self) {
+        if self.limit == 0 {
+            return;
+        }
+        self.left -= 1;
+        if self.left == 0 {
+            self.left = self.limit;
+            crate::rt::yield_now().await;
+        }
+    }
+}
+
+// handler 并发上限（`Topology::max_concurrent_handlers`）：未配置时不等待
+pub async fn __handler_permit(ctx: &ComponentContext) -> Option<tokio::sync::OwnedSemaphorePermit> {
+    match &ctx.shared.handler_permits {
+        Some(permits) => permits.clone().acquire_owned().await.ok(),
+        None => None,
+    }
+}
+
+// `#[active]` 循环的派生：按 `Topology::actives` 共享运行时或独占线程
+pub fn __spawn_active<F>(ctx: &ComponentContext, fut: F) -> crate::rt::JoinHandle<()>
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    match ctx.shared.cfg.topology.actives {
+        crate::config::ActiveScheduling::Shared => crate::rt::spawn(fut),
+        crate::config::ActiveScheduling::Dedicated => crate::rt::spawn_dedicated(ctx.name, fut),
+    }
+}
+
+// 慢 handler 检测：未配置阈值（且未启用 `metrics` 特性）时不取时间戳，保持热路径零开销；
+// 自然完成检测开启时计入进行中的调用。
+#[must_use]
+pub fn __handler_begin(ctx: &ComponentContext) -> Option<Instant> {
+    ctx.shared.completion.handler_begin();
+    let timed = cfg!(feature = "metrics") || ctx.shared.cfg.slow_handler_threshold.is_some();
+    timed.then(Instant::now)
+}
+pub fn __handler_end(
+    ctx: &ComponentContext,
+    method: &'static str,
+    message_type: &'static str,
+    begin: Option<Instant>,
+) {
+    ctx.shared.completion.handler_end();
+    let Some(t0) = begin else {
+        return;
+    };
+    let elapsed = t0.elapsed();
+    #[cfg(feature = "metrics")]
+    crate::metrics::handler_duration(ctx.name, method, elapsed);
+    let Some(threshold) = ctx.shared.cfg.slow_handler_threshold else {
+        return;
+    };
+    if elapsed > threshold {
+        tracing::warn!(
+            component = ctx.name,
+            method,
+            message_type,
+            elapsed_ms = duration_ms(elapsed),
+            threshold_ms = duration_ms(threshold),
+            "slow handler invocation"
+        );
+    }
+}
+
+fn duration_ms(d: Duration) -> u64 {
+    u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+}
+
+// `#[handle(sample = ..)]`：每 N 条处理一条（首条起计），或按概率独立抽样；跳过数计入自省 `sampling`
+pub struct __Sampler {
+    every: u64,     // 0 表示按概率
+    threshold: u64, // 概率 × 2^64
+    seen: u64,
+    rng: u64,
+    skipped: Arc<std::sync::atomic::AtomicU64>,
+}
+
+impl __Sampler {
+    #[must_use]
+    pub fn every(
+        ctx: &ComponentContext,
+        method: &'static str,
+        type_name: &'static str,
+        n: u64,
+    ) -> Self {
+        Self::new(ctx, method, type_name, n, 0)
+    }
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn ratio(
+        ctx: &ComponentContext,
+        method: &'static str,
+        type_name: &'static str,
+        p: f64,
+    ) -> Self {
+        // 浮点转整数饱和：p = 1 时为 u64::MAX
+        Self::new(
+            ctx,
+            method,
+            type_name,
+            0,
+            (p * 18_446_744_073_709_551_616.0) as u64,
+        )
+    }
+    fn new(
+        ctx: &ComponentContext,
+        method: &'static str,
+        type_name: &'static str,
+        every: u64,
+        threshold: u64,
+    ) -> Self {
+        use std::hash::{BuildHasher, Hasher};
+        Self {
+            every,
+            threshold,
+            seen: 0,
+            rng: std::collections::hash_map::RandomState::new()
+                .build_hasher()
+                .finish()
+                | 1,
+            skipped: ctx.shared.sampling.register(ctx.name, method, type_name),
+        }
+    }
+    pub fn admit(&mut self) -> bool {
+        let keep = if self.every > 0 {
+            let keep = self.seen.is_multiple_of(self.every);
+            self.seen = self.seen.wrapping_add(1);
+            keep
+        } else {
+            // xorshift64*：抽样无需密码学随机
+            self.rng ^= self.rng >> 12;
+            self.rng ^= self.rng << 25;
+            self.rng ^= self.rng >> 27;
+            self.threshold == u64::MAX
+                || self.rng.wrapping_mul(0x2545_F491_4F6C_DD1D) < self.threshold
+        };
+        if !keep {
+            self.skipped.fetch_add(1, Ordering::Relaxed);
+        }
+        keep
+    }
+}
+
+// `#[handle(debounce = ..)]` / `#[handle(throttle = ..)]` 的 worker 节流状态（时长由宏在编译期解析为纳秒）：
+// - debounce：每条新消息重置计时，静默满 `d` 后处理最后一条；
+// - throttle：相邻两次处理至少间隔 `d`，间隔内到达的消息只保留最新一条，到期补处理（尾沿不丢最终状态）。
+pub struct __Pacer<E> {
+    throttle: bool,
+    interval: Duration,
+    pending: Option<E>,
+    deadline: Option<Instant>, // debounce：待处理消息的触发时刻；throttle：下一次允许处理的时刻
+    held: crate::monitor::Held, // 暂存消息计入自然完成判定
+}
+
+impl<E> __Pacer<E> {
+    #[must_use]
+    pub fn debounce(ctx: &ComponentContext, nanos: u64) -> Self {
+        Self::new(ctx, false, nanos)
+    }
+    #[must_use]
+    pub fn throttle(ctx: &ComponentContext, nanos: u64) -> Self {
+        Self::new(ctx, true, nanos)
+    }
+    fn new(ctx: &ComponentContext, throttle: bool, nanos: u64) -> Self {
+        Self {
+            throttle,
+            interval: Duration::from_nanos(nanos),
+            pending: None,
+            deadline: None,
+            held: ctx.held(),
+        }
+    }
+    // 新消息到达：返回 Some 表示立即处理，None 表示已暂存等待 `due`
+    pub fn offer(&mut self, env: E) -> Option<E> {
+        let now = Instant::now();
+        if self.throttle && self.deadline.is_none_or(|t| now >= t) {
+            self.deadline = Some(now + self.interval);
+            return Some(env);
+        }
+        if !self.throttle {
+            self.deadline = Some(now + self.interval);
+        }
+        self.pending = Some(env);
+        self.held.set(1);
+        None
+    }
+    // 暂存消息到期时完成；无暂存时永不完成。仅在计时结束后修改状态，可在 select 中安全取消
+    pub async fn due(&mut self) -> E {
+        let (Some(_), Some(t)) = (&self.pending, self.deadline) else {
+            return std::future::pending().await;
+        };
+        crate::rt::sleep(t.saturating_duration_since(Instant::now())).await;
+        if self.throttle {
+            self.deadline = Some(Instant::now() + self.interval);
+        }
+        match self.pending.take() {
+            Some(env) => {
+                self.held.set(0);
+                env
+            }
+            None => std::future::pending().await,
+        }
+    }
+}
+
+// 生成的 worker 在启动时取得暂停状态的观察端
+#[must_use]
+pub fn __control_rx(ctx: &ComponentContext) -> tokio::sync::watch::Receiver<bool> {
+    ctx.control.paused.subscribe()
+}
+
+// 暂停时等待解除；未暂停时立即返回
+pub async fn __wait_resumed(ctl: &mut tokio::sync::watch::Receiver<bool>) {
+    let _ = ctl.wait_for(|paused| !*paused).await;
+}
+
+// 控制优先的取消息：每条消息前检查暂停，等待期间收到暂停即放弃本次等待（消息留在队列中）
+// 取信封而非 Arc：内联消息全程不装箱
+pub async fn __recv_controlled<T: Send + Sync + 'static>(
+    ctl: &mut tokio::sync::watch::Receiver<bool>,
+    sub: &mut AutoSubscription<T>,
+) -> Option<crate::bus::Envelope<T>> {
+    loop {
+        __wait_resumed(ctl).await;
+        crate::rt::select! {
+            biased;
+            changed = ctl.changed() => {
+                if changed.is_err() {
+                    return sub.recv_envelope().await;
+                }
+            }
+            msg = sub.recv_envelope() => return msg,
+        }
+    }
+}
+
 /// 内部停止信号（仅供宏生成的 `run()` 使用）
 pub async fn __recv_stop(ctx: &ComponentContext) {
-    if ctx.stop.is_set() {
-        return;
+    crate::rt::select! {
+        () = ctx.stop.wait() => {}
+        () = ctx.halt.wait() => {}
     }
-    ctx.stop.notify.notified().await;
 }
 
+// handler 错误策略：仅停止本组件（stop 钩子照常执行）
+pub fn __stop_component(ctx: &ComponentContext, method: &'static str) {
+    tracing::error!(
+        component = ctx.name,
+        method,
+        "handler error policy: stopping component"
+    );
+    ctx.halt.trigger();
+}
+
+// handler 错误策略：停止整个 App（等效于 `app.stop()` 的停止信号）
+pub fn __stop_app(ctx: &ComponentContext, method: &'static str) {
+    tracing::error!(
+        component = ctx.name,
+        method,
+        "handler error policy: stopping app"
+    );
+    ctx.stop.trigger();
+}
+
+// active 空闲退避（`#[active(idle_backoff = "1ms..100ms")]`）：连续空闲时等待时长自下限起逐次翻倍至上限，一旦产出即复位
+pub struct __IdleBackoff {
+    min: u64,
+    max: u64,
+    next: u64,
+}
+
+impl __IdleBackoff {
+    #[must_use]
+    pub const fn new(min_nanos: u64, max_nanos: u64) -> Self {
+        Self {
+            min: min_nanos,
+            max: max_nanos,
+            next: min_nanos,
+        }
+    }
+    pub async fn step(&mut self, idle: bool) {
+        if !idle {
+            self.next = self.min;
+            return;
+        }
+        crate::rt::sleep(Duration::from_nanos(self.next)).await;
+        self.next = self.next.saturating_mul(2).min(self.max);
+    }
+}
+
+// `#[component(actor)]` 的组件信箱：handler / active / stop 调用逐个取得 `&mut Self`，严格串行执行；
+// 等待方按到达顺序（FIFO）获得信箱，任一调用都不会被其它调用长期饿死
+pub struct __Mailbox<T>(tokio::sync::Mutex<T>);
+
+impl<T> __Mailbox<T> {
+    pub const fn new(state: T) -> Self {
+        Self(tokio::sync::Mutex::const_new(state))
+    }
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, T> {
+        self.0.lock().await
+    }
+}
+
+// active 返回 `ControlFlow::Break`：该循环自然结束，发布 ActiveCompleted 供宿主判定数据源已耗尽
+pub async fn __active_completed(ctx: &ComponentContext, method: &'static str) {
+    tracing::info!(component = ctx.name, method, "active loop completed");
+    __publish_auto(
+        ctx,
+        crate::events::ActiveCompleted {
+            component: ctx.name,
+            method,
+        },
+    )
+    .await;
+}
+
+// 非阻塞检查停机信号：供轮询型内置组件（无法 select 等待的忙循环）使用
+#[cfg(any(
+    all(feature = "bridge-shm", unix),
+    feature = "durable",
+    feature = "bridge-kafka"
+))]
+pub(crate) fn __stop_requested(ctx: &ComponentContext) -> bool {
+    ctx.stop.is_set() || ctx.halt.is_set()
+}
 pub(crate) fn __new_stop_flag() -> Arc<StopFlag> {
     Arc::new(StopFlag::new())
 }
@@ -169,19 +1071,36 @@ impl StartupBarrier {
     }
 
     async fn wait_ready(&self) {
-        while !self.is_ready() {
-            self.notify.notified().await;
+        self.wait_until(Self::is_ready).await;
+    }
+
+    // 先登记唤醒再检查条件，避免检查与等待之间的通知丢失
+    async fn wait_until(&self, ready: impl Fn(&Self) -> bool) {
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if ready(self) {
+                return;
+            }
+            notified.await;
         }
     }
 
     async fn arrive_and_wait(&self) {
-        let n = self.arrived.fetch_add(1, Ordering::AcqRel) + 1;
-        if n == self.total {
-            self.notify.notify_waiters();
-            return;
-        }
+        self.arrived.fetch_add(1, Ordering::AcqRel);
+        // 每次到达均唤醒：按阶段启动时 App 等待的是部分到达
+        self.notify.notify_waiters();
         self.wait_ready().await;
     }
+
+    // 至少 `n` 个组件到达或启动失败（按依赖阶段启动使用）
+    pub(crate) async fn wait_arrived(&self, n: usize) {
+        self.wait_until(|b| {
+            b.arrived.load(Ordering::Acquire) >= n || b.failed.load(Ordering::Acquire)
+        })
+        .await;
+    }
     pub fn mark_failed(&self) {
         if !self.failed.swap(true, Ordering::AcqRel) {
             self.notify.notify_waiters();
@@ -190,21 +1109,58 @@ impl StartupBarrier {
     pub fn is_failed(&self) -> bool {
         self.failed.load(Ordering::Acquire)
     }
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.total
+    }
     pub async fn wait_all(&self) {
         self.wait_ready().await;
     }
 }
 
+// `#[component(requires(C))]` 展开使用：限定 `C` 为组件类型，取其类型名
+#[doc(hidden)]
+#[must_use]
+pub fn __requires<C: Component>() -> &'static str {
+    std::any::type_name::<C>()
+}
+
 pub(crate) fn __new_startup_barrier(total: usize) -> Arc<StartupBarrier> {
     Arc::new(StartupBarrier::new(total))
 }
 pub async fn __startup_arrive_and_wait(ctx: &ComponentContext) {
+    ctx.shared
+        .components
+        .set_status(ctx.name, crate::introspect::ComponentStatus::Ready);
     ctx.startup.arrive_and_wait().await;
+    if !ctx.startup.is_failed() {
+        ctx.shared
+            .components
+            .set_status(ctx.name, crate::introspect::ComponentStatus::Running);
+        ctx.bus
+            .publish_type_nowait(crate::events::ComponentStarted {
+                component: ctx.name,
+            })
+            .await;
+    }
 }
 
 pub fn __startup_mark_failed(ctx: &ComponentContext) {
+    ctx.shared
+        .start_traces
+        .lock()
+        .push((ctx.name, crate::error::Trace::capture()));
     ctx.startup.mark_failed();
 }
+
+// `#[init]` / 快照恢复失败：标记启动失败并归一为框架错误（日志由生成代码在组件所在模块记录，调用方 `return Err(..)`）
+pub fn __init_failed<E: Into<crate::error::BoxError>>(
+    ctx: &ComponentContext,
+    e: E,
+) -> crate::error::MicrobusError {
+    __startup_mark_failed(ctx);
+    crate::error::__into_error(e)
+}
 pub fn __startup_mark_failed_barrier(b: &Arc<StartupBarrier>) {
     b.mark_failed();
 }
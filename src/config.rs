@@ -1,15 +1,278 @@
+use std::any::TypeId;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::{MicrobusError, Result};
+
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub queue_capacity: usize,
+    /// 慢 handler 阈值：单次 `#[handle]` 调用耗时超过该值时输出结构化 warn（`None` 关闭检测）。
+    pub slow_handler_threshold: Option<Duration>,
+    /// 订阅滞后监控：队列持续高水位时在总线上发布 `SubscriberLagging`（`None` 关闭）。
+    pub subscriber_lag: Option<LagMonitorConfig>,
+    /// 启动屏障等待期间，按该周期输出已到达 / 未到达组件列表（`None` 关闭）。
+    pub startup_progress_interval: Option<Duration>,
+    /// `#[handle]` 返回 `Err` 时，除 warn 外在总线上发布 `events::HandlerError`（默认关闭）。
+    pub publish_handler_errors: bool,
+    /// 按类型为每次发布编号（`Envelope::seq`），配合 `bus::GapDetector` 检测跳号（默认关闭）。
+    pub sequence_numbers: bool,
+    /// 封印前（`start()` 完成前）的发布先缓冲，封印后按发布顺序投递给全部订阅者；
+    /// 关闭时（默认）封印前的发布只投递给当时已建立的订阅。启动失败时缓冲被丢弃。
+    pub buffer_pre_seal: bool,
+    /// 运行拓扑：handler 并发上限、专用运行时线程与 `#[active]` 调度方式（默认沿用调用方运行时）。
+    pub topology: Topology,
+    /// `start()` 前执行严格装配校验（`App::validate_strict`），存在无人发布的订阅或无人订阅的发布时启动失败（默认关闭）。
+    pub strict_wiring: bool,
+    /// 有限流水线的自然完成检测：全部数据源结束、非弱订阅队列排空、无进行中的 handler 且无节流 / 窗口暂存消息时触发停止信号
+    /// （`wait_for_stop()` 返回，`is_completed()` 为真；默认关闭）。
+    pub stop_on_completion: bool,
+    /// 按类型的发布限速（经 [`rate_limit`](Self::rate_limit) 添加；同一类型以最后一项为准）。
+    pub rate_limits: Vec<RateLimit>,
+    /// 按类型的发布去重窗口（经 [`dedup`](Self::dedup) 添加；同一类型以最后一项为准）。
+    pub dedup: Vec<Dedup>,
+}
+
+/// 超出限速时的处理方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateExcess {
+    /// 发布方等待至令牌可用（与背压相同，发布调用在此期间不返回）。
+    Delay,
+    /// 丢弃该条发布，计入 `introspect().rate_limits`。
+    Drop,
+}
+
+/// 单个消息类型的发布限速：令牌桶容量与补充速率均为每 `per` 内 `count` 条（允许的突发即 `count` 条）。
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    pub(crate) type_id: TypeId,
+    pub type_name: &'static str,
+    pub count: u32,
+    pub per: Duration,
+    pub on_excess: RateExcess,
+}
+
+impl RateLimit {
+    #[must_use]
+    pub fn of<T: 'static>(count: u32, per: Duration, on_excess: RateExcess) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            count,
+            per,
+            on_excess,
+        }
+    }
+}
+
+/// 单个消息类型的发布去重：距上次放行不足 `window` 的同键发布被抑制，计入 `introspect().dedup`。
+#[derive(Clone)]
+pub struct Dedup {
+    pub(crate) type_id: TypeId,
+    pub type_name: &'static str,
+    pub window: Duration,
+    // 每个 App 各建一份去重表：配置可被克隆复用，状态不随之共享
+    pub(crate) build:
+        Arc<dyn Fn() -> Box<dyn crate::dedup::Filter> + Send + Sync + std::panic::RefUnwindSafe>,
+}
+
+impl Dedup {
+    #[must_use]
+    pub fn of<T, K>(window: Duration, key: fn(&T) -> K) -> Self
+    where
+        T: Send + Sync + 'static,
+        K: Hash + Eq + Send + 'static,
+    {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            window,
+            build: Arc::new(move || Box::new(crate::dedup::Deduper::new(window, key))),
+        }
+    }
+}
+
+impl std::fmt::Debug for Dedup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dedup")
+            .field("type_name", &self.type_name)
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 组件任务的派生方式；同一二进制可按部署机器（2 核边缘盒 / 64 核服务器）调整。
+#[derive(Debug, Clone, Default)]
+pub struct Topology {
+    /// 全部组件同时执行中的 `#[handle]` 调用上限（`None` 不限）；单个 handler 本身始终逐条处理。
+    ///
+    /// 许可只覆盖方法调用本身，返回值发布在释放后进行；handler 内 `ctx.query` 等待的应答方同样需要许可。
+    pub max_concurrent_handlers: Option<usize>,
+    /// 组件在 App 自建的多线程运行时上运行（`Some(n)`：n 个 worker 线程）；`None` 使用调用 `start()` 的运行时。
+    pub worker_threads: Option<usize>,
+    /// `#[active]` 循环的调度方式。
+    pub actives: ActiveScheduling,
+    /// 公平调度预算（`None` 不启用）：每个 `#[handle]` worker 连续处理 `n × weight` 条后让出执行权一次，
+    /// 洪泛类型不会在小运行时上长期占住 worker 线程，同组件（及其它组件）的其余订阅仍能推进。
+    /// `weight` 取自 `#[handle(weight = k)]`（默认 1），权重大的 handler 每轮获得相应更多的处理份额。
+    pub handler_budget: Option<u32>,
+}
+
+/// `#[active]` 循环的调度方式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ActiveScheduling {
+    /// 与 handler 共享组件所在运行时，各循环为普通任务（默认）。
+    #[default]
+    Shared,
+    /// 每个循环独占一个 OS 线程（自带单线程运行时），不与其它任务争抢 worker；
+    /// 不受 `tokio::time::pause` 虚拟时间影响。
+    Dedicated,
+}
+
+/// 订阅滞后判定：队列占用率 >= `threshold_percent` 且持续 `sustain` 即视为滞后。
+#[derive(Debug, Clone)]
+pub struct LagMonitorConfig {
+    pub threshold_percent: u8,
+    pub sustain: Duration,
+    /// 采样间隔（监控任务轮询各订阅队列深度的周期）
+    pub check_interval: Duration,
+}
+
+impl Default for LagMonitorConfig {
+    fn default() -> Self {
+        Self {
+            threshold_percent: 80,
+            sustain: Duration::from_millis(500),
+            check_interval: Duration::from_millis(100),
+        }
+    }
 }
 
 pub const APP_DEFAULT_QUEUE: usize = 1024;
+/// `queue_capacity` 上限：每个订阅队列按容量预留，超出即视为配置错误。
+pub const MAX_QUEUE_CAPACITY: usize = 1 << 24;
+
+impl AppConfig {
+    /// 限制类型 `T` 的发布速率为每 `per` 至多 `count` 条（如 `cfg.rate_limit::<Heartbeat>(100, Duration::from_secs(1), RateExcess::Drop)`）。
+    ///
+    /// 作用于全部发布入口（组件返回值、`BusHandle` 的各发布方法、桥与适配器），防止失控的发布方淹没下游；
+    /// 限速在发布方任务内判定，超出部分按 `on_excess` 等待或丢弃。
+    pub fn rate_limit<T: Send + Sync + 'static>(
+        &mut self,
+        count: u32,
+        per: Duration,
+        on_excess: RateExcess,
+    ) -> &mut Self {
+        let limit = RateLimit::of::<T>(count, per, on_excess);
+        self.rate_limits.retain(|l| l.type_id != limit.type_id);
+        self.rate_limits.push(limit);
+        self
+    }
+
+    /// 对类型 `T` 的发布按 `key` 去重：与上次放行的同键发布相距不足 `window` 时抑制（如
+    /// `cfg.dedup::<Position, _>(Duration::from_secs(1), |p| (p.symbol.clone(), p.qty))`）。
+    ///
+    /// 用于反复推送相同状态的上游数据源；作用于全部发布入口，先于限速判定（被抑制的发布不占令牌）。
+    /// 窗口自上次放行起算，到期后同键发布再次放行一次。
+    pub fn dedup<T, K>(&mut self, window: Duration, key: fn(&T) -> K) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        K: Hash + Eq + Send + 'static,
+    {
+        let dedup = Dedup::of::<T, K>(window, key);
+        self.dedup.retain(|d| d.type_id != dedup.type_id);
+        self.dedup.push(dedup);
+        self
+    }
+
+    /// 检查各字段取值范围（`App::new` / `App::try_new` 构造时调用）。
+    ///
+    /// # Errors
+    /// [`MicrobusError::Config`]，逐项列出全部无效字段及其取值。
+    pub fn validate(&self) -> Result<()> {
+        let mut errs = Vec::new();
+        if !(1..=MAX_QUEUE_CAPACITY).contains(&self.queue_capacity) {
+            errs.push(format!(
+                "queue_capacity must be in 1..={MAX_QUEUE_CAPACITY}, got {}",
+                self.queue_capacity
+            ));
+        }
+        if self.startup_progress_interval == Some(Duration::ZERO) {
+            errs.push("startup_progress_interval must be positive (None disables it)".into());
+        }
+        if let Some(lag) = &self.subscriber_lag {
+            if !(1..=100).contains(&lag.threshold_percent) {
+                errs.push(format!(
+                    "subscriber_lag.threshold_percent must be in 1..=100, got {}",
+                    lag.threshold_percent
+                ));
+            }
+            if lag.check_interval.is_zero() {
+                errs.push("subscriber_lag.check_interval must be positive".into());
+            }
+        }
+        let topo = &self.topology;
+        if let Some(n) = topo.max_concurrent_handlers {
+            if !(1..=tokio::sync::Semaphore::MAX_PERMITS).contains(&n) {
+                errs.push(format!(
+                    "topology.max_concurrent_handlers must be in 1..={} (None means unlimited), got {n}",
+                    tokio::sync::Semaphore::MAX_PERMITS
+                ));
+            }
+        }
+        if topo.handler_budget == Some(0) {
+            errs.push("topology.handler_budget must be at least 1 (None disables it)".into());
+        }
+        if topo.worker_threads == Some(0) {
+            errs.push(
+                "topology.worker_threads must be at least 1 (None runs on the caller's runtime)"
+                    .into(),
+            );
+        }
+        for l in &self.rate_limits {
+            if l.count == 0 || l.per.is_zero() {
+                errs.push(format!(
+                    "rate_limit for {} must allow at least 1 message per positive period, got {}/{:?}",
+                    l.type_name, l.count, l.per
+                ));
+            } else if (l.per / l.count).is_zero() {
+                // 补充间隔按整纳秒计：截断为 0 时限速失效
+                errs.push(format!(
+                    "rate_limit for {} exceeds 1 message per nanosecond, got {}/{:?}",
+                    l.type_name, l.count, l.per
+                ));
+            }
+        }
+        for d in &self.dedup {
+            if d.window.is_zero() {
+                errs.push(format!("dedup window for {} must be positive", d.type_name));
+            }
+        }
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(MicrobusError::Config(errs.join("; ")))
+        }
+    }
+}
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             queue_capacity: APP_DEFAULT_QUEUE,
+            slow_handler_threshold: None,
+            subscriber_lag: None,
+            startup_progress_interval: Some(Duration::from_secs(5)),
+            publish_handler_errors: false,
+            sequence_numbers: false,
+            buffer_pre_seal: false,
+            topology: Topology::default(),
+            strict_wiring: false,
+            stop_on_completion: false,
+            rate_limits: Vec::new(),
+            dedup: Vec::new(),
         }
     }
 }
-// 运行期配置仅保留队列容量；组件采用全局单例自动发现。
+// 运行期配置：队列容量、诊断、启动 / 停机行为、运行拓扑与按类型的发布限速 / 去重；组件采用全局单例自动发现。
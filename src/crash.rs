@@ -0,0 +1,133 @@
+//! 故障现场包（特性 `crash-bundle`）：handler 返回 `Err` 或 panic 时，把出错消息、组件与错误信息，
+//! 连同此前最近的总线消息写入磁盘目录，供本地复现。
+//!
+//! `app.crash_bundles(CrashBundles::new(dir).codec::<Quote>(c).recent(&recorder.handle(), 50))`，须在 `start()` 之前调用。
+//! 每次失败写入 `<dir>/<unix_micros>-<序号>/`：
+//! - `bundle.txt`：`key: value` 行（`component` / `method` / `message_type` / `error` / `encoded`）；
+//! - `message.rec`：出错消息（单行，与 [`Recorder`](crate::recorder::Recorder) 文件格式相同）；登记了编解码器时经其编码，否则仅记录类型名；
+//! - `recent.rec`：[`recent`](CrashBundles::recent) 所给录制器环形缓冲中最近的记录（可能已含出错消息本身）。
+//!
+//! 两个 `.rec` 文件均可由 [`Replay`](crate::replay::Replay) 直接回放。写盘在 handler 所在任务内同步完成，
+//! 达到 [`limit`](CrashBundles::limit) 后不再写入，避免故障风暴占满磁盘。
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::codec::MessageCodec;
+use crate::recorder::{RecordedMessage, RecorderHandle};
+use crate::rt::{SystemTime, UNIX_EPOCH};
+
+pub const CRASH_BUNDLE_DEFAULT_LIMIT: usize = 100;
+
+type EncodeFn = Box<dyn Fn(&dyn Any) -> Option<String> + Send + Sync>;
+
+/// 故障现场包配置。
+pub struct CrashBundles {
+    dir: PathBuf,
+    encoders: HashMap<TypeId, EncodeFn>,
+    recent: Option<(RecorderHandle, usize)>,
+    limit: usize,
+    written: AtomicUsize,
+}
+
+impl CrashBundles {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            encoders: HashMap::new(),
+            recent: None,
+            limit: CRASH_BUNDLE_DEFAULT_LIMIT,
+            written: AtomicUsize::new(0),
+        }
+    }
+    /// 出错消息为 `T` 时经 `codec` 编码写入（与 `Replay::codec` 对应）。
+    #[must_use]
+    pub fn codec<T: 'static>(mut self, codec: impl MessageCodec<T>) -> Self {
+        self.encoders.insert(
+            TypeId::of::<T>(),
+            Box::new(move |msg| msg.downcast_ref::<T>().map(|v| codec.encode(v))),
+        );
+        self
+    }
+    /// 附带录制器环形缓冲中最近的 `n` 条记录。
+    #[must_use]
+    pub fn recent(mut self, recorder: &RecorderHandle, n: usize) -> Self {
+        self.recent = Some((recorder.clone(), n));
+        self
+    }
+    /// 本进程内最多写入的现场包数量（默认 [`CRASH_BUNDLE_DEFAULT_LIMIT`]）。
+    #[must_use]
+    pub const fn limit(mut self, n: usize) -> Self {
+        self.limit = n;
+        self
+    }
+
+    // handler 失败时调用；写盘失败只记录日志，不影响错误处理流程
+    pub(crate) fn capture<T: 'static>(
+        &self,
+        component: &'static str,
+        method: &'static str,
+        message: &T,
+        error: &dyn std::fmt::Display,
+    ) {
+        let n = self.written.fetch_add(1, Ordering::Relaxed);
+        if n >= self.limit {
+            return;
+        }
+        let timestamp = SystemTime::now();
+        let micros = timestamp
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_micros());
+        let path = self.dir.join(format!("{micros}-{n}"));
+        let payload = self
+            .encoders
+            .get(&TypeId::of::<T>())
+            .and_then(|encode| encode(message));
+        let offending = RecordedMessage {
+            timestamp,
+            type_name: std::any::type_name::<T>(),
+            encoded: payload.is_some(),
+            payload,
+        };
+        let mut meta = String::new();
+        let _ = writeln!(meta, "component: {component}");
+        let _ = writeln!(meta, "method: {method}");
+        let _ = writeln!(meta, "message_type: {}", offending.type_name);
+        // 错误文本可能多行：仅保留首行以维持 `key: value` 行格式
+        let error = error.to_string();
+        let _ = writeln!(meta, "error: {}", error.lines().next().unwrap_or(""));
+        let _ = writeln!(meta, "encoded: {}", offending.encoded);
+        let recent: String = self.recent.as_ref().map_or_else(String::new, |(rec, n)| {
+            let all = rec.snapshot();
+            all[all.len().saturating_sub(*n)..]
+                .iter()
+                .map(RecordedMessage::to_line)
+                .collect()
+        });
+        match write_bundle(&path, &meta, &offending.to_line(), &recent) {
+            Ok(()) => tracing::warn!(
+                component,
+                method,
+                path = %path.display(),
+                "crash bundle written"
+            ),
+            Err(e) => tracing::error!(
+                component,
+                method,
+                path = %path.display(),
+                error = %e,
+                "crash bundle write failed"
+            ),
+        }
+    }
+}
+
+fn write_bundle(path: &Path, meta: &str, message: &str, recent: &str) -> std::io::Result<()> {
+    std::fs::create_dir_all(path)?;
+    std::fs::write(path.join("bundle.txt"), meta)?;
+    std::fs::write(path.join("message.rec"), message)?;
+    std::fs::write(path.join("recent.rec"), recent)
+}
@@ -0,0 +1,85 @@
+//! 按类型的发布去重（`AppConfig::dedup`）：按用户给定的键记录上次放行时刻，窗口内的同键发布被抑制。
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::rt::Instant;
+
+// 去重表清理的最小规模：表长达到阈值时剔除已过窗口的键，阈值随存活键数翻倍
+const PRUNE_MIN: usize = 64;
+
+// 类型擦除的去重判定：键类型由配置处的泛型固定
+pub(crate) trait Filter: Send + Sync {
+    // 返回 false 表示该条发布被抑制
+    fn admit(&self, msg: &(dyn Any + Send + Sync)) -> bool;
+    fn metrics(&self) -> crate::introspect::DedupMetrics;
+}
+
+pub(crate) struct Deduper<T, K> {
+    window: Duration,
+    key: fn(&T) -> K,
+    seen: parking_lot::Mutex<Seen<K>>,
+    suppressed: AtomicU64,
+}
+
+struct Seen<K> {
+    last: HashMap<K, Instant>,
+    prune_at: usize,
+}
+
+impl<T, K> Deduper<T, K> {
+    pub(crate) fn new(window: Duration, key: fn(&T) -> K) -> Self {
+        Self {
+            window,
+            key,
+            seen: parking_lot::Mutex::new(Seen {
+                last: HashMap::new(),
+                prune_at: PRUNE_MIN,
+            }),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<T, K> Filter for Deduper<T, K>
+where
+    T: Send + Sync + 'static,
+    K: Hash + Eq + Send + 'static,
+{
+    fn admit(&self, msg: &(dyn Any + Send + Sync)) -> bool {
+        let Some(msg) = msg.downcast_ref::<T>() else {
+            return true;
+        };
+        let key = (self.key)(msg);
+        let now = Instant::now();
+        let mut seen = self.seen.lock();
+        if let Some(last) = seen.last.get_mut(&key) {
+            if now.saturating_duration_since(*last) < self.window {
+                drop(seen);
+                self.suppressed.fetch_add(1, Ordering::Relaxed);
+                #[cfg(feature = "metrics")]
+                crate::metrics::dropped(std::any::type_name::<T>(), "duplicate", 1);
+                return false;
+            }
+            *last = now;
+            return true;
+        }
+        seen.last.insert(key, now);
+        if seen.last.len() >= seen.prune_at {
+            let window = self.window;
+            seen.last
+                .retain(|_, last| now.saturating_duration_since(*last) < window);
+            seen.prune_at = (seen.last.len() * 2).max(PRUNE_MIN);
+        }
+        true
+    }
+
+    fn metrics(&self) -> crate::introspect::DedupMetrics {
+        crate::introspect::DedupMetrics {
+            type_name: std::any::type_name::<T>(),
+            suppressed: self.suppressed.load(Ordering::Relaxed),
+        }
+    }
+}
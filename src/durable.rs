@@ -0,0 +1,320 @@
+//! 持久队列（特性 `durable`）：登记类型在发布时写入内嵌 SQLite，所有订阅者处理完毕后才删除；进程重启后重新投递未完成的消息。
+//!
+//! - 写入在发布方任务内、投递订阅者之前完成；默认 WAL + `synchronous=NORMAL`（进程崩溃不丢失），`fsync(true)` 改为 `FULL`（掉电不丢失）；
+//! - “处理完毕”以消息 `Arc` 的引用计数判定：各订阅者的 handler 返回并释放消息后，后台按 `sweep_interval` 批量删除。
+//!   订阅端已关闭（组件退出）或 handler panic 导致的丢弃同样视为完毕；handler 保留消息 `Arc` 会推迟删除；
+//! - 停机开始后不再删除：在途与排队中的消息保留到下次启动，越过启动屏障后按原顺序重新投递（至少一次，handler 应幂等）；
+//! - 存储中的未登记类型保留不动并记录 warn；解码失败即启动失败。
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection};
+
+use crate::bus::PublishTap;
+use crate::codec::{BusMessage, MessageCodec};
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+
+type Message = Arc<dyn Any + Send + Sync>;
+type EncodeFn = Box<dyn Fn(&(dyn Any + Send + Sync)) -> Option<String> + Send + Sync>;
+type DecodeFn = Box<dyn Fn(&str) -> std::result::Result<Message, String> + Send + Sync>;
+
+const DEFAULT_SWEEP: Duration = Duration::from_millis(50);
+
+tokio::task_local! {
+    // 重新投递期间为真：消息已在存储中，旁路跳过
+    static REDELIVERING: ();
+}
+
+/// 持久队列计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct DurableHandle {
+    pending: Arc<AtomicU64>,
+    persisted: Arc<AtomicU64>,
+    completed: Arc<AtomicU64>,
+    redelivered: Arc<AtomicU64>,
+    failed: Arc<AtomicU64>,
+}
+
+impl DurableHandle {
+    /// 存储中尚未完成的消息数。
+    #[must_use]
+    pub fn pending(&self) -> u64 {
+        self.pending.load(Ordering::Relaxed)
+    }
+    /// 本次运行写入的消息数。
+    #[must_use]
+    pub fn persisted(&self) -> u64 {
+        self.persisted.load(Ordering::Relaxed)
+    }
+    /// 本次运行确认完成并删除的消息数。
+    #[must_use]
+    pub fn completed(&self) -> u64 {
+        self.completed.load(Ordering::Relaxed)
+    }
+    /// 启动时重新投递的消息数。
+    #[must_use]
+    pub fn redelivered(&self) -> u64 {
+        self.redelivered.load(Ordering::Relaxed)
+    }
+    /// 写入失败的消息数（消息照常投递，但不具备持久性）。
+    #[must_use]
+    pub fn failed(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+}
+
+struct Entry {
+    name: &'static str,
+    encode: EncodeFn,
+}
+
+pub struct Durable {
+    path: PathBuf,
+    encoders: HashMap<TypeId, Entry>,
+    decoders: HashMap<&'static str, DecodeFn>,
+    fsync: bool,
+    sweep_interval: Duration,
+    handle: DurableHandle,
+}
+
+impl Durable {
+    /// 以 `path` 为 SQLite 数据库文件（不存在时创建）。
+    #[must_use]
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            encoders: HashMap::new(),
+            decoders: HashMap::new(),
+            fsync: false,
+            sweep_interval: DEFAULT_SWEEP,
+            handle: DurableHandle::default(),
+        }
+    }
+    /// 持久化类型 `T`（按类型名存储，重启前后须使用同一类型定义）。
+    #[must_use]
+    pub fn codec<T: Send + Sync + 'static>(self, codec: impl MessageCodec<T>) -> Self {
+        self.push::<T>(std::any::type_name::<T>(), codec)
+    }
+    /// 持久化 [`BusMessage`] 类型 `T`（按稳定名称 `T::NAME` 存储）。
+    #[must_use]
+    pub fn message<T: BusMessage>(self, codec: impl MessageCodec<T>) -> Self {
+        self.push::<T>(T::NAME, codec)
+    }
+    fn push<T: Send + Sync + 'static>(
+        mut self,
+        name: &'static str,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        let codec = Arc::new(codec);
+        let enc = codec.clone();
+        self.encoders.insert(
+            TypeId::of::<T>(),
+            Entry {
+                name,
+                encode: Box::new(move |msg| msg.downcast_ref::<T>().map(|v| enc.encode(v))),
+            },
+        );
+        self.decoders.insert(
+            name,
+            Box::new(move |text| codec.decode(text).map(|v| Arc::new(v) as Message)),
+        );
+        self
+    }
+    /// 每次写入同步落盘（`synchronous=FULL`，默认关闭）。
+    #[must_use]
+    pub const fn fsync(mut self, enabled: bool) -> Self {
+        self.fsync = enabled;
+        self
+    }
+    /// 完成判定与删除的周期（默认 50ms）。
+    #[must_use]
+    pub const fn sweep_interval(mut self, interval: Duration) -> Self {
+        self.sweep_interval = interval;
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> DurableHandle {
+        self.handle.clone()
+    }
+
+    // 打开数据库并读取未完成的消息（按写入顺序）
+    fn load(&self) -> Result<(Connection, Vec<(i64, Message)>)> {
+        let db_err = |e: rusqlite::Error| {
+            MicrobusError::Dynamic(format!("durable: {}: {e}", self.path.display()))
+        };
+        let conn = Connection::open(&self.path).map_err(db_err)?;
+        let sync = if self.fsync { "FULL" } else { "NORMAL" };
+        conn.execute_batch(&format!(
+            "PRAGMA journal_mode=WAL; PRAGMA synchronous={sync};
+             CREATE TABLE IF NOT EXISTS messages (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 type TEXT NOT NULL,
+                 payload TEXT NOT NULL
+             );"
+        ))
+        .map_err(db_err)?;
+        let rows: Vec<(i64, String, String)> = {
+            let mut stmt = conn
+                .prepare("SELECT id, type, payload FROM messages ORDER BY id")
+                .map_err(db_err)?;
+            let rows = stmt
+                .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+                .map_err(db_err)?;
+            rows.collect::<rusqlite::Result<_>>().map_err(db_err)?
+        };
+        let mut pending = Vec::new();
+        for (id, type_name, payload) in rows {
+            let Some(decode) = self.decoders.get(type_name.as_str()) else {
+                tracing::warn!(id, %type_name, "durable: type not registered; message kept");
+                continue;
+            };
+            let msg = decode(&payload).map_err(|e| {
+                MicrobusError::Dynamic(format!("durable: cannot decode {type_name} (id {id}): {e}"))
+            })?;
+            pending.push((id, msg));
+        }
+        Ok((conn, pending))
+    }
+}
+
+struct Store {
+    conn: Mutex<Connection>,
+    // 已写入、尚未确认完成的消息；持有一份引用用于完成判定
+    inflight: Mutex<Vec<(i64, Message)>>,
+    handle: DurableHandle,
+}
+
+impl Store {
+    fn track(&self, id: i64, msg: Message) {
+        self.inflight.lock().push((id, msg));
+        self.handle.pending.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // 删除引用计数已回落到仅剩本存储的消息；`stopping` 在候选收集之后检查，停机后的释放不计为完成
+    fn sweep(&self, stopping: impl Fn() -> bool) {
+        let done: HashSet<i64> = self
+            .inflight
+            .lock()
+            .iter()
+            .filter(|(_, msg)| Arc::strong_count(msg) == 1)
+            .map(|(id, _)| *id)
+            .collect();
+        std::sync::atomic::fence(Ordering::Acquire);
+        if done.is_empty() || stopping() {
+            return;
+        }
+        let deleted = {
+            let mut conn = self.conn.lock();
+            conn.transaction().and_then(|tx| {
+                {
+                    let mut stmt = tx.prepare_cached("DELETE FROM messages WHERE id = ?1")?;
+                    for id in &done {
+                        stmt.execute(params![id])?;
+                    }
+                }
+                tx.commit()
+            })
+        };
+        if let Err(e) = deleted {
+            tracing::warn!(error = %e, "durable: delete failed; retrying next sweep");
+            return;
+        }
+        self.inflight.lock().retain(|(id, _)| !done.contains(id));
+        let n = done.len() as u64;
+        self.handle.pending.fetch_sub(n, Ordering::Relaxed);
+        self.handle.completed.fetch_add(n, Ordering::Relaxed);
+    }
+}
+
+struct DurableTap {
+    encoders: HashMap<TypeId, Entry>,
+    store: Arc<Store>,
+}
+
+impl PublishTap for DurableTap {
+    fn on_publish(&self, type_id: TypeId, _type_name: &'static str, msg: &Message) {
+        let Some(entry) = self.encoders.get(&type_id) else {
+            return;
+        };
+        if REDELIVERING.try_with(|()| ()).is_ok() {
+            return;
+        }
+        let Some(payload) = (entry.encode)(&**msg) else {
+            return;
+        };
+        let inserted = {
+            let conn = self.store.conn.lock();
+            conn.execute(
+                "INSERT INTO messages (type, payload) VALUES (?1, ?2)",
+                params![entry.name, payload],
+            )
+            .map(|_| conn.last_insert_rowid())
+        };
+        match inserted {
+            Ok(id) => {
+                self.store.track(id, msg.clone());
+                self.store.handle.persisted.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.store.handle.failed.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(error = %e, type_name = entry.name, "durable write failed");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Component for Durable {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        // 打开与读取在启动屏障之前完成：存储错误按启动失败处理
+        let (conn, pending) = self.load().inspect_err(|_| {
+            crate::component::__startup_mark_failed(&ctx);
+        })?;
+        let Self {
+            encoders,
+            sweep_interval,
+            handle,
+            ..
+        } = *self;
+        let store = Arc::new(Store {
+            conn: Mutex::new(conn),
+            inflight: Mutex::new(Vec::new()),
+            handle,
+        });
+        ctx.bus().add_tap(Arc::new(DurableTap {
+            encoders,
+            store: store.clone(),
+        }));
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        if !pending.is_empty() {
+            tracing::info!(
+                count = pending.len(),
+                "durable: redelivering pending messages"
+            );
+        }
+        REDELIVERING
+            .scope((), async {
+                for (id, msg) in pending {
+                    store.track(id, msg.clone());
+                    store.handle.redelivered.fetch_add(1, Ordering::Relaxed);
+                    crate::component::__publish_any_arc(&ctx, msg).await;
+                }
+            })
+            .await;
+        let stopping = || crate::component::__stop_requested(&ctx);
+        loop {
+            crate::rt::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                () = crate::rt::sleep(sweep_interval) => store.sweep(stopping),
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,43 @@
-//! 框架统一错误类型：最小化枚举，避免依赖第三方错误栈，实现简单直接。
+//! 框架统一错误类型：按失败类别区分的枚举，避免依赖第三方错误栈；结构化变体经 `source()` 保留原因链。
 use std::{error::Error as StdError, fmt};
 
+/// 可跨任务传递的原因错误。
+pub type BoxError = Box<dyn StdError + Send + Sync + 'static>;
+
 #[derive(Debug)]
 pub enum MicrobusError {
     Other(&'static str), // 简单静态消息
     Dynamic(String),     // 动态字符串（极少使用）
+    /// 启动失败（`app.start()` 返回）：汇总同一轮启动中全部失败的组件，`source()` 为首个失败的错误。
+    Startup {
+        failures: Vec<StartupFailure>,
+    },
+    /// 发布 `type_name` 失败（编码、投递等）。
+    Publish {
+        type_name: &'static str,
+        source: BoxError,
+        trace: Trace,
+    },
+    /// 订阅 `type_name` 失败。
+    Subscribe {
+        type_name: &'static str,
+        source: BoxError,
+        trace: Trace,
+    },
+    /// 装配或配置无效。
+    Config(String),
+    /// 桥接端点（套接字、共享内存、外部代理）建立或通信失败。
+    Bridge {
+        endpoint: String,
+        source: BoxError,
+    },
+    /// 请求（`ctx.request(..).timeout(d)`）在 `after` 内未得到应答；应答方经 `Ask::cancelled` 感知放弃。
+    Timeout {
+        type_name: &'static str,
+        after: std::time::Duration,
+    },
+    /// 业务自定义错误（透明包装：`Display` 与 `source()` 均转发给内层，可经 `downcast_ref` 取回原类型）。
+    Custom(BoxError),
 }
 
 impl fmt::Display for MicrobusError {
@@ -12,11 +45,161 @@ impl fmt::Display for MicrobusError {
         match self {
             Self::Other(msg) => write!(f, "{msg}"),
             Self::Dynamic(s) => write!(f, "{s}"),
+            Self::Startup { failures } => {
+                write!(f, "{} component(s) failed to start", failures.len())?;
+                for (i, x) in failures.iter().enumerate() {
+                    let sep = if i == 0 { ": " } else { "; " };
+                    write!(f, "{sep}{} ({:?}): {}", x.component, x.phase, x.error)?;
+                }
+                Ok(())
+            }
+            Self::Publish { type_name, .. } => write!(f, "publish {type_name} failed"),
+            Self::Subscribe { type_name, .. } => write!(f, "subscribe {type_name} failed"),
+            Self::Config(s) => write!(f, "invalid configuration: {s}"),
+            Self::Bridge { endpoint, .. } => write!(f, "bridge {endpoint} failed"),
+            Self::Timeout { type_name, after } => {
+                write!(f, "request {type_name} timed out after {after:?}")
+            }
+            Self::Custom(e) => e.fmt(f),
+        }
+    }
+}
+
+impl StdError for MicrobusError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Startup { failures } => failures.first().map(|x| &x.error as _),
+            Self::Publish { source, .. }
+            | Self::Subscribe { source, .. }
+            | Self::Bridge { source, .. } => Some(&**source),
+            Self::Custom(e) => e.source(),
+            Self::Other(_) | Self::Dynamic(_) | Self::Config(_) | Self::Timeout { .. } => None,
+        }
+    }
+}
+
+// 特性 `anyhow`：`?` 可直接把 anyhow::Error 转入框架错误，原因链保持不变
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for MicrobusError {
+    fn from(e: anyhow::Error) -> Self {
+        __into_error(e)
+    }
+}
+
+// 宏生成代码使用：`#[init]` 等返回的任意错误类型归一为框架错误；已是框架错误时原样返回
+#[doc(hidden)]
+pub fn __into_error<E: Into<BoxError>>(e: E) -> MicrobusError {
+    match e.into().downcast::<MicrobusError>() {
+        Ok(e) => *e,
+        Err(e) => MicrobusError::Custom(e),
+    }
+}
+
+/// 订阅失败：由 `try_subscribe` 等可失败接口返回，或作为 [`MicrobusError::Subscribe`] 的 `source`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscribeError {
+    /// 总线已封印：启动完成后订阅图只读。
+    Sealed { type_name: &'static str },
+}
+
+impl fmt::Display for SubscribeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sealed { type_name } => {
+                write!(f, "cannot subscribe {type_name}: bus sealed after startup")
+            }
+        }
+    }
+}
+
+impl StdError for SubscribeError {}
+
+impl From<SubscribeError> for MicrobusError {
+    fn from(e: SubscribeError) -> Self {
+        let SubscribeError::Sealed { type_name } = e;
+        Self::Subscribe {
+            type_name,
+            source: Box::new(e),
+            trace: Trace::capture(),
+        }
+    }
+}
+
+/// 发布失败：由 `try_publish_*` 等可失败接口返回，或作为 [`MicrobusError::Publish`] 的 `source`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublishError {
+    /// 类型擦除消息与路由目标的实际类型不一致。
+    TypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl fmt::Display for PublishError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { expected, found } => {
+                write!(f, "type mismatch: expected {expected}, found {found}")
+            }
+        }
+    }
+}
+
+impl StdError for PublishError {}
+
+impl From<PublishError> for MicrobusError {
+    fn from(e: PublishError) -> Self {
+        let PublishError::TypeMismatch { expected, .. } = e;
+        Self::Publish {
+            type_name: expected,
+            source: Box::new(e),
+            trace: Trace::capture(),
+        }
+    }
+}
+
+/// 启动失败报告中的一项；`trace` 取自组件标记启动失败之处（`init` 返回错误的位置）。
+#[derive(Debug)]
+pub struct StartupFailure {
+    pub component: &'static str,
+    pub phase: crate::events::FailurePhase,
+    pub error: MicrobusError,
+    pub trace: Trace,
+}
+
+/// 错误构造处的调用栈：特性 `backtrace` 启用时强制捕获（不依赖 `RUST_BACKTRACE`）并出现在 `Debug` 输出中，
+/// 未启用时为零大小占位。
+#[derive(Default)]
+pub struct Trace {
+    #[cfg(feature = "backtrace")]
+    backtrace: Option<std::backtrace::Backtrace>,
+}
+
+impl Trace {
+    #[must_use]
+    pub fn capture() -> Self {
+        Self {
+            #[cfg(feature = "backtrace")]
+            backtrace: Some(std::backtrace::Backtrace::force_capture()),
         }
     }
+    /// 捕获到的调用栈；未启用特性 `backtrace` 时恒为 `None`。
+    #[cfg(feature = "backtrace")]
+    #[must_use]
+    pub const fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.backtrace.as_ref()
+    }
 }
-impl StdError for MicrobusError {}
 
-pub type Result<T = ()> = std::result::Result<T, MicrobusError>;
+impl fmt::Debug for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "backtrace")]
+        if let Some(bt) = &self.backtrace {
+            return write!(f, "\n{bt}");
+        }
+        f.write_str("<disabled>")
+    }
+}
 
-// 已无动态错误构造辅助需求，保留枚举即可（err_dynamic 移除）
+// 错误参数可选：prelude 导入后 `Result<T, MyError>` 仍可直接书写
+pub type Result<T = (), E = MicrobusError> = std::result::Result<T, E>;
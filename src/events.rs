@@ -0,0 +1,97 @@
+//! 框架级事件：由框架自身发布到总线的消息类型，业务组件按普通 `&T` 订阅即可观测。
+
+/// 某订阅队列持续处于高水位（见 `AppConfig::subscriber_lag`）。
+///
+/// 每次进入滞后状态只发布一次；队列回落到阈值以下后重新计数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriberLagging {
+    pub component: &'static str,
+    pub type_name: &'static str,
+    pub depth: usize,
+    pub capacity: usize,
+}
+
+/// `#[handle]` 返回 `Err`（见 `AppConfig::publish_handler_errors`），供集中的错误上报组件汇总与告警。
+///
+/// `error` 为错误值的 `Display` 文本（与 warn 日志一致）；处理 `HandlerError` 本身时的错误只记录日志。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerError {
+    pub component: &'static str,
+    pub method: &'static str,
+    pub message_type: &'static str,
+    pub error: String,
+}
+
+// ---- 生命周期事件 ----
+// 投递为尽力而为且不等待：订阅队列已满时丢弃并计入 `introspect().drops`（`full`），绝不反压组件启停；
+// 停机阶段订阅方 worker 可能已退出，`ComponentStopped` 等事件未必被消费。
+
+/// 组件已完成 init 与订阅装配并越过启动屏障（启动失败时不发布）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStarted {
+    pub component: &'static str,
+}
+
+/// 组件 `run()` 正常返回。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStopped {
+    pub component: &'static str,
+}
+
+/// 组件失败所处阶段。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailurePhase {
+    /// 工厂构建失败
+    Build,
+    /// `run()` 返回错误（含 `#[init]` 失败）
+    Run,
+}
+
+/// 组件构建失败或 `run()` 返回错误。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentFailed {
+    pub component: &'static str,
+    pub phase: FailurePhase,
+    pub error: String,
+}
+
+/// `#[active]` 循环返回 `ControlFlow::Break` 而自然结束（组件其余 handler / active 继续运行）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveCompleted {
+    pub component: &'static str,
+    pub method: &'static str,
+}
+
+/// 全部组件到达启动屏障，总线已封印，应用进入运行期。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppSealed {
+    pub components: usize,
+}
+
+/// 桥 / 回放边界处对端消息的模式版本与本地不一致（同一组件对同一名称与对端版本只发布一次）。
+///
+/// `compatibility` 为 `Incompatible` 时对应消息被丢弃（桥）或回放启动失败（回放，此时事件不会发布）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaMismatch {
+    pub component: &'static str,
+    pub name: &'static str,
+    pub local_version: u32,
+    pub remote_version: u32,
+    pub compatibility: crate::codec::Compatibility,
+}
+
+// 框架事件类型判定（测试工具据此区分组件业务输出；装配校验视其为已有发布方）
+pub(crate) fn is_framework_event(type_id: std::any::TypeId) -> bool {
+    use std::any::TypeId;
+    [
+        TypeId::of::<SubscriberLagging>(),
+        TypeId::of::<HandlerError>(),
+        TypeId::of::<ComponentStarted>(),
+        TypeId::of::<ComponentStopped>(),
+        TypeId::of::<ComponentFailed>(),
+        TypeId::of::<ActiveCompleted>(),
+        TypeId::of::<AppSealed>(),
+        TypeId::of::<SchemaMismatch>(),
+    ]
+    .contains(&type_id)
+}
@@ -0,0 +1,307 @@
+//! C 嵌入接口（特性 `ffi`）：C / C++ 宿主经 `include/mmg_microbus.h` 创建 App，以 JSON 跨边界发布与订阅消息。
+//!
+//! - 可跨边界的类型须由 Rust 侧先以 [`expose::<T>()`](expose) 登记（`T: BusMessage`，按 `T::NAME` 匹配，经 `codec::Json` 编解码）；
+//! - 嵌入方以本 crate（开启 `ffi`）为依赖构建 `staticlib` / `cdylib`，其中 inventory 自动发现的 Rust 组件照常启动；
+//! - 每个句柄自带 tokio 多线程运行时；回调在运行时工作线程上调用，须线程安全且尽快返回。
+//!
+//! 返回值：`MMG_OK`（0）或负的 `MMG_ERR_*`。
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+
+use crate::app::App;
+use crate::bus::ErasedEvent;
+use crate::codec::{BusMessage, Json, MessageCodec};
+use crate::component::{Component, ComponentContext};
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::rt::JoinHandle;
+
+pub const MMG_OK: c_int = 0;
+/// 空指针参数。
+pub const MMG_ERR_NULL: c_int = -1;
+/// 字符串不是合法 UTF-8。
+pub const MMG_ERR_UTF8: c_int = -2;
+/// 类型名未经 `expose` 登记。
+pub const MMG_ERR_UNKNOWN_TYPE: c_int = -3;
+/// JSON 无法解码为登记类型。
+pub const MMG_ERR_DECODE: c_int = -4;
+/// 调用时机不符：启动后订阅、启动前发布或重复启动。
+pub const MMG_ERR_STATE: c_int = -5;
+/// 启动失败（组件构建 / 初始化失败）。
+pub const MMG_ERR_START: c_int = -6;
+/// 入站队列已满（仅回调线程内发布）或 App 已停止。
+pub const MMG_ERR_BUSY: c_int = -7;
+
+const IN_QUEUE: usize = 1024;
+const SHUTDOWN_GRACE: Duration = Duration::from_millis(200);
+
+/// 订阅回调：`type_name` 与 `json` 仅在回调期间有效。
+pub type MmgCallback =
+    unsafe extern "C" fn(type_name: *const c_char, json: *const c_char, user_data: *mut c_void);
+
+type SpawnSubscriber = fn(&ComponentContext, Subscriber) -> JoinHandle<()>;
+
+#[derive(Clone, Copy)]
+struct Exposed {
+    name: &'static str,
+    decode: fn(&str) -> std::result::Result<ErasedEvent, String>,
+    subscribe: SpawnSubscriber,
+}
+
+static EXPOSED: Mutex<Vec<Exposed>> = Mutex::new(Vec::new());
+
+/// 登记可跨 FFI 边界收发的类型（重复登记无副作用）；须在 `mmg_app_new` 之前完成。
+pub fn expose<T: BusMessage>() {
+    let mut all = EXPOSED.lock();
+    if all.iter().all(|e| e.name != T::NAME) {
+        all.push(Exposed {
+            name: T::NAME,
+            decode: |json| MessageCodec::<T>::decode(&Json, json).map(ErasedEvent::new),
+            subscribe: spawn_subscriber::<T>,
+        });
+    }
+}
+
+fn lookup(name: &str) -> Option<Exposed> {
+    EXPOSED.lock().iter().find(|e| e.name == name).copied()
+}
+
+// 回调与宿主上下文指针；线程安全由宿主保证（见回调约定）
+#[derive(Clone, Copy)]
+struct Subscriber {
+    callback: MmgCallback,
+    user_data: *mut c_void,
+}
+
+unsafe impl Send for Subscriber {}
+unsafe impl Sync for Subscriber {}
+
+impl Subscriber {
+    fn call(&self, name: &CStr, json: &CStr) {
+        unsafe { (self.callback)(name.as_ptr(), json.as_ptr(), self.user_data) };
+    }
+}
+
+fn spawn_subscriber<T: BusMessage>(
+    ctx: &ComponentContext,
+    subscriber: Subscriber,
+) -> JoinHandle<()> {
+    let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+    let ctx = ctx.__fork();
+    crate::rt::spawn(async move {
+        let name = CString::new(T::NAME).unwrap_or_default();
+        loop {
+            tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                msg = sub.recv() => {
+                    let Some(msg) = msg else { break };
+                    // serde_json 转义控制字符，输出不含内部 NUL
+                    let Ok(json) = CString::new(MessageCodec::<T>::encode(&Json, &msg)) else {
+                        continue;
+                    };
+                    subscriber.call(&name, &json);
+                }
+            }
+        }
+    })
+}
+
+// 宿主发布入口：订阅在启动屏障前登记，入站消息以本组件为来源发布
+struct FfiEndpoint {
+    rx: mpsc::Receiver<ErasedEvent>,
+    subscribers: Vec<(SpawnSubscriber, Subscriber)>,
+}
+
+#[async_trait]
+impl Component for FfiEndpoint {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            mut rx,
+            subscribers,
+        } = *self;
+        let workers: Vec<_> = subscribers
+            .into_iter()
+            .map(|(spawn, subscriber)| spawn(&ctx, subscriber))
+            .collect();
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                ev = rx.recv() => match ev {
+                    Some(ev) => crate::component::__publish_erased(&ctx, ev).await,
+                    None => break,
+                },
+            }
+        }
+        for w in workers {
+            w.abort().await;
+        }
+        Ok(())
+    }
+}
+
+/// 不透明 App 句柄。
+pub struct MmgApp {
+    runtime: tokio::runtime::Runtime,
+    app: App,
+    tx: mpsc::Sender<ErasedEvent>,
+    // 启动时移交给 App
+    endpoint: Option<FfiEndpoint>,
+}
+
+unsafe fn str_arg<'a>(ptr: *const c_char) -> std::result::Result<&'a str, c_int> {
+    if ptr.is_null() {
+        return Err(MMG_ERR_NULL);
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| MMG_ERR_UTF8)
+}
+
+/// 创建 App（默认配置）；运行时创建失败返回空指针。
+#[no_mangle]
+pub extern "C" fn mmg_app_new() -> *mut MmgApp {
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            tracing::error!(error = %e, "ffi: runtime setup failed");
+            return std::ptr::null_mut();
+        }
+    };
+    let (tx, rx) = mpsc::channel(IN_QUEUE);
+    Box::into_raw(Box::new(MmgApp {
+        runtime,
+        app: App::new(AppConfig::default()),
+        tx,
+        endpoint: Some(FfiEndpoint {
+            rx,
+            subscribers: Vec::new(),
+        }),
+    }))
+}
+
+/// 订阅已登记类型 `type_name`：每条消息以 JSON 调用 `callback`；须在 `mmg_app_start` 之前调用。
+///
+/// # Safety
+/// `app` 须为 `mmg_app_new` 返回且未释放的句柄；`type_name` 为 NUL 结尾字符串；
+/// `callback` 与 `user_data` 在 App 释放前保持有效，且可从任意线程并发调用。
+#[no_mangle]
+pub unsafe extern "C" fn mmg_subscribe_callback(
+    app: *mut MmgApp,
+    type_name: *const c_char,
+    callback: Option<MmgCallback>,
+    user_data: *mut c_void,
+) -> c_int {
+    let (Some(app), Some(callback)) = (unsafe { app.as_mut() }, callback) else {
+        return MMG_ERR_NULL;
+    };
+    let name = match unsafe { str_arg(type_name) } {
+        Ok(name) => name,
+        Err(code) => return code,
+    };
+    let Some(exposed) = lookup(name) else {
+        return MMG_ERR_UNKNOWN_TYPE;
+    };
+    let Some(endpoint) = app.endpoint.as_mut() else {
+        return MMG_ERR_STATE;
+    };
+    endpoint.subscribers.push((
+        exposed.subscribe,
+        Subscriber {
+            callback,
+            user_data,
+        },
+    ));
+    MMG_OK
+}
+
+/// 启动 App（阻塞至启动屏障完成）。
+///
+/// # Safety
+/// `app` 须为 `mmg_app_new` 返回且未释放的句柄。
+#[no_mangle]
+pub unsafe extern "C" fn mmg_app_start(app: *mut MmgApp) -> c_int {
+    let Some(app) = (unsafe { app.as_mut() }) else {
+        return MMG_ERR_NULL;
+    };
+    let Some(endpoint) = app.endpoint.take() else {
+        return MMG_ERR_STATE;
+    };
+    app.app.add_component(endpoint);
+    match app.runtime.block_on(app.app.start()) {
+        Ok(()) => MMG_OK,
+        Err(e) => {
+            tracing::error!(error = %e, "ffi: app start failed");
+            MMG_ERR_START
+        }
+    }
+}
+
+/// 发布一条 JSON 消息（走常规路由与背压：宿主线程上阻塞等待入队）。
+///
+/// # Safety
+/// `app` 须为 `mmg_app_new` 返回且未释放的句柄；`type_name` 与 `json` 为 NUL 结尾字符串。
+#[no_mangle]
+pub unsafe extern "C" fn mmg_publish_json(
+    app: *mut MmgApp,
+    type_name: *const c_char,
+    json: *const c_char,
+) -> c_int {
+    let Some(app) = (unsafe { app.as_ref() }) else {
+        return MMG_ERR_NULL;
+    };
+    let (name, json) = match unsafe { (str_arg(type_name), str_arg(json)) } {
+        (Ok(name), Ok(json)) => (name, json),
+        (Err(code), _) | (_, Err(code)) => return code,
+    };
+    if app.endpoint.is_some() {
+        return MMG_ERR_STATE;
+    }
+    let Some(exposed) = lookup(name) else {
+        return MMG_ERR_UNKNOWN_TYPE;
+    };
+    let ev = match (exposed.decode)(json) {
+        Ok(ev) => ev,
+        Err(e) => {
+            tracing::debug!(type_name = name, error = %e, "ffi: json decode failed");
+            return MMG_ERR_DECODE;
+        }
+    };
+    // 回调线程处于运行时内，不可阻塞等待
+    let sent = if tokio::runtime::Handle::try_current().is_ok() {
+        app.tx.try_send(ev).is_ok()
+    } else {
+        app.tx.blocking_send(ev).is_ok()
+    };
+    if sent {
+        MMG_OK
+    } else {
+        MMG_ERR_BUSY
+    }
+}
+
+/// 停止并释放 App；空指针无操作。不可在回调内调用。
+///
+/// # Safety
+/// `app` 须为 `mmg_app_new` 返回的句柄或空指针，且此后不再使用。
+#[no_mangle]
+pub unsafe extern "C" fn mmg_app_free(app: *mut MmgApp) {
+    if app.is_null() {
+        return;
+    }
+    let MmgApp {
+        runtime, mut app, ..
+    } = *unsafe { Box::from_raw(app) };
+    {
+        let _guard = runtime.enter();
+        app.stop();
+    }
+    runtime.shutdown_timeout(SHUTDOWN_GRACE);
+}
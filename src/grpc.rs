@@ -0,0 +1,367 @@
+//! gRPC 适配（特性 `grpc`，基于 tonic + prost）：把请求 / 应答 handler 暴露为 gRPC 服务，或把总线请求转发给外部 gRPC 服务。
+//!
+//! - [`GrpcIngress`]：监听地址，按方法路径（`/pkg.Service/Method`）把一元调用转为总线请求
+//!   （`bus.request::<Q, A>(q)`），由订阅 `Ask<Q, A>` 的普通 `#[handle]` 应答；业务代码不接触 tonic。
+//!   客户端 deadline（`grpc-timeout`）到期或取消时请求随之丢弃，应答方经 `Ask::cancelled` 感知。
+//! - [`GrpcEgress`]：订阅 `Ask<Q, A>`，以生成的消息类型调用外部服务并把响应作为应答回传；组件内照常 `ctx.request(q).await`。
+//!
+//! `Q` / `A` 为 prost 生成（或 `#[derive(prost::Message)]`）的消息类型，与 `.proto` 中的请求 / 响应一一对应；
+//! 无需 tonic-build 生成服务桩。仅支持一元调用，不含 TLS。
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::codegen::{http, BoxFuture, Context, Poll, Service};
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::{Channel, Endpoint, Server};
+use tonic::{body::Body, Status};
+use tonic_prost::ProstCodec;
+
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+use crate::query::Ask;
+
+/// 底层 tonic / prost（状态码、`#[derive(prost::Message)]` 等）。
+pub use {prost, tonic};
+
+type UnaryFn = Arc<
+    dyn Fn(
+            Arc<ComponentContext>,
+            http::Request<Body>,
+        ) -> BoxFuture<http::Response<Body>, Infallible>
+        + Send
+        + Sync,
+>;
+type SpawnCall = Box<
+    dyn FnOnce(&ComponentContext, Channel, Arc<Semaphore>, GrpcHandle) -> JoinHandle<()>
+        + Send
+        + Sync,
+>;
+
+const DEFAULT_CONCURRENCY: usize = 64;
+
+/// gRPC 组件计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct GrpcHandle {
+    calls: Arc<AtomicU64>,
+    failed: Arc<AtomicU64>,
+    local_addr: Arc<Mutex<Option<SocketAddr>>>,
+}
+
+impl GrpcHandle {
+    /// 成功完成的调用数（Ingress：已应答；Egress：已把响应回传）。
+    #[must_use]
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+    /// 失败的调用数（Ingress：无应答方 / 超时 / 未应答；Egress：远端返回错误状态）。
+    #[must_use]
+    pub fn failed(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+    /// Ingress 实际监听地址（端口 0 解析为实际端口）；启动完成后可用。
+    #[must_use]
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        *self.local_addr.lock()
+    }
+}
+
+// 总线请求失败映射为 gRPC 状态：超时 -> DEADLINE_EXCEEDED，无应答方 / 未应答 -> UNAVAILABLE
+fn status(e: &MicrobusError) -> Status {
+    match e {
+        MicrobusError::Timeout { .. } => Status::deadline_exceeded(e.to_string()),
+        _ => Status::unavailable(e.to_string()),
+    }
+}
+
+// ---- Ingress ----
+
+/// gRPC 服务端：把登记的方法路径转为总线请求，由 `Ask<Q, A>` handler 应答。
+pub struct GrpcIngress {
+    addr: String,
+    routes: HashMap<String, UnaryFn>,
+    handle: GrpcHandle,
+}
+
+impl GrpcIngress {
+    /// 监听 `addr`（如 `0.0.0.0:50051`、`127.0.0.1:0`）；绑定在启动阶段完成，失败按启动失败处理。
+    #[must_use]
+    pub fn bind(addr: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into(),
+            routes: HashMap::new(),
+            handle: GrpcHandle::default(),
+        }
+    }
+    /// 一元方法 `path`（`/pkg.Service/Method`）：请求解码为 `Q` 发起总线请求，首个应答 `A` 作为响应。
+    #[must_use]
+    pub fn unary<Q, A>(self, path: impl Into<String>) -> Self
+    where
+        Q: prost::Message + Default + Send + Sync + 'static,
+        A: prost::Message + Send + Sync + 'static,
+    {
+        self.route::<Q, A>(path.into(), None)
+    }
+    /// 同 [`unary`](Self::unary)，总线请求以 `timeout` 为上限（到期返回 `DEADLINE_EXCEEDED`）；
+    /// 未设置时仅受客户端 deadline 约束。
+    #[must_use]
+    pub fn unary_with_timeout<Q, A>(self, path: impl Into<String>, timeout: Duration) -> Self
+    where
+        Q: prost::Message + Default + Send + Sync + 'static,
+        A: prost::Message + Send + Sync + 'static,
+    {
+        self.route::<Q, A>(path.into(), Some(timeout))
+    }
+    fn route<Q, A>(mut self, path: String, timeout: Option<Duration>) -> Self
+    where
+        Q: prost::Message + Default + Send + Sync + 'static,
+        A: prost::Message + Send + Sync + 'static,
+    {
+        let handle = self.handle.clone();
+        self.routes.insert(
+            path,
+            Arc::new(move |ctx, req| {
+                let svc = Unary::<Q, A> {
+                    ctx,
+                    timeout,
+                    handle: handle.clone(),
+                    _types: std::marker::PhantomData,
+                };
+                Box::pin(async move {
+                    let mut grpc = tonic::server::Grpc::new(ProstCodec::<A, Q>::default());
+                    Ok(grpc.unary(svc, req).await)
+                })
+            }),
+        );
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> GrpcHandle {
+        self.handle.clone()
+    }
+}
+
+struct Unary<Q, A> {
+    ctx: Arc<ComponentContext>,
+    timeout: Option<Duration>,
+    handle: GrpcHandle,
+    _types: std::marker::PhantomData<fn(Q) -> A>,
+}
+
+impl<Q, A> tonic::server::UnaryService<Q> for Unary<Q, A>
+where
+    Q: Send + Sync + 'static,
+    A: Send + 'static,
+{
+    type Response = A;
+    type Future = BoxFuture<tonic::Response<A>, Status>;
+    fn call(&mut self, request: tonic::Request<Q>) -> Self::Future {
+        let mut req = self.ctx.request::<Q, A>(request.into_inner());
+        if let Some(d) = self.timeout {
+            req = req.timeout(d);
+        }
+        let handle = self.handle.clone();
+        Box::pin(async move {
+            match req.await {
+                Ok(a) => {
+                    handle.calls.fetch_add(1, Ordering::Relaxed);
+                    Ok(tonic::Response::new(a))
+                }
+                Err(e) => {
+                    handle.failed.fetch_add(1, Ordering::Relaxed);
+                    Err(status(&e))
+                }
+            }
+        })
+    }
+}
+
+// 按请求路径分派到登记的方法；未登记的路径返回 UNIMPLEMENTED
+#[derive(Clone)]
+struct Router {
+    ctx: Arc<ComponentContext>,
+    routes: Arc<HashMap<String, UnaryFn>>,
+}
+
+impl Service<http::Request<Body>> for Router {
+    type Response = http::Response<Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, req: http::Request<Body>) -> Self::Future {
+        match self.routes.get(req.uri().path()) {
+            Some(route) => route(self.ctx.clone(), req),
+            None => {
+                let path = req.uri().path().to_owned();
+                Box::pin(async move {
+                    Ok(Status::unimplemented(format!("method {path} not exposed")).into_http())
+                })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Component for GrpcIngress {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            addr,
+            routes,
+            handle,
+        } = *self;
+        let bridge_error = |source: crate::error::BoxError| {
+            crate::component::__startup_mark_failed(&ctx);
+            MicrobusError::Bridge {
+                endpoint: addr.clone(),
+                source,
+            }
+        };
+        let sock: SocketAddr = addr.parse().map_err(|e| bridge_error(Box::new(e)))?;
+        let incoming = TcpIncoming::bind(sock).map_err(|e| bridge_error(Box::new(e)))?;
+        *handle.local_addr.lock() = incoming.local_addr().ok();
+        let router = Router {
+            ctx: Arc::new(ctx.__fork()),
+            routes: Arc::new(routes),
+        };
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        if let Err(e) = Server::builder()
+            .serve_with_incoming_shutdown(router, incoming, crate::component::__recv_stop(&ctx))
+            .await
+        {
+            tracing::error!(addr = %addr, error = %e, "grpc ingress stopped");
+        }
+        Ok(())
+    }
+}
+
+// ---- Egress ----
+
+/// gRPC 客户端：订阅 `Ask<Q, A>`，调用外部服务并把响应作为应答回传。
+pub struct GrpcEgress {
+    endpoint: String,
+    calls: Vec<SpawnCall>,
+    concurrency: usize,
+    handle: GrpcHandle,
+}
+
+impl GrpcEgress {
+    /// 连接 `endpoint`（如 `http://host:50051`）；连接在首次调用时建立，远端不可达不影响本地启动。
+    #[must_use]
+    pub fn connect(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            calls: Vec::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            handle: GrpcHandle::default(),
+        }
+    }
+    /// 同时在途的调用上限（默认 64，全部方法共享）；达到上限时后续 `Ask` 在队列中等待（背压）。
+    #[must_use]
+    pub const fn concurrency(mut self, n: usize) -> Self {
+        self.concurrency = n;
+        self
+    }
+    /// 以一元方法 `path`（`/pkg.Service/Method`）应答 `Ask<Q, A>`；远端返回错误状态时不应答并记录 `warn`，
+    /// 发起方得到 `MicrobusError::Other`（未应答）或自身的超时。发起方放弃时在途调用随之取消。
+    #[must_use]
+    pub fn unary<Q, A>(mut self, path: &'static str) -> Self
+    where
+        Q: prost::Message + Clone + Send + Sync + 'static,
+        A: prost::Message + Default + Send + Sync + 'static,
+    {
+        let path = PathAndQuery::from_static(path);
+        self.calls.push(Box::new(move |ctx, channel, permits, handle| {
+            let mut sub = crate::component::__subscribe_any_auto::<Ask<Q, A>>(ctx);
+            let ctx = ctx.__fork();
+            tokio::spawn(async move {
+                loop {
+                    let ask = tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => break,
+                        ask = sub.recv() => match ask {
+                            Some(ask) => ask,
+                            None => break,
+                        },
+                    };
+                    let Ok(permit) = permits.clone().acquire_owned().await else {
+                        break;
+                    };
+                    let (channel, path, handle) = (channel.clone(), path.clone(), handle.clone());
+                    tokio::spawn(async move {
+                        let _permit = permit;
+                        let call = async {
+                            let mut client = tonic::client::Grpc::new(channel);
+                            client.ready().await.map_err(|e| Status::unavailable(e.to_string()))?;
+                            client
+                                .unary(
+                                    tonic::Request::new(ask.query().clone()),
+                                    path.clone(),
+                                    ProstCodec::<Q, A>::default(),
+                                )
+                                .await
+                        };
+                        tokio::select! {
+                            () = ask.cancelled() => {}
+                            r = call => match r {
+                                Ok(resp) => {
+                                    handle.calls.fetch_add(1, Ordering::Relaxed);
+                                    ask.reply(resp.into_inner());
+                                }
+                                Err(s) => {
+                                    tracing::warn!(path = %path, code = ?s.code(), message = s.message(), "grpc egress call failed");
+                                    handle.failed.fetch_add(1, Ordering::Relaxed);
+                                }
+                            },
+                        }
+                    });
+                }
+            })
+        }));
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> GrpcHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for GrpcEgress {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            endpoint,
+            calls,
+            concurrency,
+            handle,
+        } = *self;
+        let channel = Endpoint::from_shared(endpoint.clone())
+            .map(|e| e.connect_lazy())
+            .map_err(|e| {
+                crate::component::__startup_mark_failed(&ctx);
+                MicrobusError::Bridge {
+                    endpoint,
+                    source: Box::new(e),
+                }
+            })?;
+        let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+        let workers: Vec<_> = calls
+            .into_iter()
+            .map(|f| f(&ctx, channel.clone(), permits.clone(), handle.clone()))
+            .collect();
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        crate::component::__recv_stop(&ctx).await;
+        for w in workers {
+            w.abort();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+//! 运行期自省：组件状态、订阅拓扑与队列深度、（`bus-metrics` 特性下）按类型计数。
+//!
+//! 入口：`App::introspect()` 或组件内 `ctx.introspect()`，返回一次性快照。
+use parking_lot::RwLock;
+use serde::Serialize;
+
+/// 组件运行状态。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", content = "error", rename_all = "snake_case")]
+pub enum ComponentStatus {
+    /// 已派生任务，正在构建 / init / 订阅装配
+    Starting,
+    /// 已到达启动屏障，等待其余组件
+    Ready,
+    /// 已越过启动屏障
+    Running,
+    /// `run()` 正常返回
+    Stopped,
+    /// 构建失败或 `run()` 返回错误
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentInfo {
+    pub name: &'static str,
+    pub status: ComponentStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriptionInfo {
+    pub component: &'static str,
+    pub type_name: &'static str,
+    pub depth: usize,
+    pub capacity: usize,
+    /// 订阅端已关闭（组件退出）
+    pub closed: bool,
+    /// `#[handle(in_order)]` 订阅：按发布方 FIFO 逐条交付
+    pub ordered: bool,
+    /// 弱订阅（`#[handle(weak)]` / `try_subscribe_weak`）：不计为消费者，从不向发布方施加背压
+    pub weak: bool,
+    /// 弱订阅因队列满丢弃的消息数（其它订阅恒为 0）
+    pub dropped: u64,
+}
+
+/// 消息类型登记项（`App::message_types()`）：装配清单与实际订阅中出现的类型，按 `size` 降序。
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageTypeInfo {
+    pub type_name: &'static str,
+    /// `size_of::<T>()`：按值发布时每条消息搬移的字节数（不含堆上载荷）
+    pub size: usize,
+    /// 静态清单中的发布组件；宿主经 `declare_publisher` 登记时为 `"<external>"`（动态发布不在其列）
+    pub publishers: Vec<&'static str>,
+    /// 实际订阅的归属组件（每个订阅一项；组件之外的订阅为 `"<external>"`）
+    pub subscribers: Vec<&'static str>,
+    /// 全部订阅队列容量之和（合并订阅计 1）：最坏情况下同时排队的条数
+    pub capacity: usize,
+}
+
+/// 按消息类型的发布计数（仅 `bus-metrics` 特性下采集，否则为空）。
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeMetrics {
+    pub type_name: &'static str,
+    pub published: u64,
+    /// 近似字节量：`size_of::<T>() × 发布次数`，加上 `App::message_size_hint` 登记的附加尺寸。
+    pub bytes: u64,
+}
+
+/// 按消息类型的丢弃计数：`closed` 为订阅端已关闭（所属组件已退出）导致投递失败的份数，
+/// `full` 为不等待发布的框架生命周期事件遇订阅队列已满而丢弃的份数。
+#[derive(Debug, Clone, Serialize)]
+pub struct DropMetrics {
+    pub type_name: &'static str,
+    pub closed: u64,
+    pub full: u64,
+}
+
+/// 按消息类型的背压统计：发布方因订阅队列已满而在 `send().await` 上等待的次数与时长。
+#[derive(Debug, Clone, Serialize)]
+pub struct BackpressureMetrics {
+    pub type_name: &'static str,
+    pub blocked_sends: u64,
+    pub blocked_total: std::time::Duration,
+    pub blocked_max: std::time::Duration,
+}
+
+/// 按消息类型的发布限速统计（`AppConfig::rate_limit`）：因超速等待与丢弃的发布数。
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitMetrics {
+    pub type_name: &'static str,
+    pub delayed: u64,
+    pub dropped: u64,
+}
+
+/// 按消息类型的发布去重统计（`AppConfig::dedup`）：窗口内被抑制的重复发布数。
+#[derive(Debug, Clone, Serialize)]
+pub struct DedupMetrics {
+    pub type_name: &'static str,
+    pub suppressed: u64,
+}
+
+/// 按 handler 的采样统计（`#[handle(sample = ..)]`）：未调用 handler 而跳过的消息数。
+#[derive(Debug, Clone, Serialize)]
+pub struct SamplingMetrics {
+    pub component: &'static str,
+    pub method: &'static str,
+    pub type_name: &'static str,
+    pub skipped: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub sealed: bool,
+    pub components: Vec<ComponentInfo>,
+    pub subscriptions: Vec<SubscriptionInfo>,
+    pub types: Vec<TypeMetrics>,
+    pub drops: Vec<DropMetrics>,
+    pub backpressure: Vec<BackpressureMetrics>,
+    pub sampling: Vec<SamplingMetrics>,
+    pub rate_limits: Vec<RateLimitMetrics>,
+    pub dedup: Vec<DedupMetrics>,
+}
+
+impl Snapshot {
+    /// 已封印且无失败组件。
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.sealed
+            && !self
+                .components
+                .iter()
+                .any(|c| matches!(c.status, ComponentStatus::Failed(_)))
+    }
+}
+
+/// 启动屏障进度：`pending` 为尚未到达屏障（仍在构建 / init / 订阅装配）的组件。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StartupProgress {
+    pub arrived: Vec<&'static str>,
+    pub pending: Vec<&'static str>,
+    pub failed: Vec<&'static str>,
+}
+
+pub(crate) fn snapshot(shared: &crate::app::AppShared, bus: &crate::bus::BusHandle) -> Snapshot {
+    Snapshot {
+        sealed: bus.is_sealed(),
+        components: shared.components.snapshot(),
+        subscriptions: bus.subscriptions_snapshot(),
+        types: bus.type_metrics(),
+        drops: bus.closed_drops(),
+        backpressure: bus.backpressure(),
+        sampling: shared.sampling.snapshot(),
+        rate_limits: bus.rate_limits(),
+        dedup: bus.dedup(),
+    }
+}
+
+// 组件控制通道表：上下文构造时按名称登记（同名复用）
+#[derive(Default)]
+pub(crate) struct ControlRegistry {
+    planes: RwLock<
+        std::collections::HashMap<&'static str, std::sync::Arc<crate::component::ControlPlane>>,
+    >,
+}
+
+impl ControlRegistry {
+    pub(crate) fn register(
+        &self,
+        name: &'static str,
+    ) -> std::sync::Arc<crate::component::ControlPlane> {
+        self.planes
+            .write()
+            .entry(name)
+            .or_insert_with(|| std::sync::Arc::new(crate::component::ControlPlane::new()))
+            .clone()
+    }
+    pub(crate) fn send(&self, name: &str, c: crate::component::Control) -> bool {
+        self.planes.read().get(name).is_some_and(|p| p.send(c))
+    }
+    pub(crate) fn get(&self, name: &str) -> Option<std::sync::Arc<crate::component::ControlPlane>> {
+        self.planes.read().get(name).cloned()
+    }
+}
+
+// 采样 handler 的跳过计数：worker 启动时登记，热路径仅做原子自增
+#[derive(Default)]
+pub(crate) struct SamplingRegistry {
+    entries: RwLock<Vec<SamplingEntry>>,
+}
+
+struct SamplingEntry {
+    component: &'static str,
+    method: &'static str,
+    type_name: &'static str,
+    skipped: std::sync::Arc<std::sync::atomic::AtomicU64>,
+}
+
+impl SamplingRegistry {
+    pub(crate) fn register(
+        &self,
+        component: &'static str,
+        method: &'static str,
+        type_name: &'static str,
+    ) -> std::sync::Arc<std::sync::atomic::AtomicU64> {
+        let skipped = std::sync::Arc::default();
+        self.entries.write().push(SamplingEntry {
+            component,
+            method,
+            type_name,
+            skipped: std::sync::Arc::clone(&skipped),
+        });
+        skipped
+    }
+    fn snapshot(&self) -> Vec<SamplingMetrics> {
+        self.entries
+            .read()
+            .iter()
+            .map(|e| SamplingMetrics {
+                component: e.component,
+                method: e.method,
+                type_name: e.type_name,
+                skipped: e.skipped.load(std::sync::atomic::Ordering::Relaxed),
+            })
+            .collect()
+    }
+}
+
+// 组件状态表：启动时登记，生命周期各节点按名称更新（组件为单例，名称即键）。
+#[derive(Default)]
+pub(crate) struct ComponentRegistry {
+    entries: RwLock<Vec<ComponentInfo>>,
+}
+
+impl ComponentRegistry {
+    pub(crate) fn register(&self, name: &'static str) {
+        self.entries.write().push(ComponentInfo {
+            name,
+            status: ComponentStatus::Starting,
+        });
+    }
+    pub(crate) fn set_status(&self, name: &'static str, status: ComponentStatus) {
+        if let Some(e) = self.entries.write().iter_mut().find(|e| e.name == name) {
+            e.status = status;
+        }
+    }
+    pub(crate) fn is_starting(&self, name: &'static str) -> bool {
+        self.entries
+            .read()
+            .iter()
+            .any(|e| e.name == name && matches!(e.status, ComponentStatus::Starting))
+    }
+    pub(crate) fn snapshot(&self) -> Vec<ComponentInfo> {
+        self.entries.read().clone()
+    }
+    pub(crate) fn startup_progress(&self) -> StartupProgress {
+        let mut p = StartupProgress::default();
+        for e in self.entries.read().iter() {
+            match e.status {
+                ComponentStatus::Starting => p.pending.push(e.name),
+                ComponentStatus::Failed(_) => p.failed.push(e.name),
+                _ => p.arrived.push(e.name),
+            }
+        }
+        p
+    }
+}
+
+// 按类型计数器（`bus-metrics` 特性）：发布路径上仅做原子自增。
+#[cfg(feature = "bus-metrics")]
+pub(crate) type SizeHintFn = Box<dyn Fn(&(dyn std::any::Any + Send + Sync)) -> usize + Send + Sync>;
+
+#[cfg(feature = "bus-metrics")]
+pub(crate) struct TypeStats {
+    type_name: &'static str,
+    published: std::sync::atomic::AtomicU64,
+    bytes: std::sync::atomic::AtomicU64,
+    size_hint: Option<SizeHintFn>,
+}
+
+#[cfg(feature = "bus-metrics")]
+impl TypeStats {
+    pub(crate) const fn new(type_name: &'static str) -> Self {
+        Self {
+            type_name,
+            published: std::sync::atomic::AtomicU64::new(0),
+            bytes: std::sync::atomic::AtomicU64::new(0),
+            size_hint: None,
+        }
+    }
+    pub(crate) fn set_size_hint(&mut self, f: SizeHintFn) {
+        self.size_hint = Some(f);
+    }
+    // shallow：消息本体尺寸（静态路径 size_of::<T>，动态路径 size_of_val）
+    #[inline]
+    pub(crate) fn record(&self, msg: &(dyn std::any::Any + Send + Sync), shallow: usize) {
+        use std::sync::atomic::Ordering;
+        let extra = self.size_hint.as_ref().map_or(0, |f| f(msg));
+        self.published.fetch_add(1, Ordering::Relaxed);
+        self.bytes
+            .fetch_add((shallow + extra) as u64, Ordering::Relaxed);
+    }
+    pub(crate) fn snapshot(&self) -> TypeMetrics {
+        use std::sync::atomic::Ordering;
+        TypeMetrics {
+            type_name: self.type_name,
+            published: self.published.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! 预写日志（journal）组件：在发布时把登记类型同步追加到文件，启动时将既有记录回放到新 App，为有状态流水线提供崩溃恢复。
+//!
+//! - 写入在发布方任务内、投递订阅者之前完成（写前日志）；每条记录带单调递增的序号，进程重启后接续；
+//! - 默认每条记录直接写入操作系统（进程崩溃不丢失），`fsync(true)` 额外落盘（掉电不丢失，延迟显著增加）；
+//! - 启动时读取全部记录（末尾不完整的行视为崩溃残留并截断），越过启动屏障后按序号依次重新发布；回放的消息不会再次写入；
+//! - 仅登记外部输入类型：由输入派生的消息在回放时会被重新计算，一并登记将导致重复；
+//! - 与组件快照（特性 `snapshot`，`Snapshots::journal`）联用时，仅回放各组件快照序号中最小者之后的记录。
+//!
+//! 行格式：`<seq>\t<unix_micros>\t<type>\t<payload>`，payload 为编解码器文本（转义同录制文件）。
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+use crate::bus::{ErasedEvent, PublishTap};
+use crate::codec::{BusMessage, Compatibility, MessageCodec, Schema};
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+use crate::recorder::{escape_field, unescape_field};
+use crate::rt::{SystemTime, UNIX_EPOCH};
+
+type EncodeFn = Box<dyn Fn(&(dyn Any + Send + Sync)) -> Option<String> + Send + Sync>;
+type DecodeFn = Box<dyn Fn(&str) -> std::result::Result<ErasedEvent, String> + Send + Sync>;
+
+tokio::task_local! {
+    // 回放发布期间为真：journal 旁路跳过，避免重复写入
+    static REPLAYING: ();
+}
+
+/// 日志计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone)]
+pub struct JournalHandle {
+    last_seq: Arc<AtomicU64>,
+    appended: Arc<AtomicU64>,
+    replayed: Arc<AtomicU64>,
+    failed: Arc<AtomicU64>,
+    // 已分配序号、尚未完成入队的发布数
+    in_route: Arc<AtomicUsize>,
+    // 快照恢复后的回放起点（不含）；`u64::MAX` 表示无快照参与
+    replay_after: Arc<AtomicU64>,
+}
+
+impl Default for JournalHandle {
+    fn default() -> Self {
+        Self {
+            last_seq: Arc::default(),
+            appended: Arc::default(),
+            replayed: Arc::default(),
+            failed: Arc::default(),
+            in_route: Arc::default(),
+            replay_after: Arc::new(AtomicU64::new(u64::MAX)),
+        }
+    }
+}
+
+impl JournalHandle {
+    /// 最近写入（或启动时读到）的记录序号；空日志为 0。
+    #[must_use]
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq.load(Ordering::Acquire)
+    }
+    /// 本次运行追加的记录数。
+    #[must_use]
+    pub fn appended(&self) -> u64 {
+        self.appended.load(Ordering::Relaxed)
+    }
+    /// 启动时回放的记录数。
+    #[must_use]
+    pub fn replayed(&self) -> u64 {
+        self.replayed.load(Ordering::Relaxed)
+    }
+    /// 写入失败的记录数（消息照常投递）。
+    #[must_use]
+    pub fn failed(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+    // 快照点：返回的序号及之前的记录均已完成入队；仍有发布在途时返回 `None`
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn checkpoint(&self) -> Option<u64> {
+        let seq = self.last_seq.load(Ordering::Acquire);
+        (self.in_route.load(Ordering::Acquire) == 0).then_some(seq)
+    }
+    // 组件恢复到序号 `seq` 的快照（无快照为 0）；回放从所有组件中最小者之后开始
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn restored(&self, seq: u64) {
+        self.replay_after.fetch_min(seq, Ordering::AcqRel);
+    }
+}
+
+struct Entry {
+    name: &'static str,
+    encode: EncodeFn,
+}
+
+pub struct Journal {
+    path: PathBuf,
+    encoders: HashMap<TypeId, Entry>,
+    decoders: HashMap<&'static str, (DecodeFn, Option<Schema>)>,
+    fsync: bool,
+    replay: bool,
+    handle: JournalHandle,
+}
+
+impl Journal {
+    /// 以 `path` 为日志文件（不存在时创建）。
+    #[must_use]
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            encoders: HashMap::new(),
+            decoders: HashMap::new(),
+            fsync: false,
+            replay: true,
+            handle: JournalHandle::default(),
+        }
+    }
+    /// 记录类型 `T`（按类型名匹配，两次运行须使用同一版本的类型定义）。
+    #[must_use]
+    pub fn codec<T: Send + Sync + 'static>(self, codec: impl MessageCodec<T>) -> Self {
+        self.push::<T>(
+            std::any::type_name::<T>(),
+            std::any::type_name::<T>(),
+            None,
+            codec,
+        )
+    }
+    /// 记录 [`BusMessage`] 类型 `T`：以 `NAME@VERSION` 标识，回放时按名称匹配并检查版本（不兼容时启动失败）。
+    #[must_use]
+    pub fn message<T: BusMessage>(self, codec: impl MessageCodec<T>) -> Self {
+        let schema = Schema::of::<T>();
+        // 每次登记泄漏一个短字符串，以满足 `&'static str` 类型名
+        let wire: &'static str = Box::leak(schema.wire_name().into_boxed_str());
+        self.push::<T>(wire, T::NAME, Some(schema), codec)
+    }
+    fn push<T: Send + Sync + 'static>(
+        mut self,
+        wire: &'static str,
+        key: &'static str,
+        schema: Option<Schema>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        let codec = Arc::new(codec);
+        let enc = codec.clone();
+        self.encoders.insert(
+            TypeId::of::<T>(),
+            Entry {
+                name: wire,
+                encode: Box::new(move |msg| msg.downcast_ref::<T>().map(|v| enc.encode(v))),
+            },
+        );
+        self.decoders.insert(
+            key,
+            (
+                Box::new(move |text| codec.decode(text).map(ErasedEvent::new)),
+                schema,
+            ),
+        );
+        self
+    }
+    /// 每条记录写入后 `fsync`（默认关闭）。
+    #[must_use]
+    pub const fn fsync(mut self, enabled: bool) -> Self {
+        self.fsync = enabled;
+        self
+    }
+    /// 启动时是否回放既有记录（默认回放）；关闭时仅接续序号追加。
+    #[must_use]
+    pub const fn replay(mut self, enabled: bool) -> Self {
+        self.replay = enabled;
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> JournalHandle {
+        self.handle.clone()
+    }
+
+    // 读取既有记录并截断末尾残缺行；返回（追加用文件, 待回放消息）
+    fn load(&self) -> Result<(File, Vec<(u64, ErasedEvent)>)> {
+        let io_err = |e: std::io::Error| {
+            MicrobusError::Dynamic(format!("journal: {}: {e}", self.path.display()))
+        };
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&self.path)
+            .map_err(io_err)?;
+        let text = std::fs::read(&self.path).map_err(io_err)?;
+        let complete = text.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+        if complete < text.len() {
+            tracing::warn!(
+                path = %self.path.display(),
+                bytes = text.len() - complete,
+                "journal: truncating incomplete trailing record"
+            );
+            file.set_len(complete as u64).map_err(io_err)?;
+            file.seek(SeekFrom::End(0)).map_err(io_err)?;
+        }
+        let text = std::str::from_utf8(&text[..complete]).map_err(|e| {
+            MicrobusError::Dynamic(format!("journal: {}: {e}", self.path.display()))
+        })?;
+        let mut events = Vec::new();
+        let mut last_seq = 0;
+        for (no, line) in text.lines().enumerate() {
+            let bad = |what: &str| {
+                MicrobusError::Dynamic(format!(
+                    "journal: {what} at {}:{}",
+                    self.path.display(),
+                    no + 1
+                ))
+            };
+            let mut parts = line.splitn(4, '\t');
+            let (Some(seq), Some(_micros), Some(type_name), Some(payload)) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                return Err(bad("malformed record"));
+            };
+            let seq: u64 = seq.parse().map_err(|_| bad("malformed sequence"))?;
+            last_seq = seq;
+            if !self.replay {
+                continue;
+            }
+            let (name, version) = crate::codec::split_wire_name(type_name);
+            let Some((decode, schema)) = self.decoders.get(name) else {
+                tracing::warn!(
+                    type_name,
+                    seq,
+                    "journal: type not registered; record skipped"
+                );
+                continue;
+            };
+            if let (Some(schema), Some(version)) = (schema, version) {
+                if schema.check(version) == Compatibility::Incompatible {
+                    return Err(bad(&format!(
+                        "{name} version {version} incompatible with local version {}",
+                        schema.version
+                    )));
+                }
+            }
+            let ev = decode(&unescape_field(payload))
+                .map_err(|e| bad(&format!("cannot decode {type_name}: {e}")))?;
+            events.push((seq, ev));
+        }
+        self.handle.last_seq.store(last_seq, Ordering::Release);
+        Ok((file, events))
+    }
+}
+
+struct JournalTap {
+    encoders: HashMap<TypeId, Entry>,
+    file: Mutex<File>,
+    fsync: bool,
+    handle: JournalHandle,
+}
+
+impl PublishTap for JournalTap {
+    fn on_publish(
+        &self,
+        type_id: TypeId,
+        _type_name: &'static str,
+        msg: &Arc<dyn Any + Send + Sync>,
+    ) {
+        let Some(entry) = self.encoders.get(&type_id) else {
+            return;
+        };
+        if REPLAYING.try_with(|()| ()).is_ok() {
+            return;
+        }
+        let Some(payload) = (entry.encode)(&**msg) else {
+            return;
+        };
+        // 先计入在途再分配序号：快照点读取序号后确认在途为 0 即可
+        self.handle.in_route.fetch_add(1, Ordering::AcqRel);
+        let micros = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_micros());
+        // 序号在文件锁内分配，保证文件顺序与序号一致
+        let mut file = self.file.lock();
+        let seq = self.handle.last_seq.load(Ordering::Acquire) + 1;
+        let line = format!(
+            "{seq}\t{micros}\t{}\t{}\n",
+            entry.name,
+            escape_field(&payload)
+        );
+        let written = file.write_all(line.as_bytes()).and_then(|()| {
+            if self.fsync {
+                file.sync_data()
+            } else {
+                Ok(())
+            }
+        });
+        match written {
+            Ok(()) => {
+                self.handle.last_seq.store(seq, Ordering::Release);
+                self.handle.appended.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.handle.failed.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(error = %e, type_name = entry.name, "journal write failed");
+            }
+        }
+    }
+
+    fn on_routed(&self, type_id: TypeId) {
+        if !self.encoders.contains_key(&type_id) || REPLAYING.try_with(|()| ()).is_ok() {
+            return;
+        }
+        // tap 可能在发布途中登记：只抵消已计入的部分
+        let _ = self
+            .handle
+            .in_route
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1));
+    }
+}
+
+#[async_trait]
+impl Component for Journal {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        // 读取在启动屏障之前完成：文件错误按启动失败处理
+        let (file, events) = self.load().inspect_err(|_| {
+            crate::component::__startup_mark_failed(&ctx);
+        })?;
+        let Self {
+            path,
+            encoders,
+            fsync,
+            handle,
+            ..
+        } = *self;
+        ctx.bus().add_tap(Arc::new(JournalTap {
+            encoders,
+            file: Mutex::new(file),
+            fsync,
+            handle: handle.clone(),
+        }));
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        // 快照恢复均在启动屏障之前完成
+        let after = match handle.replay_after.load(Ordering::Acquire) {
+            u64::MAX => 0,
+            seq => seq,
+        };
+        let events: Vec<_> = events.into_iter().filter(|(seq, _)| *seq > after).collect();
+        let total = events.len();
+        REPLAYING
+            .scope((), async {
+                for (_, ev) in events {
+                    handle.replayed.fetch_add(1, Ordering::Relaxed);
+                    crate::component::__publish_erased(&ctx, ev).await;
+                }
+            })
+            .await;
+        if total > 0 {
+            tracing::info!(path = %path.display(), replayed = total, "journal replayed");
+        }
+        crate::component::__recv_stop(&ctx).await;
+        Ok(())
+    }
+}
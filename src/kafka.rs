@@ -0,0 +1,394 @@
+//! Kafka 组件（特性 `bridge-kafka`，基于 rdkafka，随附编译 librdkafka）：把 topic 中的记录解码后发布到总线，或把选定类型写入 topic。
+//!
+//! - [`KafkaSource`]：以消费组订阅登记的 topic，按 topic 解码为对应类型在本地发布；
+//!   offset 在消息“处理完毕”后才提交：以消息 `Arc` 的引用计数判定，全部持有方释放消息即视为已处理
+//!   （含 `latest` 覆盖与弱订阅丢弃；handler 之外继续持有消息会阻止提交），
+//!   同一分区内按 offset 顺序推进，前面的消息未完成时后面的不提交；提交为异步、按 `SWEEP_INTERVAL` 批量进行。
+//!   停机开始后不再推进，未完成或提交未送达的记录由消费组重新投递（至少一次，handler 应幂等）。
+//! - [`KafkaSink`]：订阅登记类型，编码后交给生产者；批量由 librdkafka 按 `linger.ms` / `batch.num.messages` 聚合，
+//!   本地缓冲满时等待（背压），停机时在 `FLUSH_TIMEOUT` 内刷出已缓冲的记录。
+//!
+//! 编解码器复用 [`MessageCodec`]，每个类型对应一个 topic；其余客户端参数经 `set` 透传（如 `security.protocol`）。
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::Message as _;
+use rdkafka::producer::{DeliveryFuture, FutureProducer, FutureRecord};
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::codec::MessageCodec;
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+
+/// 底层客户端（额外配置类型、测试用 `mocking::MockCluster` 等）。
+pub use rdkafka;
+
+type Message = Arc<dyn Any + Send + Sync>;
+type DecodeFn = Box<dyn Fn(&[u8]) -> std::result::Result<Message, String> + Send + Sync>;
+type SpawnForward = Box<
+    dyn FnOnce(&ComponentContext, FutureProducer, mpsc::Sender<DeliveryFuture>) -> JoinHandle<()>
+        + Send
+        + Sync,
+>;
+
+// 在途（已交给生产者、尚未确认送达）记录数上限
+const OUT_QUEUE: usize = 1024;
+const SWEEP_INTERVAL: Duration = Duration::from_millis(50);
+const QUEUE_FULL_BACKOFF: Duration = Duration::from_millis(10);
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Kafka 组件计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct KafkaHandle {
+    frames: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+    committed: Arc<AtomicU64>,
+}
+
+impl KafkaHandle {
+    /// Sink：broker 已确认的记录数；Source：已在本地发布的记录数。
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+    /// Sink：入队或送达失败的记录数；Source：topic 未登记或解码失败的记录数（随后续记录一并提交，不重投）。
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+    /// Source：处理完毕并已提交 offset 的记录数（含被丢弃的记录）。
+    #[must_use]
+    pub fn committed(&self) -> u64 {
+        self.committed.load(Ordering::Relaxed)
+    }
+}
+
+fn client_error(ctx: &ComponentContext, config: &ClientConfig, e: KafkaError) -> MicrobusError {
+    crate::component::__startup_mark_failed(ctx);
+    MicrobusError::Bridge {
+        endpoint: config
+            .get("bootstrap.servers")
+            .unwrap_or_default()
+            .to_owned(),
+        source: Box::new(e),
+    }
+}
+
+// ---- Source ----
+
+/// Kafka 消费组件：订阅登记的 topic，解码后在本地发布，处理完毕后提交 offset。
+pub struct KafkaSource {
+    config: ClientConfig,
+    topics: HashMap<String, DecodeFn>,
+    handle: KafkaHandle,
+}
+
+impl KafkaSource {
+    /// 连接 `brokers`（`host:port[,host:port]`），以消费组 `group` 消费；无已提交 offset 时从最早处读起。
+    #[must_use]
+    pub fn new(brokers: impl Into<String>, group: impl Into<String>) -> Self {
+        let mut config = ClientConfig::new();
+        config
+            .set("bootstrap.servers", brokers)
+            .set("group.id", group)
+            .set("enable.auto.commit", "false")
+            .set("enable.auto.offset.store", "false")
+            .set("auto.offset.reset", "earliest");
+        Self {
+            config,
+            topics: HashMap::new(),
+            handle: KafkaHandle::default(),
+        }
+    }
+    /// 透传客户端参数（覆盖默认值；`enable.auto.commit` 须保持关闭，否则提交不再等待处理完毕）。
+    #[must_use]
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.config.set(key, value);
+        self
+    }
+    /// 把 `topic` 中的记录按 `codec` 解码为 `T` 发布。
+    #[must_use]
+    pub fn receive<T: Send + Sync + 'static>(
+        mut self,
+        topic: impl Into<String>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.topics.insert(
+            topic.into(),
+            Box::new(move |bytes| codec.decode_bytes(bytes).map(|v| Arc::new(v) as Message)),
+        );
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> KafkaHandle {
+        self.handle.clone()
+    }
+}
+
+// 一个分区内按 offset 顺序排队的已发布记录；`None` 为被丢弃、视为已完成的记录
+type PartitionQueue = VecDeque<(i64, Option<Message>)>;
+
+#[derive(Default)]
+struct Inflight {
+    partitions: HashMap<(String, i32), PartitionQueue>,
+}
+
+impl Inflight {
+    // 弹出各分区队首连续完成的记录，返回待提交的 offset（下一条待读位置）与条数。
+    // “完成”即本组件持有的是最后一个引用：全部持有方均已释放消息。`latest` 订阅覆盖旧值、弱订阅队列满丢弃
+    // 同样释放引用，按已处理提交；handler 之外长期持有 `Arc`（存入状态、转交其他任务）则该记录及同分区其后的记录均不提交。
+    fn sweep(&mut self) -> (TopicPartitionList, u64) {
+        let mut tpl = TopicPartitionList::new();
+        let mut n = 0;
+        for ((topic, partition), queue) in &mut self.partitions {
+            let mut next = None;
+            while let Some((offset, msg)) = queue.front() {
+                if msg.as_ref().is_some_and(|m| Arc::strong_count(m) > 1) {
+                    break;
+                }
+                next = Some(offset + 1);
+                queue.pop_front();
+                n += 1;
+            }
+            if let Some(next) = next {
+                let _ = tpl.add_partition_offset(topic, *partition, Offset::Offset(next));
+            }
+        }
+        (tpl, n)
+    }
+}
+
+#[async_trait]
+impl Component for KafkaSource {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let consumer: StreamConsumer = self
+            .config
+            .create()
+            .map_err(|e| client_error(&ctx, &self.config, e))?;
+        let topics: Vec<&str> = self.topics.keys().map(String::as_str).collect();
+        consumer
+            .subscribe(&topics)
+            .map_err(|e| client_error(&ctx, &self.config, e))?;
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        let mut inflight = Inflight::default();
+        let mut sweep = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                biased;
+                () = crate::component::__recv_stop(&ctx) => break,
+                _ = sweep.tick() => {
+                    // 停机后订阅端丢弃消息同样释放引用，不计为完成：先检查停机再弹出，未提交的记录留待重新投递
+                    if crate::component::__stop_requested(&ctx) {
+                        continue;
+                    }
+                    let (tpl, n) = inflight.sweep();
+                    if n == 0 {
+                        continue;
+                    }
+                    match consumer.commit(&tpl, CommitMode::Async) {
+                        Ok(()) => {
+                            self.handle.committed.fetch_add(n, Ordering::Relaxed);
+                        }
+                        Err(e) => tracing::warn!(error = %e, "kafka source commit failed"),
+                    }
+                }
+                msg = consumer.recv() => match msg {
+                    Ok(m) => {
+                        let key = (m.topic().to_owned(), m.partition());
+                        let offset = m.offset();
+                        let decoded = match self.topics.get(m.topic()) {
+                            Some(decode) => decode(m.payload().unwrap_or_default()),
+                            None => Err("topic not registered".to_owned()),
+                        };
+                        drop(m);
+                        let queue = inflight.partitions.entry(key).or_default();
+                        match decoded {
+                            Ok(msg) => {
+                                queue.push_back((offset, Some(msg.clone())));
+                                self.handle.frames.fetch_add(1, Ordering::Relaxed);
+                                crate::component::__publish_any_arc(&ctx, msg).await;
+                            }
+                            Err(e) => {
+                                tracing::warn!(error = %e, "kafka source record rejected");
+                                queue.push_back((offset, None));
+                                self.handle.dropped.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                    Err(e) => tracing::warn!(error = %e, "kafka source consume failed"),
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+// ---- Sink ----
+
+/// Kafka 生产组件：订阅登记类型，编码后写入对应 topic。
+pub struct KafkaSink {
+    config: ClientConfig,
+    forwards: Vec<SpawnForward>,
+    handle: KafkaHandle,
+}
+
+impl KafkaSink {
+    /// 连接 `brokers`；默认 `linger.ms = 5`（最多等待 5ms 聚合一批）。
+    #[must_use]
+    pub fn new(brokers: impl Into<String>) -> Self {
+        let mut config = ClientConfig::new();
+        config
+            .set("bootstrap.servers", brokers)
+            .set("linger.ms", "5");
+        Self {
+            config,
+            forwards: Vec::new(),
+            handle: KafkaHandle::default(),
+        }
+    }
+    /// 透传客户端参数（如 `acks`、`compression.type`）。
+    #[must_use]
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.config.set(key, value);
+        self
+    }
+    /// 批量聚合等待时长（`linger.ms`）。
+    #[must_use]
+    pub fn linger(self, linger: Duration) -> Self {
+        self.set("linger.ms", linger.as_millis().to_string())
+    }
+    /// 单批最大记录数（`batch.num.messages`）。
+    #[must_use]
+    pub fn batch_size(self, n: usize) -> Self {
+        self.set("batch.num.messages", n.to_string())
+    }
+    /// 把 `T` 写入 `topic`（无键，由分区器分配分区）。
+    #[must_use]
+    pub fn forward<T: Send + Sync + 'static>(
+        self,
+        topic: impl Into<String>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.push(topic.into(), codec, None::<fn(&T) -> String>)
+    }
+    /// 以 `key(&msg)` 为记录键写入 `topic`：同键记录落在同一分区，保持相对顺序。
+    #[must_use]
+    pub fn forward_keyed<T: Send + Sync + 'static>(
+        self,
+        topic: impl Into<String>,
+        codec: impl MessageCodec<T>,
+        key: impl Fn(&T) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.push(topic.into(), codec, Some(key))
+    }
+    fn push<T: Send + Sync + 'static, K: Fn(&T) -> String + Send + Sync + 'static>(
+        mut self,
+        topic: String,
+        codec: impl MessageCodec<T>,
+        key: Option<K>,
+    ) -> Self {
+        let handle = self.handle.clone();
+        self.forwards.push(Box::new(move |ctx, producer, tx| {
+            let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+            let ctx = ctx.__fork();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => break,
+                        msg = sub.recv() => {
+                            let Some(msg) = msg else { break };
+                            let payload = codec.encode_bytes(&msg);
+                            let k = key.as_ref().map(|f| f(&msg));
+                            let mut record = FutureRecord::to(&topic).payload(&payload);
+                            if let Some(k) = &k {
+                                record = record.key(k);
+                            }
+                            let delivery = loop {
+                                match producer.send_result(record) {
+                                    Ok(delivery) => break Some(delivery),
+                                    Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), r)) => {
+                                        record = r;
+                                        tokio::time::sleep(QUEUE_FULL_BACKOFF).await;
+                                    }
+                                    Err((e, _)) => {
+                                        tracing::warn!(topic = %topic, error = %e, "kafka sink enqueue failed");
+                                        handle.dropped.fetch_add(1, Ordering::Relaxed);
+                                        break None;
+                                    }
+                                }
+                            };
+                            if let Some(delivery) = delivery {
+                                if tx.send(delivery).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        }));
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> KafkaHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for KafkaSink {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            config,
+            forwards,
+            handle,
+        } = *self;
+        let producer: FutureProducer = config
+            .create()
+            .map_err(|e| client_error(&ctx, &config, e))?;
+        let (tx, mut rx) = mpsc::channel::<DeliveryFuture>(OUT_QUEUE);
+        let workers: Vec<_> = forwards
+            .into_iter()
+            .map(|f| f(&ctx, producer.clone(), tx.clone()))
+            .collect();
+        drop(tx);
+        // 按入队顺序等待送达结果；转发任务全部结束后随通道关闭退出
+        let deliveries = {
+            let handle = handle.clone();
+            tokio::spawn(async move {
+                while let Some(delivery) = rx.recv().await {
+                    match delivery.await {
+                        Ok(Ok(_)) => handle.frames.fetch_add(1, Ordering::Relaxed),
+                        Ok(Err((e, _))) => {
+                            tracing::warn!(error = %e, "kafka sink delivery failed");
+                            handle.dropped.fetch_add(1, Ordering::Relaxed)
+                        }
+                        Err(_) => handle.dropped.fetch_add(1, Ordering::Relaxed),
+                    };
+                }
+            })
+        };
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        crate::component::__recv_stop(&ctx).await;
+        for w in workers {
+            w.abort();
+        }
+        let flushed = tokio::task::spawn_blocking(move || {
+            rdkafka::producer::Producer::flush(&producer, FLUSH_TIMEOUT)
+        })
+        .await;
+        if let Ok(Err(e)) = flushed {
+            tracing::warn!(error = %e, "kafka sink flush incomplete");
+        }
+        let _ = deliveries.await;
+        Ok(())
+    }
+}
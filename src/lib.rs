@@ -1,17 +1,86 @@
+pub mod adapters;
+#[cfg(feature = "admin-http")]
+pub mod admin;
 pub mod app;
+pub mod app_bridge;
+#[cfg(any(feature = "bridge-tcp", feature = "bridge-ipc"))]
+pub mod bridge;
 pub mod bus;
+pub mod bus_core;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod codec;
 pub mod component;
 pub mod config;
+#[cfg(feature = "crash-bundle")]
+pub mod crash;
+mod dedup;
+#[cfg(feature = "durable")]
+pub mod durable;
 pub mod error;
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod introspect;
+pub mod journal;
+#[cfg(feature = "bridge-kafka")]
+pub mod kafka;
+pub mod merge;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+mod monitor;
+pub mod pipeline;
+mod postmortem;
+#[cfg(feature = "process-stats")]
+pub mod process;
+pub mod profile;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod query;
+mod rate_limit;
+pub mod recorder;
+#[cfg(feature = "bridge-redis")]
+pub mod redis;
+pub mod replay;
+pub mod rt;
+#[cfg(all(feature = "bridge-shm", unix))]
+pub mod shm;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+#[cfg(feature = "sources")]
+pub mod sources;
+pub mod state;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "wasm-host")]
+pub mod wasm;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+pub mod window;
+pub mod wiring;
+#[cfg(feature = "bridge-zmq")]
+pub mod zmq;
 
 // 允许在本 crate 内通过 `mmg_microbus::...` 自引用（供 proc-macro 展开使用）
 extern crate self as mmg_microbus;
+#[doc(hidden)]
+pub use inventory as __inventory;
+#[doc(hidden)]
+pub use tracing as __tracing;
 
 pub mod prelude {
     pub use crate::app::App;
     // 参数注入：仅通过函数参数访问上下文、消息与配置
+    pub use crate::bus::Envelope;
     pub use crate::component::ComponentContext;
     pub use crate::error::{MicrobusError, Result};
+    pub use crate::window::Window;
 }
 
 pub use microbus_macros::*;
@@ -0,0 +1,172 @@
+//! 多类型有序合并：把若干订阅（如 `Trade` 与 `Quote`）合并为单一流，按各自提取的时间戳排序交付。
+//!
+//! 每个输入只暂存一条队首；全部仍开放的输入都有队首时交付时间戳最小者（相同时按登记顺序）。
+//! 某个输入暂时无消息时，已暂存的队首最多等待 `max_wait` 后照常交付，空闲输入不会拖住整条流；
+//! 此后该输入到达的更早时间戳即为乱序，照常交付并计入 [`OrderedMerge::out_of_order`]。
+//! 单个输入内部保持到达顺序：输出有序的前提是各输入自身时间戳单调不减。
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::bus::Subscription;
+use crate::rt::Instant;
+
+/// 有序合并订阅：`E` 为合并后的事件类型（通常是各输入类型的枚举），`K` 为排序键（时间戳）。
+///
+/// ```ignore
+/// let mut md = OrderedMerge::new(Duration::from_millis(20))
+///     .input(bus.try_subscribe::<Trade>()?, |t| t.ts, Market::Trade)
+///     .input(bus.try_subscribe::<Quote>()?, |q| q.ts, Market::Quote);
+/// while let Some(ev) = md.recv().await { /* 按 ts 交付 */ }
+/// ```
+pub struct OrderedMerge<E, K> {
+    inputs: Vec<Input<E, K>>,
+    max_wait: Duration,
+    last: Option<K>,
+    out_of_order: u64,
+}
+
+struct Input<E, K> {
+    source: Box<dyn Source<E, K>>,
+    // 暂存的队首：(排序键, 事件, 到达时刻)
+    head: Option<(K, E, Instant)>,
+    closed: bool,
+}
+
+// 类型擦除的输入：取出一条并提取排序键、转换为合并事件
+trait Source<E, K>: Send {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<(K, E)>>;
+}
+
+struct Typed<T, F, M> {
+    sub: Subscription<T>,
+    key: F,
+    map: M,
+}
+
+impl<T, E, K, F, M> Source<E, K> for Typed<T, F, M>
+where
+    T: Send + Sync + 'static,
+    F: Fn(&T) -> K + Send,
+    M: Fn(Arc<T>) -> E + Send,
+{
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<(K, E)>> {
+        self.sub
+            .poll_recv(cx)
+            .map(|m| m.map(|m| ((self.key)(&m), (self.map)(m))))
+    }
+}
+
+impl<E: Send, K: Ord + Copy + Send> OrderedMerge<E, K> {
+    /// `max_wait`：队首等待空闲输入的上限（即允许的跨输入到达延迟）；`Duration::ZERO` 时只在各输入均有积压时排序。
+    #[must_use]
+    pub const fn new(max_wait: Duration) -> Self {
+        Self {
+            inputs: Vec::new(),
+            max_wait,
+            last: None,
+            out_of_order: 0,
+        }
+    }
+
+    /// 登记一个输入：`key` 提取排序键，`map` 把消息转换为合并事件；登记顺序即同键时的交付顺序。
+    #[must_use]
+    pub fn input<T, F, M>(mut self, sub: Subscription<T>, key: F, map: M) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&T) -> K + Send + 'static,
+        M: Fn(Arc<T>) -> E + Send + 'static,
+        E: 'static,
+        K: 'static,
+    {
+        self.inputs.push(Input {
+            source: Box::new(Typed { sub, key, map }),
+            head: None,
+            closed: false,
+        });
+        self
+    }
+
+    /// 取出下一条事件；全部输入的发送端关闭且暂存已交付完毕后返回 `None`。
+    pub async fn recv(&mut self) -> Option<E> {
+        loop {
+            // 先取走已就绪的消息，再判定能否交付
+            std::future::poll_fn(|cx| {
+                self.fill(cx);
+                Poll::Ready(())
+            })
+            .await;
+            let waiting = self.inputs.iter().any(|i| i.head.is_none() && !i.closed);
+            let oldest = self
+                .inputs
+                .iter()
+                .filter_map(|i| i.head.as_ref())
+                .map(|h| h.2)
+                .min();
+            match (waiting, oldest) {
+                (false, None) => return None,
+                (false, Some(_)) => return self.pop(),
+                (true, Some(t)) if t + self.max_wait <= Instant::now() => return self.pop(),
+                (true, Some(t)) => {
+                    let deadline = t + self.max_wait;
+                    crate::rt::select! {
+                        () = self.progress() => {}
+                        () = crate::rt::sleep_until(deadline) => {}
+                    }
+                }
+                (true, None) => self.progress().await,
+            }
+        }
+    }
+
+    /// 因空闲输入超过 `max_wait` 而晚到、时间戳早于已交付事件的条数。
+    #[must_use]
+    pub const fn out_of_order(&self) -> u64 {
+        self.out_of_order
+    }
+
+    // 轮询缺少队首的输入；返回本次是否有进展（取到消息或输入关闭）
+    fn fill(&mut self, cx: &mut Context<'_>) -> bool {
+        let mut progressed = false;
+        for input in self
+            .inputs
+            .iter_mut()
+            .filter(|i| i.head.is_none() && !i.closed)
+        {
+            match input.source.poll_next(cx) {
+                Poll::Ready(Some((k, e))) => input.head = Some((k, e, Instant::now())),
+                Poll::Ready(None) => input.closed = true,
+                Poll::Pending => continue,
+            }
+            progressed = true;
+        }
+        progressed
+    }
+
+    async fn progress(&mut self) {
+        std::future::poll_fn(|cx| {
+            if self.fill(cx) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+
+    // 交付时间戳最小的队首（同键取先登记者）
+    fn pop(&mut self) -> Option<E> {
+        let input = self
+            .inputs
+            .iter_mut()
+            .filter(|i| i.head.is_some())
+            .min_by_key(|i| i.head.as_ref().map(|h| h.0))?;
+        let (k, e, _) = input.head.take()?;
+        if self.last.is_some_and(|last| k < last) {
+            self.out_of_order += 1;
+        } else {
+            self.last = Some(k);
+        }
+        Some(e)
+    }
+}
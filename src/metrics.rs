@@ -0,0 +1,78 @@
+//! `metrics` 门面埋点（特性 `metrics`）：框架只经 [`metrics`](::metrics) crate 的宏上报，
+//! 导出方式（Prometheus / statsd / OTLP 等）由应用安装的 recorder 决定；未安装时为空操作。
+//!
+//! recorder 须在 `App::start()` 之前安装。各指标名与标签如下：
+//! - [`PUBLISHED`]（counter，`message_type`）：发布次数（每次发布计 1，与订阅者数无关）；
+//! - [`DROPPED`]（counter，`message_type` / `reason`）：`closed` 为订阅端已关闭，`weak` 为弱订阅队列满，`rate_limited` 为超出发布限速（`RateExcess::Drop`），`duplicate` 为去重窗口内的重复发布，`full` 为框架生命周期事件遇订阅队列已满（不等待发布）；
+//! - [`BACKPRESSURE_WAIT`]（histogram，秒，`message_type`）：发布方因队列满在 `send().await` 上的等待时长；
+//! - [`HANDLER_DURATION`]（histogram，秒，`component` / `method`）：单次 `#[handle]` 调用耗时；
+//! - [`HANDLER_ERRORS`]（counter，`component` / `method`）：handler 返回 `Err` 或 panic 的次数；
+//! - [`HANDLER_PANICS`]（counter，`component` / `method`）：其中 panic 的次数。
+//!
+//! 高频的发布计数与 handler 耗时按标签缓存句柄（首次上报时向 recorder 注册），热路径上不再构造标签。
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use ::metrics::{Counter, Histogram};
+use parking_lot::RwLock;
+
+pub const PUBLISHED: &str = "microbus_messages_published_total";
+pub const DROPPED: &str = "microbus_messages_dropped_total";
+pub const BACKPRESSURE_WAIT: &str = "microbus_backpressure_wait_seconds";
+pub const HANDLER_DURATION: &str = "microbus_handler_duration_seconds";
+pub const HANDLER_ERRORS: &str = "microbus_handler_errors_total";
+pub const HANDLER_PANICS: &str = "microbus_handler_panics_total";
+
+static PUBLISHED_HANDLES: LazyLock<RwLock<HashMap<&'static str, Counter>>> =
+    LazyLock::new(RwLock::default);
+type HandlerKey = (&'static str, &'static str);
+static DURATION_HANDLES: LazyLock<RwLock<HashMap<HandlerKey, Histogram>>> =
+    LazyLock::new(RwLock::default);
+
+// 读锁命中即返回；未命中时注册并写入（并发注册同一键时以先写入者为准）
+fn cached<K, H: Clone>(map: &RwLock<HashMap<K, H>>, key: K, register: impl FnOnce() -> H) -> H
+where
+    K: std::hash::Hash + Eq + Copy,
+{
+    if let Some(h) = map.read().get(&key) {
+        return h.clone();
+    }
+    map.write().entry(key).or_insert_with(register).clone()
+}
+
+#[inline]
+pub(crate) fn published(message_type: &'static str) {
+    cached(
+        &PUBLISHED_HANDLES,
+        message_type,
+        || ::metrics::counter!(PUBLISHED, "message_type" => message_type),
+    )
+    .increment(1);
+}
+
+pub(crate) fn dropped(message_type: &'static str, reason: &'static str, n: u64) {
+    ::metrics::counter!(DROPPED, "message_type" => message_type, "reason" => reason).increment(n);
+}
+
+pub(crate) fn backpressure_wait(message_type: &'static str, waited: Duration) {
+    ::metrics::histogram!(BACKPRESSURE_WAIT, "message_type" => message_type).record(waited);
+}
+
+#[inline]
+pub(crate) fn handler_duration(component: &'static str, method: &'static str, elapsed: Duration) {
+    cached(
+        &DURATION_HANDLES,
+        (component, method),
+        || ::metrics::histogram!(HANDLER_DURATION, "component" => component, "method" => method),
+    )
+    .record(elapsed);
+}
+
+pub(crate) fn handler_error(component: &'static str, method: &'static str) {
+    ::metrics::counter!(HANDLER_ERRORS, "component" => component, "method" => method).increment(1);
+}
+
+pub(crate) fn handler_panic(component: &'static str, method: &'static str) {
+    ::metrics::counter!(HANDLER_PANICS, "component" => component, "method" => method).increment(1);
+}
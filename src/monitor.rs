@@ -0,0 +1,209 @@
+// 运行期监控任务：封印后由 App 启动，随停止信号退出。
+// 虚拟时间（tokio::time::pause）下同样推进
+use crate::rt::Instant;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::bus::BusHandle;
+use crate::component::StopFlag;
+use crate::config::LagMonitorConfig;
+use crate::events::SubscriberLagging;
+
+// 单个订阅的滞后状态：首次越过阈值的时刻 + 本轮是否已上报
+#[derive(Default, Clone, Copy)]
+struct LagState {
+    above_since: Option<Instant>,
+    reported: bool,
+}
+
+pub(crate) async fn run_lag_monitor(bus: BusHandle, cfg: LagMonitorConfig, stop: Arc<StopFlag>) {
+    // 弱订阅满时丢弃而非积压，不做滞后监控
+    let probes: Vec<_> = bus
+        .subscriber_probes()
+        .into_iter()
+        .filter(|p| !p.weak)
+        .collect();
+    let mut states = vec![LagState::default(); probes.len()];
+    // 首轮立即检查，此后每 check_interval 一次（处理耗时不补发）
+    let mut wait = std::time::Duration::ZERO;
+    loop {
+        tokio::select! {
+            () = stop.wait() => break,
+            () = crate::rt::sleep(wait) => {}
+        }
+        wait = cfg.check_interval;
+        let now = Instant::now();
+        for (probe, state) in probes.iter().zip(states.iter_mut()) {
+            let Some((depth, capacity)) = probe.depth() else {
+                *state = LagState::default();
+                continue;
+            };
+            if depth * 100 < capacity * usize::from(cfg.threshold_percent) {
+                *state = LagState::default();
+                continue;
+            }
+            let since = *state.above_since.get_or_insert(now);
+            if !state.reported && now.duration_since(since) >= cfg.sustain {
+                state.reported = true;
+                tracing::warn!(
+                    component = probe.component,
+                    type_name = probe.type_name,
+                    depth,
+                    capacity,
+                    "subscriber lagging"
+                );
+                bus.publish_type(SubscriberLagging {
+                    component: probe.component,
+                    type_name: probe.type_name,
+                    depth,
+                    capacity,
+                })
+                .await;
+            }
+        }
+    }
+}
+
+// 自然完成检测（`AppConfig::stop_on_completion`）的运行期计数；未开启时各计数保持为 0，热路径只多一次布尔判断
+#[derive(Default)]
+pub(crate) struct Completion {
+    enabled: bool,
+    registered: AtomicUsize, // 曾登记的数据源
+    running: AtomicUsize,    // 尚未结束的数据源
+    in_flight: AtomicUsize,  // 进行中的 handler 调用
+    held: AtomicUsize,       // debounce / throttle / window worker 暂存、尚未交给 handler 的消息
+    handled: AtomicU64,      // 已完成的 handler 调用（两次检查之间有变化即未静止）
+    completed: AtomicBool,
+    // `#[handle(local)]` 的组件私有总线：不在全局订阅表中，队列单独检查
+    local_buses: parking_lot::Mutex<Vec<BusHandle>>,
+    // 最后一个数据源结束时通知：数据源未结束期间检测任务不轮询（虚拟时钟下长时间跨度不产生大量空转）
+    sources_done: tokio::sync::Notify,
+}
+
+impl Completion {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+    pub(crate) fn source_started(&self) {
+        self.registered.fetch_add(1, Ordering::AcqRel);
+        self.running.fetch_add(1, Ordering::AcqRel);
+    }
+    pub(crate) fn source_finished(&self) {
+        if self.running.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.sources_done.notify_one();
+        }
+    }
+    #[inline]
+    pub(crate) fn handler_begin(&self) {
+        if self.enabled {
+            self.in_flight.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+    #[inline]
+    pub(crate) fn handler_end(&self) {
+        if self.enabled {
+            self.handled.fetch_add(1, Ordering::AcqRel);
+            self.in_flight.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+    pub(crate) fn register_local_bus(&self, bus: &BusHandle) {
+        if self.enabled {
+            self.local_buses.lock().push(bus.clone());
+        }
+    }
+    pub(crate) fn has_sources(&self) -> bool {
+        self.registered.load(Ordering::Acquire) > 0
+    }
+    pub(crate) fn is_completed(&self) -> bool {
+        self.completed.load(Ordering::Acquire)
+    }
+    // 数据源全部结束、无进行中的 handler、无暂存消息、非弱订阅队列（含组件私有总线）全部排空
+    fn quiet(&self, bus: &BusHandle) -> bool {
+        self.running.load(Ordering::Acquire) == 0
+            && self.in_flight.load(Ordering::Acquire) == 0
+            && self.held.load(Ordering::Acquire) == 0
+            && drained(bus)
+            && self.local_buses.lock().iter().all(drained)
+    }
+}
+
+fn drained(bus: &BusHandle) -> bool {
+    bus.subscriber_probes()
+        .iter()
+        .filter(|p| !p.weak)
+        .all(|p| p.depth().is_none_or(|(depth, _)| depth == 0))
+}
+
+// worker 暂存消息数的登记句柄：`set` 同步当前暂存数，释放时清零（worker 退出后不再阻止完成判定）
+pub(crate) struct Held {
+    completion: Arc<Completion>,
+    count: usize,
+}
+
+impl Held {
+    pub(crate) const fn new(completion: Arc<Completion>) -> Self {
+        Self {
+            completion,
+            count: 0,
+        }
+    }
+    pub(crate) fn set(&mut self, count: usize) {
+        if !self.completion.enabled || count == self.count {
+            return;
+        }
+        if count > self.count {
+            self.completion
+                .held
+                .fetch_add(count - self.count, Ordering::AcqRel);
+        } else {
+            self.completion
+                .held
+                .fetch_sub(self.count - count, Ordering::AcqRel);
+        }
+        self.count = count;
+    }
+}
+
+impl Drop for Held {
+    fn drop(&mut self) {
+        self.set(0);
+    }
+}
+
+const COMPLETION_POLL: std::time::Duration = std::time::Duration::from_millis(10);
+
+// 连续两次检查均静止且其间没有 handler 完成才判定完成：覆盖消息出队与 handler 开始计数之间的空隙
+pub(crate) async fn run_completion_monitor(
+    bus: BusHandle,
+    completion: Arc<Completion>,
+    stop: Arc<StopFlag>,
+) {
+    let mut settled = None;
+    loop {
+        if completion.running.load(Ordering::Acquire) > 0 {
+            tokio::select! {
+                () = stop.wait() => return,
+                () = completion.sources_done.notified() => {}
+            }
+        }
+        tokio::select! {
+            () = stop.wait() => return,
+            () = crate::rt::sleep(COMPLETION_POLL) => {}
+        }
+        if !completion.quiet(&bus) {
+            settled = None;
+            continue;
+        }
+        let handled = completion.handled.load(Ordering::Acquire);
+        if settled == Some(handled) {
+            break;
+        }
+        settled = Some(handled);
+    }
+    completion.completed.store(true, Ordering::Release);
+    tracing::info!("all sources finished and queues drained; stopping app");
+    stop.trigger();
+}
@@ -0,0 +1,263 @@
+//! 声明式流水线：以闭包串联阶段，无需编写组件结构体，适用于小工具与测试。
+//!
+//! `Pipeline::new().source(stream).map(..).filter(..).sink(..)` 装配为同一总线上的组件，经 `app.add_pipeline(..)` 加入：
+//! - 源：`source(stream)`（特性 `stream`）为独立组件，越过启动屏障后逐项发布 `T`；`subscribe::<T>()` 以总线上已有的 `T` 为输入；
+//! - map / filter 在同一组件内融合执行（中间值不经总线）；终端 `sink` 就地消费结果，`publish` 将结果发布到总线。
+//!
+//! 输入按类型订阅：总线上其它来源发布的 `T` 同样进入流水线；`publish` 的结果类型不可与输入相同（会回流）。
+//!
+//! 单个异步闭包 handler 见 `app.on::<T>(..)`（同样装配为独立组件）。
+use std::any::Any;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::component::{Component, ComponentContext, ComponentFactory, InstanceFactory};
+use crate::error::Result;
+
+type Outputs = Vec<Arc<dyn Any + Send + Sync>>;
+
+/// 融合后的阶段链：对每个输入调用 `emit` 零到多次。
+pub trait Chain<In, Out>: FnMut(&In, &mut dyn FnMut(&Out)) + Send + 'static {}
+impl<In, Out, F: FnMut(&In, &mut dyn FnMut(&Out)) + Send + 'static> Chain<In, Out> for F {}
+
+/// `app.on::<T>(..)` 的异步闭包：`Fn(Arc<T>) -> impl Future<Output = ()>`。
+pub trait OnFn<T>: Fn(Arc<T>) -> <Self as OnFn<T>>::Fut + Send + Sync + 'static {
+    type Fut: Future<Output = ()> + Send + 'static;
+}
+impl<T, F, Fut> OnFn<T> for F
+where
+    F: Fn(Arc<T>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    type Fut = Fut;
+}
+
+/// 流水线入口：选择输入后得到可继续串联的 [`Stages`]。
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Pipeline;
+
+impl Pipeline {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// 以总线上的 `T` 为输入。
+    pub fn subscribe<T: Send + Sync + 'static>(self) -> Stages<T, T, impl Chain<T, T>> {
+        Stages {
+            source: None,
+            chain: |x: &T, emit: &mut dyn FnMut(&T)| emit(x),
+            _types: PhantomData,
+        }
+    }
+
+    /// 以 `stream` 为源：各项先发布到总线，再进入后续阶段；流结束后源组件保持空闲直至停机。
+    #[cfg(feature = "stream")]
+    pub fn source<S>(self, stream: S) -> Stages<S::Item, S::Item, impl Chain<S::Item, S::Item>>
+    where
+        S: futures_core::Stream + Send + 'static,
+        S::Item: Send + Sync + 'static,
+    {
+        let mut stages = self.subscribe::<S::Item>();
+        stages.source = Some(Box::new(InstanceFactory::new(StreamSource {
+            stream: parking_lot::Mutex::new(stream),
+        })));
+        stages
+    }
+}
+
+/// 已选定输入 `In`、当前输出 `Out` 的流水线；`C` 为融合后的阶段链。
+#[must_use]
+pub struct Stages<In, Out, C> {
+    source: Option<Box<dyn ComponentFactory>>,
+    chain: C,
+    _types: PhantomData<fn(&In) -> Out>,
+}
+
+impl<In, Out, C> Stages<In, Out, C>
+where
+    In: Send + Sync + 'static,
+    Out: 'static,
+    C: Chain<In, Out>,
+{
+    pub fn map<U: 'static, F>(self, mut f: F) -> Stages<In, U, impl Chain<In, U>>
+    where
+        F: FnMut(&Out) -> U + Send + 'static,
+    {
+        let mut prev = self.chain;
+        Stages {
+            source: self.source,
+            chain: move |x: &In, emit: &mut dyn FnMut(&U)| prev(x, &mut |y: &Out| emit(&f(y))),
+            _types: PhantomData,
+        }
+    }
+
+    pub fn filter<F>(self, mut keep: F) -> Stages<In, Out, impl Chain<In, Out>>
+    where
+        F: FnMut(&Out) -> bool + Send + 'static,
+    {
+        let mut prev = self.chain;
+        Stages {
+            source: self.source,
+            chain: move |x: &In, emit: &mut dyn FnMut(&Out)| {
+                prev(x, &mut |y: &Out| {
+                    if keep(y) {
+                        emit(y);
+                    }
+                });
+            },
+            _types: PhantomData,
+        }
+    }
+
+    /// 终端：就地消费每个结果。
+    pub fn sink<F>(self, mut f: F) -> Assembled
+    where
+        F: FnMut(&Out) + Send + 'static,
+    {
+        let mut chain = self.chain;
+        Self::assemble(self.source, move |x: &In, _: &mut Outputs| {
+            chain(x, &mut |y: &Out| f(y));
+        })
+    }
+
+    /// 终端：把每个结果发布到总线（克隆一次）。
+    pub fn publish(self) -> Assembled
+    where
+        Out: Clone + Send + Sync,
+    {
+        let mut chain = self.chain;
+        Self::assemble(self.source, move |x: &In, out: &mut Outputs| {
+            chain(x, &mut |y: &Out| out.push(Arc::new(y.clone())));
+        })
+    }
+
+    fn assemble<P>(source: Option<Box<dyn ComponentFactory>>, step: P) -> Assembled
+    where
+        P: FnMut(&In, &mut Outputs) + Send + 'static,
+    {
+        let stage = Processor::<In, P> {
+            step: parking_lot::Mutex::new(step),
+            _in: PhantomData,
+        };
+        let mut factories = Vec::from_iter(source);
+        factories.push(Box::new(InstanceFactory::new(stage)));
+        Assembled { factories }
+    }
+}
+
+/// 装配完成的流水线：`app.add_pipeline(..)`。
+#[must_use]
+pub struct Assembled {
+    pub(crate) factories: Vec<Box<dyn ComponentFactory>>,
+}
+
+#[cfg(feature = "stream")]
+struct StreamSource<S> {
+    stream: parking_lot::Mutex<S>,
+}
+
+#[cfg(feature = "stream")]
+#[async_trait]
+impl<S> Component for StreamSource<S>
+where
+    S: futures_core::Stream + Send + 'static,
+    S::Item: Send + Sync + 'static,
+{
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let mut stream = std::pin::pin!(self.stream.into_inner());
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            crate::rt::select! {
+                () = crate::component::__recv_stop(&ctx) => return Ok(()),
+                item = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)) => match item {
+                    Some(msg) => crate::component::__publish_auto(&ctx, msg).await,
+                    None => break,
+                },
+            }
+        }
+        crate::component::__recv_stop(&ctx).await;
+        Ok(())
+    }
+}
+
+// `App::on`：异步闭包 handler，逐条顺序处理
+pub(crate) struct OnHandler<T, F> {
+    f: F,
+    _in: PhantomData<fn(&T)>,
+}
+
+impl<T, F> OnHandler<T, F> {
+    pub(crate) const fn new(f: F) -> Self {
+        Self {
+            f,
+            _in: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<T, F> Component for OnHandler<T, F>
+where
+    T: Send + Sync + 'static,
+    F: OnFn<T>,
+{
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let mut sub = crate::component::__subscribe_any_auto::<T>(&ctx);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            crate::rt::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                msg = sub.recv() => {
+                    let Some(msg) = msg else { break };
+                    (self.f)(msg).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 订阅可知；闭包经 BusHandle 的发布无法判定
+    fn __wiring() -> Option<crate::wiring::Wiring> {
+        Some(crate::wiring::Wiring {
+            subscribes: vec![crate::wiring::MessageType::of::<T>()],
+            publishes: Vec::new(),
+            dynamic: true,
+        })
+    }
+}
+
+struct Processor<In, P> {
+    step: parking_lot::Mutex<P>,
+    _in: PhantomData<fn(&In)>,
+}
+
+#[async_trait]
+impl<In, P> Component for Processor<In, P>
+where
+    In: Send + Sync + 'static,
+    P: FnMut(&In, &mut Outputs) + Send + 'static,
+{
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let mut step = self.step.into_inner();
+        let mut sub = crate::component::__subscribe_any_auto::<In>(&ctx);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            crate::rt::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                msg = sub.recv() => {
+                    let Some(msg) = msg else { break };
+                    let mut out = Outputs::new();
+                    step(&msg, &mut out);
+                    for o in out {
+                        crate::component::__publish_any_arc(&ctx, o).await;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+// panic 现场快照（`App::dump_on_panic`）：进程级 panic 钩子在展开前把各登记 App 的自省快照写入日志。
+//
+// 钩子只持有弱引用，不延长 App 生命周期；快照在辅助线程上构建并至多等待 `DUMP_WAIT`：
+// panic 发生在持有注册表写锁的代码中时，当前线程读锁会自锁，超时后放弃输出，照常展开。
+use std::fmt::Write as _;
+use std::sync::{Arc, Once, Weak};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::app::AppShared;
+use crate::bus::WeakBusHandle;
+use crate::introspect::{ComponentStatus, Snapshot};
+
+const DUMP_WAIT: Duration = Duration::from_secs(1);
+
+static TARGETS: Mutex<Vec<(Weak<AppShared>, WeakBusHandle)>> = Mutex::new(Vec::new());
+static INSTALL: Once = Once::new();
+
+thread_local! {
+    // 钩子输出期间再次 panic 时不重复输出
+    static DUMPING: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+pub(crate) fn register(shared: &Arc<AppShared>, bus: &crate::bus::BusHandle) {
+    INSTALL.call_once(|| {
+        let prev = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !DUMPING.replace(true) {
+                dump(info);
+                DUMPING.set(false);
+            }
+            prev(info);
+        }));
+    });
+    let mut targets = TARGETS.lock();
+    targets.retain(|(s, _)| s.strong_count() > 0);
+    targets.push((Arc::downgrade(shared), bus.downgrade()));
+}
+
+fn dump(info: &std::panic::PanicHookInfo<'_>) {
+    let Some(targets) = TARGETS.try_lock().map(|t| t.clone()) else {
+        return;
+    };
+    let location = info
+        .location()
+        .map_or_else(String::new, ToString::to_string);
+    let (tx, rx) = std::sync::mpsc::channel();
+    let spawned = std::thread::Builder::new()
+        .name("microbus-panic-dump".into())
+        .spawn(move || {
+            let texts: Vec<String> = targets
+                .iter()
+                .filter_map(|(shared, bus)| {
+                    let shared = shared.upgrade()?;
+                    let bus = bus.upgrade()?;
+                    Some(render(&crate::introspect::snapshot(&shared, &bus)))
+                })
+                .collect();
+            let _ = tx.send(texts);
+        });
+    if spawned.is_err() {
+        return;
+    }
+    match rx.recv_timeout(DUMP_WAIT) {
+        Ok(texts) => {
+            for text in texts {
+                tracing::error!(target: "mmg_microbus::panic", location, "bus snapshot at panic\n{text}");
+            }
+        }
+        Err(_) => {
+            tracing::error!(target: "mmg_microbus::panic", location, "bus snapshot at panic unavailable (timed out)");
+        }
+    }
+}
+
+// 组件状态逐行列出；订阅只列出有积压的队列（即仍待处理的类型）
+fn render(snap: &Snapshot) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "sealed: {}", snap.sealed);
+    let _ = writeln!(out, "components:");
+    for c in &snap.components {
+        let status = match &c.status {
+            ComponentStatus::Starting => "starting".to_owned(),
+            ComponentStatus::Ready => "ready".to_owned(),
+            ComponentStatus::Running => "running".to_owned(),
+            ComponentStatus::Stopped => "stopped".to_owned(),
+            ComponentStatus::Failed(e) => format!("failed: {e}"),
+        };
+        let _ = writeln!(out, "  {}: {status}", c.name);
+    }
+    let pending: Vec<_> = snap.subscriptions.iter().filter(|s| s.depth > 0).collect();
+    if pending.is_empty() {
+        let _ = writeln!(out, "pending: none");
+    } else {
+        let _ = writeln!(out, "pending:");
+        for s in pending {
+            let _ = writeln!(
+                out,
+                "  {} <- {}: {}/{}",
+                s.component, s.type_name, s.depth, s.capacity
+            );
+        }
+    }
+    out
+}
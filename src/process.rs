@@ -0,0 +1,164 @@
+//! 进程资源遥测组件（特性 `process-stats`）：按周期在总线上发布 [`ProcessStats`]，任何订阅方都可据此做自监控。
+//!
+//! `app.add_component(ProcessMonitor::new().interval(Duration::from_secs(5)))`；默认每 10 秒一次，首条在一个周期后发布。
+//! 常驻内存、CPU 与打开的文件描述符读自 `/proc/self`，非 Linux 平台为 `None`；运行时指标取自组件所在的 tokio 运行时。
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::component::{Component, ComponentContext};
+use crate::error::Result;
+use crate::wiring::{MessageType, Wiring};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(10);
+// `/proc/<pid>/stat` 的 CPU 时间以 USER_HZ 计，该值属内核 ABI，固定为 100
+#[cfg(target_os = "linux")]
+const USER_HZ: f64 = 100.0;
+
+/// 进程资源采样（由 [`ProcessMonitor`] 周期发布）。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessStats {
+    /// 常驻内存（字节）
+    pub rss_bytes: Option<u64>,
+    /// 上一周期内的 CPU 占用百分比（单核满载为 100，多核可超过 100）
+    pub cpu_percent: Option<f64>,
+    /// 打开的文件描述符数
+    pub open_fds: Option<u64>,
+    /// tokio 运行时指标；不在 tokio 运行时上时为 `None`
+    pub runtime: Option<RuntimeStats>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeStats {
+    pub workers: usize,
+    /// 存活任务数（含各组件的 handler worker）
+    pub alive_tasks: usize,
+    /// 全局注入队列中等待调度的任务数
+    pub global_queue_depth: usize,
+}
+
+pub struct ProcessMonitor {
+    interval: Duration,
+}
+
+impl Default for ProcessMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessMonitor {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+    /// 发布周期（默认 10 秒）。
+    #[must_use]
+    pub const fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+#[async_trait]
+impl Component for ProcessMonitor {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        let mut cpu = CpuSampler::new();
+        loop {
+            crate::rt::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                () = crate::rt::sleep(self.interval) => {}
+            }
+            let stats = ProcessStats {
+                rss_bytes: rss_bytes(),
+                cpu_percent: cpu.sample(),
+                open_fds: open_fds(),
+                runtime: runtime_stats(),
+            };
+            crate::component::__publish_auto(&ctx, stats).await;
+        }
+        Ok(())
+    }
+
+    fn __wiring() -> Option<Wiring> {
+        Some(Wiring {
+            publishes: vec![MessageType::of::<ProcessStats>()],
+            ..Wiring::default()
+        })
+    }
+}
+
+// CPU 占用：两次采样间进程 CPU 时间增量 / 墙钟时间增量
+struct CpuSampler {
+    last: Option<(Instant, f64)>,
+}
+
+impl CpuSampler {
+    fn new() -> Self {
+        Self {
+            last: cpu_seconds().map(|s| (Instant::now(), s)),
+        }
+    }
+    fn sample(&mut self) -> Option<f64> {
+        let now = (Instant::now(), cpu_seconds()?);
+        let prev = self.last.replace(now)?;
+        let wall = now.0.duration_since(prev.0).as_secs_f64();
+        (wall > 0.0).then(|| (now.1 - prev.1) / wall * 100.0)
+    }
+}
+
+// procfs 读取均为内存中的小文件，直接同步读取
+#[cfg(target_os = "linux")]
+fn cpu_seconds() -> Option<f64> {
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    // comm 字段可能含空格：从最后一个 ')' 之后按空格切分，utime / stime 为其后第 12、13 项
+    let mut fields = stat
+        .get(stat.rfind(')')? + 1..)?
+        .split_whitespace()
+        .skip(11);
+    let utime: f64 = fields.next()?.parse().ok()?;
+    let stime: f64 = fields.next()?.parse().ok()?;
+    Some((utime + stime) / USER_HZ)
+}
+
+#[cfg(target_os = "linux")]
+fn rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(target_os = "linux")]
+fn open_fds() -> Option<u64> {
+    // 计数时 read_dir 自身占用的描述符不计入
+    let n = std::fs::read_dir("/proc/self/fd").ok()?.count() as u64;
+    Some(n.saturating_sub(1))
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn cpu_seconds() -> Option<f64> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn rss_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn open_fds() -> Option<u64> {
+    None
+}
+
+fn runtime_stats() -> Option<RuntimeStats> {
+    let metrics = tokio::runtime::Handle::try_current().ok()?.metrics();
+    Some(RuntimeStats {
+        workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+        global_queue_depth: metrics.global_queue_depth(),
+    })
+}
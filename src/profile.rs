@@ -0,0 +1,127 @@
+//! 运行档（profile）：把一组组件选择与配置覆盖绑定为具名档（如 "backtest" / "paper" / "live"），
+//! 构造 App 时按名称选择，切换运行模式无需分散的 `cfg!` / `if` 判断。
+use std::fmt;
+use std::sync::Arc;
+
+use crate::app::App;
+use crate::config::AppConfig;
+use crate::error::{MicrobusError, Result};
+
+type Override = Arc<dyn Fn(&mut AppConfig) + Send + Sync>;
+
+/// 具名运行档：组件允许列表 / 排除列表（名称规则同 `App::enable_component`）与配置覆盖。
+#[derive(Clone)]
+pub struct Profile {
+    name: String,
+    enabled: Option<Vec<String>>,
+    disabled: Vec<String>,
+    overrides: Vec<Override>,
+}
+
+impl Profile {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            enabled: None,
+            disabled: Vec::new(),
+            overrides: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 加入允许列表：一旦设置，本档只启动列出的自动发现组件。
+    #[must_use]
+    pub fn enable_component(mut self, name: impl Into<String>) -> Self {
+        self.enabled.get_or_insert_with(Vec::new).push(name.into());
+        self
+    }
+
+    /// 本档不启动该自动发现组件。
+    #[must_use]
+    pub fn disable_component(mut self, name: impl Into<String>) -> Self {
+        self.disabled.push(name.into());
+        self
+    }
+
+    /// 配置覆盖：按登记顺序作用于基础配置。
+    #[must_use]
+    pub fn config(mut self, f: impl Fn(&mut AppConfig) + Send + Sync + 'static) -> Self {
+        self.overrides.push(Arc::new(f));
+        self
+    }
+
+    /// 将本档的配置覆盖应用到 `cfg`。
+    #[must_use]
+    pub fn configure(&self, mut cfg: AppConfig) -> AppConfig {
+        for f in &self.overrides {
+            f(&mut cfg);
+        }
+        cfg
+    }
+
+    /// 将本档的组件选择登记到 `app`（须在 `start()` 之前）。
+    pub fn select_components(&self, app: &mut App) {
+        for name in self.enabled.iter().flatten() {
+            app.enable_component(name.clone());
+        }
+        for name in &self.disabled {
+            app.disable_component(name.clone());
+        }
+    }
+}
+
+impl fmt::Debug for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profile")
+            .field("name", &self.name)
+            .field("enabled", &self.enabled)
+            .field("disabled", &self.disabled)
+            .field("overrides", &self.overrides.len())
+            .finish()
+    }
+}
+
+/// 运行档集合，按名称选择。
+#[derive(Debug, Clone, Default)]
+pub struct Profiles {
+    list: Vec<Profile>,
+}
+
+impl Profiles {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记运行档；同名时后者替换前者。
+    #[must_use]
+    pub fn with(mut self, profile: Profile) -> Self {
+        self.list.retain(|p| p.name != profile.name);
+        self.list.push(profile);
+        self
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.list.iter().find(|p| p.name == name)
+    }
+
+    /// 按名称取运行档。
+    ///
+    /// # Errors
+    /// 未登记该名称时返回 [`MicrobusError::Config`]（列出可选名称）。
+    pub fn select(&self, name: &str) -> Result<&Profile> {
+        self.get(name).ok_or_else(|| {
+            let names: Vec<_> = self.list.iter().map(|p| p.name.as_str()).collect();
+            MicrobusError::Config(format!(
+                "unknown profile `{name}` (available: {})",
+                names.join(", ")
+            ))
+        })
+    }
+}
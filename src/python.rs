@@ -0,0 +1,255 @@
+//! Python 组件（特性 `python`）：把带 `handle_*` 方法的 Python 对象包装为组件，与 Rust 组件共用同一总线，便于策略原型验证。
+//!
+//! - 输入：`input::<T>()` 订阅 `T`，以 JSON 转为 Python 对象（`json.loads`）后调用 `handle_<name>`，`name` 为 [`BusMessage::NAME`] 的 snake_case 形式
+//!   （`Quote` → `handle_quote`，`market.Trade.v1` → `handle_market_trade_v1`）；
+//! - 输出：方法返回 `None`、`(name, obj)` 或其列表，`obj` 经 `json.dumps` 后按 `output::<T>()` 登记的 `T::NAME` 解码发布；
+//! - 调用经 `spawn_blocking` 在阻塞线程池上持 GIL 执行，同一组件的消息按到达顺序串行处理；Python 异常记录并计入 `errors()`，组件继续运行。
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pyo3::prelude::*;
+use pyo3::types::PyModule;
+use tokio::sync::mpsc;
+
+use crate::bus::ErasedEvent;
+use crate::codec::{BusMessage, Json, MessageCodec};
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+use crate::rt::JoinHandle;
+
+const IN_QUEUE: usize = 1024;
+
+type SpawnInput = Box<
+    dyn FnOnce(&ComponentContext, mpsc::Sender<(&'static str, String)>) -> JoinHandle<()>
+        + Send
+        + Sync,
+>;
+type DecodeFn = Box<dyn Fn(&str) -> std::result::Result<ErasedEvent, String> + Send + Sync>;
+
+/// Python 组件计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct PyHandle {
+    calls: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl PyHandle {
+    /// 正常返回的 `handle_*` 调用数。
+    #[must_use]
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+    /// 抛出异常或返回值格式不合法的调用数。
+    #[must_use]
+    pub fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+    /// 未登记类型或解码失败的输出数。
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+pub struct PyComponent {
+    object: Arc<Py<PyAny>>,
+    inputs: Vec<(&'static str, SpawnInput)>,
+    outputs: HashMap<&'static str, DecodeFn>,
+    handle: PyHandle,
+}
+
+impl PyComponent {
+    /// 包装已创建的 Python 对象。
+    #[must_use]
+    pub fn new(object: Py<PyAny>) -> Self {
+        Self {
+            object: Arc::new(object),
+            inputs: Vec::new(),
+            outputs: HashMap::new(),
+            handle: PyHandle::default(),
+        }
+    }
+    /// 导入模块 `module` 并以无参构造 `class` 的实例（模块须在 `sys.path` 中）。
+    ///
+    /// # Errors
+    /// 导入或构造失败时返回 Python 异常文本。
+    pub fn from_module(module: &str, class: &str) -> Result<Self> {
+        Python::attach(|py| {
+            let object = PyModule::import(py, module)?.getattr(class)?.call0()?;
+            Ok(Self::new(object.unbind()))
+        })
+        .map_err(|e: PyErr| MicrobusError::Dynamic(format!("python: {module}.{class}: {e}")))
+    }
+    /// 输入类型 `T`：每条 `T` 调用 `handle_<snake(T::NAME)>(obj)`。
+    #[must_use]
+    pub fn input<T: BusMessage>(mut self) -> Self {
+        let method: &'static str = Box::leak(handler_name(T::NAME).into_boxed_str());
+        self.inputs.push((
+            method,
+            Box::new(move |ctx, tx| {
+                let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+                let ctx = ctx.__fork();
+                crate::rt::spawn(async move {
+                    loop {
+                        tokio::select! {
+                            () = crate::component::__recv_stop(&ctx) => break,
+                            msg = sub.recv() => {
+                                let Some(msg) = msg else { break };
+                                let json = MessageCodec::<T>::encode(&Json, &msg);
+                                if tx.send((method, json)).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                })
+            }),
+        ));
+        self
+    }
+    /// 输出类型 `T`：方法返回的 `(T::NAME, obj)` 解码为 `T` 后发布。
+    #[must_use]
+    pub fn output<T: BusMessage>(mut self) -> Self {
+        self.outputs.insert(
+            T::NAME,
+            Box::new(|json| MessageCodec::<T>::decode(&Json, json).map(ErasedEvent::new)),
+        );
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> PyHandle {
+        self.handle.clone()
+    }
+}
+
+// `Quote` → `handle_quote`，`market.Trade.v1` → `handle_market_trade_v1`
+fn handler_name(name: &str) -> String {
+    let mut out = String::from("handle_");
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !out.ends_with('_') {
+                out.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    out
+}
+
+// 调用 `method(json.loads(payload))`，返回 `(名称, JSON)` 输出列表
+fn call_handler(
+    object: &Py<PyAny>,
+    method: &str,
+    payload: &str,
+) -> PyResult<Vec<(String, String)>> {
+    Python::attach(|py| {
+        let json = PyModule::import(py, "json")?;
+        let arg = json.call_method1("loads", (payload,))?;
+        let ret = object.bind(py).call_method1(method, (arg,))?;
+        let items: Vec<(String, Bound<'_, PyAny>)> = if ret.is_none() {
+            Vec::new()
+        } else if let Ok(one) = ret.extract::<(String, Bound<'_, PyAny>)>() {
+            vec![one]
+        } else {
+            ret.extract()?
+        };
+        items
+            .into_iter()
+            .map(|(name, obj)| {
+                let text: String = json.call_method1("dumps", (obj,))?.extract()?;
+                Ok((name, text))
+            })
+            .collect()
+    })
+}
+
+#[async_trait]
+impl Component for PyComponent {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            object,
+            inputs,
+            outputs,
+            handle,
+        } = *self;
+        // 启动前校验入口方法，缺失即启动失败
+        let missing: Vec<&str> = Python::attach(|py| {
+            let obj = object.bind(py);
+            inputs
+                .iter()
+                .map(|(method, _)| *method)
+                .filter(|m| !obj.hasattr(*m).unwrap_or(false))
+                .collect()
+        });
+        if !missing.is_empty() {
+            crate::component::__startup_mark_failed(&ctx);
+            return Err(MicrobusError::Dynamic(format!(
+                "python: object has no method {}",
+                missing.join(", ")
+            )));
+        }
+        // 订阅须在启动屏障（总线封印）之前登记
+        let (tx, mut rx) = mpsc::channel(IN_QUEUE);
+        let workers: Vec<_> = inputs
+            .into_iter()
+            .map(|(_, f)| f(&ctx, tx.clone()))
+            .collect();
+        drop(tx);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            let (method, payload) = tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                msg = rx.recv() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+            };
+            let obj = object.clone();
+            let result =
+                tokio::task::spawn_blocking(move || call_handler(&obj, method, &payload)).await;
+            let out = match result {
+                Ok(Ok(out)) => {
+                    handle.calls.fetch_add(1, Ordering::Relaxed);
+                    out
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!(method, error = %e, "python handler failed");
+                    handle.errors.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(method, error = %e, "python handler task failed");
+                    handle.errors.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                }
+            };
+            for (name, json) in out {
+                match outputs.get(name.as_str()).map(|decode| decode(&json)) {
+                    Some(Ok(ev)) => crate::component::__publish_erased(&ctx, ev).await,
+                    Some(Err(e)) => {
+                        tracing::debug!(%name, error = %e, "python output decode failed");
+                        handle.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    None => {
+                        tracing::debug!(%name, "python output type not registered");
+                        handle.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+        for w in workers {
+            w.abort().await;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,323 @@
+//! 散发-汇集查询：一次发布 `Ask<Q, A>`，收集全部应答方的 `A`。
+//!
+//! 应答方即订阅 `Ask<Q, A>` 的普通 `#[handle]`，在处理中调用 [`Ask::reply`]；无需为每个应答方建立请求 / 应答通道。
+//! 发起方：组件内 `ctx.query::<Q, A>(q, timeout)` / `ctx.query_with(..)`，组件外 `bus.query(..)`。
+//! 只需一个应答时用 [`Request`]（`ctx.request(q).timeout(d).await`），超时以 [`MicrobusError::Timeout`] 返回。
+//! 发起方放弃等待时触发请求的 [`CancelToken`]：应答方经 [`Ask::cancelled`] 或 `ctx.request_token()` 感知。
+use crate::bus::BusHandle;
+use crate::error::{MicrobusError, Result};
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::future::{Future, IntoFuture};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{mpsc, Notify};
+
+/// 查询消息：`Deref` 到查询内容 `Q`，应答经 [`reply`](Self::reply) 回传给发起方。
+pub struct Ask<Q, A> {
+    query: Q,
+    reply: mpsc::Sender<A>,
+    token: CancelToken,
+}
+
+impl<Q, A> Ask<Q, A> {
+    #[must_use]
+    pub const fn query(&self) -> &Q {
+        &self.query
+    }
+    /// 回传应答；发起方已结束（收齐、提前结束或超时）时返回 `false`，应答被丢弃。
+    ///
+    /// 每个应答方应只应答一次：应答通道容量等于发布时的应答方数。
+    pub fn reply(&self, answer: A) -> bool {
+        self.reply.try_send(answer).is_ok()
+    }
+    /// 发起方是否已放弃等待（收齐、超时或请求被丢弃）；长耗时的应答方可据此提前结束。
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+    /// 发起方放弃等待时完成；应答方可与自身的耗时工作 `select!`，超时后不再占用 handler。
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await;
+    }
+    /// 本次请求的取消信号，可克隆后交给应答方派生的任务。
+    #[must_use]
+    pub const fn token(&self) -> &CancelToken {
+        &self.token
+    }
+}
+
+/// 请求的取消信号：发起方返回（收齐、超时）或请求 future 被丢弃时触发，触发后不再复位。
+///
+/// 应答方经 [`Ask::token`] 取得；以 `ctx` 为参数的 handler 也可在处理 `Ask` 期间经 `ctx.request_token()` 取得。
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<CancelState>);
+
+#[derive(Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelToken {
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Acquire)
+    }
+    /// 触发时完成（已触发则立即完成）。
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.0.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+    fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Release);
+        self.0.notify.notify_waiters();
+    }
+}
+
+impl std::fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CancelToken")
+            .field(&self.is_cancelled())
+            .finish()
+    }
+}
+
+// 发起方持有：随请求 future 返回或被丢弃而释放，释放即触发
+struct CancelOnDrop(CancelToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+thread_local! {
+    // 当前线程正在轮询的应答 handler 所属请求的取消信号
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+// `ctx.request_token()`：仅在 `Ask` handler 的轮询期间有值
+pub(crate) fn current_token() -> Option<CancelToken> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+// 宏生成代码经方法解析选择作用域：消息为 `Ask` 时命中 `__ViaAsk`（按值接收者），否则自动取引用后命中 `__ViaOther`（原样返回）
+#[doc(hidden)]
+pub trait __ViaAsk {
+    fn __request_scope<F: Future>(&self, fut: F) -> __RequestScope<F>;
+}
+
+impl<Q, A> __ViaAsk for Ask<Q, A> {
+    fn __request_scope<F: Future>(&self, fut: F) -> __RequestScope<F> {
+        __RequestScope {
+            token: self.token.clone(),
+            fut: Box::pin(fut),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait __ViaOther {
+    fn __request_scope<F: Future>(&self, fut: F) -> F;
+}
+
+impl<T: ?Sized> __ViaOther for &T {
+    fn __request_scope<F: Future>(&self, fut: F) -> F {
+        fut
+    }
+}
+
+// 轮询 handler future 期间把取消信号置为当前值（嵌套时恢复外层）
+#[doc(hidden)]
+pub struct __RequestScope<F> {
+    token: CancelToken,
+    fut: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for __RequestScope<F> {
+    type Output = F::Output;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        struct Restore(Option<CancelToken>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|c| *c.borrow_mut() = self.0.take());
+            }
+        }
+        let _restore = Restore(CURRENT.with(|c| c.borrow_mut().replace(self.token.clone())));
+        self.fut.as_mut().poll(cx)
+    }
+}
+
+impl<Q, A> std::ops::Deref for Ask<Q, A> {
+    type Target = Q;
+    fn deref(&self) -> &Q {
+        &self.query
+    }
+}
+
+impl<Q: std::fmt::Debug, A> std::fmt::Debug for Ask<Q, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Ask").field(&self.query).finish()
+    }
+}
+
+/// 汇集策略：何时结束等待（均以 `timeout` 为上限，超时返回已收到的应答）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gather {
+    /// 首个应答
+    First,
+    /// 全部应答方（发布时刻的订阅数）
+    All,
+    /// 至少 n 个应答（超过应答方数时按 `All`）
+    Quorum(usize),
+}
+
+/// 单应答请求：取首个应答方的应答。由 `ctx.request(q)` / `bus.request(q)` 构造，`.await` 发起。
+///
+/// - 默认无超时；[`timeout`](Self::timeout) 设定后到期返回 [`MicrobusError::Timeout`]（含投递时的背压等待）。
+/// - 无应答方、或全部应答方处理完毕而未应答时返回 `MicrobusError::Other`。
+/// - 返回（含超时、请求 future 被丢弃）即关闭应答通道：迟到的 [`Ask::reply`] 返回 `false`，
+///   应答方经 [`Ask::cancelled`] / [`Ask::is_cancelled`] 感知放弃，排队中的 `Ask` 可据此跳过。
+#[must_use = "requests do nothing unless awaited"]
+pub struct Request<Q, A> {
+    bus: BusHandle,
+    query: Q,
+    timeout: Option<Duration>,
+    _answer: PhantomData<fn() -> A>,
+}
+
+impl<Q, A> Request<Q, A> {
+    pub(crate) const fn new(bus: BusHandle, query: Q) -> Self {
+        Self {
+            bus,
+            query,
+            timeout: None,
+            _answer: PhantomData,
+        }
+    }
+    /// 等待应答的上限。
+    pub const fn timeout(mut self, d: Duration) -> Self {
+        self.timeout = Some(d);
+        self
+    }
+}
+
+impl<Q, A> IntoFuture for Request<Q, A>
+where
+    Q: Send + Sync + 'static,
+    A: Send + 'static,
+{
+    type Output = Result<A>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Result<A>> + Send>>;
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let Self {
+                bus,
+                query,
+                timeout,
+                ..
+            } = self;
+            let ask = request_one(&bus, query);
+            match timeout {
+                None => ask.await,
+                Some(after) => {
+                    crate::rt::timeout(after, ask)
+                        .await
+                        .unwrap_or(Err(MicrobusError::Timeout {
+                            type_name: std::any::type_name::<Q>(),
+                            after,
+                        }))
+                }
+            }
+        })
+    }
+}
+
+async fn request_one<Q, A>(bus: &BusHandle, q: Q) -> Result<A>
+where
+    Q: Send + Sync + 'static,
+    A: Send + 'static,
+{
+    let responders = bus.open_subscribers(TypeId::of::<Ask<Q, A>>());
+    if responders == 0 {
+        return Err(MicrobusError::Other("request has no responder"));
+    }
+    // 先于通道声明：释放时先关闭应答通道再触发取消，被唤醒的应答方不会再投递成功
+    let cancel = CancelOnDrop(CancelToken::default());
+    let (tx, mut rx) = mpsc::channel(responders);
+    bus.publish_type(Ask {
+        query: q,
+        reply: tx,
+        token: cancel.0.clone(),
+    })
+    .await;
+    // `rx` 与 `cancel` 随本 future 释放：超时 / 丢弃后应答方立即观察到取消
+    rx.recv()
+        .await
+        .ok_or(MicrobusError::Other("request finished without reply"))
+}
+
+impl BusHandle {
+    /// 组件外发起单应答请求，语义同 `ComponentContext::request`。
+    pub fn request<Q, A>(&self, q: Q) -> Request<Q, A> {
+        Request::new(self.clone(), q)
+    }
+
+    /// 组件外发起查询，语义同 `ComponentContext::query_with`。
+    pub async fn query<Q, A>(&self, q: Q, gather: Gather, timeout: Duration) -> Vec<A>
+    where
+        Q: Send + Sync + 'static,
+        A: Send + 'static,
+    {
+        self::gather(self, q, gather, timeout).await
+    }
+}
+
+pub(crate) async fn gather<Q, A>(bus: &BusHandle, q: Q, gather: Gather, timeout: Duration) -> Vec<A>
+where
+    Q: Send + Sync + 'static,
+    A: Send + 'static,
+{
+    let responders = bus.open_subscribers(TypeId::of::<Ask<Q, A>>());
+    if responders == 0 {
+        return Vec::new();
+    }
+    let want = match gather {
+        Gather::First => 1,
+        Gather::All => responders,
+        Gather::Quorum(n) => n.min(responders),
+    };
+    let cancel = CancelOnDrop(CancelToken::default());
+    let (tx, mut rx) = mpsc::channel(responders);
+    let mut answers = Vec::with_capacity(want);
+    // 投递本身受背压约束，同样计入超时
+    let collect = async {
+        bus.publish_type(Ask {
+            query: q,
+            reply: tx,
+            token: cancel.0.clone(),
+        })
+        .await;
+        while answers.len() < want {
+            match rx.recv().await {
+                Some(a) => answers.push(a),
+                // 全部应答方已处理完毕（`Ask` 被释放）
+                None => break,
+            }
+        }
+    };
+    let _ = crate::rt::timeout(timeout, collect).await;
+    answers
+}
@@ -0,0 +1,81 @@
+//! 按类型的发布限速（`AppConfig::rate_limit`）：GCRA 形式的令牌桶，在发布入口处判定。
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::config::{RateExcess, RateLimit};
+use crate::rt::Instant;
+
+const DROP_WARN_INTERVAL: Duration = Duration::from_secs(5);
+
+pub(crate) struct RateLimiter {
+    type_name: &'static str,
+    interval: Duration,  // 补充一个令牌的间隔
+    tolerance: Duration, // 突发容量折算的提前量：(count - 1) × interval
+    on_excess: RateExcess,
+    // 理论到达时刻（TAT）：下一条在不超速情况下最早的发布时刻
+    tat: parking_lot::Mutex<Option<Instant>>,
+    delayed: AtomicU64,
+    dropped: AtomicU64,
+    last_drop_warn: parking_lot::Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: &RateLimit) -> Self {
+        let interval = limit.per / limit.count;
+        Self {
+            type_name: limit.type_name,
+            interval,
+            tolerance: interval * (limit.count - 1),
+            on_excess: limit.on_excess,
+            tat: parking_lot::Mutex::new(None),
+            delayed: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            last_drop_warn: parking_lot::Mutex::new(None),
+        }
+    }
+
+    // 取一个令牌；`Delay` 时等待至令牌可用，`Drop` 时超速返回 false（不占用令牌）
+    pub(crate) async fn admit(&self) -> bool {
+        let wait = {
+            let mut tat = self.tat.lock();
+            let now = Instant::now();
+            let t = tat.map_or(now, |t| t.max(now));
+            let ahead = t.saturating_duration_since(now);
+            if ahead > self.tolerance && self.on_excess == RateExcess::Drop {
+                drop(tat);
+                self.record_drop();
+                return false;
+            }
+            *tat = Some(t + self.interval);
+            ahead.saturating_sub(self.tolerance)
+        };
+        if !wait.is_zero() {
+            self.delayed.fetch_add(1, Ordering::Relaxed);
+            crate::rt::sleep(wait).await;
+        }
+        true
+    }
+
+    fn record_drop(&self) {
+        let total = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+        #[cfg(feature = "metrics")]
+        crate::metrics::dropped(self.type_name, "rate_limited", 1);
+        let mut last = self.last_drop_warn.lock();
+        if last.is_none_or(|t| t.elapsed() >= DROP_WARN_INTERVAL) {
+            *last = Some(Instant::now());
+            tracing::warn!(
+                message_type = self.type_name,
+                total,
+                "message dropped: publish rate limit exceeded"
+            );
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> crate::introspect::RateLimitMetrics {
+        crate::introspect::RateLimitMetrics {
+            type_name: self.type_name,
+            delayed: self.delayed.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! 内置消息录制组件：经发布旁路（tap）捕获消息，带时间戳写入环形缓冲与可选文件，用于事后复盘。
+//!
+//! 通过 `App::add_component(recorder)` 显式启用；不参与 inventory 自动发现。
+//! 经 `codec` 登记编解码器的类型可由 [`Replay`](crate::replay::Replay) 回放。
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::bus::PublishTap;
+pub use crate::codec::MessageCodec;
+use crate::codec::{BusMessage, Schema};
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+use crate::rt::{SystemTime, UNIX_EPOCH};
+
+pub const RECORDER_DEFAULT_RING: usize = 1024;
+// 文件写入通道容量：写盘跟不上时丢弃并计数，绝不反压发布方
+const RECORDER_FILE_QUEUE: usize = 4096;
+
+type FormatFn = Box<dyn Fn(&(dyn Any + Send + Sync)) -> String + Send + Sync>;
+
+/// 一条录制记录。`payload` 为 `Debug` 文本或编解码器输出（`encoded`）；仅按类型名录制（`record_all`）时为 `None`。
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    pub timestamp: SystemTime,
+    pub type_name: &'static str,
+    pub payload: Option<String>,
+    pub encoded: bool,
+}
+
+// 文件行中 payload 类别标记
+const KIND_NONE: &str = "-";
+const KIND_DEBUG: &str = "debug";
+pub(crate) const KIND_CODEC: &str = "codec";
+
+impl RecordedMessage {
+    // 单行文本：`<unix_micros>\t<type>\t<kind>\t<payload>`，payload 中的 `\\` `\t` `\n` `\r` 转义
+    pub(crate) fn to_line(&self) -> String {
+        let micros = self
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_micros());
+        let kind = match (&self.payload, self.encoded) {
+            (None, _) => KIND_NONE,
+            (Some(_), false) => KIND_DEBUG,
+            (Some(_), true) => KIND_CODEC,
+        };
+        format!(
+            "{micros}\t{}\t{kind}\t{}\n",
+            self.type_name,
+            escape_field(self.payload.as_deref().unwrap_or(""))
+        )
+    }
+}
+
+// 录制文件的一行（回放读取）
+pub(crate) struct RecordLine<'a> {
+    pub(crate) micros: u64,
+    pub(crate) type_name: &'a str,
+    pub(crate) kind: &'a str,
+    pub(crate) payload: String,
+}
+
+pub(crate) fn parse_line(line: &str) -> Option<RecordLine<'_>> {
+    let mut parts = line.splitn(4, '\t');
+    Some(RecordLine {
+        micros: parts.next()?.parse().ok()?,
+        type_name: parts.next()?,
+        kind: parts.next()?,
+        payload: unescape_field(parts.next()?),
+    })
+}
+
+pub(crate) fn escape_field(s: &str) -> std::borrow::Cow<'_, str> {
+    if !s.contains(['\\', '\t', '\n', '\r']) {
+        return s.into();
+    }
+    let mut out = String::with_capacity(s.len() + 8);
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.into()
+}
+
+pub(crate) fn unescape_field(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// 录制结果的只读访问句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone)]
+pub struct RecorderHandle {
+    ring: Arc<Mutex<VecDeque<RecordedMessage>>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl RecorderHandle {
+    /// 环形缓冲中的记录快照（按发布顺序）。
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<RecordedMessage> {
+        self.ring.lock().iter().cloned().collect()
+    }
+    /// 因文件写入队列已满而未写盘的记录数。
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+pub struct Recorder {
+    ring_capacity: usize,
+    file: Option<PathBuf>,
+    formatters: HashMap<TypeId, Formatter>,
+    record_all: bool,
+    handle: RecorderHandle,
+}
+
+struct Formatter {
+    encoded: bool,
+    // 覆盖记录中的类型名（`message::<T>()` 写入 `NAME@VERSION`）
+    name: Option<&'static str>,
+    format: FormatFn,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recorder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            ring_capacity: RECORDER_DEFAULT_RING,
+            file: None,
+            formatters: HashMap::new(),
+            record_all: false,
+            handle: RecorderHandle {
+                ring: Arc::new(Mutex::new(VecDeque::new())),
+                dropped: Arc::new(AtomicU64::new(0)),
+            },
+        }
+    }
+    /// 环形缓冲容量；0 表示不保留内存记录。
+    #[must_use]
+    pub const fn ring_capacity(mut self, capacity: usize) -> Self {
+        self.ring_capacity = capacity;
+        self
+    }
+    /// 额外将每条记录追加写入文件（行格式见 `RecordedMessage`）。
+    #[must_use]
+    pub fn to_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+    /// 录制类型 `T`，内容以 `Debug` 文本保存。
+    #[must_use]
+    pub fn record<T: Debug + Send + Sync + 'static>(mut self) -> Self {
+        self.formatters.insert(
+            TypeId::of::<T>(),
+            Formatter {
+                encoded: false,
+                name: None,
+                format: Box::new(|msg| {
+                    msg.downcast_ref::<T>()
+                        .map_or_else(String::new, |v| format!("{v:?}"))
+                }),
+            },
+        );
+        self
+    }
+    /// 录制类型 `T`，内容经 `codec` 编码保存（可回放）；覆盖同类型的 `record::<T>()`。
+    #[must_use]
+    pub fn codec<T: Send + Sync + 'static>(mut self, codec: impl MessageCodec<T>) -> Self {
+        self.formatters.insert(
+            TypeId::of::<T>(),
+            Formatter {
+                encoded: true,
+                name: None,
+                format: Box::new(move |msg| {
+                    msg.downcast_ref::<T>()
+                        .map_or_else(String::new, |v| codec.encode(v))
+                }),
+            },
+        );
+        self
+    }
+    /// 同 `codec`，但记录类型名为 `NAME@VERSION`（[`BusMessage`]），供 `Replay::message` 按稳定名称回放并检查版本。
+    #[must_use]
+    pub fn message<T: BusMessage>(mut self, codec: impl MessageCodec<T>) -> Self {
+        // 每次登记泄漏一个短字符串，以满足记录中 `&'static str` 类型名
+        let name: &'static str = Box::leak(Schema::of::<T>().wire_name().into_boxed_str());
+        self.formatters.insert(
+            TypeId::of::<T>(),
+            Formatter {
+                encoded: true,
+                name: Some(name),
+                format: Box::new(move |msg| {
+                    msg.downcast_ref::<T>()
+                        .map_or_else(String::new, |v| codec.encode(v))
+                }),
+            },
+        );
+        self
+    }
+    /// 录制所有类型；未通过 `record::<T>()` 登记的类型仅记录类型名。
+    #[must_use]
+    pub const fn record_all(mut self) -> Self {
+        self.record_all = true;
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> RecorderHandle {
+        self.handle.clone()
+    }
+}
+
+struct RecorderTap {
+    formatters: HashMap<TypeId, Formatter>,
+    record_all: bool,
+    ring_capacity: usize,
+    handle: RecorderHandle,
+    file_tx: Option<mpsc::Sender<FileOp>>,
+}
+
+/// 写盘线程的指令：写入一条记录，或停机时刷新后退出。
+enum FileOp {
+    Record(RecordedMessage),
+    Close,
+}
+
+impl PublishTap for RecorderTap {
+    fn on_publish(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        msg: &Arc<dyn Any + Send + Sync>,
+    ) {
+        let (payload, encoded, type_name) = match self.formatters.get(&type_id) {
+            Some(f) => (
+                Some((f.format)(&**msg)),
+                f.encoded,
+                f.name.unwrap_or(type_name),
+            ),
+            None if self.record_all => (None, false, type_name),
+            None => return,
+        };
+        let rec = RecordedMessage {
+            timestamp: SystemTime::now(),
+            type_name,
+            payload,
+            encoded,
+        };
+        if let Some(tx) = &self.file_tx {
+            // 仅统计队列满的丢弃；写盘线程退出（停机）后的发布不计入
+            if let Err(mpsc::error::TrySendError::Full(_)) =
+                tx.try_send(FileOp::Record(rec.clone()))
+            {
+                self.handle.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        if self.ring_capacity > 0 {
+            let mut ring = self.handle.ring.lock();
+            if ring.len() == self.ring_capacity {
+                ring.pop_front();
+            }
+            ring.push_back(rec);
+        }
+    }
+}
+
+#[async_trait]
+impl Component for Recorder {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let (file_tx, writer_done) = match &self.file {
+            Some(path) => {
+                let (tx, done) = spawn_file_writer(path.clone()).await.inspect_err(|_| {
+                    crate::component::__startup_mark_failed(&ctx);
+                })?;
+                (Some(tx), Some(done))
+            }
+            None => (None, None),
+        };
+        let Self {
+            ring_capacity,
+            formatters,
+            record_all,
+            handle,
+            ..
+        } = *self;
+        ctx.bus().add_tap(Arc::new(RecorderTap {
+            formatters,
+            record_all,
+            ring_capacity,
+            handle,
+            file_tx: file_tx.clone(),
+        }));
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        crate::component::__recv_stop(&ctx).await;
+        if let (Some(tx), Some(done)) = (file_tx, writer_done) {
+            // 停机：等写盘线程落盘已排队记录并刷新
+            if tx.send(FileOp::Close).await.is_ok() {
+                let _ = done.await;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 在专用线程打开录制文件并消费写盘队列：文件 I/O 不占用异步执行器，且与运行时后端无关。
+/// 打开失败时返回错误；线程在收到 [`FileOp::Close`] 或队列关闭后刷新退出，随后 `done` 完成。
+async fn spawn_file_writer(path: PathBuf) -> Result<(mpsc::Sender<FileOp>, oneshot::Receiver<()>)> {
+    let (tx, mut rx) = mpsc::channel(RECORDER_FILE_QUEUE);
+    let (opened_tx, opened_rx) = oneshot::channel();
+    let (done_tx, done_rx) = oneshot::channel();
+    let display = path.display().to_string();
+    std::thread::Builder::new()
+        .name("microbus-recorder".into())
+        .spawn(move || {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path);
+            let mut out = match file {
+                Ok(f) => {
+                    let _ = opened_tx.send(Ok(()));
+                    std::io::BufWriter::new(f)
+                }
+                Err(e) => {
+                    let _ = opened_tx.send(Err(e));
+                    return;
+                }
+            };
+            while let Some(FileOp::Record(rec)) = rx.blocking_recv() {
+                write_record(&mut out, &rec);
+                // 队列排空即刷新，进程异常退出时尽量保留已录制内容
+                if rx.is_empty() {
+                    let _ = out.flush();
+                }
+            }
+            if let Err(e) = out.flush() {
+                tracing::warn!(error = %e, "recorder flush failed");
+            }
+            let _ = done_tx.send(());
+        })
+        .map_err(|e| {
+            MicrobusError::Dynamic(format!("recorder: cannot spawn writer thread: {e}"))
+        })?;
+    match opened_rx.await {
+        Ok(Ok(())) => Ok((tx, done_rx)),
+        Ok(Err(e)) => Err(MicrobusError::Dynamic(format!(
+            "recorder: cannot open {display}: {e}"
+        ))),
+        Err(_) => Err(MicrobusError::Dynamic(format!(
+            "recorder: writer thread for {display} exited"
+        ))),
+    }
+}
+
+fn write_record(out: &mut impl Write, rec: &RecordedMessage) {
+    if let Err(e) = out.write_all(rec.to_line().as_bytes()) {
+        tracing::warn!(error = %e, "recorder write failed");
+    }
+}
@@ -0,0 +1,603 @@
+//! Redis 桥（特性 `bridge-redis`）：经 Redis pub/sub 或 Streams 在进程间按类型扇出消息，无需独立消息代理。
+//!
+//! - [`RedisBridgeOut`]：订阅登记类型，编码后 `PUBLISH`（pub/sub，至多一次）或 `XADD`（Streams）。
+//! - [`RedisBridgeIn`]：`SUBSCRIBE` 频道，或以消费组 `XREADGROUP` 读取流，解码后在本地总线发布；
+//!   流消息在本地发布（入队）完成后才 `XACK`，进程中途退出时未确认消息由消费组重新投递（至少一次）。
+//!
+//! 内置最小 RESP2 客户端（仅用到的命令，无 TLS / AUTH）；编解码器复用 [`MessageCodec`]，每个类型对应一个频道 / 流键。
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::bus::ErasedEvent;
+use crate::codec::MessageCodec;
+use crate::component::{Component, ComponentContext};
+use crate::error::Result;
+
+const OUT_QUEUE: usize = 1024;
+// 单次流水线写出的最大命令数
+const PIPELINE: usize = 128;
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+// XREADGROUP 阻塞时长 / 单次条数
+const STREAM_BLOCK_MS: u64 = 1000;
+const STREAM_COUNT: usize = 128;
+const STREAM_FIELD: &str = "data";
+const MAX_BULK: usize = 64 * 1024 * 1024;
+
+type SpawnForward =
+    Box<dyn FnOnce(&ComponentContext, mpsc::Sender<Command>) -> JoinHandle<()> + Send + Sync>;
+type DecodeFn = Box<dyn Fn(&[u8]) -> std::result::Result<ErasedEvent, String> + Send + Sync>;
+
+/// Redis 桥计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct RedisHandle {
+    frames: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl RedisHandle {
+    /// 出站：服务端已确认的命令数；入站：已在本地发布的消息数。
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+    /// 出站：未连接、写失败或服务端返回错误的消息数；入站：未登记或解码失败的消息数。
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+// ---- RESP2 ----
+
+#[derive(Debug)]
+enum Reply {
+    Simple(String),
+    Error(String),
+    Int,
+    Bulk(Option<Vec<u8>>),
+    Array(Option<Vec<Reply>>),
+}
+
+impl Reply {
+    fn into_bytes(self) -> Option<Vec<u8>> {
+        match self {
+            Self::Bulk(Some(b)) => Some(b),
+            Self::Simple(s) => Some(s.into_bytes()),
+            _ => None,
+        }
+    }
+    fn into_text(self) -> Option<String> {
+        match self {
+            Self::Bulk(Some(b)) => String::from_utf8(b).ok(),
+            Self::Simple(s) => Some(s),
+            _ => None,
+        }
+    }
+    fn into_array(self) -> Option<Vec<Self>> {
+        match self {
+            Self::Array(Some(items)) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+fn encode_command(args: &[&[u8]]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(16 + args.iter().map(|a| a.len() + 16).sum::<usize>());
+    out.extend_from_slice(format!("*{}\r\n", args.len()).as_bytes());
+    for a in args {
+        out.extend_from_slice(format!("${}\r\n", a.len()).as_bytes());
+        out.extend_from_slice(a);
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+fn protocol_error(msg: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, msg.into())
+}
+
+fn read_reply<'a, R: AsyncBufRead + Unpin + Send>(
+    r: &'a mut R,
+) -> Pin<Box<dyn Future<Output = std::io::Result<Reply>> + Send + 'a>> {
+    Box::pin(async move {
+        let mut line = Vec::new();
+        if r.read_until(b'\n', &mut line).await? == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        if !line.ends_with(b"\r\n") || line.len() < 3 {
+            return Err(protocol_error("malformed reply line"));
+        }
+        let text = std::str::from_utf8(&line[1..line.len() - 2])
+            .map_err(|_| protocol_error("non-utf8 reply header"))?;
+        let int = || {
+            text.parse::<i64>()
+                .map_err(|_| protocol_error(format!("bad integer {text:?}")))
+        };
+        match line[0] {
+            b'+' => Ok(Reply::Simple(text.to_owned())),
+            b'-' => Ok(Reply::Error(text.to_owned())),
+            b':' => int().map(|_| Reply::Int),
+            b'$' => {
+                let Ok(len) = usize::try_from(int()?) else {
+                    return Ok(Reply::Bulk(None));
+                };
+                if len > MAX_BULK {
+                    return Err(protocol_error("bulk string too large"));
+                }
+                let mut buf = vec![0u8; len + 2];
+                r.read_exact(&mut buf).await?;
+                buf.truncate(len);
+                Ok(Reply::Bulk(Some(buf)))
+            }
+            b'*' => {
+                let Ok(n) = usize::try_from(int()?) else {
+                    return Ok(Reply::Array(None));
+                };
+                let mut items = Vec::with_capacity(n.min(1024));
+                for _ in 0..n {
+                    items.push(read_reply(r).await?);
+                }
+                Ok(Reply::Array(Some(items)))
+            }
+            _ => Err(protocol_error("unknown reply type")),
+        }
+    })
+}
+
+type Conn = BufReader<TcpStream>;
+
+async fn connect(addr: &str) -> std::io::Result<Conn> {
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(addr))
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "connect timed out"))??;
+    stream.set_nodelay(true)?;
+    Ok(BufReader::new(stream))
+}
+
+async fn call(conn: &mut Conn, args: &[&[u8]]) -> std::io::Result<Reply> {
+    conn.get_mut().write_all(&encode_command(args)).await?;
+    read_reply(conn).await
+}
+
+// ---- 出站 ----
+
+struct Command {
+    key: Arc<str>,
+    stream: bool,
+    payload: Vec<u8>,
+}
+
+/// Redis 出站桥：每个登记类型对应一个频道（`forward`）或流键（`forward_stream`）。
+pub struct RedisBridgeOut {
+    addr: String,
+    forwards: Vec<SpawnForward>,
+    handle: RedisHandle,
+}
+
+impl RedisBridgeOut {
+    /// 连接 Redis（`host:port`）；连接在启动后建立，失败不影响本地启动。
+    #[must_use]
+    pub fn connect(addr: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into(),
+            forwards: Vec::new(),
+            handle: RedisHandle::default(),
+        }
+    }
+    /// 以 `PUBLISH channel` 转发 `T`（无订阅方时消息即丢失）。
+    #[must_use]
+    pub fn forward<T: Send + Sync + 'static>(
+        self,
+        channel: impl Into<String>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.push::<T>(channel.into(), false, codec)
+    }
+    /// 以 `XADD key * data <payload>` 追加到流，配合 [`RedisBridgeIn::receive_stream`] 获得至少一次投递。
+    #[must_use]
+    pub fn forward_stream<T: Send + Sync + 'static>(
+        self,
+        key: impl Into<String>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.push::<T>(key.into(), true, codec)
+    }
+    fn push<T: Send + Sync + 'static>(
+        mut self,
+        key: String,
+        stream: bool,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        let key: Arc<str> = key.into();
+        self.forwards.push(Box::new(move |ctx, tx| {
+            let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+            let ctx = ctx.__fork();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => break,
+                        msg = sub.recv() => {
+                            let Some(msg) = msg else { break };
+                            let cmd = Command { key: key.clone(), stream, payload: codec.encode_bytes(&msg) };
+                            if tx.send(cmd).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            })
+        }));
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> RedisHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for RedisBridgeOut {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            addr,
+            forwards,
+            handle,
+        } = *self;
+        let (tx, mut rx) = mpsc::channel(OUT_QUEUE);
+        let workers: Vec<_> = forwards.into_iter().map(|f| f(&ctx, tx.clone())).collect();
+        drop(tx);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        let mut conn = connect_logged(&addr).await;
+        let mut retry_at = tokio::time::Instant::now() + RECONNECT_INTERVAL;
+        let mut batch = Vec::with_capacity(PIPELINE);
+        loop {
+            tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                n = rx.recv_many(&mut batch, PIPELINE) => {
+                    if n == 0 {
+                        break;
+                    }
+                    if conn.is_none() && tokio::time::Instant::now() >= retry_at {
+                        conn = connect_logged(&addr).await;
+                        retry_at = tokio::time::Instant::now() + RECONNECT_INTERVAL;
+                    }
+                    let sent = match conn.as_mut() {
+                        Some(c) => send_batch(c, &batch).await,
+                        None => Err(std::io::ErrorKind::NotConnected.into()),
+                    };
+                    match sent {
+                        Ok(failed) => {
+                            handle.frames.fetch_add((batch.len() - failed) as u64, Ordering::Relaxed);
+                            handle.dropped.fetch_add(failed as u64, Ordering::Relaxed);
+                        }
+                        Err(e) => {
+                            if conn.take().is_some() {
+                                tracing::warn!(addr = %addr, error = %e, "redis bridge write failed; reconnecting");
+                            }
+                            handle.dropped.fetch_add(batch.len() as u64, Ordering::Relaxed);
+                        }
+                    }
+                    batch.clear();
+                }
+            }
+        }
+        for w in workers {
+            w.abort();
+        }
+        Ok(())
+    }
+}
+
+// 流水线写出一批命令并读取全部应答；返回服务端报错的条数
+async fn send_batch(conn: &mut Conn, batch: &[Command]) -> std::io::Result<usize> {
+    let mut buf = Vec::new();
+    for cmd in batch {
+        let args: [&[u8]; 5];
+        let args: &[&[u8]] = if cmd.stream {
+            args = [
+                b"XADD",
+                cmd.key.as_bytes(),
+                b"*",
+                STREAM_FIELD.as_bytes(),
+                &cmd.payload,
+            ];
+            &args
+        } else {
+            &[b"PUBLISH", cmd.key.as_bytes(), &cmd.payload]
+        };
+        buf.extend_from_slice(&encode_command(args));
+    }
+    conn.get_mut().write_all(&buf).await?;
+    let mut failed = 0;
+    for cmd in batch {
+        if let Reply::Error(e) = read_reply(conn).await? {
+            tracing::warn!(key = %cmd.key, error = %e, "redis bridge command rejected");
+            failed += 1;
+        }
+    }
+    Ok(failed)
+}
+
+async fn connect_logged(addr: &str) -> Option<Conn> {
+    match connect(addr).await {
+        Ok(c) => {
+            tracing::info!(addr = %addr, "redis bridge connected");
+            Some(c)
+        }
+        Err(e) => {
+            tracing::warn!(addr = %addr, error = %e, "redis bridge connect failed");
+            None
+        }
+    }
+}
+
+// ---- 入站 ----
+
+/// Redis 入站桥：订阅频道 / 以消费组读取流，解码后在本地发布。
+pub struct RedisBridgeIn {
+    addr: String,
+    channels: HashMap<String, DecodeFn>,
+    streams: HashMap<String, DecodeFn>,
+    group: String,
+    consumer: String,
+    handle: RedisHandle,
+}
+
+impl RedisBridgeIn {
+    /// 连接 Redis（`host:port`）。流消费组默认 `microbus`，消费者名默认 `microbus-<pid>`。
+    #[must_use]
+    pub fn connect(addr: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into(),
+            channels: HashMap::new(),
+            streams: HashMap::new(),
+            group: "microbus".to_owned(),
+            consumer: format!("microbus-{}", std::process::id()),
+            handle: RedisHandle::default(),
+        }
+    }
+    /// 订阅 `channel`，按 `codec` 解码为 `T` 发布。
+    #[must_use]
+    pub fn receive<T: Send + Sync + 'static>(
+        mut self,
+        channel: impl Into<String>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.channels.insert(channel.into(), decoder(codec));
+        self
+    }
+    /// 以消费组读取流 `key`（流与组不存在时创建，新组从 `$` 起读），本地发布后 `XACK`。
+    #[must_use]
+    pub fn receive_stream<T: Send + Sync + 'static>(
+        mut self,
+        key: impl Into<String>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.streams.insert(key.into(), decoder(codec));
+        self
+    }
+    /// 流消费组与消费者名（同组多进程分摊消费，不同组各自全量消费）。
+    #[must_use]
+    pub fn consumer_group(mut self, group: impl Into<String>, consumer: impl Into<String>) -> Self {
+        self.group = group.into();
+        self.consumer = consumer.into();
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> RedisHandle {
+        self.handle.clone()
+    }
+}
+
+fn decoder<T: Send + Sync + 'static>(codec: impl MessageCodec<T>) -> DecodeFn {
+    Box::new(move |bytes| codec.decode_bytes(bytes).map(ErasedEvent::new))
+}
+
+#[async_trait]
+impl Component for RedisBridgeIn {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let this = Arc::new(*self);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        let mut workers = Vec::new();
+        if !this.channels.is_empty() {
+            let (this, ctx) = (this.clone(), ctx.__fork());
+            workers.push(tokio::spawn(async move {
+                reconnect_loop(&this, &ctx, "pubsub", run_pubsub).await;
+            }));
+        }
+        if !this.streams.is_empty() {
+            let (this, ctx) = (this.clone(), ctx.__fork());
+            workers.push(tokio::spawn(async move {
+                reconnect_loop(&this, &ctx, "streams", run_streams).await;
+            }));
+        }
+        crate::component::__recv_stop(&ctx).await;
+        for w in workers {
+            w.abort();
+        }
+        Ok(())
+    }
+}
+
+// 连接断开后按间隔重连，直到停机
+async fn reconnect_loop<F>(this: &RedisBridgeIn, ctx: &ComponentContext, mode: &str, session: F)
+where
+    F: for<'a> Fn(
+        &'a RedisBridgeIn,
+        &'a ComponentContext,
+        &'a mut Conn,
+    ) -> Pin<Box<dyn Future<Output = std::io::Result<()>> + Send + 'a>>,
+{
+    loop {
+        let outcome = match connect(&this.addr).await {
+            Ok(mut conn) => {
+                tracing::info!(addr = %this.addr, mode, "redis bridge connected");
+                session(this, ctx, &mut conn).await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = outcome {
+            tracing::warn!(addr = %this.addr, mode, error = %e, "redis bridge session ended; reconnecting");
+        }
+        tokio::select! {
+            () = crate::component::__recv_stop(ctx) => return,
+            () = tokio::time::sleep(RECONNECT_INTERVAL) => {}
+        }
+    }
+}
+
+fn run_pubsub<'a>(
+    this: &'a RedisBridgeIn,
+    ctx: &'a ComponentContext,
+    conn: &'a mut Conn,
+) -> Pin<Box<dyn Future<Output = std::io::Result<()>> + Send + 'a>> {
+    Box::pin(async move {
+        let mut args: Vec<&[u8]> = vec![b"SUBSCRIBE"];
+        args.extend(this.channels.keys().map(String::as_bytes));
+        conn.get_mut().write_all(&encode_command(&args)).await?;
+        loop {
+            let reply = read_reply(conn).await?;
+            let Some(mut parts) = reply.into_array() else {
+                continue;
+            };
+            // ["message", channel, payload]；订阅确认等其它推送忽略
+            if parts.len() != 3 {
+                continue;
+            }
+            let payload = parts.pop().and_then(Reply::into_bytes);
+            let channel = parts.pop().and_then(Reply::into_text);
+            let kind = parts.pop().and_then(Reply::into_text);
+            if kind.as_deref() != Some("message") {
+                continue;
+            }
+            let (Some(channel), Some(payload)) = (channel, payload) else {
+                continue;
+            };
+            deliver(this, ctx, &this.channels, &channel, &payload).await;
+        }
+    })
+}
+
+fn run_streams<'a>(
+    this: &'a RedisBridgeIn,
+    ctx: &'a ComponentContext,
+    conn: &'a mut Conn,
+) -> Pin<Box<dyn Future<Output = std::io::Result<()>> + Send + 'a>> {
+    Box::pin(async move {
+        let group = this.group.as_bytes();
+        for key in this.streams.keys() {
+            // 组已存在（BUSYGROUP）视为正常
+            if let Reply::Error(e) = call(
+                conn,
+                &[
+                    b"XGROUP",
+                    b"CREATE",
+                    key.as_bytes(),
+                    group,
+                    b"$",
+                    b"MKSTREAM",
+                ],
+            )
+            .await?
+            {
+                if !e.starts_with("BUSYGROUP") {
+                    return Err(protocol_error(e));
+                }
+            }
+        }
+        let keys: Vec<&String> = this.streams.keys().collect();
+        let block = STREAM_BLOCK_MS.to_string();
+        let count = STREAM_COUNT.to_string();
+        let mut args: Vec<&[u8]> = vec![
+            b"XREADGROUP",
+            b"GROUP",
+            group,
+            this.consumer.as_bytes(),
+            b"COUNT",
+            count.as_bytes(),
+            b"BLOCK",
+            block.as_bytes(),
+            b"STREAMS",
+        ];
+        args.extend(keys.iter().map(|k| k.as_bytes()));
+        args.extend(keys.iter().map(|_| b">".as_slice()));
+        loop {
+            let reply = call(conn, &args).await?;
+            if let Reply::Error(e) = reply {
+                return Err(protocol_error(e));
+            }
+            // [[key, [[id, [field, value, ..]], ..]], ..]；超时为 nil
+            for stream in reply.into_array().unwrap_or_default() {
+                let Some(mut stream) = stream.into_array().filter(|s| s.len() == 2) else {
+                    continue;
+                };
+                let entries = stream.pop().and_then(Reply::into_array).unwrap_or_default();
+                let Some(key) = stream.pop().and_then(Reply::into_text) else {
+                    continue;
+                };
+                for entry in entries {
+                    let Some(mut entry) = entry.into_array().filter(|e| e.len() == 2) else {
+                        continue;
+                    };
+                    let fields = entry.pop().and_then(Reply::into_array).unwrap_or_default();
+                    let Some(id) = entry.pop().and_then(Reply::into_text) else {
+                        continue;
+                    };
+                    let payload = field_value(fields, STREAM_FIELD);
+                    match payload {
+                        Some(payload) => deliver(this, ctx, &this.streams, &key, &payload).await,
+                        None => {
+                            this.handle.dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    // 已发布或无法解码的条目均确认，避免毒消息反复投递
+                    call(conn, &[b"XACK", key.as_bytes(), group, id.as_bytes()]).await?;
+                }
+            }
+        }
+    })
+}
+
+fn field_value(fields: Vec<Reply>, name: &str) -> Option<Vec<u8>> {
+    let mut it = fields.into_iter();
+    while let (Some(k), Some(v)) = (it.next(), it.next()) {
+        if k.into_text().as_deref() == Some(name) {
+            return v.into_bytes();
+        }
+    }
+    None
+}
+
+async fn deliver(
+    this: &RedisBridgeIn,
+    ctx: &ComponentContext,
+    decoders: &HashMap<String, DecodeFn>,
+    key: &str,
+    payload: &[u8],
+) {
+    let decoded = match decoders.get(key) {
+        Some(decode) => decode(payload),
+        None => Err("key not registered".to_owned()),
+    };
+    match decoded {
+        Ok(ev) => {
+            this.handle.frames.fetch_add(1, Ordering::Relaxed);
+            crate::component::__publish_erased(ctx, ev).await;
+        }
+        Err(e) => {
+            tracing::warn!(key, error = %e, "redis bridge message rejected");
+            this.handle.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! 回放组件：读取 `Recorder` 写出的录制文件，按原始（或加速）节奏重新发布经编解码器录制的消息。
+//!
+//! 通过 `App::add_component(replay)` 显式启用；仅回放已登记解码器且以 codec 录制的记录，其余跳过。
+//! 记录按类型名（`std::any::type_name`）匹配，录制与回放须使用同一版本的类型定义；
+//! 以 `Recorder::message` 录制的 [`BusMessage`] 类型经 `message` 按稳定名称匹配并检查版本（不兼容时启动失败）。
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::bus::ErasedEvent;
+use crate::codec::{BusMessage, Compatibility, MessageCodec, Schema, VersionGate};
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+use crate::events::SchemaMismatch;
+use crate::recorder::{parse_line, KIND_CODEC};
+
+type DecodeFn = Box<dyn Fn(&str) -> std::result::Result<ErasedEvent, String> + Send + Sync>;
+
+#[derive(Debug, Clone, Copy)]
+enum Pacing {
+    // 按录制时间间隔 / 倍速
+    Scaled(f64),
+    // 忽略时间间隔，依次立即发布
+    Immediate,
+}
+
+pub struct Replay {
+    path: PathBuf,
+    decoders: HashMap<&'static str, (DecodeFn, Option<Schema>)>,
+    pacing: Pacing,
+}
+
+impl Replay {
+    #[must_use]
+    pub fn from_file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            decoders: HashMap::new(),
+            pacing: Pacing::Scaled(1.0),
+        }
+    }
+    /// 登记类型 `T` 的解码器（须与录制时的 `Recorder::codec` 对应）。
+    #[must_use]
+    pub fn codec<T: Send + Sync + 'static>(mut self, codec: impl MessageCodec<T>) -> Self {
+        self.decoders.insert(
+            std::any::type_name::<T>(),
+            (
+                Box::new(move |text| codec.decode(text).map(ErasedEvent::new)),
+                None,
+            ),
+        );
+        self
+    }
+    /// 登记 [`BusMessage`] 类型 `T`（须与录制时的 `Recorder::message` 对应），按名称匹配并检查版本。
+    #[must_use]
+    pub fn message<T: BusMessage>(mut self, codec: impl MessageCodec<T>) -> Self {
+        self.decoders.insert(
+            T::NAME,
+            (
+                Box::new(move |text| codec.decode(text).map(ErasedEvent::new)),
+                Some(Schema::of::<T>()),
+            ),
+        );
+        self
+    }
+    /// 倍速回放：`factor > 1` 加速；非正数按立即回放处理。
+    #[must_use]
+    pub fn speed(mut self, factor: f64) -> Self {
+        self.pacing = if factor > 0.0 {
+            Pacing::Scaled(factor)
+        } else {
+            Pacing::Immediate
+        };
+        self
+    }
+    /// 忽略录制时间间隔，尽快依次发布。
+    #[must_use]
+    pub const fn immediate(mut self) -> Self {
+        self.pacing = Pacing::Immediate;
+        self
+    }
+
+    // 读取并解码全部记录：(录制时间 µs, 消息)、跳过条数、待发布的版本不一致事件
+    fn load(&self, component: &'static str) -> Result<Loaded> {
+        let text = std::fs::read_to_string(&self.path).map_err(|e| {
+            MicrobusError::Dynamic(format!("replay: cannot read {}: {e}", self.path.display()))
+        })?;
+        let gate = VersionGate::default();
+        let mut events = Vec::new();
+        let mut mismatches = Vec::new();
+        let mut skipped = 0;
+        for (no, line) in text.lines().enumerate() {
+            let Some(rec) = parse_line(line) else {
+                return Err(MicrobusError::Dynamic(format!(
+                    "replay: malformed record at {}:{}",
+                    self.path.display(),
+                    no + 1
+                )));
+            };
+            let (name, version) = crate::codec::split_wire_name(rec.type_name);
+            let (decoder, schema) = match self.decoders.get(name) {
+                Some(d) if rec.kind == KIND_CODEC => d,
+                _ => {
+                    skipped += 1;
+                    continue;
+                }
+            };
+            if let (Some(schema), Some(version)) = (schema, version) {
+                let (compat, ev) = gate.observe(component, schema, version);
+                if compat == Compatibility::Incompatible {
+                    return Err(MicrobusError::Dynamic(format!(
+                        "replay: {name} version {version} at {}:{} is incompatible with local version {} (min {})",
+                        self.path.display(),
+                        no + 1,
+                        schema.version,
+                        schema.min_compatible
+                    )));
+                }
+                mismatches.extend(ev);
+            }
+            let ev = decoder(&rec.payload).map_err(|e| {
+                MicrobusError::Dynamic(format!(
+                    "replay: cannot decode {} at {}:{}: {e}",
+                    rec.type_name,
+                    self.path.display(),
+                    no + 1
+                ))
+            })?;
+            events.push((rec.micros, ev));
+        }
+        Ok(Loaded {
+            events,
+            skipped,
+            mismatches,
+        })
+    }
+}
+
+struct Loaded {
+    events: Vec<(u64, ErasedEvent)>,
+    skipped: usize,
+    mismatches: Vec<SchemaMismatch>,
+}
+
+#[async_trait]
+impl Component for Replay {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        // 读取与解码在启动屏障之前完成：文件错误按启动失败处理
+        let Loaded {
+            events,
+            skipped,
+            mismatches,
+        } = self.load(ctx.component_name()).inspect_err(|_| {
+            crate::component::__startup_mark_failed(&ctx);
+        })?;
+        // 有限数据源：回放完毕（run 返回）即结束
+        let _source = ctx.register_source();
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        for ev in mismatches {
+            crate::component::__publish_auto(&ctx, ev).await;
+        }
+        let total = events.len();
+        let mut prev = events.first().map_or(0, |(t, _)| *t);
+        for (at, ev) in events {
+            if let Pacing::Scaled(factor) = self.pacing {
+                let gap = Duration::from_micros(at.saturating_sub(prev)).div_f64(factor);
+                prev = at;
+                if !gap.is_zero() {
+                    tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => return Ok(()),
+                        () = crate::rt::sleep(gap) => {}
+                    }
+                }
+            }
+            crate::component::__publish_erased(&ctx, ev).await;
+        }
+        tracing::info!(
+            path = %self.path.display(),
+            published = total,
+            skipped,
+            "replay finished"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+//! 最小运行时抽象：框架与宏生成代码经此派生任务与计时，不直接绑定 tokio 运行时。
+//!
+//! - 当前线程处于 tokio 运行时内时始终使用 tokio（默认；`tokio::time::pause` 虚拟时间照常生效）；
+//! - 否则使用按特性启用的后端：`rt-smol` 或 `rt-async-std`（同时启用时 smol 优先）；均未启用时行为同 tokio（运行时外调用 panic）。
+//! - 总线通道（`tokio::sync`）与 [`select!`] 不依赖运行时，任何执行器下均可使用；内置网络组件（桥、admin、webhook）仍需 tokio 运行时。
+//! - 路由与 fanout 逻辑位于 [`bus_core`](crate::bus_core)，不依赖 tokio；通道与时钟经 trait 注入。
+//! - `wasm32` 目标（浏览器）：不在 tokio 运行时内时经 `wasm_bindgen_futures::spawn_local` 派生、`gloo-timers` 计时，
+//!   [`Instant`] 取自 `web-time`；无多线程运行时，`Topology::worker_threads` 与 `ActiveScheduling::Dedicated` 退化为在当前线程派生。
+use std::future::Future;
+use std::time::Duration;
+
+/// 运行时无关的多路等待（即 `tokio::select!`）。
+pub use tokio::select;
+
+/// 框架计时所用的单调时刻：原生目标为 `tokio::time::Instant`（虚拟时间下同样推进），`wasm32` 为 `web_time::Instant`。
+#[cfg(not(target_arch = "wasm32"))]
+pub use tokio::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub use web_time::Instant;
+
+/// 墙钟时刻（录制、日志与崩溃包的时间戳）：原生目标为 `std::time::SystemTime`，`wasm32` 为 `web_time::SystemTime`
+///（浏览器中 `std::time::SystemTime::now()` 会 panic）。
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub use web_time::{SystemTime, UNIX_EPOCH};
+
+// `LocalOnly` 依赖单线程前提；带 atomics 的 wasm32（多线程）暂不支持
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+compile_error!("mmg-microbus does not support wasm32 with the `atomics` target feature");
+
+enum Inner<T> {
+    Tokio(tokio::task::JoinHandle<T>),
+    #[cfg(all(feature = "rt-async-std", not(feature = "rt-smol")))]
+    AsyncStd(async_std::task::JoinHandle<T>),
+    #[cfg(feature = "rt-smol")]
+    Smol(smol::Task<T>),
+    // spawn_local 无句柄：经 oneshot 取消并等待结束
+    #[cfg(target_arch = "wasm32")]
+    Local {
+        cancel: tokio::sync::oneshot::Sender<()>,
+        done: tokio::sync::oneshot::Receiver<()>,
+    },
+}
+
+/// 任务句柄：丢弃即分离（任务继续运行），`abort` 取消并等待其结束。
+pub struct JoinHandle<T>(Option<Inner<T>>);
+
+impl<T> JoinHandle<T> {
+    /// 取消任务并等待其结束；任务已结束时立即返回。
+    pub async fn abort(mut self) {
+        match self.0.take() {
+            Some(Inner::Tokio(h)) => {
+                h.abort();
+                let _ = h.await;
+            }
+            #[cfg(all(feature = "rt-async-std", not(feature = "rt-smol")))]
+            Some(Inner::AsyncStd(h)) => {
+                h.cancel().await;
+            }
+            #[cfg(feature = "rt-smol")]
+            Some(Inner::Smol(t)) => {
+                t.cancel().await;
+            }
+            #[cfg(target_arch = "wasm32")]
+            Some(Inner::Local { cancel, done }) => {
+                let _ = cancel.send(());
+                let _ = done.await;
+            }
+            None => {}
+        }
+    }
+}
+
+impl<T> Drop for JoinHandle<T> {
+    fn drop(&mut self) {
+        // smol 的 Task 丢弃即取消，显式分离以与其余后端一致
+        #[cfg(feature = "rt-smol")]
+        if let Some(Inner::Smol(t)) = self.0.take() {
+            t.detach();
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "rt-smol",
+    feature = "rt-async-std",
+    feature = "testing",
+    target_arch = "wasm32"
+))]
+fn on_tokio() -> bool {
+    tokio::runtime::Handle::try_current().is_ok()
+}
+
+// 当前线程可派生任务：处于 tokio 运行时内，或启用了其他后端
+#[cfg(feature = "testing")]
+pub(crate) fn can_spawn() -> bool {
+    cfg!(any(
+        feature = "rt-smol",
+        feature = "rt-async-std",
+        target_arch = "wasm32"
+    )) || on_tokio()
+}
+
+/// 在当前运行时上派生任务。
+///
+/// # Panics
+/// 未启用其他后端且不在 tokio 运行时内调用。
+pub fn spawn<F>(fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    #[cfg(feature = "rt-smol")]
+    if !on_tokio() {
+        return JoinHandle(Some(Inner::Smol(smol::spawn(fut))));
+    }
+    #[cfg(all(feature = "rt-async-std", not(feature = "rt-smol")))]
+    if !on_tokio() {
+        return JoinHandle(Some(Inner::AsyncStd(async_std::task::spawn(fut))));
+    }
+    #[cfg(target_arch = "wasm32")]
+    if !on_tokio() {
+        return spawn_local(fut);
+    }
+    JoinHandle(Some(Inner::Tokio(tokio::spawn(fut))))
+}
+
+// 浏览器事件循环上派生：取消信号到达时丢弃 `fut`；句柄丢弃（分离）时取消分支失效，任务继续运行
+#[cfg(target_arch = "wasm32")]
+fn spawn_local<F>(fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + 'static,
+{
+    let (cancel, cancelled) = tokio::sync::oneshot::channel::<()>();
+    let (finished, done) = tokio::sync::oneshot::channel::<()>();
+    wasm_bindgen_futures::spawn_local(async move {
+        select! {
+            _ = fut => {}
+            Ok(()) = cancelled => {}
+        }
+        let _ = finished.send(());
+    });
+    JoinHandle(Some(Inner::Local { cancel, done }))
+}
+
+// 在指定 tokio 运行时上派生任务（App 自建运行时使用）
+pub(crate) fn spawn_on<F>(handle: &tokio::runtime::Handle, fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    JoinHandle(Some(Inner::Tokio(handle.spawn(fut))))
+}
+
+// 在独立 OS 线程（自带单线程 tokio 运行时）上运行 `fut`：返回的句柄为调用方运行时上的代理任务，
+// 其结束即线程上的任务结束；`abort` 代理时取消线程上的任务，线程随之退出。
+pub(crate) fn spawn_dedicated<F>(name: &str, fut: F) -> JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    // 浏览器中无法创建线程：与其余任务同在事件循环上运行
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = name;
+        spawn(fut)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    spawn_dedicated_thread(name, fut)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_dedicated_thread<F>(name: &str, fut: F) -> JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel::<()>();
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel::<()>();
+    let spawned = std::thread::Builder::new()
+        .name(format!("microbus:{name}"))
+        .spawn(move || {
+            match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(rt) => rt.block_on(async {
+                    select! {
+                        () = fut => {}
+                        _ = cancel_rx => {}
+                    }
+                }),
+                Err(e) => tracing::error!(error = %e, "dedicated runtime setup failed"),
+            }
+            let _ = done_tx.send(());
+        });
+    if let Err(e) = spawned {
+        tracing::error!(error = %e, "dedicated thread spawn failed");
+    }
+    spawn(async move {
+        let _cancel = cancel_tx;
+        let _ = done_rx.await;
+    })
+}
+
+/// 休眠 `duration`（按首次轮询所在的运行时选择计时器）。
+pub async fn sleep(duration: Duration) {
+    #[cfg(feature = "rt-smol")]
+    if !on_tokio() {
+        smol::Timer::after(duration).await;
+        return;
+    }
+    #[cfg(all(feature = "rt-async-std", not(feature = "rt-smol")))]
+    if !on_tokio() {
+        async_std::task::sleep(duration).await;
+        return;
+    }
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    if !on_tokio() {
+        let ms = u32::try_from(duration.as_millis()).unwrap_or(u32::MAX);
+        LocalOnly(gloo_timers::future::TimeoutFuture::new(ms)).await;
+        return;
+    }
+    tokio::time::sleep(duration).await;
+}
+
+/// 休眠至 `deadline`；已过期时立即返回。
+pub async fn sleep_until(deadline: Instant) {
+    sleep(deadline.saturating_duration_since(Instant::now())).await;
+}
+
+// 浏览器计时器持有 JS 闭包（非 `Send`）；无线程的 wasm32 上只有一个执行线程，包装后可置于 `Send` future 中
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+struct LocalOnly<F>(F);
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+// SAFETY: 未启用 atomics 的 wasm32 不存在其他线程，值不会跨线程移动
+unsafe impl<F> Send for LocalOnly<F> {}
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+impl<F: Future + Unpin> Future for LocalOnly<F> {
+    type Output = F::Output;
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<F::Output> {
+        std::pin::Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+/// 让出执行权一次：当前任务重新排队，同一执行器上其余就绪任务先运行（任何执行器下均可使用）。
+pub async fn yield_now() {
+    tokio::task::yield_now().await;
+}
+
+/// 在 `duration` 内等待 `fut` 完成；超时返回 `None`。
+pub async fn timeout<F: Future>(duration: Duration, fut: F) -> Option<F::Output> {
+    select! {
+        biased;
+        v = fut => Some(v),
+        () = sleep(duration) => None,
+    }
+}
@@ -0,0 +1,473 @@
+//! 共享内存桥（特性 `bridge-shm`，仅 unix）：同机两进程间按类型经内存映射 SPSC 环形队列转发 POD 消息。
+//!
+//! - [`ShmBridgeOut`]：订阅登记类型，按字节写入对应环（每类型一个映射文件，单生产者）；环满时等待，背压传回本地发布方。
+//! - [`ShmBridgeIn`]：轮询对应环（单消费者），读出后在本地总线重新发布；环文件尚未创建时等待生产方。
+//!
+//! 只适用于 [`ShmPod`] 类型（按字节复制即有效、不含指针）；两端须使用同一类型定义（布局以类型名与大小校验）。
+//! 映射文件建议放在 `/dev/shm` 等内存文件系统；生产方重启时沿用布局一致的既有环，消费方从原进度继续。
+use std::fs::OpenOptions;
+use std::marker::PhantomData;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+
+/// 可经共享内存按字节传递的消息类型。
+///
+/// # Safety
+/// 实现者须保证：类型布局固定（`#[repr(C)]` 或基本类型组合），不含指针 / 引用 / 堆数据，
+/// 任意一份按字节复制得到的值在另一进程中依然有效。
+pub unsafe trait ShmPod: Copy + Send + Sync + 'static {}
+
+const MAGIC: u64 = 0x6d6d_6753_484d_0001; // "mmgSHM" v1
+const SHM_DEFAULT_CAPACITY: usize = 4096;
+// 消费方空闲退避：先自旋，再让出调度，长时间空闲后短眠
+const SPIN_ROUNDS: u32 = 64;
+const YIELD_ROUNDS: u32 = 1024;
+const IDLE_SLEEP: Duration = Duration::from_millis(1);
+const OPEN_RETRY: Duration = Duration::from_millis(100);
+
+// 环头：生产 / 消费游标各占独立缓存行，避免伪共享
+#[repr(C)]
+struct Header {
+    magic: AtomicU64,
+    slot_size: AtomicU64,
+    capacity: AtomicU64,
+    type_hash: AtomicU64,
+    _pad0: [u64; 4],
+    tail: AtomicU64,
+    _pad1: [u64; 7],
+    head: AtomicU64,
+    _pad2: [u64; 7],
+}
+
+const HEADER_SIZE: usize = std::mem::size_of::<Header>();
+
+// 类型指纹：类型名 + 大小（FNV-1a）
+fn type_hash<T>() -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    let size = std::mem::size_of::<T>() as u64;
+    for b in std::any::type_name::<T>().bytes().chain(size.to_le_bytes()) {
+        h ^= u64::from(b);
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h
+}
+
+// 一段 MAP_SHARED 映射；drop 时解除映射
+struct Mapping {
+    ptr: *mut u8,
+    len: usize,
+}
+
+// 映射内存仅经原子游标协调的 SPSC 协议访问
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    fn map(path: &Path, len: usize, create: bool) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(create)
+            .truncate(false)
+            .open(path)?;
+        let cur = file.metadata()?.len();
+        if cur < len as u64 {
+            if !create {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::WouldBlock,
+                    "ring not initialized yet",
+                ));
+            }
+            file.set_len(len as u64)?;
+        }
+        // SAFETY: fd 有效且文件长度不小于 len；映射在 Mapping drop 时解除
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self {
+            ptr: ptr.cast(),
+            len,
+        })
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: ptr/len 来自成功的 mmap
+        unsafe {
+            libc::munmap(self.ptr.cast(), self.len);
+        }
+    }
+}
+
+struct Ring<T> {
+    map: Mapping,
+    capacity: u64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ShmPod> Ring<T> {
+    const SLOT: usize = std::mem::size_of::<T>();
+
+    fn len_for(capacity: usize) -> usize {
+        HEADER_SIZE + capacity * Self::SLOT
+    }
+
+    fn header(&self) -> &Header {
+        // SAFETY: 映射长度 >= HEADER_SIZE，页对齐满足 Header 对齐
+        unsafe { &*self.map.ptr.cast::<Header>() }
+    }
+
+    // 生产方：布局一致则沿用既有环，否则重新初始化
+    fn create(path: &Path, capacity: usize) -> std::io::Result<Self> {
+        let capacity = capacity.max(1);
+        let map = Mapping::map(path, Self::len_for(capacity), true)?;
+        let ring = Self {
+            map,
+            capacity: capacity as u64,
+            _marker: PhantomData,
+        };
+        let h = ring.header();
+        let same = h.magic.load(Ordering::Acquire) == MAGIC
+            && h.slot_size.load(Ordering::Relaxed) == Self::SLOT as u64
+            && h.capacity.load(Ordering::Relaxed) == ring.capacity
+            && h.type_hash.load(Ordering::Relaxed) == type_hash::<T>();
+        if !same {
+            // 初始化期间消费方以 magic 判定未就绪
+            h.magic.store(0, Ordering::Release);
+            h.slot_size.store(Self::SLOT as u64, Ordering::Relaxed);
+            h.capacity.store(ring.capacity, Ordering::Relaxed);
+            h.type_hash.store(type_hash::<T>(), Ordering::Relaxed);
+            h.tail.store(0, Ordering::Relaxed);
+            h.head.store(0, Ordering::Relaxed);
+            h.magic.store(MAGIC, Ordering::Release);
+        }
+        Ok(ring)
+    }
+
+    // 消费方：环未就绪返回 WouldBlock，布局不一致返回 InvalidData
+    fn open(path: &Path) -> std::io::Result<Self> {
+        let head_map = Mapping::map(path, HEADER_SIZE, false)?;
+        // SAFETY: 同 header()
+        let h = unsafe { &*head_map.ptr.cast::<Header>() };
+        if h.magic.load(Ordering::Acquire) != MAGIC {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::WouldBlock,
+                "ring not initialized yet",
+            ));
+        }
+        if h.slot_size.load(Ordering::Relaxed) != Self::SLOT as u64
+            || h.type_hash.load(Ordering::Relaxed) != type_hash::<T>()
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("ring layout mismatch for {}", std::any::type_name::<T>()),
+            ));
+        }
+        let capacity = h.capacity.load(Ordering::Relaxed);
+        if capacity == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "bad ring size",
+            ));
+        }
+        drop(head_map);
+        let len = usize::try_from(capacity)
+            .ok()
+            .and_then(|c| c.checked_mul(Self::SLOT))
+            .and_then(|s| s.checked_add(HEADER_SIZE))
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "bad ring size"))?;
+        Ok(Self {
+            map: Mapping::map(path, len, false)?,
+            capacity,
+            _marker: PhantomData,
+        })
+    }
+
+    fn slot(&self, seq: u64) -> *mut T {
+        let idx = usize::try_from(seq % self.capacity).unwrap_or(0);
+        // SAFETY: idx < capacity，槽位落在映射范围内
+        unsafe { self.map.ptr.add(HEADER_SIZE + idx * Self::SLOT).cast() }
+    }
+
+    fn try_push(&self, v: T) -> bool {
+        let h = self.header();
+        let tail = h.tail.load(Ordering::Relaxed);
+        if tail.wrapping_sub(h.head.load(Ordering::Acquire)) >= self.capacity {
+            return false;
+        }
+        // SAFETY: 单生产者；该槽位已被消费方释放（head 之后）
+        unsafe { self.slot(tail).write_unaligned(v) };
+        h.tail.store(tail.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    fn try_pop(&self) -> Option<T> {
+        let h = self.header();
+        let head = h.head.load(Ordering::Relaxed);
+        if head == h.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        // SAFETY: 单消费者；tail 的 Release 保证槽位写入可见
+        let v = unsafe { self.slot(head).read_unaligned() };
+        h.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(v)
+    }
+}
+
+/// 共享内存桥计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct ShmHandle {
+    frames: Arc<AtomicU64>,
+}
+
+impl ShmHandle {
+    /// 出站：已写入环的消息数；入站：已在本地发布的消息数。
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+}
+
+type SpawnOut =
+    Box<dyn FnOnce(&ComponentContext, ShmHandle) -> std::io::Result<JoinHandle<()>> + Send + Sync>;
+type SpawnIn = Box<dyn FnOnce(&ComponentContext, ShmHandle, bool) -> JoinHandle<()> + Send + Sync>;
+
+/// 共享内存出站桥：每个登记类型对应一个生产方环。
+pub struct ShmBridgeOut {
+    rings: Vec<SpawnOut>,
+    handle: ShmHandle,
+}
+
+impl Default for ShmBridgeOut {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShmBridgeOut {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rings: Vec::new(),
+            handle: ShmHandle::default(),
+        }
+    }
+    /// 转发类型 `T` 到 `path` 处的环（容量 `SHM_DEFAULT_CAPACITY` 条）。
+    #[must_use]
+    pub fn forward<T: ShmPod>(self, path: impl Into<PathBuf>) -> Self {
+        self.forward_with_capacity::<T>(path, SHM_DEFAULT_CAPACITY)
+    }
+    /// 同 [`forward`](Self::forward)，指定环容量（条）。
+    #[must_use]
+    pub fn forward_with_capacity<T: ShmPod>(
+        mut self,
+        path: impl Into<PathBuf>,
+        capacity: usize,
+    ) -> Self {
+        let path = path.into();
+        self.rings.push(Box::new(move |ctx, handle| {
+            let ring = Ring::<T>::create(&path, capacity)?;
+            let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+            let ctx = ctx.__fork();
+            Ok(tokio::spawn(async move {
+                loop {
+                    let msg = tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => break,
+                        msg = sub.recv() => match msg {
+                            Some(msg) => *msg,
+                            None => break,
+                        },
+                    };
+                    // 环满：等待消费方腾出槽位（停机时放弃）
+                    let mut idle = 0u32;
+                    while !ring.try_push(msg) {
+                        if crate::component::__stop_requested(&ctx) {
+                            return;
+                        }
+                        idle = backoff(idle).await;
+                    }
+                    handle.frames.fetch_add(1, Ordering::Relaxed);
+                }
+            }))
+        }));
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> ShmHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for ShmBridgeOut {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self { rings, handle } = *self;
+        // 环创建与订阅登记在启动屏障之前完成：创建失败按启动失败处理
+        let mut workers = Vec::with_capacity(rings.len());
+        for spawn in rings {
+            match spawn(&ctx, handle.clone()) {
+                Ok(w) => workers.push(w),
+                Err(e) => {
+                    crate::component::__startup_mark_failed(&ctx);
+                    return Err(MicrobusError::Dynamic(format!(
+                        "shm bridge: cannot create ring: {e}"
+                    )));
+                }
+            }
+        }
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        crate::component::__recv_stop(&ctx).await;
+        for w in workers {
+            w.abort();
+        }
+        Ok(())
+    }
+}
+
+/// 共享内存入站桥：每个登记类型对应一个消费方环。
+pub struct ShmBridgeIn {
+    rings: Vec<SpawnIn>,
+    busy_poll: bool,
+    handle: ShmHandle,
+}
+
+impl Default for ShmBridgeIn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShmBridgeIn {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rings: Vec::new(),
+            busy_poll: false,
+            handle: ShmHandle::default(),
+        }
+    }
+    /// 从 `path` 处的环接收类型 `T` 并在本地发布。
+    #[must_use]
+    pub fn receive<T: ShmPod>(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        self.rings.push(Box::new(move |ctx, handle, busy_poll| {
+            let ctx = ctx.__fork();
+            tokio::spawn(async move {
+                let Some(ring) = open_ring::<T>(&path, &ctx).await else {
+                    return;
+                };
+                let mut idle = 0u32;
+                loop {
+                    if let Some(v) = ring.try_pop() {
+                        idle = 0;
+                        handle.frames.fetch_add(1, Ordering::Relaxed);
+                        crate::component::__publish_auto(&ctx, v).await;
+                        continue;
+                    }
+                    if crate::component::__stop_requested(&ctx) {
+                        break;
+                    }
+                    idle = if busy_poll {
+                        tokio::task::yield_now().await;
+                        0
+                    } else {
+                        backoff(idle).await
+                    };
+                }
+            })
+        }));
+        self
+    }
+    /// 空闲时不休眠（仅让出调度），以 CPU 占用换取最低延迟。
+    #[must_use]
+    pub const fn busy_poll(mut self) -> Self {
+        self.busy_poll = true;
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> ShmHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for ShmBridgeIn {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            rings,
+            busy_poll,
+            handle,
+        } = *self;
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        let workers: Vec<_> = rings
+            .into_iter()
+            .map(|spawn| spawn(&ctx, handle.clone(), busy_poll))
+            .collect();
+        crate::component::__recv_stop(&ctx).await;
+        for w in workers {
+            w.abort();
+        }
+        Ok(())
+    }
+}
+
+// 等待生产方创建环；布局不一致时放弃该类型
+async fn open_ring<T: ShmPod>(path: &Path, ctx: &ComponentContext) -> Option<Ring<T>> {
+    let mut warned = false;
+    loop {
+        match Ring::<T>::open(path) {
+            Ok(ring) => return Some(ring),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::NotFound | std::io::ErrorKind::WouldBlock
+                ) =>
+            {
+                if !warned {
+                    tracing::info!(path = %path.display(), "shm bridge waiting for producer");
+                    warned = true;
+                }
+            }
+            Err(e) => {
+                tracing::error!(path = %path.display(), error = %e, "shm bridge cannot open ring");
+                return None;
+            }
+        }
+        tokio::select! {
+            () = crate::component::__recv_stop(ctx) => return None,
+            () = tokio::time::sleep(OPEN_RETRY) => {}
+        }
+    }
+}
+
+// 自旋 -> 让出 -> 短眠；返回新的空闲轮数
+async fn backoff(idle: u32) -> u32 {
+    if idle < SPIN_ROUNDS {
+        std::hint::spin_loop();
+    } else if idle < SPIN_ROUNDS + YIELD_ROUNDS {
+        tokio::task::yield_now().await;
+    } else {
+        tokio::time::sleep(IDLE_SLEEP).await;
+    }
+    idle.saturating_add(1)
+}
@@ -0,0 +1,231 @@
+//! 确定性仿真（特性 `sim`）：在暂停的 tokio 时钟与单线程运行时上运行 App，以虚拟时间驱动合成的消息时间表，
+//! 使实盘组件代码可直接用于回测。
+//!
+//! - 运行时空闲时虚拟时钟自动跳到下一个定时器：组件内的 `tokio::time::sleep` / `interval`、回放间隔与
+//!   框架内部计时都不占真实时间，一小时的时间表通常在毫秒级跑完；
+//! - 录制的消息经 [`Replay`](crate::replay::Replay) 驱动：在 [`setup`](Simulation::setup) 中 `add_component`，
+//!   其回放间隔同样按虚拟时间推进；
+//! - 单线程调度下同一输入得到同一结果；组件不应依赖 `std::time` 或真实 I/O 的时序（阻塞任务期间虚拟时钟仍可能推进）。
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use crate::app::App;
+use crate::bus::{BusHandle, ErasedEvent};
+use crate::component::{Component, ComponentContext};
+use crate::config::AppConfig;
+use crate::error::{MicrobusError, Result};
+
+type Setup = Box<dyn FnOnce(&mut App)>;
+type Captured = Vec<(Duration, Arc<dyn Any + Send + Sync>)>;
+type Capture = Box<
+    dyn FnOnce(
+        &BusHandle,
+        Instant,
+        watch::Receiver<bool>,
+    ) -> Result<tokio::task::JoinHandle<Captured>>,
+>;
+
+/// 仿真配置：`Simulation::new(cfg).setup(..).at(t, msg).capture::<T>().run_for(d)?`。
+pub struct Simulation {
+    cfg: AppConfig,
+    setup: Vec<Setup>,
+    schedule: Vec<(Duration, ErasedEvent)>,
+    captures: Vec<(TypeId, Capture)>,
+}
+
+impl Simulation {
+    #[must_use]
+    pub fn new(cfg: AppConfig) -> Self {
+        Self {
+            cfg,
+            setup: Vec::new(),
+            schedule: Vec::new(),
+            captures: Vec::new(),
+        }
+    }
+    /// 启动前配置 App（`add_component`、`inject_faults` 等）；按调用顺序执行。
+    #[must_use]
+    pub fn setup(mut self, f: impl FnOnce(&mut App) + 'static) -> Self {
+        self.setup.push(Box::new(f));
+        self
+    }
+    /// 在虚拟时刻 `at`（相对越过启动屏障之时）发布 `msg`；同一时刻按登记顺序发布。
+    #[must_use]
+    pub fn at<T: Send + Sync + 'static>(mut self, at: Duration, msg: T) -> Self {
+        self.schedule.push((at, ErasedEvent::new(msg)));
+        self
+    }
+    /// 批量登记 `(时刻, 消息)`，同 [`at`](Self::at)。
+    #[must_use]
+    pub fn schedule<T: Send + Sync + 'static>(
+        mut self,
+        events: impl IntoIterator<Item = (Duration, T)>,
+    ) -> Self {
+        self.schedule.extend(
+            events
+                .into_iter()
+                .map(|(at, msg)| (at, ErasedEvent::new(msg))),
+        );
+        self
+    }
+    /// 记录总线上的全部 `T` 及其虚拟到达时刻，结果见 [`SimReport::messages`]。
+    #[must_use]
+    pub fn capture<T: Send + Sync + 'static>(mut self) -> Self {
+        self.captures.push((
+            TypeId::of::<T>(),
+            Box::new(|bus, t0, mut done| {
+                let mut sub = bus.try_subscribe::<T>()?;
+                Ok(tokio::spawn(async move {
+                    let mut out: Captured = Vec::new();
+                    // 优先取消息：收到结束信号时队列中已有的消息仍会取完
+                    loop {
+                        tokio::select! {
+                            biased;
+                            msg = sub.recv() => match msg {
+                                Some(msg) => out.push((t0.elapsed(), msg)),
+                                None => break,
+                            },
+                            _ = done.wait_for(|d| *d) => break,
+                        }
+                    }
+                    out
+                }))
+            }),
+        ));
+        self
+    }
+
+    /// 运行至虚拟时刻 `horizon` 后停机。须在 tokio 运行时之外调用（内部自建运行时）。
+    ///
+    /// # Errors
+    /// 启动失败或 `capture` 订阅失败时返回错误。
+    pub fn run_for(self, horizon: Duration) -> Result<SimReport> {
+        self.run(horizon, false)
+    }
+    /// 开启 `stop_on_completion`，运行至自然完成或虚拟时刻 `limit`（先到者）；
+    /// 时间表驱动方作为数据源参与完成检测。
+    ///
+    /// # Errors
+    /// 同 [`run_for`](Self::run_for)。
+    pub fn run_to_completion(mut self, limit: Duration) -> Result<SimReport> {
+        self.cfg.stop_on_completion = true;
+        self.run(limit, true)
+    }
+
+    fn run(self, limit: Duration, until_complete: bool) -> Result<SimReport> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .start_paused(true)
+            .build()
+            .map_err(|e| MicrobusError::Dynamic(format!("sim: cannot build runtime: {e}")))?;
+        rt.block_on(self.drive(limit, until_complete))
+    }
+
+    async fn drive(self, limit: Duration, until_complete: bool) -> Result<SimReport> {
+        let Self {
+            cfg,
+            setup,
+            mut schedule,
+            captures,
+        } = self;
+        let t0 = Instant::now();
+        let mut app = App::new(cfg);
+        for f in setup {
+            f(&mut app);
+        }
+        if !schedule.is_empty() {
+            schedule.sort_by_key(|(at, _)| *at);
+            app.add_component(Schedule { events: schedule });
+        }
+        let bus = app.bus_handle();
+        let (done, done_rx) = watch::channel(false);
+        let mut tasks = Vec::with_capacity(captures.len());
+        for (id, capture) in captures {
+            tasks.push((id, capture(&bus, t0, done_rx.clone())?));
+        }
+        app.start().await?;
+        let deadline = tokio::time::sleep_until(t0 + limit);
+        if until_complete {
+            tokio::select! {
+                () = app.wait_for_stop() => {}
+                () = deadline => {}
+            }
+        } else {
+            deadline.await;
+        }
+        let elapsed = t0.elapsed();
+        let completed = app.is_completed();
+        app.stop();
+        done.send_replace(true);
+        let mut captured = HashMap::new();
+        for (id, task) in tasks {
+            let out = task
+                .await
+                .map_err(|e| MicrobusError::Dynamic(format!("sim: capture task failed: {e}")))?;
+            captured.insert(id, out);
+        }
+        Ok(SimReport {
+            elapsed,
+            completed,
+            captured,
+        })
+    }
+}
+
+/// 仿真结果。
+pub struct SimReport {
+    elapsed: Duration,
+    completed: bool,
+    captured: HashMap<TypeId, Captured>,
+}
+
+impl SimReport {
+    /// 结束时的虚拟时刻（相对仿真开始）。
+    #[must_use]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+    /// 是否因自然完成而结束（仅 `run_to_completion`）。
+    #[must_use]
+    pub const fn completed(&self) -> bool {
+        self.completed
+    }
+    /// 捕获的 `T` 及其虚拟到达时刻（按到达顺序）；未 `capture::<T>()` 时为空。
+    #[must_use]
+    pub fn messages<T: Send + Sync + 'static>(&self) -> Vec<(Duration, Arc<T>)> {
+        self.captured
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flatten()
+            .filter_map(|(at, msg)| Some((*at, msg.clone().downcast::<T>().ok()?)))
+            .collect()
+    }
+}
+
+// 时间表驱动组件：越过启动屏障后按虚拟时刻依次发布（有限数据源，发布完毕即结束）
+struct Schedule {
+    events: Vec<(Duration, ErasedEvent)>,
+}
+
+#[async_trait]
+impl Component for Schedule {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let _source = ctx.register_source();
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        let t0 = Instant::now();
+        for (at, ev) in self.events {
+            tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => return Ok(()),
+                () = tokio::time::sleep_until(t0 + at) => {}
+            }
+            crate::component::__publish_erased(&ctx, ev).await;
+        }
+        Ok(())
+    }
+}
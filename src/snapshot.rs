@@ -0,0 +1,263 @@
+//! 组件状态快照（特性 `snapshot`）：`#[snapshot]` 钩子周期性保存组件状态，重启时恢复，配合 journal 只回放快照之后的记录。
+//!
+//! - 组件内声明 `#[snapshot] fn save(&self) -> S` 与 `#[snapshot(restore)] fn load(&mut self, state: S)`（`S: Serialize + DeserializeOwned`，以 JSON 存储）；
+//! - App 以 [`App::snapshots`](crate::app::App::snapshots) 提供存储；恢复在 `#[init]` 之后、启动屏障之前进行，读取或解码失败即启动失败；
+//! - 保存在 `every` 周期及停机时进行，仅当组件空闲（订阅队列为空且无处理中消息）时保存，忙碌时跳过本轮；
+//! - 关联 journal 时快照记录其序号：该序号及之前的记录均已被组件处理；重启后 journal 从所有组件快照序号中最小者之后回放。
+//!   快照较新的组件可能再次收到已包含在快照中的消息（至少一次），状态更新应幂等。
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::component::ComponentContext;
+use crate::error::{MicrobusError, Result};
+use crate::journal::JournalHandle;
+
+// 周期保存：忙碌时的重试次数与间隔
+const IDLE_ATTEMPTS: u32 = 20;
+const IDLE_RETRY: Duration = Duration::from_millis(5);
+// 空闲确认间隔：覆盖“已出队、尚未计入处理中”的窗口
+const IDLE_CONFIRM: Duration = Duration::from_millis(1);
+
+/// 一份已保存的快照。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredSnapshot {
+    /// 关联 journal 的序号（未关联时为 0）。
+    pub seq: u64,
+    /// 状态的 JSON 文本。
+    pub data: String,
+}
+
+/// 快照存储：按组件类型名存取最近一份快照。
+pub trait SnapshotStore: Send + Sync + 'static {
+    /// 读取组件的最近快照；不存在时返回 `Ok(None)`。
+    ///
+    /// # Errors
+    /// 存储不可读或内容损坏。
+    fn load(&self, component: &str) -> Result<Option<StoredSnapshot>>;
+    /// 覆盖保存组件的快照。
+    ///
+    /// # Errors
+    /// 存储不可写。
+    fn save(&self, component: &str, snapshot: &StoredSnapshot) -> Result<()>;
+}
+
+/// 文件快照存储：每个组件一个 `<dir>/<component>.snap`（首行序号，其后为状态），先写临时文件再改名替换。
+pub struct FileSnapshotStore {
+    dir: PathBuf,
+}
+
+impl FileSnapshotStore {
+    /// 以 `dir` 为快照目录（保存时按需创建）。
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+    fn path(&self, component: &str, ext: &str) -> PathBuf {
+        let name: String = component
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{name}.{ext}"))
+    }
+}
+
+fn store_err(path: &std::path::Path, e: impl std::fmt::Display) -> MicrobusError {
+    MicrobusError::Dynamic(format!("snapshot: {}: {e}", path.display()))
+}
+
+impl SnapshotStore for FileSnapshotStore {
+    fn load(&self, component: &str) -> Result<Option<StoredSnapshot>> {
+        let path = self.path(component, "snap");
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(store_err(&path, e)),
+        };
+        let (seq, data) = text
+            .split_once('\n')
+            .ok_or_else(|| store_err(&path, "missing sequence line"))?;
+        let seq = seq
+            .parse()
+            .map_err(|_| store_err(&path, "malformed sequence"))?;
+        Ok(Some(StoredSnapshot {
+            seq,
+            data: data.to_owned(),
+        }))
+    }
+    fn save(&self, component: &str, snapshot: &StoredSnapshot) -> Result<()> {
+        let (tmp, path) = (self.path(component, "tmp"), self.path(component, "snap"));
+        std::fs::create_dir_all(&self.dir).map_err(|e| store_err(&self.dir, e))?;
+        std::fs::write(&tmp, format!("{}\n{}", snapshot.seq, snapshot.data))
+            .map_err(|e| store_err(&tmp, e))?;
+        std::fs::rename(&tmp, &path).map_err(|e| store_err(&path, e))
+    }
+}
+
+/// 快照配置：`app.snapshots(Snapshots::new(store).every(d).journal(&handle))`。
+pub struct Snapshots {
+    store: Arc<dyn SnapshotStore>,
+    every: Option<Duration>,
+    journal: Option<JournalHandle>,
+}
+
+impl Snapshots {
+    /// 仅在停机时保存；`every` 开启周期保存。
+    #[must_use]
+    pub fn new(store: impl SnapshotStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            every: None,
+            journal: None,
+        }
+    }
+    /// 周期保存间隔。
+    #[must_use]
+    pub const fn every(mut self, interval: Duration) -> Self {
+        self.every = Some(interval);
+        self
+    }
+    /// 关联 journal：快照记录其序号，重启时据此确定回放起点。
+    #[must_use]
+    pub fn journal(mut self, journal: &JournalHandle) -> Self {
+        self.journal = Some(journal.clone());
+        self
+    }
+}
+
+/// 内部：组件处理中计数（仅声明 `#[snapshot]` 的组件由宏生成代码维护）。
+#[doc(hidden)]
+#[derive(Default)]
+pub struct __Activity {
+    in_flight: AtomicUsize,
+    handled: AtomicU64,
+}
+
+impl __Activity {
+    #[must_use]
+    pub fn new() -> Arc<Self> {
+        Arc::default()
+    }
+    pub fn begin(&self) {
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+    }
+    pub fn end(&self) {
+        self.handled.fetch_add(1, Ordering::AcqRel);
+        self.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// 内部：读取并解码组件快照，同时向关联 journal 报告恢复序号。
+///
+/// # Errors
+/// 存储读取或解码失败。
+#[doc(hidden)]
+pub fn __restore<S: DeserializeOwned>(ctx: &ComponentContext) -> Result<Option<S>> {
+    let Some(cfg) = ctx.snapshots() else {
+        return Ok(None);
+    };
+    let component = ctx.component_name();
+    let Some(stored) = cfg.store.load(component)? else {
+        if let Some(journal) = &cfg.journal {
+            journal.restored(0);
+        }
+        return Ok(None);
+    };
+    let state = serde_json::from_str(&stored.data)
+        .map_err(|e| MicrobusError::Dynamic(format!("snapshot: {component}: {e}")))?;
+    if let Some(journal) = &cfg.journal {
+        journal.restored(stored.seq);
+    }
+    tracing::info!(component, seq = stored.seq, "component state restored");
+    Ok(Some(state))
+}
+
+/// 内部：按配置周期保存（未配置 `every` 时立即返回）。
+#[doc(hidden)]
+pub async fn __save_periodically<S: Serialize>(
+    ctx: &ComponentContext,
+    activity: &__Activity,
+    state: impl Fn() -> S,
+) {
+    let Some(cfg) = ctx.snapshots() else {
+        return;
+    };
+    let Some(every) = cfg.every else {
+        return;
+    };
+    loop {
+        crate::rt::sleep(every).await;
+        save(ctx, &cfg, activity, &state, IDLE_ATTEMPTS).await;
+    }
+}
+
+/// 内部：停机时保存一次（停机宽限有限，忙碌即放弃，以上一份快照与 journal 为准）。
+#[doc(hidden)]
+pub async fn __save_final<S: Serialize>(
+    ctx: &ComponentContext,
+    activity: &__Activity,
+    state: impl Fn() -> S,
+) {
+    if let Some(cfg) = ctx.snapshots() {
+        save(ctx, &cfg, activity, &state, 1).await;
+    }
+}
+
+async fn save<S: Serialize>(
+    ctx: &ComponentContext,
+    cfg: &Snapshots,
+    activity: &__Activity,
+    state: &impl Fn() -> S,
+    attempts: u32,
+) {
+    let component = ctx.component_name();
+    for attempt in 0..attempts {
+        if attempt > 0 {
+            crate::rt::sleep(IDLE_RETRY).await;
+        }
+        let Some(seq) = quiescent(ctx, cfg, activity).await else {
+            continue;
+        };
+        let data = match serde_json::to_string(&state()) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::warn!(component, error = %e, "snapshot encode failed");
+                return;
+            }
+        };
+        match cfg.store.save(component, &StoredSnapshot { seq, data }) {
+            Ok(()) => tracing::debug!(component, seq, "component state saved"),
+            Err(e) => tracing::warn!(component, error = %e, "snapshot save failed"),
+        }
+        return;
+    }
+    tracing::debug!(component, "snapshot skipped: component busy");
+}
+
+// 快照点：journal 中该序号及之前的记录均已入队，且本组件已全部处理
+async fn quiescent(ctx: &ComponentContext, cfg: &Snapshots, activity: &__Activity) -> Option<u64> {
+    let seq = match &cfg.journal {
+        Some(journal) => journal.checkpoint()?,
+        None => 0,
+    };
+    let handled = activity.handled.load(Ordering::Acquire);
+    if !idle(ctx, activity) {
+        return None;
+    }
+    crate::rt::sleep(IDLE_CONFIRM).await;
+    (idle(ctx, activity) && activity.handled.load(Ordering::Acquire) == handled).then_some(seq)
+}
+
+fn idle(ctx: &ComponentContext, activity: &__Activity) -> bool {
+    activity.in_flight.load(Ordering::Acquire) == 0
+        && ctx
+            .bus()
+            .subscriber_probes()
+            .iter()
+            .filter(|p| p.component == ctx.component_name() && !p.weak)
+            .all(|p| p.depth().is_none_or(|(depth, _)| depth == 0))
+}
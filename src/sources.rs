@@ -0,0 +1,219 @@
+//! 行输入源组件（特性 `sources`）：跟随文件追加内容（`tail -f`）或读取标准输入，逐行发布 `String` 或经闭包解析后的类型。
+//!
+//! - `LineSource::tail(path)`：默认从文件末尾开始，只发布之后追加的行（`from_start()` 从头读取）；按 `poll_interval` 轮询，
+//!   文件变短（截断 / 轮转）时从头重读，文件不存在时等待其出现；
+//! - `LineSource::stdin()`：读到 EOF 后保持空闲直至停机；作为有限数据源参与自然完成检测（EOF 即结束）；
+//! - 行尾的 `\r\n` / `\n` 被去除；未以换行结尾的残行等待后续内容补齐；`parse` 返回 `None` 的行计入 `skipped()`。
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, BufReader};
+
+use crate::component::{Component, ComponentContext, SourceGuard};
+use crate::error::Result;
+
+const DEFAULT_POLL: Duration = Duration::from_millis(200);
+
+type ParseFn<T> = Box<dyn Fn(&str) -> Option<T> + Send + Sync>;
+
+enum Input {
+    Tail {
+        path: PathBuf,
+        from_start: bool,
+        poll: Duration,
+    },
+    Stdin,
+}
+
+/// 行源计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct SourceHandle {
+    lines: Arc<AtomicU64>,
+    published: Arc<AtomicU64>,
+    skipped: Arc<AtomicU64>,
+}
+
+impl SourceHandle {
+    /// 读取的完整行数。
+    #[must_use]
+    pub fn lines(&self) -> u64 {
+        self.lines.load(Ordering::Relaxed)
+    }
+    /// 发布的消息数。
+    #[must_use]
+    pub fn published(&self) -> u64 {
+        self.published.load(Ordering::Relaxed)
+    }
+    /// 解析闭包拒绝的行数。
+    #[must_use]
+    pub fn skipped(&self) -> u64 {
+        self.skipped.load(Ordering::Relaxed)
+    }
+}
+
+pub struct LineSource<T> {
+    input: Input,
+    parse: ParseFn<T>,
+    handle: SourceHandle,
+}
+
+impl LineSource<String> {
+    /// 跟随文件 `path` 的追加内容，每行发布一条 `String`。
+    #[must_use]
+    pub fn tail(path: impl Into<PathBuf>) -> Self {
+        Self::with_input(Input::Tail {
+            path: path.into(),
+            from_start: false,
+            poll: DEFAULT_POLL,
+        })
+    }
+    /// 读取标准输入，每行发布一条 `String`。
+    #[must_use]
+    pub fn stdin() -> Self {
+        Self::with_input(Input::Stdin)
+    }
+    fn with_input(input: Input) -> Self {
+        Self {
+            input,
+            parse: Box::new(|line| Some(line.to_owned())),
+            handle: SourceHandle::default(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> LineSource<T> {
+    /// 以 `f` 把每行解析为 `U` 后发布；返回 `None` 的行跳过。
+    #[must_use]
+    pub fn parse<U>(self, f: impl Fn(&str) -> Option<U> + Send + Sync + 'static) -> LineSource<U> {
+        LineSource {
+            input: self.input,
+            parse: Box::new(f),
+            handle: self.handle,
+        }
+    }
+    /// 跟随文件时从头读取已有内容（对 stdin 无效）。
+    #[must_use]
+    pub fn from_start(mut self) -> Self {
+        if let Input::Tail { from_start, .. } = &mut self.input {
+            *from_start = true;
+        }
+        self
+    }
+    /// 跟随文件时的轮询间隔（默认 200ms，对 stdin 无效）。
+    #[must_use]
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        if let Input::Tail { poll, .. } = &mut self.input {
+            *poll = interval;
+        }
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> SourceHandle {
+        self.handle.clone()
+    }
+
+    async fn emit(&self, ctx: &ComponentContext, line: &str) {
+        self.handle.lines.fetch_add(1, Ordering::Relaxed);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (self.parse)(line) {
+            Some(v) => {
+                self.handle.published.fetch_add(1, Ordering::Relaxed);
+                crate::component::__publish_auto(ctx, v).await;
+            }
+            None => {
+                self.handle.skipped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    async fn run_stdin(&self, ctx: &ComponentContext, source: Option<SourceGuard>) {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        loop {
+            crate::rt::select! {
+                () = crate::component::__recv_stop(ctx) => return,
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => self.emit(ctx, &line).await,
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "stdin source: read failed");
+                        break;
+                    }
+                },
+            }
+        }
+        drop(source);
+        crate::component::__recv_stop(ctx).await;
+    }
+
+    async fn run_tail(
+        &self,
+        ctx: &ComponentContext,
+        path: &PathBuf,
+        from_start: bool,
+        poll: Duration,
+    ) {
+        // 起始位置：文件当前长度（不存在视为 0）
+        let mut pos = if from_start {
+            0
+        } else {
+            tokio::fs::metadata(path).await.map_or(0, |m| m.len())
+        };
+        let mut pending = Vec::new();
+        loop {
+            match read_from(path, &mut pos, &mut pending).await {
+                Ok(()) => {
+                    while let Some(i) = pending.iter().position(|&b| b == b'\n') {
+                        let line: Vec<u8> = pending.drain(..=i).collect();
+                        self.emit(ctx, &String::from_utf8_lossy(&line[..i])).await;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "tail source: read failed")
+                }
+            }
+            crate::rt::select! {
+                () = crate::component::__recv_stop(ctx) => return,
+                () = crate::rt::sleep(poll) => {}
+            }
+        }
+    }
+}
+
+// 读取 `pos` 之后的新内容追加到 `buf`；文件变短时从头重读
+async fn read_from(path: &PathBuf, pos: &mut u64, buf: &mut Vec<u8>) -> std::io::Result<()> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    if len < *pos {
+        tracing::info!(path = %path.display(), "tail source: file truncated; reading from start");
+        *pos = 0;
+        buf.clear();
+    }
+    if len == *pos {
+        return Ok(());
+    }
+    file.seek(std::io::SeekFrom::Start(*pos)).await?;
+    let n = file.read_to_end(buf).await?;
+    *pos += n as u64;
+    Ok(())
+}
+
+#[async_trait]
+impl<T: Send + Sync + 'static> Component for LineSource<T> {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let source = matches!(self.input, Input::Stdin).then(|| ctx.register_source());
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        match &self.input {
+            Input::Tail {
+                path,
+                from_start,
+                poll,
+            } => self.run_tail(&ctx, path, *from_start, *poll).await,
+            Input::Stdin => self.run_stdin(&ctx, source).await,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+//! 单写多读状态单元：组件内一个 handler 写入、其余 handler / active 读取的常见模式。
+//!
+//! 组件方法只能取 `&self`，内部可变性不必再到处使用 `Mutex<T>`：读取为不持锁的快照（`Arc<T>`），
+//! 写入为写时复制，读方可 `changed().await` 等待下一次写入。
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// 单写多读的异步状态单元。
+///
+/// 写入应集中在一个 handler（多个写入方时各次写入仍互斥，但相互覆盖的语义由调用方负责）；
+/// 读取方持有的快照不阻塞写入，写入时若快照仍被持有则复制一份再修改。
+pub struct State<T> {
+    tx: watch::Sender<Arc<T>>,
+    version: AtomicU64,
+}
+
+impl<T: Send + Sync + 'static> State<T> {
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self {
+            tx: watch::Sender::new(Arc::new(value)),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    /// 当前值的快照；持有期间不影响写入。
+    #[must_use]
+    pub fn get(&self) -> Arc<T> {
+        self.tx.borrow().clone()
+    }
+
+    /// 以引用读取当前值（不复制 `Arc`）；闭包内勿 `.await` 或写入本单元。
+    pub fn read<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.tx.borrow())
+    }
+
+    /// 替换当前值并唤醒等待 [`changed`](Self::changed) 的读方。
+    pub fn set(&self, value: T) {
+        self.tx.send_modify(|v| {
+            *v = Arc::new(value);
+            self.version.fetch_add(1, Ordering::AcqRel);
+        });
+    }
+
+    /// 原地修改当前值（写时复制：读方仍持有旧快照时先复制），返回闭包结果。
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> R
+    where
+        T: Clone,
+    {
+        let mut out = None;
+        // 版本号在写锁内递增，被唤醒的读方看到的版本与值一致
+        self.tx.send_modify(|v| {
+            out = Some(f(Arc::make_mut(v)));
+            self.version.fetch_add(1, Ordering::AcqRel);
+        });
+        out.expect("send_modify runs the closure")
+    }
+
+    /// 写入次数（`set` / `update` 各计一次），可用于判断快照是否过期。
+    #[must_use]
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// 等待调用之后的下一次写入，返回写入后的快照。
+    pub async fn changed(&self) -> Arc<T> {
+        let mut rx = self.tx.subscribe();
+        // 发送端由 self 持有，不会关闭
+        let _ = rx.changed().await;
+        let value = rx.borrow_and_update().clone();
+        value
+    }
+}
+
+impl<T: Default + Send + Sync + 'static> Default for State<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for State<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State")
+            .field("value", &*self.tx.borrow())
+            .field("version", &self.version.load(Ordering::Acquire))
+            .finish()
+    }
+}
@@ -0,0 +1,553 @@
+//! 测试工具（特性 `testing`）：只启动指定组件，向总线注入消息并等待期望的输出。
+//!
+//! ```ignore
+//! let t = TestApp::builder().component::<Pricer>().start().await?;
+//! t.inject(Tick(1)).await;
+//! let price = t.expect::<Price>(Duration::from_secs(1)).await;
+//! ```
+//!
+//! `inject` 是框架内唯一的外部发布入口，仅用于测试；生产代码请通过组件返回值发布。
+//!
+//! 只需观察某一类型时可用 [`BusProbe`]：`BusProbe::<Price>::attach(&app)` 后按条数等待与断言。
+//!
+//! 不启动 App 的纯单元测试可用 [`MockBus`]：直接把消息调度到组件的 `#[handle]`，同步捕获返回值发布。
+//!
+//! 长时间运行的泄漏检查可用 [`Soak`]：周期采样队列积压、订阅登记项、任务数与分配量，报告单调增长的指标。
+use std::any::{Any, TypeId};
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+use crate::app::{App, AppShared};
+use crate::bus::{Bus, PublishTap};
+use crate::component::{Component, ComponentContext, ComponentFactory, InstanceFactory};
+use crate::config::AppConfig;
+use crate::error::Result;
+
+type Observed = (TypeId, Arc<dyn Any + Send + Sync>);
+
+tokio::task_local! {
+    // 标记测试侧注入的发布（tap 在发布方任务内同步回调，可据此区分来源）
+    static INJECTING: ();
+}
+
+// 旁路捕获总线上的消息，供 expect 按类型消费；outputs_only 时忽略注入消息与框架事件
+#[derive(Default)]
+struct Capture {
+    outputs_only: bool,
+    seen: Mutex<VecDeque<Observed>>,
+    history: Mutex<Vec<&'static str>>,
+    notify: Notify,
+}
+
+impl PublishTap for Capture {
+    fn on_publish(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        msg: &Arc<dyn Any + Send + Sync>,
+    ) {
+        if self.outputs_only
+            && (INJECTING.try_with(|()| ()).is_ok() || crate::events::is_framework_event(type_id))
+        {
+            return;
+        }
+        self.seen.lock().push_back((type_id, msg.clone()));
+        self.history.lock().push(type_name);
+        self.notify.notify_waiters();
+    }
+}
+
+impl Capture {
+    fn take<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        let mut seen = self.seen.lock();
+        let idx = seen.iter().position(|(id, _)| *id == TypeId::of::<T>())?;
+        let (_, msg) = seen.remove(idx)?;
+        msg.downcast().ok()
+    }
+}
+
+pub struct TestAppBuilder {
+    cfg: AppConfig,
+    only: Option<Vec<&'static str>>,
+    extra: Vec<Box<dyn ComponentFactory>>,
+    outputs_only: bool,
+}
+
+impl TestAppBuilder {
+    #[must_use]
+    pub fn config(mut self, cfg: AppConfig) -> Self {
+        self.cfg = cfg;
+        self
+    }
+    /// 选择一个自动发现的组件；从未调用时启动全部自动发现组件。
+    #[must_use]
+    pub fn component<C: Component>(mut self) -> Self {
+        self.only
+            .get_or_insert_with(Vec::new)
+            .push(std::any::type_name::<C>());
+        self
+    }
+    /// 显式添加组件实例（同 `App::add_component`）。
+    #[must_use]
+    pub fn add_component<C: Component>(mut self, component: C) -> Self {
+        self.extra.push(Box::new(InstanceFactory::new(component)));
+        self
+    }
+    /// 启动应用；返回时总线已封印，可开始注入。
+    ///
+    /// # Errors
+    /// 配置无效、组件构建或初始化失败时返回错误。
+    pub async fn start(self) -> Result<TestApp> {
+        let mut app = App::try_new(self.cfg)?;
+        if let Some(only) = self.only {
+            app.select_components(only);
+        }
+        for factory in self.extra {
+            app.add_factory(factory);
+        }
+        let capture = Arc::new(Capture {
+            outputs_only: self.outputs_only,
+            ..Capture::default()
+        });
+        app.bus_handle().add_tap(capture.clone());
+        app.start().await?;
+        Ok(TestApp { app, capture })
+    }
+}
+
+/// 测试用应用：drop 时自动停机。
+pub struct TestApp {
+    app: App,
+    capture: Arc<Capture>,
+}
+
+impl TestApp {
+    #[must_use]
+    pub fn builder() -> TestAppBuilder {
+        TestAppBuilder {
+            cfg: AppConfig::default(),
+            only: None,
+            extra: Vec::new(),
+            outputs_only: false,
+        }
+    }
+    /// 以外部来源向总线发布一条消息（走与组件发布相同的路由与背压）。
+    pub async fn inject<T: Send + Sync + 'static>(&self, msg: T) {
+        let bus = self.app.bus_handle();
+        INJECTING.scope((), bus.publish_type(msg)).await;
+    }
+    /// 等待并取出下一条 `T`（含启动以来已发布但尚未取出的）；超时返回 `None`。
+    pub async fn try_expect<T: Send + Sync + 'static>(&self, timeout: Duration) -> Option<Arc<T>> {
+        let wait = async {
+            loop {
+                // 先登记等待再检查，避免检查与通知之间的竞态
+                let notified = self.capture.notify.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if let Some(msg) = self.capture.take::<T>() {
+                    return msg;
+                }
+                notified.await;
+            }
+        };
+        crate::rt::timeout(timeout, wait).await
+    }
+    /// 同 [`try_expect`](Self::try_expect)，超时即 panic。
+    ///
+    /// # Panics
+    /// `timeout` 内未观察到 `T`。
+    pub async fn expect<T: Send + Sync + 'static>(&self, timeout: Duration) -> Arc<T> {
+        match self.try_expect::<T>(timeout).await {
+            Some(msg) => msg,
+            None => panic!("expected {} within {timeout:?}", std::any::type_name::<T>()),
+        }
+    }
+    #[must_use]
+    pub const fn app(&self) -> &App {
+        &self.app
+    }
+}
+
+/// 单组件测试工具：只运行给定组件实例（不做 inventory 自动发现），脚本化输入并捕获其全部发布。
+///
+/// 捕获结果不含经 `feed` 注入的输入与框架生命周期事件。
+pub struct ComponentHarness {
+    inner: TestApp,
+}
+
+impl ComponentHarness {
+    /// # Errors
+    /// 组件构建或初始化失败时返回错误。
+    pub async fn start<C: Component>(component: C) -> Result<Self> {
+        Self::start_with_config(component, AppConfig::default()).await
+    }
+    /// # Errors
+    /// 组件构建或初始化失败时返回错误。
+    pub async fn start_with_config<C: Component>(component: C, cfg: AppConfig) -> Result<Self> {
+        let mut builder = TestApp::builder().config(cfg).add_component(component);
+        builder.only = Some(Vec::new());
+        builder.outputs_only = true;
+        Ok(Self {
+            inner: builder.start().await?,
+        })
+    }
+    /// 向组件投递一条输入。
+    pub async fn feed<T: Send + Sync + 'static>(&self, msg: T) {
+        self.inner.inject(msg).await;
+    }
+    /// 等待并取出组件发布的下一条 `T`；超时返回 `None`。
+    pub async fn try_expect<T: Send + Sync + 'static>(&self, timeout: Duration) -> Option<Arc<T>> {
+        self.inner.try_expect(timeout).await
+    }
+    /// # Panics
+    /// `timeout` 内组件未发布 `T`。
+    pub async fn expect<T: Send + Sync + 'static>(&self, timeout: Duration) -> Arc<T> {
+        self.inner.expect(timeout).await
+    }
+    /// 组件迄今发布的全部消息类型名（按发布顺序，不受 `expect` 取出影响）。
+    #[must_use]
+    pub fn published(&self) -> Vec<&'static str> {
+        self.inner.capture.history.lock().clone()
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        // 停机需在运行时内派生回收任务；运行时已关闭时组件任务随之结束
+        if crate::rt::can_spawn() {
+            self.app.stop();
+        }
+    }
+}
+
+/// 游离总线：不属于任何 App、没有订阅者，发布即经旁路同步捕获。
+///
+/// ```ignore
+/// let bus = MockBus::new();
+/// let pricer = Pricer::default();
+/// assert!(bus.deliver_blocking(&pricer, Tick(1)));
+/// assert_eq!(bus.take::<Price>().unwrap().0, 1);
+/// ```
+///
+/// 调度不经订阅队列与 worker，`#[init]` / `#[active]` / `#[stop]` 不会执行；
+/// handler 内部若使用 tokio 计时器等运行时能力，需改用 `deliver` 并在 tokio 运行时中 await。
+pub struct MockBus {
+    bus: Bus,
+    shared: Arc<AppShared>,
+    capture: Arc<Capture>,
+}
+
+impl Default for MockBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockBus {
+    #[must_use]
+    pub fn new() -> Self {
+        let cfg = AppConfig::default();
+        let bus = Bus::new(cfg.queue_capacity);
+        let capture = Arc::new(Capture::default());
+        bus.handle().add_tap(capture.clone());
+        bus.handle().seal();
+        Self {
+            bus,
+            shared: Arc::new(AppShared::new(cfg)),
+            capture,
+        }
+    }
+    /// 组件 `C` 在此总线上的上下文，可直接传给带 `&ComponentContext` 参数的方法。
+    #[must_use]
+    pub fn context<C: Component>(&self) -> ComponentContext {
+        ComponentContext::new_with_service(
+            std::any::type_name::<C>(),
+            self.shared.clone(),
+            self.bus.handle().clone(),
+            crate::component::__new_stop_flag(),
+            crate::component::__new_startup_barrier(1),
+        )
+    }
+    /// 把 `msg` 调度到 `component` 中所有接收 `&T` 的 `#[handle]`（按声明顺序），其返回值按常规规则发布到本总线。
+    /// 返回是否有 handler 接收。
+    pub async fn deliver<C: Component, T: Send + Sync + 'static>(
+        &self,
+        component: &C,
+        msg: T,
+    ) -> bool {
+        let ctx = self.context::<C>();
+        component.__dispatch(&ctx, Arc::new(msg)).await
+    }
+    /// 同 [`deliver`](Self::deliver)，在当前线程上同步驱动完成（无需 tokio 运行时）。
+    pub fn deliver_blocking<C: Component, T: Send + Sync + 'static>(
+        &self,
+        component: &C,
+        msg: T,
+    ) -> bool {
+        block_on(self.deliver(component, msg))
+    }
+    /// 取出最早捕获且尚未取出的一条 `T`。
+    #[must_use]
+    pub fn take<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.capture.take::<T>()
+    }
+    /// 迄今发布的全部消息类型名（按发布顺序，不受 `take` 影响）。
+    #[must_use]
+    pub fn published(&self) -> Vec<&'static str> {
+        self.capture.history.lock().clone()
+    }
+}
+
+// 最小单线程执行器：handler 的 future 不依赖运行时时即可就地完成
+fn block_on<F: Future>(fut: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = std::pin::pin!(fut);
+    loop {
+        if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+            return out;
+        }
+        std::thread::park();
+    }
+}
+
+// 单类型收集 tap：按发布顺序保留全部 `T`
+struct ProbeTap<T> {
+    seen: Mutex<Vec<Arc<T>>>,
+    notify: Notify,
+}
+
+impl<T: Send + Sync + 'static> PublishTap for ProbeTap<T> {
+    fn on_publish(
+        &self,
+        type_id: TypeId,
+        _type_name: &'static str,
+        msg: &Arc<dyn Any + Send + Sync>,
+    ) {
+        if type_id != TypeId::of::<T>() {
+            return;
+        }
+        if let Ok(msg) = msg.clone().downcast::<T>() {
+            self.seen.lock().push(msg);
+            self.notify.notify_waiters();
+        }
+    }
+}
+
+/// 按类型收集总线消息的测试探针；建议在 `start()` 之前挂载以免漏掉启动期消息。
+pub struct BusProbe<T> {
+    tap: Arc<ProbeTap<T>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + Sync + 'static> BusProbe<T> {
+    #[must_use]
+    pub fn attach(app: &App) -> Self {
+        let tap = Arc::new(ProbeTap {
+            seen: Mutex::new(Vec::new()),
+            notify: Notify::new(),
+        });
+        app.bus_handle().add_tap(tap.clone());
+        Self {
+            tap,
+            _marker: PhantomData,
+        }
+    }
+    /// 挂载以来收到的全部 `T`（按发布顺序）。
+    #[must_use]
+    pub fn received(&self) -> Vec<Arc<T>> {
+        self.tap.seen.lock().clone()
+    }
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.tap.seen.lock().len()
+    }
+    /// 等待累计收到至少 `n` 条；超时返回 `false`。
+    pub async fn wait_for(&self, n: usize, timeout: Duration) -> bool {
+        let wait = async {
+            loop {
+                let notified = self.tap.notify.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if self.count() >= n {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        crate::rt::timeout(timeout, wait).await.is_some()
+    }
+    /// 在 `timeout` 内等待收到 `n` 条后断言总数恰为 `n`。
+    ///
+    /// # Panics
+    /// 超时未达到 `n` 条，或收到多于 `n` 条。
+    pub async fn assert_count(&self, n: usize, timeout: Duration) {
+        let reached = self.wait_for(n, timeout).await;
+        let got = self.count();
+        assert!(
+            reached && got == n,
+            "expected {n} x {} within {timeout:?}, got {got}",
+            std::any::type_name::<T>()
+        );
+    }
+}
+
+impl<T: PartialEq + Debug + Send + Sync + 'static> BusProbe<T> {
+    /// 在 `timeout` 内等待收到 `expected.len()` 条后断言收到的序列与 `expected` 完全一致。
+    ///
+    /// # Panics
+    /// 超时或序列不一致（含多收 / 顺序不同）。
+    pub async fn assert_received_in_order(&self, expected: &[T], timeout: Duration) {
+        let reached = self.wait_for(expected.len(), timeout).await;
+        let got = self.received();
+        let got: Vec<&T> = got.iter().map(AsRef::as_ref).collect();
+        let expected: Vec<&T> = expected.iter().collect();
+        assert!(
+            reached && got == expected,
+            "{} sequence mismatch within {timeout:?}\n  expected: {expected:?}\n  received: {got:?}",
+            std::any::type_name::<T>()
+        );
+    }
+}
+
+/// 长时间运行的增长检查：按周期采样队列积压、订阅登记项、存活任务数与（可选）分配量，
+/// 运行结束后找出单调增长的指标。
+///
+/// ```ignore
+/// app.start().await?;
+/// Soak::new(Duration::from_secs(600)).sample_every(Duration::from_secs(10)).run(&app).await.assert_bounded();
+/// ```
+///
+/// 计时使用 tokio 时钟：在 `#[tokio::test(start_paused = true)]` 下以虚拟时间运行，数分钟的负载可在秒级跑完。
+pub struct Soak {
+    duration: Duration,
+    interval: Duration,
+    warmup: usize,
+    allocated: Option<Box<dyn Fn() -> u64 + Send + Sync>>,
+}
+
+/// 一次采样。
+#[derive(Debug, Clone)]
+pub struct SoakSample {
+    /// 相对开始运行的时刻
+    pub at: Duration,
+    /// 全部订阅队列的积压条数之和
+    pub queued: usize,
+    /// 订阅登记项数（含订阅端已关闭、仍留在路由表中的项）
+    pub subscriptions: usize,
+    /// 当前 tokio 运行时的存活任务数
+    pub tasks: usize,
+    /// [`Soak::allocated`] 采样函数的读数
+    pub allocated: Option<u64>,
+}
+
+impl Soak {
+    /// 运行 `duration`，默认每秒采样一次、跳过首个采样（预热）。
+    #[must_use]
+    pub const fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            interval: Duration::from_secs(1),
+            warmup: 1,
+            allocated: None,
+        }
+    }
+    #[must_use]
+    pub const fn sample_every(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+    /// 判定增长时跳过的前 `n` 个采样（启动期的缓存填充等）。
+    #[must_use]
+    pub const fn warmup(mut self, n: usize) -> Self {
+        self.warmup = n;
+        self
+    }
+    /// 分配量采样（如计数型全局分配器的已分配字节数）；本 crate 不安装全局分配器。
+    #[must_use]
+    pub fn allocated(mut self, sample: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.allocated = Some(Box::new(sample));
+        self
+    }
+    /// 在已启动的 `app` 上采样至 `duration` 结束；负载由调用方另行驱动（组件自身或并发任务）。
+    pub async fn run(&self, app: &App) -> SoakReport {
+        let t0 = tokio::time::Instant::now();
+        let mut samples = Vec::new();
+        while t0.elapsed() < self.duration {
+            tokio::time::sleep(self.interval).await;
+            let subs = app.introspect().subscriptions;
+            samples.push(SoakSample {
+                at: t0.elapsed(),
+                queued: subs.iter().map(|s| s.depth).sum(),
+                subscriptions: subs.len(),
+                tasks: tokio::runtime::Handle::current()
+                    .metrics()
+                    .num_alive_tasks(),
+                allocated: self.allocated.as_ref().map(|f| f()),
+            });
+        }
+        SoakReport {
+            samples,
+            warmup: self.warmup,
+        }
+    }
+}
+
+/// [`Soak::run`] 的结果。
+#[derive(Debug, Clone)]
+pub struct SoakReport {
+    pub samples: Vec<SoakSample>,
+    warmup: usize,
+}
+
+impl SoakReport {
+    /// 预热之后单调不减且末值大于首值的指标（`queued` / `subscriptions` / `tasks` / `allocated`）；
+    /// 有效采样少于 3 个时不做判定。
+    #[must_use]
+    pub fn growing(&self) -> Vec<&'static str> {
+        let samples = self.samples.get(self.warmup..).unwrap_or_default();
+        let series: [(&'static str, Vec<u64>); 4] = [
+            ("queued", samples.iter().map(|s| s.queued as u64).collect()),
+            (
+                "subscriptions",
+                samples.iter().map(|s| s.subscriptions as u64).collect(),
+            ),
+            ("tasks", samples.iter().map(|s| s.tasks as u64).collect()),
+            (
+                "allocated",
+                samples.iter().filter_map(|s| s.allocated).collect(),
+            ),
+        ];
+        series
+            .into_iter()
+            .filter(|(_, v)| {
+                v.len() >= 3 && v.windows(2).all(|w| w[0] <= w[1]) && v[0] < v[v.len() - 1]
+            })
+            .map(|(name, _)| name)
+            .collect()
+    }
+    /// # Panics
+    /// 存在单调增长的指标时 panic，列出指标名与全部采样。
+    pub fn assert_bounded(&self) {
+        let growing = self.growing();
+        assert!(
+            growing.is_empty(),
+            "unbounded growth in {growing:?}\n  samples: {:#?}",
+            self.samples
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! WASM 组件宿主（特性 `wasm-host`）：以 wasmtime 加载实现最小 guest ABI 的模块，在沙箱内运行不受信任或需热替换的策略逻辑。
+//!
+//! Guest ABI（名称为 UTF-8，消息内容为编解码器字节，指针与长度均为 `i32`）：
+//! - 导出 `memory`、`microbus_alloc(len) -> ptr`（宿主写入名称与内容前调用，宿主不释放）；
+//! - 导出 `microbus_on_message(name_ptr, name_len, data_ptr, data_len)`：收到登记的输入消息；
+//! - 可选导出 `microbus_init()`：每次实例化后调用一次；
+//! - 导入 `microbus.publish(name_ptr, name_len, data_ptr, data_len)`：发布输出消息（调用返回后按顺序在总线发布）。
+//!
+//! 每次调用受燃料（指令预算）与线性内存上限约束，guest 调用在组件任务内同步执行；
+//! trap / 燃料耗尽计入 `traps()` 并以全新实例继续处理后续消息，模块可经 [`WasmHandle::reload`] 热替换。
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use wasmtime::{
+    Caller, Config, Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+    TypedFunc,
+};
+
+use crate::bus::ErasedEvent;
+use crate::codec::{BusMessage, MessageCodec};
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+use crate::rt::JoinHandle;
+
+const IN_QUEUE: usize = 1024;
+const DEFAULT_FUEL: u64 = 10_000_000;
+const DEFAULT_MAX_MEMORY: usize = 64 * 1024 * 1024;
+
+type SpawnInput = Box<
+    dyn FnOnce(&ComponentContext, mpsc::Sender<(&'static str, Vec<u8>)>) -> JoinHandle<()>
+        + Send
+        + Sync,
+>;
+type DecodeFn = Box<dyn Fn(&[u8]) -> std::result::Result<ErasedEvent, String> + Send + Sync>;
+
+/// WASM 宿主计数与热替换句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone)]
+pub struct WasmHandle {
+    engine: Engine,
+    pending: Arc<Mutex<Option<Module>>>,
+    calls: Arc<AtomicU64>,
+    traps: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl WasmHandle {
+    /// 已成功完成的 `microbus_on_message` 调用数。
+    #[must_use]
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+    /// trap、燃料耗尽或实例化失败次数（对应输入消息被丢弃）。
+    #[must_use]
+    pub fn traps(&self) -> u64 {
+        self.traps.load(Ordering::Relaxed)
+    }
+    /// guest 发布的未登记类型或解码失败的输出数。
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+    /// 热替换模块（二进制或 WAT 文本）：立即编译校验，在处理下一条消息前以新模块重新实例化；
+    /// 新模块实例化失败时保留旧实例。
+    ///
+    /// # Errors
+    /// 模块无法编译时返回错误，当前实例不受影响。
+    pub fn reload(&self, module: impl AsRef<[u8]>) -> Result<()> {
+        let module = compile(&self.engine, module.as_ref())?;
+        *self.pending.lock() = Some(module);
+        Ok(())
+    }
+}
+
+pub struct WasmHost {
+    module: Module,
+    linker: Linker<GuestState>,
+    inputs: Vec<SpawnInput>,
+    outputs: HashMap<&'static str, DecodeFn>,
+    fuel: u64,
+    max_memory: usize,
+    handle: WasmHandle,
+}
+
+impl WasmHost {
+    /// 编译模块（二进制或 WAT 文本）。
+    ///
+    /// # Errors
+    /// 模块无法编译时返回错误。
+    pub fn from_bytes(module: impl AsRef<[u8]>) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)
+            .map_err(|e| MicrobusError::Dynamic(format!("wasm: engine setup failed: {e}")))?;
+        let module = compile(&engine, module.as_ref())?;
+        let mut linker = Linker::new(&engine);
+        linker
+            .func_wrap("microbus", "publish", host_publish)
+            .map_err(|e| MicrobusError::Dynamic(format!("wasm: linker setup failed: {e}")))?;
+        Ok(Self {
+            module,
+            linker,
+            inputs: Vec::new(),
+            outputs: HashMap::new(),
+            fuel: DEFAULT_FUEL,
+            max_memory: DEFAULT_MAX_MEMORY,
+            handle: WasmHandle {
+                engine,
+                pending: Arc::new(Mutex::new(None)),
+                calls: Arc::new(AtomicU64::new(0)),
+                traps: Arc::new(AtomicU64::new(0)),
+                dropped: Arc::new(AtomicU64::new(0)),
+            },
+        })
+    }
+    /// 读取并编译模块文件。
+    ///
+    /// # Errors
+    /// 文件不可读或模块无法编译时返回错误。
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| {
+            MicrobusError::Dynamic(format!("wasm: cannot read {}: {e}", path.display()))
+        })?;
+        Self::from_bytes(bytes)
+    }
+    /// 输入类型 `T`：订阅本地总线上的全部 `T`，以 [`BusMessage::NAME`] 与 `codec` 字节交给 guest。
+    #[must_use]
+    pub fn input<T: BusMessage>(mut self, codec: impl MessageCodec<T>) -> Self {
+        self.inputs.push(Box::new(move |ctx, tx| {
+            let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+            let ctx = ctx.__fork();
+            crate::rt::spawn(async move {
+                loop {
+                    tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => break,
+                        msg = sub.recv() => {
+                            let Some(msg) = msg else { break };
+                            if tx.send((T::NAME, codec.encode_bytes(&msg))).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            })
+        }));
+        self
+    }
+    /// 输出类型 `T`：guest 以 [`BusMessage::NAME`] 发布的内容经 `codec` 解码后在总线发布。
+    #[must_use]
+    pub fn output<T: BusMessage>(mut self, codec: impl MessageCodec<T>) -> Self {
+        self.outputs.insert(
+            T::NAME,
+            Box::new(move |bytes| codec.decode_bytes(bytes).map(ErasedEvent::new)),
+        );
+        self
+    }
+    /// 每次 guest 调用（含 `microbus_init`）的燃料预算，默认 1000 万。
+    #[must_use]
+    pub const fn fuel_per_call(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+    /// guest 线性内存上限（默认 64 MiB）。
+    #[must_use]
+    pub const fn max_memory(mut self, bytes: usize) -> Self {
+        self.max_memory = bytes;
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> WasmHandle {
+        self.handle.clone()
+    }
+}
+
+fn compile(engine: &Engine, bytes: &[u8]) -> Result<Module> {
+    Module::new(engine, bytes)
+        .map_err(|e| MicrobusError::Dynamic(format!("wasm: compile failed: {e}")))
+}
+
+struct GuestState {
+    limits: StoreLimits,
+    outbox: Vec<(String, Vec<u8>)>,
+}
+
+fn host_publish(
+    mut caller: Caller<'_, GuestState>,
+    name_ptr: i32,
+    name_len: i32,
+    data_ptr: i32,
+    data_len: i32,
+) -> wasmtime::Result<()> {
+    let memory = caller
+        .get_export("memory")
+        .and_then(wasmtime::Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("guest does not export memory"))?;
+    let data = memory.data(&caller);
+    let name = std::str::from_utf8(guest_slice(data, name_ptr, name_len)?)?.to_owned();
+    let payload = guest_slice(data, data_ptr, data_len)?.to_vec();
+    caller.data_mut().outbox.push((name, payload));
+    Ok(())
+}
+
+fn guest_slice(data: &[u8], ptr: i32, len: i32) -> wasmtime::Result<&[u8]> {
+    let start = usize::try_from(ptr)?;
+    let end = start.checked_add(usize::try_from(len)?);
+    end.and_then(|end| data.get(start..end))
+        .ok_or_else(|| wasmtime::Error::msg("guest pointer out of bounds"))
+}
+
+// 一个实例及其入口；trap 后整体丢弃
+struct Guest {
+    store: Store<GuestState>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    on_message: TypedFunc<(i32, i32, i32, i32), ()>,
+}
+
+impl Guest {
+    fn new(
+        linker: &Linker<GuestState>,
+        module: &Module,
+        fuel: u64,
+        max_memory: usize,
+    ) -> wasmtime::Result<Self> {
+        let state = GuestState {
+            limits: StoreLimitsBuilder::new().memory_size(max_memory).build(),
+            outbox: Vec::new(),
+        };
+        let mut store = Store::new(module.engine(), state);
+        store.limiter(|s| &mut s.limits);
+        store.set_fuel(fuel)?;
+        let instance = linker.instantiate(&mut store, module)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("guest does not export memory"))?;
+        let alloc = instance.get_typed_func(&mut store, "microbus_alloc")?;
+        let on_message = instance.get_typed_func(&mut store, "microbus_on_message")?;
+        if let Ok(init) = instance.get_typed_func::<(), ()>(&mut store, "microbus_init") {
+            init.call(&mut store, ())?;
+        }
+        Ok(Self {
+            store,
+            memory,
+            alloc,
+            on_message,
+        })
+    }
+
+    // 投递一条消息，返回本次调用期间 guest 发布的输出
+    fn deliver(
+        &mut self,
+        fuel: u64,
+        name: &str,
+        payload: &[u8],
+    ) -> wasmtime::Result<Vec<(String, Vec<u8>)>> {
+        self.store.set_fuel(fuel)?;
+        self.store.data_mut().outbox.clear();
+        let (name_ptr, name_len) = self.write(name.as_bytes())?;
+        let (data_ptr, data_len) = self.write(payload)?;
+        self.on_message
+            .call(&mut self.store, (name_ptr, name_len, data_ptr, data_len))?;
+        Ok(std::mem::take(&mut self.store.data_mut().outbox))
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> wasmtime::Result<(i32, i32)> {
+        let len = i32::try_from(bytes.len())?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        self.memory
+            .write(&mut self.store, usize::try_from(ptr)?, bytes)?;
+        Ok((ptr, len))
+    }
+}
+
+#[async_trait]
+impl Component for WasmHost {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            mut module,
+            linker,
+            inputs,
+            outputs,
+            fuel,
+            max_memory,
+            handle,
+        } = *self;
+        let mut guest = Some(Guest::new(&linker, &module, fuel, max_memory).map_err(|e| {
+            crate::component::__startup_mark_failed(&ctx);
+            MicrobusError::Dynamic(format!("wasm: instantiate failed: {e:#}"))
+        })?);
+        // 订阅须在启动屏障（总线封印）之前登记
+        let (tx, mut rx) = mpsc::channel(IN_QUEUE);
+        let workers: Vec<_> = inputs.into_iter().map(|f| f(&ctx, tx.clone())).collect();
+        drop(tx);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        loop {
+            let (name, payload) = tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                msg = rx.recv() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+            };
+            if let Some(next) = handle.pending.lock().take() {
+                match Guest::new(&linker, &next, fuel, max_memory) {
+                    Ok(g) => {
+                        tracing::info!("wasm module reloaded");
+                        (module, guest) = (next, Some(g));
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %format!("{e:#}"), "wasm reload failed; keeping current module");
+                    }
+                }
+            }
+            // 上次 trap 后以全新实例继续
+            let g = match guest.take() {
+                Some(g) => Ok(g),
+                None => Guest::new(&linker, &module, fuel, max_memory),
+            };
+            let result = g.and_then(|mut g| {
+                let out = g.deliver(fuel, name, &payload)?;
+                Ok((g, out))
+            });
+            let out = match result {
+                Ok((g, out)) => {
+                    guest = Some(g);
+                    handle.calls.fetch_add(1, Ordering::Relaxed);
+                    out
+                }
+                Err(e) => {
+                    tracing::warn!(name, error = %format!("{e:#}"), "wasm guest trapped; message dropped");
+                    handle.traps.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                }
+            };
+            for (name, payload) in out {
+                match outputs.get(name.as_str()).map(|decode| decode(&payload)) {
+                    Some(Ok(ev)) => crate::component::__publish_erased(&ctx, ev).await,
+                    Some(Err(e)) => {
+                        tracing::debug!(%name, error = %e, "wasm output decode failed");
+                        handle.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    None => {
+                        tracing::debug!(%name, "wasm output type not registered");
+                        handle.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+        for w in workers {
+            w.abort().await;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+//! Webhook 接入组件（特性 `webhook`）：把按路径登记的 JSON POST 请求体反序列化为对应类型并在总线发布。
+//!
+//! 极简 HTTP/1.x 实现：仅处理带 `Content-Length` 的 POST，每个连接一次请求一次响应；
+//! 发布完成（订阅队列入队）后才返回 `202`，总线背压直接体现为响应延迟。
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::bus::ErasedEvent;
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+
+const MAX_HEAD: usize = 8 * 1024;
+const DEFAULT_MAX_BODY: usize = 1024 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+type DecodeFn = Box<dyn Fn(&[u8]) -> std::result::Result<ErasedEvent, String> + Send + Sync>;
+
+/// Webhook 计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct WebhookHandle {
+    accepted: Arc<AtomicU64>,
+    rejected: Arc<AtomicU64>,
+}
+
+impl WebhookHandle {
+    /// 已发布的请求数。
+    #[must_use]
+    pub fn accepted(&self) -> u64 {
+        self.accepted.load(Ordering::Relaxed)
+    }
+    /// 以非 2xx 响应拒绝的请求数（路径未登记、方法 / 鉴权 / 长度 / JSON 不合法）。
+    #[must_use]
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+}
+
+struct Config {
+    routes: HashMap<String, DecodeFn>,
+    required_header: Option<(String, String)>,
+    max_body: usize,
+    handle: WebhookHandle,
+}
+
+pub struct WebhookServer {
+    listener: std::net::TcpListener,
+    local_addr: SocketAddr,
+    cfg: Config,
+}
+
+impl WebhookServer {
+    /// 立即绑定监听地址（端口 0 由系统分配，可经 `local_addr()` 取得）。
+    ///
+    /// # Errors
+    /// 地址不可绑定时返回 IO 错误。
+    pub fn bind(addr: impl Into<SocketAddr>) -> std::io::Result<Self> {
+        let listener = std::net::TcpListener::bind(addr.into())?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        Ok(Self {
+            listener,
+            local_addr,
+            cfg: Config {
+                routes: HashMap::new(),
+                required_header: None,
+                max_body: DEFAULT_MAX_BODY,
+                handle: WebhookHandle::default(),
+            },
+        })
+    }
+    #[must_use]
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+    /// 登记路径（精确匹配，忽略查询串）：请求体按 JSON 反序列化为 `T` 后发布。
+    #[must_use]
+    pub fn route<T: DeserializeOwned + Send + Sync + 'static>(
+        mut self,
+        path: impl Into<String>,
+    ) -> Self {
+        self.cfg.routes.insert(
+            path.into(),
+            Box::new(|body| {
+                serde_json::from_slice::<T>(body)
+                    .map(ErasedEvent::new)
+                    .map_err(|e| e.to_string())
+            }),
+        );
+        self
+    }
+    /// 要求请求携带指定头（名称不区分大小写，值精确匹配），否则返回 `401`；用于共享密钥校验。
+    #[must_use]
+    pub fn require_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.cfg.required_header = Some((name.into(), value.into()));
+        self
+    }
+    /// 请求体上限（默认 1 MiB），超出返回 `413`。
+    #[must_use]
+    pub const fn max_body(mut self, bytes: usize) -> Self {
+        self.cfg.max_body = bytes;
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> WebhookHandle {
+        self.cfg.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for WebhookServer {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let Self {
+            listener,
+            local_addr,
+            cfg,
+        } = *self;
+        let listener = TcpListener::from_std(listener).map_err(|e| {
+            crate::component::__startup_mark_failed(&ctx);
+            MicrobusError::Dynamic(format!("webhook: listener setup failed: {e}"))
+        })?;
+        let cfg = Arc::new(cfg);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        tracing::info!(addr = %local_addr, routes = cfg.routes.len(), "webhook endpoint listening");
+        loop {
+            tokio::select! {
+                () = crate::component::__recv_stop(&ctx) => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        let (ctx_c, cfg) = (ctx.__fork(), cfg.clone());
+                        tokio::spawn(async move { serve_conn(stream, &ctx_c, &cfg).await });
+                    }
+                    Err(e) => tracing::warn!(error = %e, "webhook accept failed"),
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+async fn serve_conn(mut stream: TcpStream, ctx: &ComponentContext, cfg: &Config) {
+    let status =
+        match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream, cfg.max_body)).await {
+            Ok(Ok(req)) => dispatch(req, ctx, cfg).await,
+            Ok(Err(status)) => Err(status),
+            Err(_) => Err("408 Request Timeout"),
+        };
+    let status = match status {
+        Ok(()) => {
+            cfg.handle.accepted.fetch_add(1, Ordering::Relaxed);
+            "202 Accepted"
+        }
+        Err(status) => {
+            cfg.handle.rejected.fetch_add(1, Ordering::Relaxed);
+            status
+        }
+    };
+    let resp = format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    let _ = stream.write_all(resp.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+async fn dispatch(
+    req: Request,
+    ctx: &ComponentContext,
+    cfg: &Config,
+) -> std::result::Result<(), &'static str> {
+    if req.method != "POST" {
+        return Err("405 Method Not Allowed");
+    }
+    let Some(decode) = cfg
+        .routes
+        .get(req.path.split('?').next().unwrap_or_default())
+    else {
+        return Err("404 Not Found");
+    };
+    if let Some((name, value)) = &cfg.required_header {
+        let ok = req
+            .headers
+            .iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case(name) && v == value);
+        if !ok {
+            return Err("401 Unauthorized");
+        }
+    }
+    match decode(&req.body) {
+        Ok(ev) => {
+            crate::component::__publish_erased(ctx, ev).await;
+            Ok(())
+        }
+        Err(e) => {
+            tracing::debug!(path = %req.path, error = %e, "webhook payload rejected");
+            Err("400 Bad Request")
+        }
+    }
+}
+
+async fn read_request(
+    stream: &mut TcpStream,
+    max_body: usize,
+) -> std::result::Result<Request, &'static str> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    // 读到请求头结束（空行）
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() >= MAX_HEAD {
+            return Err("431 Request Header Fields Too Large");
+        }
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return Err("400 Bad Request"),
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+    };
+    let head = std::str::from_utf8(&buf[..head_end]).map_err(|_| "400 Bad Request")?;
+    let mut lines = head.split("\r\n");
+    let mut parts = lines.next().unwrap_or_default().split_whitespace();
+    let (method, path) = (
+        parts.next().unwrap_or_default().to_owned(),
+        parts.next().unwrap_or("/").to_owned(),
+    );
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_owned(), v.trim().to_owned()))
+        .collect();
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    };
+    if header("transfer-encoding").is_some() {
+        return Err("411 Length Required");
+    }
+    let len = match header("content-length") {
+        Some(v) => v.parse::<usize>().map_err(|_| "400 Bad Request")?,
+        None if method == "POST" => return Err("411 Length Required"),
+        None => 0,
+    };
+    if len > max_body {
+        return Err("413 Payload Too Large");
+    }
+    let mut body = buf.split_off(head_end);
+    body.truncate(len);
+    if body.len() < len {
+        let start = body.len();
+        body.resize(len, 0);
+        stream
+            .read_exact(&mut body[start..])
+            .await
+            .map_err(|_| "400 Bad Request")?;
+    }
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
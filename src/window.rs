@@ -0,0 +1,162 @@
+//! 窗口聚合（`#[handle(window = "1s")]`）：worker 按窗口缓存输入，窗口关闭时以整批调用 handler 一次。
+//!
+//! - 滚动窗口：`window = "1s"`，相邻窗口首尾相接、互不重叠；
+//! - 滑动窗口：另加 `every = "200ms"`，每 200ms 关闭一个长 1s 的窗口，同一条消息可出现在多个窗口中；
+//! - 窗口以 worker 启动时刻为起点、按接收时刻归属，计时与停机 select 同处一个循环；
+//! - 停机或订阅关闭时，进行中的窗口提前关闭（终点为当时时刻）并最后调用一次 handler，已到达的消息不丢；
+//! - 空窗口不调用 handler；
+//! - 每个 worker 至多缓存 [`MAX_BUFFERED`] 条消息，超出时丢弃最早的一条并记录一次 `warn`（窗口仍按时关闭，只是不再完整）。
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::bus::Envelope;
+use crate::rt::Instant;
+
+/// 一个已关闭窗口内的消息（按接收顺序），`Deref` 到 `[Arc<T>]`。
+pub struct Window<T> {
+    messages: Vec<Arc<T>>,
+    start: Instant,
+    end: Instant,
+}
+
+impl<T> Window<T> {
+    /// 窗口起点（含）。
+    #[must_use]
+    pub const fn start(&self) -> Instant {
+        self.start
+    }
+    /// 窗口终点（不含），即关闭时刻。
+    #[must_use]
+    pub const fn end(&self) -> Instant {
+        self.end
+    }
+    #[must_use]
+    pub fn messages(&self) -> &[Arc<T>] {
+        &self.messages
+    }
+
+    // `__dispatch` 直接调度不经窗口：单条消息视为仅含自身的窗口
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __single(msg: Arc<T>) -> Self {
+        let now = Instant::now();
+        Self {
+            messages: vec![msg],
+            start: now,
+            end: now,
+        }
+    }
+}
+
+impl<T> std::ops::Deref for Window<T> {
+    type Target = [Arc<T>];
+    fn deref(&self) -> &[Arc<T>] {
+        &self.messages
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Window<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Window")
+            .field("len", &self.messages.len())
+            .field("span", &self.end.saturating_duration_since(self.start))
+            .field("messages", &self.messages)
+            .finish()
+    }
+}
+
+/// 单个窗口 handler 的缓存上限（条）：滑动窗口按 `window` 时长保留消息，流量极高时以此限制内存。
+pub const MAX_BUFFERED: usize = 1 << 16;
+
+// worker 的窗口状态（时长由宏在编译期解析为纳秒）：缓存（接收时刻, 消息），按 `every` 关闭长 `len` 的窗口
+#[doc(hidden)]
+pub struct __Windower<T> {
+    len: Duration,
+    every: Duration,
+    buf: VecDeque<(Instant, Arc<T>)>,
+    close_at: Instant,
+    overflowed: bool,
+    held: crate::monitor::Held, // 缓存中的消息计入自然完成判定
+}
+
+impl<T> __Windower<T> {
+    #[must_use]
+    pub fn new(ctx: &crate::component::ComponentContext, len_nanos: u64, every_nanos: u64) -> Self {
+        let every = Duration::from_nanos(every_nanos);
+        Self {
+            len: Duration::from_nanos(len_nanos),
+            every,
+            buf: VecDeque::new(),
+            close_at: Instant::now() + every,
+            overflowed: false,
+            held: ctx.held(),
+        }
+    }
+
+    pub fn push(&mut self, env: Envelope<T>) {
+        if self.buf.len() >= MAX_BUFFERED {
+            self.buf.pop_front();
+            if !self.overflowed {
+                self.overflowed = true;
+                tracing::warn!(
+                    message_type = std::any::type_name::<T>(),
+                    limit = MAX_BUFFERED,
+                    "window buffer full, dropping oldest messages"
+                );
+            }
+        }
+        self.buf.push_back((Instant::now(), env.into_message()));
+        self.held.set(self.buf.len());
+    }
+
+    // 停机 / 订阅关闭时取出进行中的窗口（终点为当前时刻）并清空缓存；无消息时为 None
+    pub fn flush(&mut self) -> Option<Window<T>> {
+        let end = Instant::now();
+        let start = self.close_at.checked_sub(self.len).unwrap_or(end);
+        let messages: Vec<_> = self
+            .buf
+            .drain(..)
+            .filter(|(t, _)| *t >= start)
+            .map(|(_, m)| m)
+            .collect();
+        self.held.set(0);
+        (!messages.is_empty()).then_some(Window {
+            messages,
+            start,
+            end,
+        })
+    }
+
+    // 下一个非空窗口关闭时完成；仅在计时结束后修改状态，可在 select 中安全取消
+    pub async fn close(&mut self) -> Window<T> {
+        loop {
+            crate::rt::sleep_until(self.close_at).await;
+            let end = self.close_at;
+            let start = end.checked_sub(self.len).unwrap_or(end);
+            self.close_at = end + self.every;
+            while self.buf.front().is_some_and(|(t, _)| *t < start) {
+                self.buf.pop_front();
+            }
+            let messages: Vec<_> = self
+                .buf
+                .iter()
+                .take_while(|(t, _)| *t < end)
+                .map(|(_, m)| m.clone())
+                .collect();
+            // 不会落入下一个窗口的消息即刻释放（滚动窗口即本窗口全部）
+            let next_start = (end + self.every).checked_sub(self.len).unwrap_or(end);
+            while self.buf.front().is_some_and(|(t, _)| *t < next_start) {
+                self.buf.pop_front();
+            }
+            self.held.set(self.buf.len());
+            if !messages.is_empty() {
+                return Window {
+                    messages,
+                    start,
+                    end,
+                };
+            }
+        }
+    }
+}
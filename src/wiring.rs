@@ -0,0 +1,376 @@
+//! 装配校验：`#[component]` 宏为每个组件生成订阅 / 发布清单，`App::validate()` 据此在启动前找出无人发布的订阅类型
+//! 与无人订阅的发布类型；`#[component(requires(..))]` 声明的组件依赖在此排定启动阶段。
+//!
+//! 发布清单只含返回值中静态可知的类型；返回 `ErasedEvent` / `Box<dyn Any>` 等动态类型的组件、
+//! 手写 `Component` 的内置组件（桥、回放、流水线等）以及组件之外经 `BusHandle` 的发布均无法静态判定。
+use std::any::TypeId;
+use std::collections::BTreeMap;
+
+use crate::config::AppConfig;
+use crate::error::{MicrobusError, Result};
+use crate::introspect::MessageTypeInfo;
+
+/// 消息类型标识。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageType {
+    pub id: TypeId,
+    pub name: &'static str,
+    /// `size_of::<T>()`：按值发布时每条消息搬移的字节数（不含堆上载荷）。
+    pub size: usize,
+}
+
+impl MessageType {
+    #[must_use]
+    pub fn of<T: 'static>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+            size: std::mem::size_of::<T>(),
+        }
+    }
+}
+
+/// 单个组件的装配清单。
+#[derive(Debug, Clone, Default)]
+pub struct Wiring {
+    /// `#[handle]` 订阅的消息类型（不含 `Ask<Q, A>` 查询）。
+    pub subscribes: Vec<MessageType>,
+    /// 返回值发布的静态类型。
+    pub publishes: Vec<MessageType>,
+    /// 存在动态类型发布（`ErasedEvent` / `Any`），实际发布类型未知。
+    pub dynamic: bool,
+}
+
+/// 启动计划（`App::plan()`）：将要启动的组件及其装配清单、组件间的消息流向与生效配置。
+///
+/// `Display` 输出可读的组件与流向列表，便于 `--check` 式检查直接打印。
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub components: Vec<PlannedComponent>,
+    /// 由静态清单推导的组件间消息流向（不含组件之外登记的发布方 / 订阅方与框架事件）。
+    pub edges: Vec<Edge>,
+    /// 启动阶段：按 `requires` 依赖排定，后一阶段在前一阶段全部完成 init 与订阅装配后才开始构建。
+    pub phases: Vec<Vec<&'static str>>,
+    pub config: AppConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedComponent {
+    pub name: &'static str,
+    /// 装配清单；手写 `Component` 等无法静态判定时为 `None`。
+    pub wiring: Option<Wiring>,
+}
+
+/// 一条消息流向：`from` 发布的 `message` 被 `to` 订阅。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub message: &'static str,
+    pub from: &'static str,
+    pub to: &'static str,
+}
+
+impl Plan {
+    pub(crate) fn new(
+        components: Vec<(&'static str, Option<Wiring>)>,
+        phases: Vec<Vec<usize>>,
+        config: AppConfig,
+    ) -> Self {
+        let mut edges = Vec::new();
+        for (from, pw) in &components {
+            let Some(pw) = pw else { continue };
+            for t in &pw.publishes {
+                for (to, sw) in &components {
+                    if sw.as_ref().is_some_and(|w| w.subscribes.contains(t)) {
+                        edges.push(Edge {
+                            message: t.name,
+                            from,
+                            to,
+                        });
+                    }
+                }
+            }
+        }
+        let phases = phases
+            .into_iter()
+            .map(|p| p.into_iter().map(|i| components[i].0).collect())
+            .collect();
+        let components = components
+            .into_iter()
+            .map(|(name, wiring)| PlannedComponent { name, wiring })
+            .collect();
+        Self {
+            components,
+            edges,
+            phases,
+            config,
+        }
+    }
+}
+
+impl std::fmt::Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = |ts: &[MessageType]| ts.iter().map(|t| t.name).collect::<Vec<_>>().join(", ");
+        writeln!(f, "components ({}):", self.components.len())?;
+        for c in &self.components {
+            match &c.wiring {
+                Some(w) => {
+                    write!(
+                        f,
+                        "  {}: subscribes [{}]; publishes [{}]",
+                        c.name,
+                        names(&w.subscribes),
+                        names(&w.publishes)
+                    )?;
+                    if w.dynamic {
+                        write!(f, " + dynamic types")?;
+                    }
+                    writeln!(f)?;
+                }
+                None => writeln!(f, "  {}: wiring unknown", c.name)?,
+            }
+        }
+        writeln!(f, "edges ({}):", self.edges.len())?;
+        for e in &self.edges {
+            writeln!(f, "  {} -> {}: {}", e.from, e.to, e.message)?;
+        }
+        if self.phases.len() > 1 {
+            writeln!(f, "startup phases ({}):", self.phases.len())?;
+            for (i, p) in self.phases.iter().enumerate() {
+                writeln!(f, "  {i}: {}", p.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 组件之外登记的发布方 / 订阅方（`App::declare_publisher` / `App::declare_subscriber`）。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Declared {
+    pub(crate) publishers: Vec<MessageType>,
+    pub(crate) subscribers: Vec<MessageType>,
+}
+
+// 消息类型登记表：静态清单与宿主登记的发布方，合并封印时的实际订阅（类型, 归属组件, 队列容量）；按尺寸降序
+pub(crate) fn message_types(
+    components: &[(&'static str, Option<Wiring>)],
+    declared: &Declared,
+    subscriptions: Vec<(MessageType, &'static str, usize)>,
+) -> Vec<MessageTypeInfo> {
+    let mut types = Vec::new();
+    let wirings = || {
+        components
+            .iter()
+            .filter_map(|(n, w)| Some((*n, w.as_ref()?)))
+    };
+    let published = wirings()
+        .flat_map(|(n, w)| w.publishes.iter().map(move |t| (t, n)))
+        .chain(
+            declared
+                .publishers
+                .iter()
+                .map(|t| (t, crate::bus::EXTERNAL_OWNER)),
+        );
+    for (t, by) in published {
+        let e = type_entry(&mut types, t);
+        if !e.publishers.contains(&by) {
+            e.publishers.push(by);
+        }
+    }
+    for (t, owner, capacity) in subscriptions {
+        let e = type_entry(&mut types, &t);
+        e.subscribers.push(owner);
+        e.capacity += capacity;
+    }
+    // 登记了订阅却没有实际订阅的类型同样列出（订阅方为空）
+    let subscribed = wirings().flat_map(|(_, w)| w.subscribes.iter());
+    for t in subscribed.chain(&declared.subscribers) {
+        type_entry(&mut types, t);
+    }
+    types.sort_by(|a, b| b.size.cmp(&a.size).then(a.type_name.cmp(b.type_name)));
+    types
+}
+
+fn type_entry<'a>(types: &'a mut Vec<MessageTypeInfo>, t: &MessageType) -> &'a mut MessageTypeInfo {
+    let i = types
+        .iter()
+        .position(|e| e.type_name == t.name)
+        .unwrap_or_else(|| {
+            types.push(MessageTypeInfo {
+                type_name: t.name,
+                size: t.size,
+                publishers: Vec::new(),
+                subscribers: Vec::new(),
+                capacity: 0,
+            });
+            types.len() - 1
+        });
+    &mut types[i]
+}
+
+// 校验：订阅类型须有组件、框架事件或宿主登记的发布方；组件发布的类型须有订阅方。
+// 无人发布：非严格模式下存在无法判定的发布方（清单未知或动态发布）时只记录 warn，严格模式一律报错。
+// 无人订阅：非严格模式只记录 warn（发布本身合法），严格模式报错。
+pub(crate) fn check(
+    components: &[(&'static str, Option<Wiring>)],
+    declared: &Declared,
+    strict: bool,
+) -> Result<()> {
+    let wirings = || {
+        components
+            .iter()
+            .filter_map(|(name, w)| Some((*name, w.as_ref()?)))
+    };
+    let published: Vec<TypeId> = wirings()
+        .flat_map(|(_, w)| w.publishes.iter().map(|t| t.id))
+        .chain(declared.publishers.iter().map(|t| t.id))
+        .collect();
+    let subscribed: Vec<TypeId> = wirings()
+        .flat_map(|(_, w)| w.subscribes.iter().map(|t| t.id))
+        .chain(declared.subscribers.iter().map(|t| t.id))
+        .collect();
+
+    let mut missing: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut unconsumed: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (name, wiring) in wirings() {
+        for t in &wiring.subscribes {
+            if !published.contains(&t.id) && !crate::events::is_framework_event(t.id) {
+                missing.entry(t.name).or_default().push(name);
+            }
+        }
+        for t in &wiring.publishes {
+            if !subscribed.contains(&t.id) {
+                unconsumed.entry(t.name).or_default().push(name);
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    if !missing.is_empty() {
+        let mut report = gap_report(&missing, "no publisher for", "subscribed by");
+        let opaque = opaque_components(components, |w| w.dynamic);
+        if !opaque.is_empty() {
+            if !strict {
+                tracing::warn!(
+                    unverifiable = ?opaque,
+                    "{report}; these components may publish types that cannot be determined statically"
+                );
+            } else {
+                report.push_str(&format!(
+                    "; publishers that cannot be verified statically: {}",
+                    opaque.join(", ")
+                ));
+            }
+        }
+        if opaque.is_empty() || strict {
+            errors.push(report);
+        }
+    }
+    if !unconsumed.is_empty() {
+        let mut report = gap_report(&unconsumed, "no subscriber for", "published by");
+        let opaque = opaque_components(components, |_| false);
+        if !strict {
+            tracing::warn!(unverifiable = ?opaque, "{report}");
+        } else {
+            if !opaque.is_empty() {
+                report.push_str(&format!(
+                    "; subscribers that cannot be verified statically: {}",
+                    opaque.join(", ")
+                ));
+            }
+            errors.push(report);
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(MicrobusError::Config(errors.join("; ")))
+    }
+}
+
+fn gap_report(gaps: &BTreeMap<&str, Vec<&str>>, what: &str, by: &str) -> String {
+    gaps.iter()
+        .map(|(ty, names)| format!("{what} {ty} ({by} {})", names.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// 清单未知（手写 `Component`）或满足 `partial` 的组件
+fn opaque_components(
+    components: &[(&'static str, Option<Wiring>)],
+    partial: impl Fn(&Wiring) -> bool,
+) -> Vec<&'static str> {
+    components
+        .iter()
+        .filter(|(_, w)| w.as_ref().is_none_or(&partial))
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+// 按 `requires` 排定启动阶段（返回各阶段的组件下标）：阶段号为依赖链长度，无依赖者均在阶段 0。
+// 依赖未在启动集合中、或依赖成环时返回 `Config` 错误（环按依赖方向列出完整路径）。
+pub(crate) fn startup_phases(
+    components: &[(&'static str, Vec<&'static str>)],
+) -> Result<Vec<Vec<usize>>> {
+    let index = |name: &str| components.iter().position(|(n, _)| *n == name);
+    let mut missing = Vec::new();
+    let deps: Vec<Vec<usize>> = components
+        .iter()
+        .map(|(name, requires)| {
+            requires
+                .iter()
+                .filter_map(|r| {
+                    let i = index(r);
+                    if i.is_none() {
+                        missing.push(format!("{name} requires {r}, which is not started"));
+                    }
+                    i
+                })
+                .collect()
+        })
+        .collect();
+    if !missing.is_empty() {
+        return Err(MicrobusError::Config(missing.join("; ")));
+    }
+
+    // 深度优先求阶段号；`path` 为当前依赖链，遇到链上的组件即成环
+    fn visit(
+        i: usize,
+        deps: &[Vec<usize>],
+        phase: &mut [Option<usize>],
+        path: &mut Vec<usize>,
+    ) -> std::result::Result<usize, Vec<usize>> {
+        if let Some(p) = phase[i] {
+            return Ok(p);
+        }
+        if let Some(at) = path.iter().position(|&x| x == i) {
+            let mut cycle = path[at..].to_vec();
+            cycle.push(i);
+            return Err(cycle);
+        }
+        path.push(i);
+        let mut p = 0;
+        for &d in &deps[i] {
+            p = p.max(visit(d, deps, phase, path)? + 1);
+        }
+        path.pop();
+        phase[i] = Some(p);
+        Ok(p)
+    }
+
+    let mut phase = vec![None; components.len()];
+    let mut phases: Vec<Vec<usize>> = Vec::new();
+    for i in 0..components.len() {
+        let p = visit(i, &deps, &mut phase, &mut Vec::new()).map_err(|cycle| {
+            let names: Vec<_> = cycle.iter().map(|&c| components[c].0).collect();
+            MicrobusError::Config(format!(
+                "component dependency cycle: {}",
+                names.join(" -> ")
+            ))
+        })?;
+        if phases.len() <= p {
+            phases.resize_with(p + 1, Vec::new);
+        }
+        phases[p].push(i);
+    }
+    Ok(phases)
+}
@@ -0,0 +1,354 @@
+//! ZeroMQ 桥（特性 `bridge-zmq`）：以 PUB/SUB 或 PUSH/PULL 套接字按类型收发消息，便于与已使用 ZeroMQ 的外部工具（如 pyzmq）互通。
+//!
+//! - 每条消息为两帧：`[topic, payload]`，topic 与类型一一对应，payload 由 [`MessageCodec`] 编解码
+//!   （对端即 `send_multipart([topic, payload])` / `recv_multipart()`）。
+//! - [`ZmqBridgeOut`]：`publisher()`（PUB，无订阅方时丢弃）或 `pusher()`（PUSH，对端间轮转，无对端时丢弃并计数）。
+//! - [`ZmqBridgeIn`]：`subscriber()`（SUB，按登记 topic 订阅）或 `puller()`（PULL）。
+//!
+//! `bind` 在启动阶段完成，失败按启动失败处理；`connect` 在启动后于后台进行，对端不可达不影响本地启动。
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use zeromq::{Socket, SocketRecv, SocketSend, ZmqMessage};
+
+use crate::bus::ErasedEvent;
+use crate::codec::MessageCodec;
+use crate::component::{Component, ComponentContext};
+use crate::error::{MicrobusError, Result};
+
+const OUT_QUEUE: usize = 1024;
+
+type SpawnForward =
+    Box<dyn FnOnce(&ComponentContext, mpsc::Sender<ZmqMessage>) -> JoinHandle<()> + Send + Sync>;
+type DecodeFn = Box<dyn Fn(&[u8]) -> std::result::Result<ErasedEvent, String> + Send + Sync>;
+
+/// ZeroMQ 桥计数句柄（可在 `add_component` 之后继续持有）。
+#[derive(Clone, Default)]
+pub struct ZmqHandle {
+    frames: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+    bound: Arc<Mutex<Vec<String>>>,
+}
+
+impl ZmqHandle {
+    /// 出站：已交给套接字的消息数；入站：已在本地发布的消息数。
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+    /// 出站：无对端或发送失败的消息数；入站：帧数不符、topic 未登记或解码失败的消息数。
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+    /// 已绑定的端点（端口 0 解析为实际端口）；启动完成后可用。
+    #[must_use]
+    pub fn bound_endpoints(&self) -> Vec<String> {
+        self.bound.lock().clone()
+    }
+}
+
+#[derive(Default)]
+struct Endpoints {
+    bind: Vec<String>,
+    connect: Vec<String>,
+}
+
+// 启动阶段绑定，记录解析后的端点
+async fn bind_all<S: Socket>(
+    socket: &mut S,
+    eps: &Endpoints,
+    handle: &ZmqHandle,
+    ctx: &ComponentContext,
+) -> Result<()> {
+    for ep in &eps.bind {
+        match socket.bind(ep).await {
+            Ok(resolved) => handle.bound.lock().push(resolved.to_string()),
+            Err(e) => {
+                crate::component::__startup_mark_failed(ctx);
+                return Err(MicrobusError::Bridge {
+                    endpoint: ep.clone(),
+                    source: Box::new(e),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+// 后台逐个连接（`connect` 会一直等待到对端可达）；停机时放弃
+async fn connect_all<S: Socket>(socket: &mut S, eps: &Endpoints, ctx: &ComponentContext) -> bool {
+    for ep in &eps.connect {
+        tokio::select! {
+            () = crate::component::__recv_stop(ctx) => return false,
+            r = socket.connect(ep) => match r {
+                Ok(()) => tracing::info!(endpoint = %ep, "zmq bridge connected"),
+                Err(e) => tracing::warn!(endpoint = %ep, error = %e, "zmq bridge connect failed"),
+            },
+        }
+    }
+    true
+}
+
+enum OutKind {
+    Pub,
+    Push,
+}
+
+/// ZeroMQ 出站桥：订阅登记类型，编码后以 `[topic, payload]` 发送。
+pub struct ZmqBridgeOut {
+    kind: OutKind,
+    endpoints: Endpoints,
+    forwards: Vec<SpawnForward>,
+    handle: ZmqHandle,
+}
+
+impl ZmqBridgeOut {
+    /// PUB 套接字：按 topic 前缀分发给订阅方。
+    #[must_use]
+    pub fn publisher() -> Self {
+        Self::new(OutKind::Pub)
+    }
+    /// PUSH 套接字：在已连接的 PULL 对端间轮转。
+    #[must_use]
+    pub fn pusher() -> Self {
+        Self::new(OutKind::Push)
+    }
+    fn new(kind: OutKind) -> Self {
+        Self {
+            kind,
+            endpoints: Endpoints::default(),
+            forwards: Vec::new(),
+            handle: ZmqHandle::default(),
+        }
+    }
+    /// 绑定端点（如 `tcp://0.0.0.0:5556`、`ipc:///tmp/x.sock`），可多次调用。
+    #[must_use]
+    pub fn bind(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoints.bind.push(endpoint.into());
+        self
+    }
+    /// 连接端点，可多次调用。
+    #[must_use]
+    pub fn connect(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoints.connect.push(endpoint.into());
+        self
+    }
+    /// 以 `topic` 转发 `T`。
+    #[must_use]
+    pub fn forward<T: Send + Sync + 'static>(
+        mut self,
+        topic: impl Into<String>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        let topic: String = topic.into();
+        self.forwards.push(Box::new(move |ctx, tx| {
+            let mut sub = crate::component::__subscribe_any_auto::<T>(ctx);
+            let ctx = ctx.__fork();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        () = crate::component::__recv_stop(&ctx) => break,
+                        msg = sub.recv() => {
+                            let Some(msg) = msg else { break };
+                            let mut zm = ZmqMessage::from(topic.clone());
+                            zm.push_back(codec.encode_bytes(&msg).into());
+                            if tx.send(zm).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            })
+        }));
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> ZmqHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for ZmqBridgeOut {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        match self.kind {
+            OutKind::Pub => self.serve(zeromq::PubSocket::new(), ctx).await,
+            OutKind::Push => self.serve(zeromq::PushSocket::new(), ctx).await,
+        }
+    }
+}
+
+impl ZmqBridgeOut {
+    async fn serve<S: Socket + SocketSend>(
+        self,
+        mut socket: S,
+        ctx: ComponentContext,
+    ) -> Result<()> {
+        let Self {
+            endpoints,
+            forwards,
+            handle,
+            ..
+        } = self;
+        bind_all(&mut socket, &endpoints, &handle, &ctx).await?;
+        let (tx, mut rx) = mpsc::channel(OUT_QUEUE);
+        let workers: Vec<_> = forwards.into_iter().map(|f| f(&ctx, tx.clone())).collect();
+        drop(tx);
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        if connect_all(&mut socket, &endpoints, &ctx).await {
+            loop {
+                tokio::select! {
+                    () = crate::component::__recv_stop(&ctx) => break,
+                    msg = rx.recv() => {
+                        let Some(msg) = msg else { break };
+                        match socket.send(msg).await {
+                            Ok(()) => handle.frames.fetch_add(1, Ordering::Relaxed),
+                            Err(e) => {
+                                tracing::debug!(error = %e, "zmq bridge send failed");
+                                handle.dropped.fetch_add(1, Ordering::Relaxed)
+                            }
+                        };
+                    }
+                }
+            }
+        }
+        for w in workers {
+            w.abort();
+        }
+        socket.close().await;
+        Ok(())
+    }
+}
+
+enum InKind {
+    Sub,
+    Pull,
+}
+
+/// ZeroMQ 入站桥：接收 `[topic, payload]`，按 topic 解码后在本地发布。
+pub struct ZmqBridgeIn {
+    kind: InKind,
+    endpoints: Endpoints,
+    topics: HashMap<Vec<u8>, DecodeFn>,
+    handle: ZmqHandle,
+}
+
+impl ZmqBridgeIn {
+    /// SUB 套接字：订阅全部登记的 topic。
+    #[must_use]
+    pub fn subscriber() -> Self {
+        Self::new(InKind::Sub)
+    }
+    /// PULL 套接字：从已连接的 PUSH 对端公平接收。
+    #[must_use]
+    pub fn puller() -> Self {
+        Self::new(InKind::Pull)
+    }
+    fn new(kind: InKind) -> Self {
+        Self {
+            kind,
+            endpoints: Endpoints::default(),
+            topics: HashMap::new(),
+            handle: ZmqHandle::default(),
+        }
+    }
+    /// 绑定端点，可多次调用。
+    #[must_use]
+    pub fn bind(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoints.bind.push(endpoint.into());
+        self
+    }
+    /// 连接端点，可多次调用。
+    #[must_use]
+    pub fn connect(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoints.connect.push(endpoint.into());
+        self
+    }
+    /// 把 topic 精确等于 `topic` 的消息解码为 `T` 发布。
+    #[must_use]
+    pub fn receive<T: Send + Sync + 'static>(
+        mut self,
+        topic: impl Into<String>,
+        codec: impl MessageCodec<T>,
+    ) -> Self {
+        self.topics.insert(
+            topic.into().into_bytes(),
+            Box::new(move |bytes| codec.decode_bytes(bytes).map(ErasedEvent::new)),
+        );
+        self
+    }
+    #[must_use]
+    pub fn handle(&self) -> ZmqHandle {
+        self.handle.clone()
+    }
+}
+
+#[async_trait]
+impl Component for ZmqBridgeIn {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        match self.kind {
+            InKind::Sub => {
+                let mut socket = zeromq::SubSocket::new();
+                for topic in self.topics.keys() {
+                    let topic = String::from_utf8_lossy(topic).into_owned();
+                    socket.subscribe(&topic).await.map_err(|e| {
+                        crate::component::__startup_mark_failed(&ctx);
+                        MicrobusError::Dynamic(format!("bridge-zmq: subscribe failed: {e}"))
+                    })?;
+                }
+                self.serve(socket, ctx).await
+            }
+            InKind::Pull => self.serve(zeromq::PullSocket::new(), ctx).await,
+        }
+    }
+}
+
+impl ZmqBridgeIn {
+    async fn serve<S: Socket + SocketRecv>(
+        self,
+        mut socket: S,
+        ctx: ComponentContext,
+    ) -> Result<()> {
+        bind_all(&mut socket, &self.endpoints, &self.handle, &ctx).await?;
+        crate::component::__startup_arrive_and_wait(&ctx).await;
+        if connect_all(&mut socket, &self.endpoints, &ctx).await {
+            loop {
+                tokio::select! {
+                    () = crate::component::__recv_stop(&ctx) => break,
+                    msg = socket.recv() => match msg {
+                        Ok(msg) => self.deliver(&ctx, &msg).await,
+                        Err(e) => tracing::warn!(error = %e, "zmq bridge recv failed"),
+                    },
+                }
+            }
+        }
+        socket.close().await;
+        Ok(())
+    }
+
+    async fn deliver(&self, ctx: &ComponentContext, msg: &ZmqMessage) {
+        let decoded = match (msg.len(), msg.get(0), msg.get(1)) {
+            (2, Some(topic), Some(payload)) => match self.topics.get(topic.as_ref()) {
+                Some(decode) => decode(payload),
+                None => Err("topic not registered".to_owned()),
+            },
+            _ => Err(format!("expected 2 frames, got {}", msg.len())),
+        };
+        match decoded {
+            Ok(ev) => {
+                self.handle.frames.fetch_add(1, Ordering::Relaxed);
+                crate::component::__publish_erased(ctx, ev).await;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "zmq bridge message rejected");
+                self.handle.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use mmg_microbus::events::ActiveCompleted;
+use mmg_microbus::prelude::*;
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq)]
+struct Line(u32);
+
+#[derive(Debug)]
+struct Ping;
+
+#[derive(Debug)]
+struct Pong;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Replayer {
+    cursor: AtomicU32,
+}
+
+#[mmg_microbus::component]
+impl Replayer {
+    #[mmg_microbus::active]
+    async fn replay(&self) -> ControlFlow<(), Line> {
+        let i = self.cursor.fetch_add(1, Ordering::SeqCst);
+        if i < 3 {
+            ControlFlow::Continue(Line(i))
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+
+    #[mmg_microbus::handle]
+    async fn on_ping(&self, _p: &Ping) -> Pong {
+        Pong
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn active_break_ends_only_its_loop() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let bus = app.bus_handle();
+    let mut lines = bus.try_subscribe::<Line>().unwrap();
+    let mut done = bus.try_subscribe::<ActiveCompleted>().unwrap();
+    let mut pongs = bus.try_subscribe::<Pong>().unwrap();
+    app.start().await.unwrap();
+
+    let wait = Duration::from_secs(5);
+    for i in 0..3 {
+        let line = tokio::time::timeout(wait, lines.recv()).await.unwrap();
+        assert_eq!(*line.unwrap(), Line(i));
+    }
+    let ev = tokio::time::timeout(wait, done.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ev.component, std::any::type_name::<Replayer>());
+    assert_eq!(ev.method, "replay");
+    let extra = tokio::time::timeout(Duration::from_millis(50), lines.recv()).await;
+    assert!(extra.is_err());
+
+    // 循环结束后组件的 handler 仍在运行
+    bus.publish_any_arc(Arc::new(Ping)).await;
+    assert!(tokio::time::timeout(wait, pongs.recv())
+        .await
+        .unwrap()
+        .is_some());
+    app.stop();
+}
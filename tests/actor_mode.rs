@@ -0,0 +1,91 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::atomic::{AtomicI64, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Order;
+#[derive(Debug)]
+struct Cancel;
+#[derive(Debug)]
+struct Ack;
+
+static OPEN: AtomicI64 = AtomicI64::new(0);
+static TICKS: AtomicU32 = AtomicU32::new(0);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct OrderBook {
+    open: i64, // 两个 handler 并发到达，撤单可能先于下单处理
+    ticks: u32,
+}
+
+#[mmg_microbus::component(actor)]
+impl OrderBook {
+    #[mmg_microbus::handle]
+    async fn on_order(&mut self, _o: &Order) -> Ack {
+        // 持有信箱期间让出执行权：其余调用在信箱外等待，读改写不会交错
+        let open = self.open;
+        tokio::task::yield_now().await;
+        self.open = open + 1;
+        Ack
+    }
+
+    #[mmg_microbus::handle]
+    async fn on_cancel(&mut self, _c: &Cancel) -> Ack {
+        let open = self.open;
+        tokio::task::yield_now().await;
+        self.open = open - 1;
+        Ack
+    }
+
+    #[mmg_microbus::active]
+    async fn tick(&mut self) {
+        self.ticks += 1;
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+
+    #[mmg_microbus::stop]
+    fn stop(&mut self) {
+        OPEN.store(self.open, Ordering::SeqCst);
+        TICKS.store(self.ticks, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn actor_calls_run_one_at_a_time_with_mut_self() {
+    let mut app = App::new(AppConfig::default());
+    let bus = app.bus_handle();
+    let mut acks = bus.try_subscribe::<Ack>().unwrap();
+    app.start().await.unwrap();
+
+    let (orders, cancels) = (bus.clone(), bus.clone());
+    let a = tokio::spawn(async move {
+        for _ in 0..50 {
+            orders.publish_any_arc(Arc::new(Order)).await;
+        }
+    });
+    let b = tokio::spawn(async move {
+        for _ in 0..20 {
+            cancels.publish_any_arc(Arc::new(Cancel)).await;
+        }
+    });
+    a.await.unwrap();
+    b.await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        for _ in 0..70 {
+            acks.recv().await.unwrap();
+        }
+    })
+    .await
+    .unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), app.stop_component::<OrderBook>())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(OPEN.load(Ordering::SeqCst), 30);
+    assert!(TICKS.load(Ordering::SeqCst) > 0);
+    app.stop();
+}
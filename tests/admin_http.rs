@@ -0,0 +1,67 @@
+use mmg_microbus::admin::AdminServer;
+use mmg_microbus::prelude::*;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[derive(Clone, Debug)]
+struct Ping;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Sink;
+
+#[mmg_microbus::component]
+impl Sink {
+    #[mmg_microbus::handle]
+    async fn on_ping(&self, _p: &Ping) {}
+}
+
+async fn get(addr: SocketAddr, path: &str) -> (String, String) {
+    let mut s = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    s.write_all(format!("GET {path} HTTP/1.1\r\nHost: x\r\n\r\n").as_bytes())
+        .await
+        .expect("write");
+    let mut out = String::new();
+    s.read_to_string(&mut out).await.expect("read");
+    let (head, body) = out.split_once("\r\n\r\n").expect("http response");
+    (
+        head.lines().next().unwrap_or_default().to_string(),
+        body.to_string(),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn admin_endpoints_report_snapshot() {
+    let server = AdminServer::bind(([127, 0, 0, 1], 0)).expect("bind");
+    let addr = server.local_addr();
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.add_component(server);
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    let (status, body) = get(addr, "/health").await;
+    assert!(status.contains("200"), "{status}");
+    assert!(body.contains("\"healthy\":true"), "{body}");
+
+    let (_, body) = get(addr, "/components").await;
+    assert!(
+        body.contains("Sink") && body.contains("AdminServer"),
+        "{body}"
+    );
+    assert!(body.contains("\"running\""), "{body}");
+
+    let (_, body) = get(addr, "/topology").await;
+    assert!(body.contains("admin_http::Ping"), "{body}");
+
+    let (status, body) = get(addr, "/metrics").await;
+    assert!(status.contains("200"), "{status}");
+    assert!(
+        body.contains("microbus_queue_capacity{component=\""),
+        "{body}"
+    );
+
+    let (status, _) = get(addr, "/nope").await;
+    assert!(status.contains("404"), "{status}");
+    app.stop();
+}
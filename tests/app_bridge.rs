@@ -0,0 +1,50 @@
+use mmg_microbus::app_bridge::AppBridge;
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::BusProbe;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Tick(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Local(u64);
+
+async fn publish<T: Send + Sync + 'static>(app: &App, msg: T) {
+    app.bus_handle().publish_any_arc(Arc::new(msg)).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn relays_selected_types_between_independent_apps() {
+    let mut a = App::new(AppConfig::default());
+    let mut b = App::new(AppConfig::default());
+    let ticks = BusProbe::<Tick>::attach(&b);
+    let locals = BusProbe::<Local>::attach(&b);
+    let bridge = AppBridge::connect(&a, &b).forward::<Tick>();
+    let stats = bridge.handle();
+    a.add_component(bridge);
+    b.start().await.unwrap();
+    a.start().await.unwrap();
+
+    publish(&a, Tick(1)).await;
+    publish(&a, Local(1)).await;
+    ticks
+        .assert_received_in_order(&[Tick(1)], Duration::from_secs(2))
+        .await;
+    assert_eq!(stats.relayed(), 1);
+
+    // 目标 App 停止不影响源 App
+    b.stop();
+    publish(&a, Tick(2)).await;
+    assert!(ticks.wait_for(2, Duration::from_secs(2)).await);
+    assert_eq!(locals.count(), 0);
+    a.stop();
+
+    // 加入目标 App 时启动失败
+    let mut c = App::new(AppConfig::default());
+    let mut d = App::new(AppConfig::default());
+    let misplaced = AppBridge::connect(&c, &d).forward::<Tick>();
+    d.add_component(misplaced);
+    assert!(d.start().await.is_err());
+    c.stop();
+}
@@ -0,0 +1,52 @@
+use mmg_microbus::bus::ErasedEvent;
+use mmg_microbus::prelude::*;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Tick;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Source;
+
+#[mmg_microbus::component]
+impl Source {
+    #[mmg_microbus::active(once)]
+    async fn burst(&self) -> Vec<ErasedEvent> {
+        (0..8).map(|_| ErasedEvent::new(Tick)).collect()
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Slow;
+
+#[mmg_microbus::component]
+impl Slow {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blocked_publisher_time_is_measured() {
+    let cfg = mmg_microbus::config::AppConfig {
+        queue_capacity: 2,
+        ..Default::default()
+    };
+    let mut app = App::new(cfg);
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let snap = app.introspect();
+    app.stop();
+
+    let bp = snap
+        .backpressure
+        .iter()
+        .find(|b| b.type_name.ends_with("::Tick"))
+        .unwrap_or_else(|| panic!("no Tick backpressure: {:?}", snap.backpressure));
+    assert!(bp.blocked_sends > 0);
+    assert!(bp.blocked_total >= Duration::from_millis(10), "{bp:?}");
+    assert!(bp.blocked_max <= bp.blocked_total);
+}
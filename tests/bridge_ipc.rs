@@ -0,0 +1,54 @@
+#![cfg(unix)]
+use mmg_microbus::bridge::{BridgeIn, BridgeOut};
+use mmg_microbus::recorder::MessageCodec;
+use mmg_microbus::testing::TestApp;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Signal(String);
+
+struct SignalCodec;
+
+impl MessageCodec<Signal> for SignalCodec {
+    fn encode(&self, s: &Signal) -> String {
+        s.0.clone()
+    }
+    fn decode(&self, s: &str) -> Result<Signal, String> {
+        Ok(Signal(s.to_owned()))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forwards_over_unix_socket_and_cleans_up() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bus.sock");
+    // 遗留的套接字文件不应阻止绑定
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+
+    let inbound = BridgeIn::bind_ipc(&path)
+        .unwrap()
+        .receive::<Signal>(SignalCodec);
+    let remote = TestApp::builder()
+        .add_component(inbound)
+        .start()
+        .await
+        .unwrap();
+    let local = TestApp::builder()
+        .add_component(BridgeOut::connect_ipc(&path).forward::<Signal>(SignalCodec))
+        .start()
+        .await
+        .unwrap();
+
+    local.inject(Signal("buy\tnow\n".into())).await;
+    let got = remote.expect::<Signal>(Duration::from_secs(2)).await;
+    assert_eq!(got.0, "buy\tnow\n");
+
+    drop(remote);
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while path.exists() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("socket file removed on stop");
+}
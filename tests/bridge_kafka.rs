@@ -0,0 +1,161 @@
+use mmg_microbus::kafka::rdkafka::consumer::{BaseConsumer, Consumer};
+use mmg_microbus::kafka::rdkafka::mocking::MockCluster;
+use mmg_microbus::kafka::rdkafka::{ClientConfig, Offset, TopicPartitionList};
+use mmg_microbus::kafka::{KafkaSink, KafkaSource};
+use mmg_microbus::recorder::MessageCodec;
+use mmg_microbus::testing::TestApp;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Tick(u64);
+
+struct TickCodec;
+
+impl MessageCodec<Tick> for TickCodec {
+    fn encode(&self, t: &Tick) -> String {
+        t.0.to_string()
+    }
+    fn decode(&self, s: &str) -> Result<Tick, String> {
+        s.parse().map(Tick).map_err(|e| format!("{e}"))
+    }
+}
+
+async fn wait_until(mut cond: impl FnMut() -> bool) {
+    for _ in 0..500 {
+        if cond() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("condition not reached");
+}
+
+// 先于 mock 集群停止组件：客户端关闭时需与 broker 通信
+async fn shutdown(remote: TestApp, local: TestApp) {
+    drop((remote, local));
+    tokio::time::sleep(Duration::from_millis(200)).await;
+}
+
+// 消费组在 broker 上已提交的 offset
+fn committed_offset(brokers: &str, group: &str, topic: &str) -> Offset {
+    let consumer: BaseConsumer = ClientConfig::new()
+        .set("bootstrap.servers", brokers)
+        .set("group.id", group)
+        .create()
+        .unwrap();
+    let mut tpl = TopicPartitionList::new();
+    tpl.add_partition(topic, 0);
+    let committed = consumer
+        .committed_offsets(tpl, Duration::from_secs(5))
+        .unwrap();
+    committed.find_partition(topic, 0).unwrap().offset()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sink_to_source_commits_offsets_after_handling() {
+    let cluster = MockCluster::new(1).unwrap();
+    cluster.create_topic("ticks", 1, 1).unwrap();
+    let brokers = cluster.bootstrap_servers();
+
+    let source = KafkaSource::new(brokers.clone(), "g").receive::<Tick>("ticks", TickCodec);
+    let in_stats = source.handle();
+    let remote = TestApp::builder()
+        .add_component(source)
+        .start()
+        .await
+        .unwrap();
+
+    let sink = KafkaSink::new(brokers.clone())
+        .linger(Duration::from_millis(1))
+        .forward::<Tick>("ticks", TickCodec);
+    let out_stats = sink.handle();
+    let local = TestApp::builder()
+        .add_component(sink)
+        .start()
+        .await
+        .unwrap();
+
+    for i in 1..=3 {
+        local.inject(Tick(i)).await;
+    }
+    let mut held = Vec::new();
+    for i in 1..=3 {
+        let tick = remote.expect::<Tick>(Duration::from_secs(10)).await;
+        assert_eq!(*tick, Tick(i));
+        held.push(tick);
+    }
+    wait_until(|| out_stats.frames() == 3).await;
+    assert_eq!(out_stats.dropped(), 0);
+    assert_eq!(in_stats.frames(), 3);
+
+    // 仍有接收方持有消息：视为未处理完毕，不提交
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(in_stats.committed(), 0);
+
+    // 按分区顺序推进：释放后两条而第一条仍被持有时同样不提交
+    let first = held.remove(0);
+    drop(held);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(in_stats.committed(), 0);
+
+    drop(first);
+    wait_until(|| in_stats.committed() == 3).await;
+    let mut offset = Offset::Invalid;
+    for _ in 0..50 {
+        let brokers = brokers.clone();
+        offset = tokio::task::spawn_blocking(move || committed_offset(&brokers, "g", "ticks"))
+            .await
+            .unwrap();
+        if offset == Offset::Offset(3) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(offset, Offset::Offset(3));
+    shutdown(remote, local).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn undecodable_records_are_counted_and_committed() {
+    let cluster = MockCluster::new(1).unwrap();
+    cluster.create_topic("ticks", 1, 1).unwrap();
+    let brokers = cluster.bootstrap_servers();
+
+    struct Raw(String);
+    struct RawCodec;
+    impl MessageCodec<Raw> for RawCodec {
+        fn encode(&self, r: &Raw) -> String {
+            r.0.clone()
+        }
+        fn decode(&self, s: &str) -> Result<Raw, String> {
+            Ok(Raw(s.to_owned()))
+        }
+    }
+    let sink =
+        KafkaSink::new(brokers.clone()).forward_keyed::<Raw>("ticks", RawCodec, |r| r.0.clone());
+    let out_stats = sink.handle();
+    let local = TestApp::builder()
+        .add_component(sink)
+        .start()
+        .await
+        .unwrap();
+    local.inject(Raw("oops".to_owned())).await;
+    local.inject(Raw("7".to_owned())).await;
+    wait_until(|| out_stats.frames() == 2).await;
+
+    let source = KafkaSource::new(brokers, "g2").receive::<Tick>("ticks", TickCodec);
+    let in_stats = source.handle();
+    let remote = TestApp::builder()
+        .add_component(source)
+        .start()
+        .await
+        .unwrap();
+    assert_eq!(
+        *remote.expect::<Tick>(Duration::from_secs(10)).await,
+        Tick(7)
+    );
+    wait_until(|| in_stats.committed() == 2).await;
+    assert_eq!(in_stats.dropped(), 1);
+    assert_eq!(in_stats.frames(), 1);
+    shutdown(remote, local).await;
+}
@@ -0,0 +1,233 @@
+use mmg_microbus::recorder::MessageCodec;
+use mmg_microbus::redis::{RedisBridgeIn, RedisBridgeOut};
+use mmg_microbus::testing::TestApp;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Tick(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Order(u64);
+
+struct TickCodec;
+
+impl MessageCodec<Tick> for TickCodec {
+    fn encode(&self, t: &Tick) -> String {
+        t.0.to_string()
+    }
+    fn decode(&self, s: &str) -> Result<Tick, String> {
+        s.parse().map(Tick).map_err(|e| format!("{e}"))
+    }
+}
+
+struct OrderCodec;
+
+impl MessageCodec<Order> for OrderCodec {
+    fn encode(&self, o: &Order) -> String {
+        o.0.to_string()
+    }
+    fn decode(&self, s: &str) -> Result<Order, String> {
+        s.parse().map(Order).map_err(|e| format!("{e}"))
+    }
+}
+
+// 极简 RESP 服务端：仅实现桥用到的命令，单消费组
+#[derive(Default)]
+struct FakeRedis {
+    subscribers: HashMap<String, Vec<mpsc::UnboundedSender<Vec<u8>>>>,
+    streams: HashMap<String, Vec<(String, String)>>,
+    cursors: HashMap<String, usize>,
+    groups: usize,
+    acked: usize,
+}
+
+fn bulk(s: &str) -> String {
+    format!("${}\r\n{s}\r\n", s.len())
+}
+
+async fn serve(listener: TcpListener, state: Arc<Mutex<FakeRedis>>) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            return;
+        };
+        let state = state.clone();
+        tokio::spawn(async move {
+            let (rd, mut wr) = stream.into_split();
+            let (tx, mut rx) = mpsc::unbounded_channel::<Vec<u8>>();
+            tokio::spawn(async move {
+                while let Some(buf) = rx.recv().await {
+                    if wr.write_all(&buf).await.is_err() {
+                        break;
+                    }
+                }
+            });
+            let mut rd = BufReader::new(rd);
+            while let Some(args) = read_command(&mut rd).await {
+                let reply = handle(&state, &tx, &args).await;
+                if tx.send(reply.into_bytes()).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+async fn read_command(rd: &mut BufReader<tokio::net::tcp::OwnedReadHalf>) -> Option<Vec<String>> {
+    let mut line = String::new();
+    rd.read_line(&mut line).await.ok()?;
+    let n: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+    let mut args = Vec::with_capacity(n);
+    for _ in 0..n {
+        line.clear();
+        rd.read_line(&mut line).await.ok()?;
+        let len: usize = line.trim_end().strip_prefix('$')?.parse().ok()?;
+        let mut buf = vec![0u8; len + 2];
+        rd.read_exact(&mut buf).await.ok()?;
+        buf.truncate(len);
+        args.push(String::from_utf8(buf).ok()?);
+    }
+    Some(args)
+}
+
+async fn handle(
+    state: &Mutex<FakeRedis>,
+    tx: &mpsc::UnboundedSender<Vec<u8>>,
+    args: &[String],
+) -> String {
+    match args[0].as_str() {
+        "SUBSCRIBE" => {
+            let mut st = state.lock().unwrap();
+            let mut out = String::new();
+            for (i, ch) in args[1..].iter().enumerate() {
+                st.subscribers
+                    .entry(ch.clone())
+                    .or_default()
+                    .push(tx.clone());
+                out += &format!("*3\r\n{}{}:{}\r\n", bulk("subscribe"), bulk(ch), i + 1);
+            }
+            out
+        }
+        "PUBLISH" => {
+            let st = state.lock().unwrap();
+            let subs = st.subscribers.get(&args[1]).map_or(&[][..], Vec::as_slice);
+            let msg = format!(
+                "*3\r\n{}{}{}",
+                bulk("message"),
+                bulk(&args[1]),
+                bulk(&args[2])
+            );
+            for s in subs {
+                let _ = s.send(msg.clone().into_bytes());
+            }
+            format!(":{}\r\n", subs.len())
+        }
+        "XADD" => {
+            let mut st = state.lock().unwrap();
+            let entries = st.streams.entry(args[1].clone()).or_default();
+            let id = format!("{}-0", entries.len() + 1);
+            entries.push((id.clone(), args[4].clone()));
+            bulk(&id)
+        }
+        "XGROUP" => {
+            state.lock().unwrap().groups += 1;
+            "+OK\r\n".to_owned()
+        }
+        "XACK" => {
+            state.lock().unwrap().acked += 1;
+            ":1\r\n".to_owned()
+        }
+        "XREADGROUP" => {
+            let key = &args[args.iter().position(|a| a == "STREAMS").unwrap() + 1];
+            let pending = {
+                let mut st = state.lock().unwrap();
+                let start = st.cursors.get(key).copied().unwrap_or(0);
+                let entries = st.streams.get(key).map_or(&[][..], Vec::as_slice)[start..].to_vec();
+                st.cursors.insert(key.clone(), start + entries.len());
+                entries
+            };
+            if pending.is_empty() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                return "*-1\r\n".to_owned();
+            }
+            let mut out = format!("*1\r\n*2\r\n{}*{}\r\n", bulk(key), pending.len());
+            for (id, payload) in pending {
+                out += &format!(
+                    "*2\r\n{}*2\r\n{}{}",
+                    bulk(&id),
+                    bulk("data"),
+                    bulk(&payload)
+                );
+            }
+            out
+        }
+        other => format!("-ERR unknown command '{other}'\r\n"),
+    }
+}
+
+async fn wait_until(mut cond: impl FnMut() -> bool) {
+    for _ in 0..200 {
+        if cond() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("condition not reached");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fans_out_via_pubsub_and_streams() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let state = Arc::new(Mutex::new(FakeRedis::default()));
+    tokio::spawn(serve(listener, state.clone()));
+
+    let inbound = RedisBridgeIn::connect(addr.clone())
+        .receive::<Tick>("ticks", TickCodec)
+        .receive_stream::<Order>("orders", OrderCodec)
+        .consumer_group("g", "c1");
+    let in_stats = inbound.handle();
+    let remote = TestApp::builder()
+        .add_component(inbound)
+        .start()
+        .await
+        .unwrap();
+    // pub/sub 不保留历史：订阅与消费组就绪后再发布
+    wait_until(|| {
+        let st = state.lock().unwrap();
+        st.subscribers.contains_key("ticks") && st.groups == 1
+    })
+    .await;
+
+    let outbound = RedisBridgeOut::connect(addr)
+        .forward::<Tick>("ticks", TickCodec)
+        .forward_stream::<Order>("orders", OrderCodec);
+    let out_stats = outbound.handle();
+    let local = TestApp::builder()
+        .add_component(outbound)
+        .start()
+        .await
+        .unwrap();
+
+    for i in 1..=3 {
+        local.inject(Tick(i)).await;
+        local.inject(Order(i * 10)).await;
+    }
+    for i in 1..=3 {
+        assert_eq!(
+            *remote.expect::<Tick>(Duration::from_secs(2)).await,
+            Tick(i)
+        );
+        assert_eq!(
+            *remote.expect::<Order>(Duration::from_secs(2)).await,
+            Order(i * 10)
+        );
+    }
+    wait_until(|| state.lock().unwrap().acked == 3 && out_stats.frames() == 6).await;
+    assert_eq!(out_stats.dropped(), 0);
+    assert_eq!(in_stats.frames(), 6);
+    assert_eq!(in_stats.dropped(), 0);
+}
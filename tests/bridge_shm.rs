@@ -0,0 +1,50 @@
+#![cfg(unix)]
+use mmg_microbus::shm::{ShmBridgeIn, ShmBridgeOut, ShmPod};
+use mmg_microbus::testing::{BusProbe, TestApp};
+use std::time::Duration;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Quote {
+    px: f64,
+    qty: u32,
+}
+
+// SAFETY: repr(C)，仅含数值字段
+unsafe impl ShmPod for Quote {}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forwards_pod_through_small_ring_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("quote.ring");
+
+    // 消费方先于生产方启动：等待环创建
+    let inbound = ShmBridgeIn::new().receive::<Quote>(&path);
+    let in_stats = inbound.handle();
+    let remote = TestApp::builder()
+        .add_component(inbound)
+        .start()
+        .await
+        .unwrap();
+    let probe = BusProbe::<Quote>::attach(remote.app());
+
+    // 容量 4：发送 200 条覆盖环回绕与环满等待
+    let local = TestApp::builder()
+        .add_component(ShmBridgeOut::new().forward_with_capacity::<Quote>(&path, 4))
+        .start()
+        .await
+        .unwrap();
+    let expected: Vec<Quote> = (0..200)
+        .map(|i| Quote {
+            px: f64::from(i) + 0.5,
+            qty: i,
+        })
+        .collect();
+    for q in &expected {
+        local.inject(*q).await;
+    }
+    probe
+        .assert_received_in_order(&expected, Duration::from_secs(5))
+        .await;
+    assert_eq!(in_stats.frames(), 200);
+}
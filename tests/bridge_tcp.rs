@@ -0,0 +1,58 @@
+use mmg_microbus::bridge::{BridgeIn, BridgeOut};
+use mmg_microbus::recorder::MessageCodec;
+use mmg_microbus::testing::TestApp;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Tick(u64);
+#[derive(Clone, Debug)]
+struct LocalOnly;
+
+struct TickCodec;
+
+impl MessageCodec<Tick> for TickCodec {
+    fn encode(&self, t: &Tick) -> String {
+        t.0.to_string()
+    }
+    fn decode(&self, s: &str) -> Result<Tick, String> {
+        s.parse().map(Tick).map_err(|e| format!("{e}"))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forwards_selected_types_between_apps() {
+    let inbound = BridgeIn::bind_tcp(([127, 0, 0, 1], 0))
+        .unwrap()
+        .receive::<Tick>(TickCodec);
+    let addr = inbound.local_addr().unwrap();
+    let in_stats = inbound.handle();
+    let remote = TestApp::builder()
+        .add_component(inbound)
+        .start()
+        .await
+        .unwrap();
+
+    let outbound = BridgeOut::connect_tcp(addr.to_string()).forward::<Tick>(TickCodec);
+    let out_stats = outbound.handle();
+    let local = TestApp::builder()
+        .add_component(outbound)
+        .start()
+        .await
+        .unwrap();
+
+    for i in 1..=3 {
+        local.inject(Tick(i)).await;
+    }
+    local.inject(LocalOnly).await;
+    for i in 1..=3 {
+        let t = remote.expect::<Tick>(Duration::from_secs(2)).await;
+        assert_eq!(*t, Tick(i));
+    }
+    assert!(remote
+        .try_expect::<LocalOnly>(Duration::from_millis(100))
+        .await
+        .is_none());
+    assert_eq!(out_stats.frames(), 3);
+    assert_eq!(out_stats.dropped(), 0);
+    assert_eq!(in_stats.frames(), 3);
+}
@@ -0,0 +1,96 @@
+use mmg_microbus::recorder::MessageCodec;
+use mmg_microbus::testing::TestApp;
+use mmg_microbus::zmq::{ZmqBridgeIn, ZmqBridgeOut};
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Tick(u64);
+
+struct TickCodec;
+
+impl MessageCodec<Tick> for TickCodec {
+    fn encode(&self, t: &Tick) -> String {
+        t.0.to_string()
+    }
+    fn decode(&self, s: &str) -> Result<Tick, String> {
+        s.parse().map(Tick).map_err(|e| format!("{e}"))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn push_pull_forwards_in_order() {
+    let inbound = ZmqBridgeIn::puller()
+        .bind("tcp://127.0.0.1:0")
+        .receive::<Tick>("tick", TickCodec);
+    let in_stats = inbound.handle();
+    let remote = TestApp::builder()
+        .add_component(inbound)
+        .start()
+        .await
+        .unwrap();
+    let endpoint = in_stats.bound_endpoints().pop().expect("bound endpoint");
+
+    let outbound = ZmqBridgeOut::pusher()
+        .connect(endpoint)
+        .forward::<Tick>("tick", TickCodec);
+    let out_stats = outbound.handle();
+    let local = TestApp::builder()
+        .add_component(outbound)
+        .start()
+        .await
+        .unwrap();
+
+    for i in 1..=3 {
+        local.inject(Tick(i)).await;
+    }
+    for i in 1..=3 {
+        assert_eq!(
+            *remote.expect::<Tick>(Duration::from_secs(2)).await,
+            Tick(i)
+        );
+    }
+    assert_eq!(out_stats.frames(), 3);
+    assert_eq!(in_stats.frames(), 3);
+    assert_eq!(in_stats.dropped(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pub_sub_delivers_after_subscription() {
+    let outbound = ZmqBridgeOut::publisher()
+        .bind("tcp://127.0.0.1:0")
+        .forward::<Tick>("tick", TickCodec);
+    let out_stats = outbound.handle();
+    let local = TestApp::builder()
+        .add_component(outbound)
+        .start()
+        .await
+        .unwrap();
+    let endpoint = out_stats.bound_endpoints().pop().expect("bound endpoint");
+
+    let inbound = ZmqBridgeIn::subscriber()
+        .connect(endpoint)
+        .receive::<Tick>("tick", TickCodec);
+    let remote = TestApp::builder()
+        .add_component(inbound)
+        .start()
+        .await
+        .unwrap();
+
+    // PUB 在订阅到达前直接丢弃：重复发送直到对端收到
+    let mut seen = None;
+    for i in 0..100 {
+        local.inject(Tick(i)).await;
+        if let Some(t) = remote.try_expect::<Tick>(Duration::from_millis(20)).await {
+            seen = Some(t.0);
+            break;
+        }
+    }
+    assert!(seen.is_some(), "subscription never established");
+    local.inject(Tick(1000)).await;
+    loop {
+        let t = remote.expect::<Tick>(Duration::from_secs(2)).await;
+        if t.0 == 1000 {
+            break;
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use mmg_microbus::bus_core::{fanout, try_fanout, Outbox, Route, StdClock, TryDeliver};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Poll, Waker};
+use std::time::Duration;
+
+// 仅用标准库实现的有界信箱：核心 fanout 不依赖 tokio 通道
+#[derive(Default)]
+struct Mailbox {
+    queue: Mutex<(VecDeque<u32>, Option<Waker>)>,
+    closed: AtomicBool,
+}
+
+#[derive(Clone)]
+struct Slot(Arc<Mailbox>);
+
+const CAP: usize = 1;
+
+impl Slot {
+    fn take(&self) -> Option<u32> {
+        let mut q = self.0.queue.lock();
+        let v = q.0.pop_front();
+        if let Some(w) = q.1.take() {
+            w.wake();
+        }
+        v
+    }
+}
+
+impl Outbox<u32> for Slot {
+    fn try_deliver(&self, msg: u32) -> TryDeliver<u32> {
+        if self.is_closed() {
+            return TryDeliver::Closed;
+        }
+        let mut q = self.0.queue.lock();
+        if q.0.len() >= CAP {
+            return TryDeliver::Full(msg);
+        }
+        q.0.push_back(msg);
+        TryDeliver::Delivered
+    }
+    async fn deliver(&self, msg: u32) -> bool {
+        std::future::poll_fn(|cx| {
+            if self.is_closed() {
+                return Poll::Ready(false);
+            }
+            let mut q = self.0.queue.lock();
+            if q.0.len() < CAP {
+                q.0.push_back(msg);
+                Poll::Ready(true)
+            } else {
+                q.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await
+    }
+    fn is_closed(&self) -> bool {
+        self.0.closed.load(Ordering::Acquire)
+    }
+}
+
+#[tokio::test]
+async fn fanout_over_custom_outboxes_reports_closed_and_backpressure() {
+    let (fast, slow, gone) = (
+        Slot(Arc::default()),
+        Slot(Arc::default()),
+        Slot(Arc::default()),
+    );
+    gone.0.closed.store(true, Ordering::Release);
+    let mut route = Route::default();
+    for s in [&fast, &slow, &gone] {
+        route.push(s.clone());
+    }
+    assert_eq!(route.open::<u32>().len(), 2);
+    route.freeze();
+    let outboxes = route.frozen().unwrap();
+
+    let d = fanout::<_, _, StdClock>(&outboxes, 1).await;
+    assert!(d.blocked.is_none());
+    assert_eq!(d.closed, 1);
+
+    // `fast` 先被取空，`slow` 仍满：第二条在 `slow` 上等待至消费方取走
+    assert_eq!(fast.take(), Some(1));
+    let consumer = slow.clone();
+    let drain = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        consumer.take()
+    });
+    let d = fanout::<_, _, StdClock>(&outboxes, 2).await;
+    assert_eq!(d.closed, 1);
+    assert!(d.blocked.unwrap() >= Duration::from_millis(10));
+    assert_eq!(drain.await.unwrap(), Some(1));
+    assert_eq!((fast.take(), slow.take()), (Some(2), Some(2)));
+}
+
+#[test]
+fn try_fanout_drops_on_full_without_waiting() {
+    let (free, busy, gone) = (
+        Slot(Arc::default()),
+        Slot(Arc::default()),
+        Slot(Arc::default()),
+    );
+    gone.0.closed.store(true, Ordering::Release);
+    assert!(matches!(busy.try_deliver(0), TryDeliver::Delivered));
+    let outboxes = [free.clone(), busy.clone(), gone];
+
+    let d = try_fanout(&outboxes, 1);
+    assert_eq!((d.closed, d.full), (1, 1));
+    assert_eq!((free.take(), busy.take()), (Some(1), Some(0)));
+}
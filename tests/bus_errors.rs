@@ -0,0 +1,28 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::{MicrobusError, SubscribeError};
+use mmg_microbus::prelude::*;
+use std::error::Error;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+struct Quote(u32);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn subscribing_after_seal_is_a_recoverable_error() {
+    let mut app = App::new(AppConfig::default());
+    let bus = app.bus_handle();
+    let mut early = bus.try_subscribe::<Quote>().expect("not sealed yet");
+    app.start().await.unwrap();
+
+    let err = bus.try_subscribe::<Quote>().err().expect("sealed");
+    assert!(matches!(err, SubscribeError::Sealed { type_name } if type_name.ends_with("::Quote")));
+
+    // 转入框架错误后按类别匹配，原因链保留
+    let err = MicrobusError::from(err);
+    assert!(matches!(err, MicrobusError::Subscribe { .. }));
+    assert!(err.source().unwrap().to_string().contains("bus sealed"));
+
+    bus.try_publish_any_arc(Arc::new(Quote(7))).await.unwrap();
+    assert_eq!(*early.recv().await.unwrap(), Quote(7));
+    app.stop();
+}
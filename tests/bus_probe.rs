@@ -0,0 +1,53 @@
+use mmg_microbus::bus::ErasedEvent;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::BusProbe;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Quote(u32);
+#[derive(Clone, Debug, PartialEq)]
+struct Done;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Feed;
+
+#[mmg_microbus::component]
+impl Feed {
+    #[mmg_microbus::active(once)]
+    async fn burst(&self) -> Vec<ErasedEvent> {
+        vec![
+            ErasedEvent::new(Quote(1)),
+            ErasedEvent::new(Quote(2)),
+            ErasedEvent::new(Quote(3)),
+            ErasedEvent::new(Done),
+        ]
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn probe_collects_and_asserts() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let quotes = BusProbe::<Quote>::attach(&app);
+    let done = BusProbe::<Done>::attach(&app);
+    app.start().await.expect("start");
+
+    let within = Duration::from_secs(1);
+    quotes
+        .assert_received_in_order(&[Quote(1), Quote(2), Quote(3)], within)
+        .await;
+    done.assert_count(1, within).await;
+    assert!(!quotes.wait_for(4, Duration::from_millis(20)).await);
+    app.stop();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[should_panic(expected = "sequence mismatch")]
+async fn order_mismatch_panics() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let quotes = BusProbe::<Quote>::attach(&app);
+    app.start().await.expect("start");
+    quotes
+        .assert_received_in_order(&[Quote(2), Quote(1), Quote(3)], Duration::from_secs(1))
+        .await;
+}
@@ -0,0 +1,43 @@
+use mmg_microbus::adapters::{ChannelSink, ChannelSource};
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Order(u64);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn bridges_legacy_channels_through_the_bus() {
+    let (legacy_tx, legacy_rx) = mpsc::channel(8);
+    let (feed_tx, mut feed_rx) = mpsc::channel(8);
+    let mut app = App::new(AppConfig::default());
+    let source = ChannelSource::new(legacy_rx);
+    let sink = ChannelSink::<Order>::new(feed_tx);
+    let (source_stats, sink_stats) = (source.handle(), sink.handle());
+    app.add_component(source).add_component(sink);
+    app.start().await.unwrap();
+
+    for id in 1..=3 {
+        legacy_tx.send(Order(id)).await.unwrap();
+    }
+    for id in 1..=3 {
+        let got = tokio::time::timeout(Duration::from_secs(2), feed_rx.recv()).await;
+        assert_eq!(got.unwrap(), Some(Order(id)));
+    }
+    assert_eq!((source_stats.forwarded(), sink_stats.forwarded()), (3, 3));
+
+    // 接收端关闭后丢弃并计数；发送端关闭不影响 App
+    drop(feed_rx);
+    legacy_tx.send(Order(4)).await.unwrap();
+    drop(legacy_tx);
+    for _ in 0..100 {
+        if sink_stats.dropped() == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(sink_stats.dropped(), 1);
+    assert!(app.is_started());
+    app.stop();
+}
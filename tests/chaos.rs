@@ -0,0 +1,75 @@
+use mmg_microbus::chaos::Chaos;
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Tick(u64);
+
+static HANDLED: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Counter;
+
+#[mmg_microbus::component]
+impl Counter {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, t: &Tick) {
+        HANDLED.lock().push(t.0);
+    }
+}
+
+const N: u64 = 200;
+
+// 返回 (handler 实际处理的序列, 丢弃数, 失败数, 重排数)
+async fn run(seed: u64) -> (Vec<u64>, u64, u64, u64) {
+    HANDLED.lock().clear();
+    let chaos = Chaos::seeded(seed)
+        .drop::<Tick>(0.2)
+        .reorder::<Tick>(0.2)
+        .delay::<Tick>(0.1, Duration::from_millis(2))
+        .fail::<Tick>(0.1);
+    let faults = chaos.handle();
+    let mut app = App::new(AppConfig::default());
+    app.inject_faults(chaos);
+    app.start().await.unwrap();
+
+    let bus = app.bus_handle();
+    for i in 0..N {
+        bus.publish_any_arc(Arc::new(Tick(i))).await;
+    }
+    let settled = async {
+        while HANDLED.lock().len() as u64 + faults.dropped() + faults.failed() < N {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), settled)
+        .await
+        .unwrap();
+    app.stop();
+    let handled = std::mem::take(&mut *HANDLED.lock());
+    (
+        handled,
+        faults.dropped(),
+        faults.failed(),
+        faults.reordered(),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn faults_are_injected_and_reproducible_by_seed() {
+    let (handled, dropped, failed, reordered) = run(7).await;
+    assert!(dropped > 0 && failed > 0 && reordered > 0);
+    assert_eq!(handled.len() as u64, N - dropped - failed);
+    assert!(
+        handled.windows(2).any(|w| w[0] > w[1]),
+        "no reordering seen"
+    );
+
+    // 单一发布方、handler 顺序执行：同一种子下注入结果完全一致
+    assert_eq!(run(7).await, (handled.clone(), dropped, failed, reordered));
+    assert_ne!(run(8).await.0, handled);
+}
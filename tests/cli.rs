@@ -0,0 +1,79 @@
+use clap::Parser;
+use mmg_microbus::cli::AppArgs;
+use mmg_microbus::config::{ActiveScheduling, AppConfig};
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::profile::{Profile, Profiles};
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Feed;
+
+#[mmg_microbus::component]
+impl Feed {}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Audit;
+
+#[mmg_microbus::component]
+impl Audit {}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn args_override_profile_and_select_components() {
+    let args = AppArgs::try_parse_from([
+        "svc",
+        "--profile",
+        "small",
+        "--worker-threads",
+        "2",
+        "--dedicated-actives",
+        "--disable-component",
+        "Audit",
+        "--log-level",
+        "debug",
+    ])
+    .unwrap();
+    assert_eq!(args.log_level, Some(tracing::Level::DEBUG));
+
+    let profiles = |name: &str| {
+        (name == "small").then(|| AppConfig {
+            queue_capacity: 16,
+            ..AppConfig::default()
+        })
+    };
+    let cfg = args.config(profiles).unwrap();
+    assert_eq!(cfg.queue_capacity, 16);
+    assert_eq!(cfg.topology.worker_threads, Some(2));
+    assert_eq!(cfg.topology.actives, ActiveScheduling::Dedicated);
+
+    let unknown = AppArgs::try_parse_from(["svc", "--profile", "huge"]).unwrap();
+    assert!(matches!(
+        unknown.config(profiles),
+        Err(MicrobusError::Config(_))
+    ));
+    let bad = AppArgs::try_parse_from(["svc", "--queue-capacity", "0"]).unwrap();
+    assert!(bad.build(bad.config(profiles).unwrap()).is_err());
+
+    let lean = Profiles::new().with(
+        Profile::new("lean")
+            .enable_component("Feed")
+            .config(|cfg| cfg.queue_capacity = 8),
+    );
+    let picked = AppArgs::try_parse_from(["svc", "--profile", "lean", "--queue-capacity", "32"])
+        .unwrap()
+        .build_with_profiles(AppConfig::default(), &lean)
+        .unwrap();
+    let plan = picked.plan().unwrap();
+    assert_eq!(plan.config.queue_capacity, 32);
+    assert_eq!(plan.components.len(), 1);
+    assert!(args
+        .build_with_profiles(AppConfig::default(), &lean)
+        .is_err());
+
+    let mut app = args.build(cfg).unwrap();
+    app.start().await.unwrap();
+    let names: Vec<_> = app.introspect().components.iter().map(|c| c.name).collect();
+    assert!(names.iter().any(|n| n.ends_with("Feed")), "{names:?}");
+    assert!(!names.iter().any(|n| n.ends_with("Audit")), "{names:?}");
+    app.stop();
+}
@@ -0,0 +1,49 @@
+use mmg_microbus::component::{Component, ComponentContext};
+use mmg_microbus::prelude::*;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Ping;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Source;
+
+#[mmg_microbus::component]
+impl Source {
+    #[mmg_microbus::active(once)]
+    async fn later(&self) -> Ping {
+        // 等待 Quitter 退出后再发布
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        Ping
+    }
+}
+
+// 手写组件：订阅 Ping 后越过启动屏障即退出，订阅端随之关闭
+struct Quitter;
+
+#[async_trait::async_trait]
+impl Component for Quitter {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let _sub = mmg_microbus::component::__subscribe_any_auto::<Ping>(&ctx);
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drops_to_closed_subscribers_are_counted() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.add_component(Quitter);
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    let snap = app.introspect();
+    app.stop();
+
+    let ping = snap
+        .drops
+        .iter()
+        .find(|d| d.type_name.ends_with("::Ping"))
+        .unwrap_or_else(|| panic!("no Ping drops: {:?}", snap.drops));
+    assert_eq!(ping.closed, 1);
+}
@@ -0,0 +1,41 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Tick(u64);
+
+#[derive(Debug)]
+struct Doubled(u64);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn closure_handlers_receive_and_publish() {
+    let mut app = App::new(AppConfig::default());
+    let bus = app.bus_handle();
+    let out = bus.clone();
+    app.on::<Tick>(move |tick| {
+        let out = out.clone();
+        async move { out.publish_any_arc(Arc::new(Doubled(tick.0 * 2))).await }
+    });
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    app.on(move |d: Arc<Doubled>| {
+        let tx = tx.clone();
+        async move {
+            let _ = tx.send(d.0);
+        }
+    });
+    app.start().await.unwrap();
+
+    for i in 1..=3 {
+        bus.publish_any_arc(Arc::new(Tick(i))).await;
+    }
+    for want in [2, 4, 6] {
+        let got = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(got, want);
+    }
+    app.stop();
+}
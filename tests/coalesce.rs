@@ -0,0 +1,29 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+struct Position(u32);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn latest_subscription_keeps_only_newest() {
+    let mut app = App::new(AppConfig {
+        queue_capacity: 2,
+        ..AppConfig::default()
+    });
+    let bus = app.bus_handle();
+    let mut latest = bus.try_subscribe_latest::<Position>().unwrap();
+    app.start().await.unwrap();
+
+    // 合并订阅从不阻塞发布方：远超队列容量也立即完成
+    for i in 0..100 {
+        bus.publish_any_arc(Arc::new(Position(i))).await;
+    }
+    assert_eq!(*latest.recv().await.unwrap(), Position(99));
+    let idle = tokio::time::timeout(std::time::Duration::from_millis(50), latest.recv()).await;
+    assert!(idle.is_err());
+
+    bus.publish_any_arc(Arc::new(Position(100))).await;
+    assert_eq!(*latest.recv().await.unwrap(), Position(100));
+    app.stop();
+}
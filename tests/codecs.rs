@@ -0,0 +1,75 @@
+use mmg_microbus::bridge::{BridgeIn, BridgeOut};
+use mmg_microbus::codec::{Bincode, BusMessage, Json, MessageCodec, Postcard};
+use mmg_microbus::testing::TestApp;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BusMessage)]
+struct Quote {
+    symbol: String,
+    bid: f64,
+    size: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BusMessage)]
+#[bus_message(name = "market.Trade.v1")]
+struct Trade {
+    id: u64,
+}
+
+fn quote() -> Quote {
+    Quote {
+        symbol: "ABC".into(),
+        bid: 10.5,
+        size: 300,
+    }
+}
+
+fn roundtrip(codec: &impl MessageCodec<Quote>) {
+    let q = quote();
+    assert_eq!(codec.decode(&codec.encode(&q)).unwrap(), q);
+    assert_eq!(codec.decode_bytes(&codec.encode_bytes(&q)).unwrap(), q);
+    assert!(codec.decode_bytes(&[0xff]).is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn derived_names_and_codecs_roundtrip_over_bridge() {
+    assert_eq!(Quote::NAME, "Quote");
+    assert_eq!(Trade::NAME, "market.Trade.v1");
+    roundtrip(&Json);
+    roundtrip(&Bincode);
+    roundtrip(&Postcard);
+    // 二进制编解码器的文本形式可安全写入录制文件
+    let text = MessageCodec::<Quote>::encode(&Postcard, &quote());
+    assert!(text.bytes().all(|b| b.is_ascii_hexdigit()));
+
+    let inbound = BridgeIn::bind_tcp(([127, 0, 0, 1], 0))
+        .unwrap()
+        .receive_message::<Quote>(Bincode)
+        .receive_message::<Trade>(Postcard);
+    let addr = inbound.local_addr().unwrap();
+    let remote = TestApp::builder()
+        .add_component(inbound)
+        .start()
+        .await
+        .unwrap();
+    let outbound = BridgeOut::connect_tcp(addr.to_string())
+        .forward_message::<Quote>(Bincode)
+        .forward_message::<Trade>(Postcard);
+    let local = TestApp::builder()
+        .add_component(outbound)
+        .start()
+        .await
+        .unwrap();
+
+    local.inject(quote()).await;
+    local.inject(Trade { id: 7 }).await;
+    assert_eq!(
+        *remote.expect::<Quote>(Duration::from_secs(2)).await,
+        quote()
+    );
+    assert_eq!(
+        *remote.expect::<Trade>(Duration::from_secs(2)).await,
+        Trade { id: 7 }
+    );
+}
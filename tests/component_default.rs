@@ -0,0 +1,41 @@
+use mmg_microbus::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Endpoint(&'static str);
+
+#[mmg_microbus::component]
+#[derive(mmg_microbus::ComponentDefault)]
+struct Gateway {
+    last_seen: Option<u64>,
+    routes: HashMap<String, u32>,
+    counter: AtomicU64,
+    #[component_default(Endpoint("localhost:9000"))]
+    endpoint: Endpoint,
+    #[component_default(Duration::from_secs(5))]
+    timeout: Duration,
+}
+
+#[mmg_microbus::component]
+impl Gateway {}
+
+#[test]
+fn derived_default_fills_fields_and_custom_initializers() {
+    let g = Gateway::default();
+    assert_eq!(g.last_seen, None);
+    assert!(g.routes.is_empty());
+    assert_eq!(g.counter.into_inner(), 0);
+    assert_eq!(g.endpoint.0, "localhost:9000");
+    assert_eq!(g.timeout, Duration::from_secs(5));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn component_default_builds_through_factory() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.start().await.unwrap();
+    let names: Vec<_> = app.introspect().components.iter().map(|c| c.name).collect();
+    assert!(names.contains(&std::any::type_name::<Gateway>()));
+    app.stop();
+}
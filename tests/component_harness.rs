@@ -0,0 +1,61 @@
+use mmg_microbus::testing::ComponentHarness;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Order(u32);
+#[derive(Clone, Debug, PartialEq)]
+struct Fill(u32);
+#[derive(Clone, Debug)]
+struct Ping;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Matcher {
+    seen: AtomicU32,
+}
+
+#[mmg_microbus::component]
+impl Matcher {
+    #[mmg_microbus::handle]
+    async fn on_order(&self, o: &Order) -> Fill {
+        let n = self.seen.fetch_add(1, Ordering::SeqCst) + 1;
+        Fill(o.0 + n)
+    }
+}
+
+// 同一测试二进制内的其它自动发现组件不应被启动
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Bystander;
+
+#[mmg_microbus::component]
+impl Bystander {
+    #[mmg_microbus::active(once)]
+    async fn hello(&self) -> Ping {
+        Ping
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn runs_one_component_and_captures_outputs() {
+    let h = ComponentHarness::start(Matcher::default())
+        .await
+        .expect("start");
+    h.feed(Order(10)).await;
+    h.feed(Order(20)).await;
+    let within = Duration::from_secs(1);
+    assert_eq!(*h.expect::<Fill>(within).await, Fill(11));
+    assert_eq!(*h.expect::<Fill>(within).await, Fill(22));
+    assert!(h
+        .try_expect::<Order>(Duration::from_millis(20))
+        .await
+        .is_none());
+    assert!(h
+        .try_expect::<Ping>(Duration::from_millis(20))
+        .await
+        .is_none());
+    let published = h.published();
+    assert_eq!(published.len(), 2, "{published:?}");
+    assert!(published.iter().all(|t| t.ends_with("::Fill")));
+}
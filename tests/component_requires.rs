@@ -0,0 +1,61 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Mutex;
+use std::time::Duration;
+
+static INITS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Clock;
+
+#[mmg_microbus::component]
+impl Clock {
+    #[mmg_microbus::init]
+    async fn init(&self) {
+        // 依赖方须等到本组件 init 完成后才构建
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        INITS.lock().unwrap().push("Clock");
+    }
+}
+
+#[mmg_microbus::component(requires(Clock))]
+#[derive(Default)]
+struct MarketData;
+
+#[mmg_microbus::component]
+impl MarketData {
+    #[mmg_microbus::init]
+    async fn init(&self) {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        INITS.lock().unwrap().push("MarketData");
+    }
+}
+
+#[mmg_microbus::component(requires(MarketData, Clock))]
+#[derive(Default)]
+struct Strategy;
+
+#[mmg_microbus::component]
+impl Strategy {
+    #[mmg_microbus::init]
+    async fn init(&self) {
+        INITS.lock().unwrap().push("Strategy");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dependencies_start_first() {
+    let mut app = App::new(AppConfig::default());
+    let plan = app.plan().unwrap();
+    let phases: Vec<Vec<_>> = plan
+        .phases
+        .iter()
+        .map(|p| p.iter().map(|n| n.rsplit("::").next().unwrap()).collect())
+        .collect();
+    assert_eq!(phases, [["Clock"], ["MarketData"], ["Strategy"]]);
+
+    app.start().await.unwrap();
+    assert_eq!(*INITS.lock().unwrap(), ["Clock", "MarketData", "Strategy"]);
+    app.stop();
+}
@@ -0,0 +1,41 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::prelude::*;
+
+#[mmg_microbus::component(requires(Risk))]
+#[derive(Default)]
+struct Orders;
+
+#[mmg_microbus::component]
+impl Orders {}
+
+#[mmg_microbus::component(requires(Orders))]
+#[derive(Default)]
+struct Risk;
+
+#[mmg_microbus::component]
+impl Risk {}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dependency_cycle_fails_start() {
+    let mut app = App::new(AppConfig::default());
+    let Err(MicrobusError::Config(msg)) = app.start().await else {
+        panic!("cyclic dependencies must fail start");
+    };
+    assert!(msg.starts_with("component dependency cycle: "), "{msg}");
+    assert!(
+        msg.contains("Orders -> ") && msg.contains("Risk -> "),
+        "{msg}"
+    );
+    assert!(app.introspect().components.is_empty());
+
+    let mut app = App::new(AppConfig::default());
+    app.disable_component("Risk");
+    let Err(MicrobusError::Config(msg)) = app.plan() else {
+        panic!("missing dependency must be reported");
+    };
+    assert!(
+        msg.contains("requires") && msg.contains("not started"),
+        "{msg}"
+    );
+}
@@ -0,0 +1,51 @@
+use mmg_microbus::config::{AppConfig, LagMonitorConfig, Topology};
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::prelude::*;
+use std::time::Duration;
+
+#[test]
+fn invalid_config_is_rejected_with_every_field_named() {
+    AppConfig::default().validate().unwrap();
+
+    let cfg = AppConfig {
+        queue_capacity: 0,
+        startup_progress_interval: Some(Duration::ZERO),
+        subscriber_lag: Some(LagMonitorConfig {
+            threshold_percent: 150,
+            check_interval: Duration::ZERO,
+            ..LagMonitorConfig::default()
+        }),
+        topology: Topology {
+            max_concurrent_handlers: Some(0),
+            worker_threads: Some(0),
+            handler_budget: Some(0),
+            ..Topology::default()
+        },
+        ..AppConfig::default()
+    };
+    let Err(MicrobusError::Config(msg)) = cfg.validate() else {
+        panic!("invalid config accepted");
+    };
+    for field in [
+        "queue_capacity",
+        "startup_progress_interval",
+        "subscriber_lag.threshold_percent",
+        "subscriber_lag.check_interval",
+        "topology.max_concurrent_handlers",
+        "topology.worker_threads",
+        "topology.handler_budget",
+    ] {
+        assert!(msg.contains(field), "{field} missing from: {msg}");
+    }
+    assert!(msg.contains("got 0") && msg.contains("got 150"), "{msg}");
+
+    assert!(matches!(
+        App::try_new(cfg.clone()),
+        Err(MicrobusError::Config(_))
+    ));
+    let Err(panic) = std::panic::catch_unwind(|| App::new(cfg)) else {
+        panic!("App::new accepted an invalid config");
+    };
+    let text = panic.downcast_ref::<String>().unwrap();
+    assert!(text.contains("queue_capacity"), "{text}");
+}
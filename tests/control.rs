@@ -0,0 +1,67 @@
+use mmg_microbus::component::Control;
+use mmg_microbus::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+static DONE: AtomicU32 = AtomicU32::new(0);
+static FLUSHED: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Clone, Debug)]
+struct Job;
+struct Flush;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Worker;
+
+#[mmg_microbus::component]
+impl Worker {
+    #[mmg_microbus::handle]
+    async fn on_job(&self, _j: &Job) {
+        DONE.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // 自定义意图在 active 中消费
+    #[mmg_microbus::active]
+    async fn control(&self, ctx: &ComponentContext) {
+        if ctx.next_intent().await.downcast_ref::<Flush>().is_some() {
+            FLUSHED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+async fn wait_until(f: impl Fn() -> bool) {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+    while !f() {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "condition not reached"
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn control_plane_pauses_workers_and_delivers_intents() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.start().await.unwrap();
+    let bus = app.bus_handle();
+
+    assert!(app.control::<Worker>(Control::Pause));
+    for _ in 0..3 {
+        bus.publish_any_arc(Arc::new(Job)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(DONE.load(Ordering::SeqCst), 0);
+
+    // 暂停期间意图照常入队（active 可能正停在两次调用之间，恢复后取出）
+    assert!(app.control::<Worker>(Control::Intent(Arc::new(Flush))));
+
+    assert!(app.control::<Worker>(Control::Resume));
+    wait_until(|| DONE.load(Ordering::SeqCst) == 3).await;
+    wait_until(|| FLUSHED.load(Ordering::SeqCst) == 1).await;
+
+    assert!(!app.control_by_name("no::such::Component", Control::Pause));
+    app.stop();
+}
@@ -0,0 +1,108 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::crash::CrashBundles;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::prelude::*;
+use mmg_microbus::recorder::Recorder;
+use mmg_microbus::replay::Replay;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Quote(u64);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pricer;
+
+#[mmg_microbus::component]
+impl Pricer {
+    #[mmg_microbus::handle]
+    async fn on_quote(&self, q: &Quote) -> mmg_microbus::error::Result<()> {
+        if q.0 == 0 {
+            return Err(MicrobusError::Other("zero quote"));
+        }
+        Ok(())
+    }
+}
+
+fn quote_codec() -> (
+    impl Fn(&Quote) -> String + Send + Sync,
+    impl Fn(&str) -> std::result::Result<Quote, String> + Send + Sync,
+) {
+    (
+        |q: &Quote| q.0.to_string(),
+        |s: &str| s.parse().map(Quote).map_err(|e| format!("{e}")),
+    )
+}
+
+async fn wait_bundle(dir: &Path) -> PathBuf {
+    let found = async {
+        loop {
+            if let Some(entry) = std::fs::read_dir(dir).ok().and_then(|mut d| d.next()) {
+                let path = entry.unwrap().path();
+                // recent.rec 最后写入：存在即整包写完
+                if path.join("recent.rec").exists() {
+                    return path;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), found)
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failing_handler_writes_a_replayable_bundle() {
+    let tmp = tempfile::tempdir().unwrap();
+    let live = tmp.path().join("live");
+    let recorder = Recorder::new().codec(quote_codec());
+    let mut app = App::new(AppConfig::default());
+    app.crash_bundles(
+        CrashBundles::new(&live)
+            .codec(quote_codec())
+            .recent(&recorder.handle(), 10),
+    );
+    app.add_component(recorder);
+    app.start().await.unwrap();
+    for q in [7, 8, 0] {
+        app.bus_handle().publish_any_arc(Arc::new(Quote(q))).await;
+    }
+    let bundle = wait_bundle(&live).await;
+    app.stop();
+
+    let meta = std::fs::read_to_string(bundle.join("bundle.txt")).unwrap();
+    assert!(meta.contains("component: crash_bundle::Pricer\n"), "{meta}");
+    assert!(meta.contains("method: on_quote\n"));
+    assert!(meta.contains("error: zero quote\n"));
+    assert!(meta.contains("encoded: true\n"));
+    let message = std::fs::read_to_string(bundle.join("message.rec")).unwrap();
+    assert!(
+        message.ends_with("\tcrash_bundle::Quote\tcodec\t0\n"),
+        "{message}"
+    );
+    let recent = std::fs::read_to_string(bundle.join("recent.rec")).unwrap();
+    let payloads: Vec<_> = recent
+        .lines()
+        .filter_map(|l| l.rsplit('\t').next())
+        .collect();
+    assert_eq!(payloads, ["7", "8", "0"]);
+
+    // 本地复现：回放出错消息，同一 handler 再次失败
+    let local = tmp.path().join("local");
+    let mut app = App::new(AppConfig::default());
+    app.crash_bundles(CrashBundles::new(&local));
+    app.add_component(
+        Replay::from_file(bundle.join("message.rec"))
+            .codec(quote_codec())
+            .immediate(),
+    );
+    app.start().await.unwrap();
+    let replayed = wait_bundle(&local).await;
+    app.stop();
+    let meta = std::fs::read_to_string(replayed.join("bundle.txt")).unwrap();
+    assert!(meta.contains("error: zero quote\n"));
+    assert!(meta.contains("encoded: false\n"));
+}
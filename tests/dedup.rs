@@ -0,0 +1,70 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Position {
+    symbol: &'static str,
+    qty: i64,
+}
+
+async fn drain(sub: &mut mmg_microbus::bus::Subscription<Position>) -> Vec<(&'static str, i64)> {
+    let mut got = Vec::new();
+    while let Ok(Some(p)) = tokio::time::timeout(Duration::from_millis(1), sub.recv()).await {
+        got.push((p.symbol, p.qty));
+    }
+    got
+}
+
+#[tokio::test(start_paused = true)]
+async fn repeated_keys_are_suppressed_within_window() {
+    let mut cfg = AppConfig::default();
+    cfg.dedup::<Position, _>(Duration::from_secs(1), |p| (p.symbol, p.qty));
+    let mut app = App::new(cfg);
+    let bus = app.bus_handle();
+    let mut positions = bus.try_subscribe::<Position>().unwrap();
+    app.start().await.unwrap();
+
+    // 同一状态重复推送只放行首条；键不同（数量变化、其他标的）照常放行
+    for qty in [10, 10, 10, 20, 20, 10] {
+        bus.publish_any_arc(Arc::new(Position { symbol: "BTC", qty }))
+            .await;
+    }
+    bus.publish_any_box(Box::new(Position {
+        symbol: "ETH",
+        qty: 10,
+    }))
+    .await;
+    assert_eq!(
+        drain(&mut positions).await,
+        [("BTC", 10), ("BTC", 20), ("ETH", 10)]
+    );
+
+    // 窗口自上次放行起算，到期后同键再放行一次
+    tokio::time::advance(Duration::from_millis(1100)).await;
+    for _ in 0..3 {
+        bus.publish_any_arc(Arc::new(Position {
+            symbol: "BTC",
+            qty: 10,
+        }))
+        .await;
+    }
+    assert_eq!(drain(&mut positions).await, [("BTC", 10)]);
+
+    let dedup = app.introspect().dedup;
+    assert_eq!(dedup.len(), 1);
+    assert!(dedup[0].type_name.ends_with("Position"));
+    assert_eq!(dedup[0].suppressed, 6);
+    app.stop();
+}
+
+#[test]
+fn zero_window_is_rejected() {
+    let mut cfg = AppConfig::default();
+    cfg.dedup::<Position, _>(Duration::ZERO, |p| p.qty);
+    let Err(MicrobusError::Config(msg)) = cfg.validate() else {
+        panic!("zero window accepted");
+    };
+    assert!(msg.contains("Position"), "{msg}");
+}
@@ -0,0 +1,90 @@
+use mmg_microbus::codec::Json;
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::durable::{Durable, DurableHandle};
+use mmg_microbus::prelude::*;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Invoice(u64);
+
+static SLOW: AtomicBool = AtomicBool::new(false);
+static BILLED: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Biller;
+
+#[mmg_microbus::component]
+impl Biller {
+    #[mmg_microbus::handle]
+    async fn on_invoice(&self, inv: &Invoice) {
+        if SLOW.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        BILLED.lock().push(inv.0);
+    }
+}
+
+async fn start(db: &Path) -> (App, DurableHandle) {
+    let mut app = App::new(AppConfig::default());
+    let durable = Durable::open(db)
+        .codec::<Invoice>(Json)
+        .sweep_interval(Duration::from_millis(10));
+    let stats = durable.handle();
+    app.add_component(durable);
+    app.start().await.unwrap();
+    (app, stats)
+}
+
+async fn wait_until(f: impl Fn() -> bool) -> bool {
+    for _ in 0..200 {
+        if f() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    false
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn keeps_messages_until_handled_and_redelivers_after_restart() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let db = dir.path().join("queue.db");
+
+    // 处理完毕即删除
+    let (mut app, stats) = start(&db).await;
+    app.bus_handle().publish_any_arc(Arc::new(Invoice(0))).await;
+    assert!(wait_until(|| stats.completed() == 1).await);
+    assert_eq!((stats.persisted(), stats.pending()), (1, 0));
+
+    // 停机时仍在处理或排队的消息保留在存储中
+    SLOW.store(true, Ordering::SeqCst);
+    for id in 1..=3 {
+        app.bus_handle()
+            .publish_any_arc(Arc::new(Invoice(id)))
+            .await;
+    }
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    app.stop();
+    assert_eq!(stats.pending(), 3);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    SLOW.store(false, Ordering::SeqCst);
+    BILLED.lock().clear();
+
+    // 重启后按原顺序重新投递，处理完毕后清空
+    let (mut app, stats) = start(&db).await;
+    assert!(wait_until(|| stats.completed() == 3).await);
+    assert_eq!((stats.redelivered(), stats.pending()), (3, 0));
+    assert_eq!(*BILLED.lock(), [1, 2, 3]);
+    app.stop();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let (mut app, stats) = start(&db).await;
+    assert_eq!(stats.redelivered(), 0);
+    app.stop();
+}
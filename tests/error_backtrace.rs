@@ -0,0 +1,37 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::{MicrobusError, SubscribeError};
+use mmg_microbus::prelude::*;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Gateway;
+
+#[mmg_microbus::component]
+impl Gateway {
+    #[mmg_microbus::init]
+    async fn init(&self) -> Result<()> {
+        Err(MicrobusError::Config("gateway.url not set".to_owned()))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn startup_failures_carry_the_failing_component_stack() {
+    let mut app = App::new(AppConfig::default());
+    let err = app.start().await.expect_err("init fails");
+    let MicrobusError::Startup { failures } = &err else {
+        panic!("unexpected error: {err:?}");
+    };
+    let bt = failures[0].trace.backtrace().expect("captured").to_string();
+    // 调用栈定位到失败组件的 run（宏生成的 init 调用处）
+    assert!(bt.contains("error_backtrace::Gateway"), "{bt}");
+    assert!(format!("{err:?}").contains("error_backtrace::Gateway"));
+}
+
+#[test]
+fn bus_errors_capture_on_conversion() {
+    let err = MicrobusError::from(SubscribeError::Sealed { type_name: "Quote" });
+    let MicrobusError::Subscribe { trace, .. } = &err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert!(trace.backtrace().is_some());
+}
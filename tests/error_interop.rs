@@ -0,0 +1,52 @@
+use anyhow::Context as _;
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::prelude::*;
+use std::error::Error;
+
+#[derive(Debug, thiserror::Error)]
+enum LoadError {
+    #[error("config unreadable")]
+    Io(#[from] std::io::Error),
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Loader;
+
+#[mmg_microbus::component]
+impl Loader {
+    // 业务错误类型直接作为 init 的错误类型
+    #[mmg_microbus::init]
+    async fn init(&self) -> std::result::Result<(), LoadError> {
+        Err(std::io::Error::other("disk gone").into())
+    }
+}
+
+fn chain(e: &(dyn Error + 'static)) -> Vec<String> {
+    std::iter::successors(Some(e), |&e| e.source())
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn user_error_types_flow_through_unchanged() {
+    let mut app = App::new(AppConfig::default());
+    let err = app.start().await.expect_err("init fails");
+    let MicrobusError::Startup { failures } = &err else {
+        panic!("unexpected error: {err:?}");
+    };
+    let MicrobusError::Custom(user) = &failures[0].error else {
+        panic!("unexpected failure: {failures:?}");
+    };
+    assert!(matches!(user.downcast_ref(), Some(LoadError::Io(_))));
+    assert_eq!(chain(&err)[1..], ["config unreadable", "disk gone"]);
+
+    // anyhow 的上下文链经 `?` 原样保留
+    let from_anyhow = || -> mmg_microbus::error::Result<()> {
+        Err(std::io::Error::other("refused")).context("connect feed")?;
+        Ok(())
+    };
+    let err = from_anyhow().unwrap_err();
+    assert_eq!(chain(&err), ["connect feed", "refused"]);
+}
@@ -0,0 +1,66 @@
+use mmg_microbus::config::{AppConfig, Topology};
+use mmg_microbus::prelude::*;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::Duration;
+
+const N: usize = 40;
+
+#[derive(Debug)]
+struct Bulk;
+#[derive(Debug)]
+struct Trickle;
+
+static ORDER: Mutex<Vec<char>> = Mutex::new(Vec::new());
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Mixer;
+
+#[mmg_microbus::component]
+impl Mixer {
+    #[mmg_microbus::handle(weight = 3)]
+    async fn on_bulk(&self, _b: &Bulk) {
+        ORDER.lock().push('b');
+    }
+    #[mmg_microbus::handle]
+    async fn on_trickle(&self, _t: &Trickle) {
+        ORDER.lock().push('t');
+    }
+}
+
+// 单线程运行时上两个 handler 同时积压：按 `budget × weight` 轮流推进，而非一方排空后另一方才开始
+#[tokio::test(flavor = "current_thread")]
+async fn handler_budget_interleaves_by_weight() {
+    let mut app = App::new(AppConfig {
+        topology: Topology {
+            handler_budget: Some(2),
+            ..Topology::default()
+        },
+        ..AppConfig::default()
+    });
+    let bus = app.bus_handle();
+    app.start().await.unwrap();
+
+    for _ in 0..N {
+        bus.publish_any_arc(Arc::new(Bulk)).await;
+    }
+    for _ in 0..N {
+        bus.publish_any_arc(Arc::new(Trickle)).await;
+    }
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while ORDER.lock().len() < 2 * N {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    // 双方都有积压时 trickle 约占 1/4（预算 2：bulk 每轮 6 条、trickle 2 条）；无预算时 bulk 先排空
+    let order: String = ORDER.lock().iter().collect();
+    let first_t = order.find('t').unwrap();
+    assert!(first_t <= 12, "{order}");
+    let t_share = order[..32].matches('t').count();
+    assert!((6..=10).contains(&t_share), "{order}");
+    app.stop();
+}
@@ -0,0 +1,100 @@
+use mmg_microbus::codec::BusMessage;
+use mmg_microbus::ffi::{
+    expose, mmg_app_free, mmg_app_new, mmg_app_start, mmg_publish_json, mmg_subscribe_callback,
+    MMG_ERR_DECODE, MMG_ERR_STATE, MMG_ERR_UNKNOWN_TYPE, MMG_OK,
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::ffi::{c_char, c_void, CStr};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, Serialize, Deserialize, BusMessage)]
+struct Order {
+    id: u64,
+    qty: u32,
+}
+#[derive(Clone, Debug, Serialize, Deserialize, BusMessage)]
+struct Fill {
+    id: u64,
+    filled: u32,
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Matcher;
+
+#[mmg_microbus::component]
+impl Matcher {
+    #[mmg_microbus::handle]
+    async fn on_order(&self, o: &Order) -> Fill {
+        Fill {
+            id: o.id,
+            filled: o.qty,
+        }
+    }
+}
+
+static FILLS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+unsafe extern "C" fn on_fill(
+    type_name: *const c_char,
+    json: *const c_char,
+    user_data: *mut c_void,
+) {
+    let tag = unsafe { *user_data.cast::<u32>() };
+    assert_eq!(tag, 42);
+    let (name, json) = unsafe { (CStr::from_ptr(type_name), CStr::from_ptr(json)) };
+    FILLS.lock().push((
+        name.to_string_lossy().into_owned(),
+        json.to_string_lossy().into_owned(),
+    ));
+}
+
+#[test]
+fn c_host_publishes_and_subscribes_via_json() {
+    expose::<Order>();
+    expose::<Fill>();
+    let mut tag = 42u32;
+    unsafe {
+        let app = mmg_app_new();
+        assert!(!app.is_null());
+        assert_eq!(
+            mmg_subscribe_callback(
+                app,
+                c"Unknown".as_ptr(),
+                Some(on_fill),
+                std::ptr::null_mut()
+            ),
+            MMG_ERR_UNKNOWN_TYPE
+        );
+        let user_data = (&raw mut tag).cast::<c_void>();
+        assert_eq!(
+            mmg_subscribe_callback(app, c"Fill".as_ptr(), Some(on_fill), user_data),
+            MMG_OK
+        );
+        assert_eq!(
+            mmg_publish_json(app, c"Order".as_ptr(), c"{}".as_ptr()),
+            MMG_ERR_STATE
+        );
+        assert_eq!(mmg_app_start(app), MMG_OK);
+        assert_eq!(mmg_app_start(app), MMG_ERR_STATE);
+
+        assert_eq!(
+            mmg_publish_json(app, c"Order".as_ptr(), c"{\"id\":1}".as_ptr()),
+            MMG_ERR_DECODE
+        );
+        assert_eq!(
+            mmg_publish_json(app, c"Order".as_ptr(), c"{\"id\":1,\"qty\":5}".as_ptr()),
+            MMG_OK
+        );
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while FILLS.lock().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        mmg_app_free(app);
+    }
+    assert_eq!(
+        *FILLS.lock(),
+        [("Fill".to_owned(), "{\"id\":1,\"filled\":5}".to_owned())]
+    );
+}
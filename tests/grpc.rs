@@ -0,0 +1,131 @@
+use mmg_microbus::grpc::tonic::codegen::http::uri::PathAndQuery;
+use mmg_microbus::grpc::tonic::transport::Channel;
+use mmg_microbus::grpc::tonic::{self, Code};
+use mmg_microbus::grpc::{GrpcEgress, GrpcIngress};
+use mmg_microbus::query::Ask;
+use mmg_microbus::testing::TestApp;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct SayRequest {
+    #[prost(string, tag = "1")]
+    text: String,
+}
+#[derive(Clone, PartialEq, prost::Message)]
+struct SayReply {
+    #[prost(string, tag = "1")]
+    text: String,
+}
+#[derive(Clone, PartialEq, prost::Message)]
+struct HoldRequest {}
+#[derive(Clone, PartialEq, prost::Message)]
+struct OrphanRequest {}
+
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Echo;
+
+#[mmg_microbus::component]
+impl Echo {
+    #[mmg_microbus::handle]
+    async fn say(&self, q: &Ask<SayRequest, SayReply>) {
+        q.reply(SayReply {
+            text: q.text.to_uppercase(),
+        });
+    }
+    #[mmg_microbus::handle]
+    async fn hold(&self, q: &Ask<HoldRequest, SayReply>) {
+        tokio::select! {
+            () = q.cancelled() => CANCELLED.store(true, Ordering::SeqCst),
+            () = tokio::time::sleep(Duration::from_secs(30)) => {}
+        }
+    }
+}
+
+async fn serve_echo() -> (TestApp, String) {
+    let ingress = GrpcIngress::bind("127.0.0.1:0")
+        .unary::<SayRequest, SayReply>("/test.Echo/Say")
+        .unary_with_timeout::<HoldRequest, SayReply>("/test.Echo/Hold", Duration::from_millis(200))
+        .unary::<OrphanRequest, SayReply>("/test.Echo/Orphan");
+    let stats = ingress.handle();
+    let app = TestApp::builder()
+        .component::<Echo>()
+        .add_component(ingress)
+        .start()
+        .await
+        .unwrap();
+    (app, format!("http://{}", stats.local_addr().unwrap()))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn egress_calls_ingress_handlers_over_grpc() {
+    let (remote, endpoint) = serve_echo().await;
+    let egress = GrpcEgress::connect(endpoint)
+        .unary::<SayRequest, SayReply>("/test.Echo/Say")
+        .unary::<HoldRequest, SayReply>("/test.Echo/Hold");
+    let stats = egress.handle();
+    // 只运行桥组件：本地不应答，请求必须经 gRPC 到达远端 handler
+    let local = TestApp::builder()
+        .component::<GrpcEgress>()
+        .add_component(egress)
+        .start()
+        .await
+        .unwrap();
+    let bus = local.app().bus_handle();
+
+    let reply: SayReply = bus
+        .request(SayRequest {
+            text: "hello".to_owned(),
+        })
+        .timeout(Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(reply.text, "HELLO");
+    assert_eq!(stats.calls(), 1);
+
+    // 远端总线请求超时 -> DEADLINE_EXCEEDED：远端应答方感知放弃，本地不会收到应答
+    //（TestApp 捕获全部消息，未应答的 `Ask` 仍被持有，本地请求以自身超时结束）
+    let err = bus
+        .request::<_, SayReply>(HoldRequest {})
+        .timeout(Duration::from_secs(1))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, mmg_microbus::error::MicrobusError::Timeout { .. }),
+        "{err}"
+    );
+    assert!(CANCELLED.load(Ordering::SeqCst));
+    assert_eq!(stats.failed(), 1);
+    drop((local, remote));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ingress_maps_missing_methods_and_responders_to_status() {
+    let (remote, endpoint) = serve_echo().await;
+    let channel = Channel::from_shared(endpoint)
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    let call = |path: &'static str| {
+        let mut client = tonic::client::Grpc::new(channel.clone());
+        async move {
+            client.ready().await.unwrap();
+            client
+                .unary(
+                    tonic::Request::new(OrphanRequest {}),
+                    PathAndQuery::from_static(path),
+                    tonic_prost::ProstCodec::<OrphanRequest, SayReply>::default(),
+                )
+                .await
+                .unwrap_err()
+                .code()
+        }
+    };
+    assert_eq!(call("/test.Echo/Missing").await, Code::Unimplemented);
+    assert_eq!(call("/test.Echo/Orphan").await, Code::Unavailable);
+    drop(remote);
+}
@@ -0,0 +1,62 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+const N: u64 = 500;
+
+#[derive(Debug)]
+struct Step(u64);
+
+#[derive(Debug)]
+struct Seen(u64);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Ledger;
+
+#[mmg_microbus::component]
+impl Ledger {
+    #[mmg_microbus::handle(in_order)]
+    async fn on_step(&self, s: &Step) -> Seen {
+        if s.0.is_multiple_of(7) {
+            tokio::task::yield_now().await;
+        }
+        Seen(s.0)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn in_order_handler_sees_publisher_fifo() {
+    // 小队列：发布方频繁因背压等待，顺序仍须保持
+    let mut app = App::new(AppConfig {
+        queue_capacity: 4,
+        ..AppConfig::default()
+    });
+    let bus = app.bus_handle();
+    let mut seen = bus.try_subscribe::<Seen>().unwrap();
+    app.start().await.unwrap();
+
+    let sub = app
+        .introspect()
+        .subscriptions
+        .into_iter()
+        .find(|s| s.component == std::any::type_name::<Ledger>())
+        .unwrap();
+    assert!(sub.ordered);
+
+    let publisher = tokio::spawn(async move {
+        for i in 0..N {
+            bus.publish_any_arc(Arc::new(Step(i))).await;
+        }
+    });
+    for i in 0..N {
+        let s = tokio::time::timeout(Duration::from_secs(5), seen.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(s.0, i);
+    }
+    publisher.await.unwrap();
+    app.stop();
+}
@@ -0,0 +1,83 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Trigger;
+
+#[derive(Debug)]
+struct Tick;
+
+#[derive(Debug, PartialEq)]
+struct Seen {
+    seq: Option<u64>,
+    origin: Option<&'static str>,
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Producer;
+
+#[mmg_microbus::component]
+impl Producer {
+    #[mmg_microbus::handle]
+    async fn on_trigger(&self, _t: &Trigger) -> Tick {
+        Tick
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Recorder;
+
+#[mmg_microbus::component]
+impl Recorder {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, env: &Envelope<Tick>) -> Seen {
+        assert!(env.enqueued_at() <= tokio::time::Instant::now());
+        Seen {
+            seq: env.seq(),
+            origin: env.origin(),
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn envelope_handler_sees_delivery_metadata() {
+    let mut app = App::new(AppConfig {
+        sequence_numbers: true,
+        ..AppConfig::default()
+    });
+    let bus = app.bus_handle();
+    let mut seen = bus.try_subscribe::<Seen>().unwrap();
+    app.start().await.unwrap();
+
+    bus.publish_any_arc(Arc::new(Tick)).await;
+    let first = tokio::time::timeout(Duration::from_secs(5), seen.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        *first,
+        Seen {
+            seq: Some(1),
+            origin: None
+        }
+    );
+
+    // 经组件发布：信封记录发布方组件
+    bus.publish_any_arc(Arc::new(Trigger)).await;
+    let second = tokio::time::timeout(Duration::from_secs(5), seen.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        *second,
+        Seen {
+            seq: Some(2),
+            origin: Some(std::any::type_name::<Producer>())
+        }
+    );
+    app.stop();
+}
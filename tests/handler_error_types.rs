@@ -0,0 +1,61 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::events::HandlerError;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::BusProbe;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Order(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Filled(u64);
+
+// 业务错误类型：不经框架错误或 anyhow
+#[derive(Debug, thiserror::Error)]
+enum VenueError {
+    #[error("venue rejected order {0}")]
+    Rejected(u64),
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Router {
+    calls: AtomicU32,
+}
+
+#[mmg_microbus::component]
+impl Router {
+    // 首次调用失败，重试后成功
+    #[mmg_microbus::handle(on_error = retry(1))]
+    async fn on_order(&self, o: &Order) -> Result<Filled, VenueError> {
+        if self.calls.fetch_add(1, Ordering::SeqCst) == 0 || o.0 == 0 {
+            return Err(VenueError::Rejected(o.0));
+        }
+        Ok(Filled(o.0))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn domain_error_types_feed_policy_and_report_display_text() {
+    let mut app = App::new(AppConfig {
+        publish_handler_errors: true,
+        ..AppConfig::default()
+    });
+    let filled = BusProbe::<Filled>::attach(&app);
+    let errors = BusProbe::<HandlerError>::attach(&app);
+    app.start().await.unwrap();
+
+    let bus = app.bus_handle();
+    bus.publish_any_arc(Arc::new(Order(5))).await;
+    filled
+        .assert_received_in_order(&[Filled(5)], Duration::from_secs(2))
+        .await;
+    assert_eq!(errors.count(), 0);
+
+    // 重试耗尽后按 Display 上报
+    bus.publish_any_arc(Arc::new(Order(0))).await;
+    errors.assert_count(1, Duration::from_secs(2)).await;
+    assert_eq!(errors.received()[0].error, "venue rejected order 0");
+    app.stop();
+}
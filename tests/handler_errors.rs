@@ -0,0 +1,61 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::events::HandlerError;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::BusProbe;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Order(u64);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Validator;
+
+#[mmg_microbus::component]
+impl Validator {
+    #[mmg_microbus::handle]
+    async fn on_order(&self, o: &Order) -> mmg_microbus::error::Result<()> {
+        if o.0 == 0 {
+            return Err(MicrobusError::Other("empty order"));
+        }
+        Ok(())
+    }
+}
+
+// 上报组件自身出错：只记录日志，不再发布 HandlerError
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Reporter;
+
+#[mmg_microbus::component]
+impl Reporter {
+    #[mmg_microbus::handle]
+    async fn on_error(&self, _e: &HandlerError) -> mmg_microbus::error::Result<()> {
+        Err(MicrobusError::Other("alert channel down"))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn handler_errors_are_published_when_enabled() {
+    let cfg = AppConfig {
+        publish_handler_errors: true,
+        ..Default::default()
+    };
+    let mut app = App::new(cfg);
+    let errors = BusProbe::<HandlerError>::attach(&app);
+    app.start().await.unwrap();
+    for id in [1, 0, 2] {
+        app.bus_handle().publish_any_arc(Arc::new(Order(id))).await;
+    }
+    errors.assert_count(1, Duration::from_secs(2)).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(errors.count(), 1);
+    let e = &errors.received()[0];
+    assert!(e.component.ends_with("::Validator"));
+    assert_eq!(e.method, "on_order");
+    assert!(e.message_type.ends_with("::Order"));
+    assert_eq!(e.error, "empty order");
+    app.stop();
+}
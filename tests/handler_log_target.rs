@@ -0,0 +1,72 @@
+use mmg_microbus::prelude::*;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Ping;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pinger;
+
+#[mmg_microbus::component]
+impl Pinger {
+    #[mmg_microbus::active(once)]
+    async fn ping(&self) -> Ping {
+        Ping
+    }
+}
+
+mod strategy {
+    use super::Ping;
+
+    #[mmg_microbus::component]
+    #[derive(Default)]
+    pub struct Failing;
+
+    #[mmg_microbus::component]
+    impl Failing {
+        #[mmg_microbus::handle]
+        async fn on_ping(&self, _p: &Ping) -> mmg_microbus::error::Result<()> {
+            Err(mmg_microbus::error::MicrobusError::Other("boom"))
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// 生成代码的出错日志以组件所在模块为 target，按业务模块过滤日志照常生效
+#[tokio::test(flavor = "multi_thread")]
+async fn handler_error_logs_under_component_module() {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_global_default(subscriber);
+
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    app.stop();
+
+    let out = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    let line = out
+        .lines()
+        .find(|l| l.contains("handle returned error"))
+        .unwrap_or_else(|| panic!("log: {out}"));
+    assert!(line.contains("handler_log_target::strategy:"), "{line}");
+    assert!(line.contains("boom"), "{line}");
+}
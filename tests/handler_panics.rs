@@ -0,0 +1,48 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::events::HandlerError;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::BusProbe;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Quote(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Priced(u64);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pricer;
+
+#[mmg_microbus::component]
+impl Pricer {
+    #[mmg_microbus::handle]
+    async fn on_quote(&self, q: &Quote) -> Priced {
+        assert!(q.0 != 0, "zero quote");
+        Priced(q.0 * 10)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn handler_panic_is_reported_and_the_worker_keeps_running() {
+    let cfg = AppConfig {
+        publish_handler_errors: true,
+        ..Default::default()
+    };
+    let mut app = App::new(cfg);
+    let priced = BusProbe::<Priced>::attach(&app);
+    let errors = BusProbe::<HandlerError>::attach(&app);
+    app.start().await.unwrap();
+
+    for q in [1, 0, 2] {
+        app.bus_handle().publish_any_arc(Arc::new(Quote(q))).await;
+    }
+    priced
+        .assert_received_in_order(&[Priced(10), Priced(20)], Duration::from_secs(2))
+        .await;
+    errors.assert_count(1, Duration::from_secs(2)).await;
+    let e = &errors.received()[0];
+    assert_eq!(e.method, "on_quote");
+    assert_eq!(e.error, "panicked: zero quote");
+    app.stop();
+}
@@ -0,0 +1,90 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::{MicrobusError, Result};
+use mmg_microbus::events::ComponentStopped;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::BusProbe;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Quote(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Ack(u64, u32);
+#[derive(Clone, Debug)]
+struct Malformed;
+#[derive(Clone, Debug)]
+struct LimitBreached;
+
+// 每条报价前两次调用失败
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Flaky {
+    calls: AtomicU32,
+}
+
+#[mmg_microbus::component]
+impl Flaky {
+    #[mmg_microbus::handle(on_error = retry(2))]
+    async fn on_quote(&self, q: &Quote) -> Result<Ack> {
+        let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if !n.is_multiple_of(3) {
+            return Err(MicrobusError::Other("upstream timeout"));
+        }
+        Ok(Ack(q.0, n))
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Parser;
+
+#[mmg_microbus::component]
+impl Parser {
+    #[mmg_microbus::handle(on_error = stop_component)]
+    async fn on_malformed(&self, _m: &Malformed) -> Result<()> {
+        Err(MicrobusError::Other("cannot parse"))
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct RiskCheck;
+
+#[mmg_microbus::component]
+impl RiskCheck {
+    #[mmg_microbus::handle(on_error = stop_app)]
+    async fn on_breach(&self, _b: &LimitBreached) -> Result<()> {
+        Err(MicrobusError::Other("position limit exceeded"))
+    }
+}
+
+async fn publish<T: Send + Sync + 'static>(app: &App, msg: T) {
+    app.bus_handle().publish_any_arc(Arc::new(msg)).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn handler_error_policies_retry_stop_component_and_stop_app() {
+    let mut app = App::new(AppConfig::default());
+    let acks = BusProbe::<Ack>::attach(&app);
+    let stopped = BusProbe::<ComponentStopped>::attach(&app);
+    app.start().await.unwrap();
+
+    publish(&app, Quote(1)).await;
+    acks.assert_received_in_order(&[Ack(1, 3)], Duration::from_secs(2))
+        .await;
+
+    // 仅 Parser 停止，其余组件照常处理
+    publish(&app, Malformed).await;
+    stopped.assert_count(1, Duration::from_secs(2)).await;
+    assert!(stopped.received()[0].component.ends_with("::Parser"));
+    publish(&app, Quote(2)).await;
+    acks.assert_received_in_order(&[Ack(1, 3), Ack(2, 6)], Duration::from_secs(2))
+        .await;
+
+    publish(&app, LimitBreached).await;
+    tokio::time::timeout(Duration::from_secs(2), app.wait_for_stop())
+        .await
+        .expect("risk check stops the app");
+    app.stop();
+}
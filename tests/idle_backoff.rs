@@ -0,0 +1,46 @@
+use mmg_microbus::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+static POLLS: AtomicU32 = AtomicU32::new(0);
+static PENDING: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Debug)]
+struct Item;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Poller;
+
+#[mmg_microbus::component]
+impl Poller {
+    #[mmg_microbus::active(idle_backoff = "1ms..8ms")]
+    async fn poll(&self) -> Option<Item> {
+        POLLS.fetch_add(1, Ordering::SeqCst);
+        PENDING
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .ok()
+            .map(|_| Item)
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn idle_active_backs_off_until_it_produces() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let bus = app.bus_handle();
+    let mut items = bus.try_subscribe::<Item>().unwrap();
+    app.start().await.unwrap();
+
+    // 空闲等待 1,2,4,8,8,...ms：100ms 内约十余次调用，而非忙转
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let polls = POLLS.load(Ordering::SeqCst);
+    assert!((10..=20).contains(&polls), "polled {polls} times");
+
+    // 上限 8ms：新数据最迟在一个上限周期内被取走
+    PENDING.store(2, Ordering::SeqCst);
+    for _ in 0..2 {
+        let item = tokio::time::timeout(Duration::from_millis(10), items.recv()).await;
+        assert!(item.unwrap().is_some());
+    }
+    app.stop();
+}
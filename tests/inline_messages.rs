@@ -0,0 +1,74 @@
+use futures_util::stream;
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// 统计堆分配次数：内联类型的发布不应逐条分配
+struct Counting;
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tick(u64);
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Boxed(u64);
+
+const N: u64 = 1000;
+
+#[tokio::test(flavor = "current_thread")]
+async fn copy_messages_skip_arc_allocation() {
+    let mut app = App::new(AppConfig {
+        queue_capacity: 2048,
+        ..AppConfig::default()
+    });
+    app.inline_message::<Tick>();
+    let bus = app.bus_handle();
+    let mut ticks = bus.try_subscribe::<Tick>().unwrap();
+    let mut boxed = bus.try_subscribe::<Boxed>().unwrap();
+    app.start().await.unwrap();
+
+    let publish_counted = |items: Vec<u64>, inline: bool| {
+        let bus = bus.clone();
+        async move {
+            let before = ALLOCS.load(Ordering::Relaxed);
+            if inline {
+                bus.publish_stream(stream::iter(items.into_iter().map(Tick)))
+                    .await;
+            } else {
+                bus.publish_stream(stream::iter(items.into_iter().map(Boxed)))
+                    .await;
+            }
+            ALLOCS.load(Ordering::Relaxed) - before
+        }
+    };
+    let inline_allocs = publish_counted((0..N).collect(), true).await;
+    let shared_allocs = publish_counted((0..N).collect(), false).await;
+    // 队列按块扩容仍会分配，但远少于逐条 Arc
+    assert!(
+        inline_allocs < 100,
+        "inline publish allocated {inline_allocs} times"
+    );
+    assert!(
+        shared_allocs >= 1000,
+        "shared publish allocated {shared_allocs} times"
+    );
+
+    for i in 0..N {
+        assert_eq!(*ticks.recv_envelope().await.unwrap(), Tick(i));
+        assert_eq!(*boxed.recv().await.unwrap(), Boxed(i));
+    }
+    app.stop();
+}
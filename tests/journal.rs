@@ -0,0 +1,62 @@
+use mmg_microbus::codec::Json;
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::journal::Journal;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::BusProbe;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Order(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Derived(u64);
+
+async fn publish<T: Send + Sync + 'static>(app: &App, msg: T) {
+    app.bus_handle().publish_any_arc(Arc::new(msg)).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn journals_inputs_and_replays_them_into_a_fresh_app() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("orders.journal");
+
+    let mut app = App::new(AppConfig::default());
+    let journal = Journal::open(&path).codec::<Order>(Json);
+    let stats = journal.handle();
+    app.add_component(journal);
+    app.start().await.unwrap();
+    publish(&app, Order(1)).await;
+    publish(&app, Derived(1)).await;
+    publish(&app, Order(2)).await;
+    assert_eq!((stats.appended(), stats.last_seq()), (2, 2));
+    app.stop();
+
+    // 模拟崩溃：末尾残留半行
+    let mut text = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(text.lines().count(), 2);
+    text.push_str("3\t0\tpartial");
+    std::fs::write(&path, text).unwrap();
+
+    let mut app = App::new(AppConfig::default());
+    let orders = BusProbe::<Order>::attach(&app);
+    let journal = Journal::open(&path).codec::<Order>(Json);
+    let stats = journal.handle();
+    app.add_component(journal);
+    app.start().await.unwrap();
+    orders
+        .assert_received_in_order(&[Order(1), Order(2)], Duration::from_secs(2))
+        .await;
+    assert_eq!((stats.replayed(), stats.appended()), (2, 0));
+
+    // 回放不重复写入，新记录接续序号
+    publish(&app, Order(3)).await;
+    assert_eq!(stats.last_seq(), 3);
+    app.stop();
+    let text = std::fs::read_to_string(&path).unwrap();
+    let seqs: Vec<&str> = text
+        .lines()
+        .map(|l| l.split('\t').next().unwrap())
+        .collect();
+    assert_eq!(seqs, ["1", "2", "3"]);
+}
@@ -0,0 +1,60 @@
+use mmg_microbus::component::{Component, ComponentContext};
+use mmg_microbus::events::{AppSealed, ComponentStarted, ComponentStopped};
+use mmg_microbus::prelude::*;
+use parking_lot::Mutex;
+use std::time::Duration;
+
+static STARTED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+static STOPPED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+static SEALED: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Watcher;
+
+#[mmg_microbus::component]
+impl Watcher {
+    #[mmg_microbus::handle]
+    async fn on_started(&self, e: &ComponentStarted) {
+        STARTED.lock().push(e.component);
+    }
+    #[mmg_microbus::handle]
+    async fn on_stopped(&self, e: &ComponentStopped) {
+        STOPPED.lock().push(e.component);
+    }
+    #[mmg_microbus::handle]
+    async fn on_sealed(&self, e: &AppSealed) {
+        SEALED.lock().push(e.components);
+    }
+}
+
+// 手写组件：越过启动屏障后立即结束，用于观测 ComponentStopped
+struct Ephemeral;
+
+#[async_trait::async_trait]
+impl Component for Ephemeral {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn lifecycle_transitions_are_published() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.add_component(Ephemeral);
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    // 停机前取快照：停机过程中其余组件同样会发布 ComponentStopped
+    let stopped = STOPPED.lock().clone();
+    app.stop();
+
+    let mut started = STARTED.lock().clone();
+    started.sort_unstable();
+    assert_eq!(started.len(), 2, "{started:?}");
+    assert!(started.iter().any(|c| c.ends_with("Watcher")));
+    assert!(started.iter().any(|c| c.ends_with("Ephemeral")));
+    assert_eq!(*SEALED.lock(), vec![2]);
+    assert_eq!(stopped.len(), 1);
+    assert!(stopped[0].ends_with("Ephemeral"));
+}
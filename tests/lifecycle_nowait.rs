@@ -0,0 +1,45 @@
+use mmg_microbus::component::{Component, ComponentContext};
+use mmg_microbus::events::ComponentStarted;
+use mmg_microbus::prelude::*;
+use std::time::Duration;
+
+// 手写组件：越过启动屏障后等待停机
+struct Idle;
+
+#[async_trait::async_trait]
+impl Component for Idle {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        mmg_microbus::component::__recv_stop(&ctx).await;
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn lifecycle_events_never_block_on_full_subscriber() {
+    let mut app = App::new(mmg_microbus::config::AppConfig {
+        queue_capacity: 1,
+        ..Default::default()
+    });
+    for _ in 0..3 {
+        app.add_component(Idle);
+    }
+    // 从不消费的订阅：容量 1，第二条起队列已满
+    let _started = app
+        .bus_handle()
+        .try_subscribe::<ComponentStarted>()
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), app.start())
+        .await
+        .expect("start must not wait on a full lifecycle subscriber")
+        .expect("start");
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let drops = app.introspect().drops;
+    let started = drops
+        .iter()
+        .find(|d| d.type_name.ends_with("ComponentStarted"))
+        .expect("full drops counted");
+    assert_eq!((started.closed, started.full), (0, 2));
+    app.stop();
+}
@@ -0,0 +1,65 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::time::Duration;
+
+// 组件内部阶段类型
+#[derive(Debug)]
+struct Raw(u32);
+#[derive(Debug)]
+struct Parsed(u32);
+
+#[derive(Debug, PartialEq)]
+struct Done(u32);
+#[derive(Debug, PartialEq)]
+struct Unrouted;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Stages;
+
+#[mmg_microbus::component]
+impl Stages {
+    #[mmg_microbus::active(once)]
+    async fn feed(&self, ctx: &ComponentContext) {
+        assert!(ctx.local_publish(Raw(4)).await);
+        // 没有局部 handler 的类型不会落到全局总线
+        assert!(!ctx.local_publish(Unrouted).await);
+    }
+
+    #[mmg_microbus::handle(local)]
+    async fn parse(&self, ctx: &ComponentContext, r: &Raw) {
+        ctx.local_publish(Parsed(r.0 + 1)).await;
+    }
+
+    #[mmg_microbus::handle(local)]
+    async fn finish(&self, p: &Parsed) -> Done {
+        Done(p.0 * 10)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn local_publish_reaches_only_local_handlers() {
+    let mut app = App::new(AppConfig::default());
+    let bus = app.bus_handle();
+    let mut done = bus.try_subscribe::<Done>().unwrap();
+    let mut raw = bus.try_subscribe::<Raw>().unwrap();
+    let mut unrouted = bus.try_subscribe::<Unrouted>().unwrap();
+    app.start().await.unwrap();
+
+    let got = tokio::time::timeout(Duration::from_secs(5), done.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*got, Done(50));
+
+    // 局部订阅不进入全局路由表；局部类型也不泄漏给外部订阅
+    let topo = app.introspect();
+    assert!(!topo
+        .subscriptions
+        .iter()
+        .any(|s| s.component.ends_with("Stages")));
+    let quiet = Duration::from_millis(50);
+    assert!(tokio::time::timeout(quiet, raw.recv()).await.is_err());
+    assert!(tokio::time::timeout(quiet, unrouted.recv()).await.is_err());
+    app.stop();
+}
@@ -0,0 +1,67 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Order(u32);
+
+// 组件内部的中间类型
+#[derive(Debug, PartialEq)]
+struct Stage(u32);
+
+#[derive(Debug, PartialEq)]
+struct Done(u32);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pipeline;
+
+#[mmg_microbus::component]
+impl Pipeline {
+    #[mmg_microbus::handle]
+    async fn on_order(&self, o: &Order) -> Stage {
+        Stage(o.0)
+    }
+
+    #[mmg_microbus::handle(scope = local)]
+    async fn on_stage(&self, s: &Stage) -> Done {
+        Done(s.0 * 10)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn local_handler_sees_only_own_publications() {
+    let mut app = App::new(AppConfig::default());
+    let bus = app.bus_handle();
+    let mut stages = bus.try_subscribe::<Stage>().unwrap();
+    let mut done = bus.try_subscribe::<Done>().unwrap();
+    // 局部订阅不计入装配校验
+    app.declare_publisher::<Order>()
+        .declare_subscriber::<Done>();
+    app.validate_strict().unwrap();
+    app.start().await.unwrap();
+
+    // 外部发布的 Stage 不进入局部 handler；组件发布的 Stage 不泄漏到全局总线
+    bus.publish_any_arc(Arc::new(Stage(7))).await;
+    bus.publish_any_arc(Arc::new(Order(1))).await;
+    let first = tokio::time::timeout(Duration::from_secs(5), done.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*first, Done(10));
+    let seen = stages.recv().await.unwrap();
+    assert_eq!(*seen, Stage(7));
+
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), done.recv())
+            .await
+            .is_err()
+    );
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), stages.recv())
+            .await
+            .is_err()
+    );
+    app.stop();
+}
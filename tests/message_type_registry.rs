@@ -0,0 +1,85 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+
+#[derive(Debug)]
+struct Small(u8);
+
+// 按值发布的大结构体：登记表按尺寸降序排在最前
+#[derive(Debug)]
+struct Frame {
+    _payload: [u8; 2048],
+}
+
+#[derive(Debug)]
+struct Audit;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Camera;
+
+#[mmg_microbus::component]
+impl Camera {
+    #[mmg_microbus::handle]
+    async fn on_small(&self, s: &Small) -> Frame {
+        Frame {
+            _payload: [s.0; 2048],
+        }
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Recorder;
+
+#[mmg_microbus::component]
+impl Recorder {
+    #[mmg_microbus::handle]
+    async fn on_frame(&self, _f: &Frame) {}
+
+    #[mmg_microbus::handle(latest)]
+    async fn on_frame_latest(&self, _f: &Frame) {}
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn registry_lists_size_publishers_subscribers_and_capacity() {
+    let cfg = AppConfig {
+        queue_capacity: 64,
+        ..AppConfig::default()
+    };
+    let mut app = App::new(cfg);
+    app.declare_publisher::<Small>();
+    app.declare_subscriber::<Audit>();
+    assert!(app.message_types().is_empty());
+    app.start().await.unwrap();
+
+    let types = app.message_types();
+    let frame = &types[0];
+    assert!(frame.type_name.ends_with("Frame"));
+    assert_eq!(frame.size, 2048);
+    assert_eq!(frame.publishers, [std::any::type_name::<Camera>()]);
+    assert_eq!(frame.subscribers.len(), 2);
+    assert!(frame
+        .subscribers
+        .iter()
+        .all(|s| *s == std::any::type_name::<Recorder>()));
+    // 普通订阅 64 + 合并订阅 1
+    assert_eq!(frame.capacity, 65);
+
+    let small = types
+        .iter()
+        .find(|t| t.type_name.ends_with("Small"))
+        .unwrap();
+    assert_eq!(small.size, 1);
+    assert_eq!(small.publishers, ["<external>"]);
+    assert_eq!(small.subscribers, [std::any::type_name::<Camera>()]);
+    assert_eq!(small.capacity, 64);
+
+    // 登记但未实际订阅的类型同样列出
+    let audit = types
+        .iter()
+        .find(|t| t.type_name.ends_with("Audit"))
+        .unwrap();
+    assert!(audit.subscribers.is_empty() && audit.capacity == 0);
+    assert!(types.windows(2).all(|w| w[0].size >= w[1].size));
+    app.stop();
+}
@@ -0,0 +1,72 @@
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Job(u32);
+#[derive(Debug)]
+struct Done;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Worker;
+
+#[mmg_microbus::component]
+impl Worker {
+    #[mmg_microbus::handle]
+    async fn on_job(&self, job: &Job) -> Result<Done> {
+        if job.0 % 2 == 1 {
+            return Err(MicrobusError::Other("odd job"));
+        }
+        Ok(Done)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn instrumentation_reaches_installed_recorder() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    recorder.install().unwrap();
+
+    let mut app = App::new(AppConfig::default());
+    let bus = app.bus_handle();
+    let mut done = bus.try_subscribe::<Done>().unwrap();
+    app.start().await.unwrap();
+    // 末条为偶数：收到其 Done 时此前的奇数 job 均已逐条处理
+    for i in 0..=10 {
+        bus.publish_any_arc(Arc::new(Job(i))).await;
+    }
+    for _ in 0..6 {
+        tokio::time::timeout(Duration::from_secs(5), done.recv())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    // 等待组件退出：末条 handler 的耗时记录随之完成
+    app.stop_component::<Worker>().await.unwrap();
+    app.stop();
+
+    // 快照读取即清零：只取一次
+    let snapshot = snapshotter.snapshot().into_vec();
+    let metric = |name: &str, label: &str| {
+        snapshot
+            .iter()
+            .find(|(k, ..)| {
+                k.key().name() == name && k.key().labels().any(|l| l.value().ends_with(label))
+            })
+            .map(|(.., v)| v)
+    };
+    let counter = |name, label| match metric(name, label) {
+        Some(DebugValue::Counter(n)) => *n,
+        other => panic!("{name}: {other:?}"),
+    };
+    assert_eq!(counter(mmg_microbus::metrics::PUBLISHED, "Job"), 11);
+    assert_eq!(counter(mmg_microbus::metrics::PUBLISHED, "Done"), 6);
+    assert_eq!(counter(mmg_microbus::metrics::HANDLER_ERRORS, "on_job"), 5);
+    match metric(mmg_microbus::metrics::HANDLER_DURATION, "on_job") {
+        Some(DebugValue::Histogram(samples)) => assert_eq!(samples.len(), 11),
+        other => panic!("handler duration: {other:?}"),
+    }
+}
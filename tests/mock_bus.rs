@@ -0,0 +1,55 @@
+use mmg_microbus::bus::ErasedEvent;
+use mmg_microbus::component::ComponentContext;
+use mmg_microbus::testing::MockBus;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(Clone, Debug)]
+struct Quote(u32);
+#[derive(Clone, Debug, PartialEq)]
+struct Mid(u32);
+#[derive(Clone, Debug, PartialEq)]
+struct Audit(&'static str);
+#[derive(Clone, Debug)]
+struct Unrelated;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pricer {
+    seen: AtomicU32,
+}
+
+#[mmg_microbus::component]
+impl Pricer {
+    #[mmg_microbus::handle]
+    async fn on_quote(&self, q: &Quote) -> Option<Mid> {
+        self.seen.fetch_add(1, Ordering::SeqCst);
+        (q.0 > 0).then_some(Mid(q.0 / 2))
+    }
+
+    #[mmg_microbus::handle]
+    async fn audit(&self, ctx: &ComponentContext, _q: &Quote) -> Vec<ErasedEvent> {
+        vec![ErasedEvent::new(Audit(ctx.component_name()))]
+    }
+}
+
+// 无 tokio 运行时：同步驱动并捕获发布
+#[test]
+fn drives_handlers_without_runtime() {
+    let bus = MockBus::new();
+    let pricer = Pricer::default();
+
+    assert!(bus.deliver_blocking(&pricer, Quote(10)));
+    assert_eq!(*bus.take::<Mid>().unwrap(), Mid(5));
+    let audit = bus.take::<Audit>().unwrap();
+    assert!(audit.0.ends_with("Pricer"));
+
+    // Option::None 不发布，但 handler 仍被调用
+    assert!(bus.deliver_blocking(&pricer, Quote(0)));
+    assert!(bus.take::<Mid>().is_none());
+    assert_eq!(pricer.seen.load(Ordering::SeqCst), 2);
+
+    assert!(!bus.deliver_blocking(&pricer, Unrelated));
+    let published = bus.published();
+    assert_eq!(published.len(), 3);
+    assert!(published[0].ends_with("Mid"));
+}
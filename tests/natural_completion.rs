@@ -0,0 +1,87 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Item(u32);
+#[derive(Debug)]
+struct Scored(u32);
+
+static SINKED: AtomicU32 = AtomicU32::new(0);
+static TOTAL: AtomicU32 = AtomicU32::new(0);
+static WINDOWED: AtomicU32 = AtomicU32::new(0);
+static LAST: AtomicU32 = AtomicU32::new(0);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Feeder {
+    next: AtomicU32,
+}
+
+#[mmg_microbus::component]
+impl Feeder {
+    #[mmg_microbus::active]
+    async fn feed(&self) -> ControlFlow<(), Item> {
+        let i = self.next.fetch_add(1, Ordering::SeqCst);
+        if i < 100 {
+            ControlFlow::Continue(Item(i))
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Scorer;
+
+#[mmg_microbus::component]
+impl Scorer {
+    // 下游处理慢于数据源：数据源结束时队列中仍有待处理消息
+    #[mmg_microbus::handle]
+    async fn score(&self, item: &Item) -> Scored {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        Scored(item.0 * 2)
+    }
+
+    #[mmg_microbus::handle]
+    async fn sink(&self, s: &Scored) {
+        TOTAL.fetch_add(s.0, Ordering::SeqCst);
+        SINKED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // 暂存在窗口 / 防抖状态中的消息同样视为未处理：须等窗口关闭、静默期满后才判定完成
+    #[mmg_microbus::handle(window = "300ms")]
+    async fn batch(&self, w: &Window<Scored>) {
+        WINDOWED.fetch_add(w.iter().map(|s| s.0).sum(), Ordering::SeqCst);
+    }
+
+    #[mmg_microbus::handle(debounce = "300ms")]
+    async fn settle(&self, s: &Scored) {
+        LAST.store(s.0, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn finite_pipeline_stops_after_draining() {
+    let cfg = AppConfig {
+        stop_on_completion: true,
+        queue_capacity: 8,
+        ..AppConfig::default()
+    };
+    let mut app = App::new(cfg);
+    app.start().await.unwrap();
+    assert!(!app.is_completed());
+
+    tokio::time::timeout(Duration::from_secs(10), app.wait_for_stop())
+        .await
+        .expect("natural completion not detected");
+    assert!(app.is_completed());
+    assert_eq!(SINKED.load(Ordering::SeqCst), 100);
+    assert_eq!(TOTAL.load(Ordering::SeqCst), 9900);
+    assert_eq!(WINDOWED.load(Ordering::SeqCst), 9900);
+    assert_eq!(LAST.load(Ordering::SeqCst), 198);
+    app.stop();
+}
@@ -0,0 +1,73 @@
+use mmg_microbus::merge::OrderedMerge;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Trade {
+    ts: u64,
+}
+#[derive(Debug)]
+struct Quote {
+    ts: u64,
+}
+
+#[derive(Debug)]
+enum Market {
+    Trade(Arc<Trade>),
+    Quote(Arc<Quote>),
+}
+
+impl Market {
+    fn label(&self) -> String {
+        match self {
+            Self::Trade(t) => format!("t{}", t.ts),
+            Self::Quote(q) => format!("q{}", q.ts),
+        }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn merges_types_by_timestamp_and_bounds_idle_waits() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let bus = app.bus_handle();
+    let mut md = OrderedMerge::new(Duration::from_millis(20))
+        .input(
+            bus.try_subscribe::<Trade>().unwrap(),
+            |t| t.ts,
+            Market::Trade,
+        )
+        .input(
+            bus.try_subscribe::<Quote>().unwrap(),
+            |q| q.ts,
+            Market::Quote,
+        );
+    app.start().await.unwrap();
+
+    // 两类消息各自有序、交错到达：合并后按时间戳交付，同键时先登记的输入在前
+    for ts in [1, 3, 5, 6] {
+        bus.publish_any_arc(Arc::new(Trade { ts })).await;
+    }
+    for ts in [2, 4, 6] {
+        bus.publish_any_arc(Arc::new(Quote { ts })).await;
+    }
+    let mut got = Vec::new();
+    for _ in 0..7 {
+        got.push(md.recv().await.unwrap().label());
+    }
+    assert_eq!(got, ["t1", "q2", "t3", "q4", "t5", "t6", "q6"]);
+
+    // 仅一个输入有消息：最多等待 max_wait 后交付，空闲输入不拖住整条流
+    bus.publish_any_arc(Arc::new(Trade { ts: 10 })).await;
+    let t0 = tokio::time::Instant::now();
+    assert_eq!(md.recv().await.unwrap().label(), "t10");
+    assert_eq!(t0.elapsed(), Duration::from_millis(20));
+
+    // 超出等待上限后晚到的更早时间戳照常交付并计为乱序
+    bus.publish_any_arc(Arc::new(Quote { ts: 9 })).await;
+    bus.publish_any_arc(Arc::new(Trade { ts: 11 })).await;
+    assert_eq!(md.recv().await.unwrap().label(), "q9");
+    assert_eq!(md.out_of_order(), 1);
+    assert_eq!(md.recv().await.unwrap().label(), "t11");
+    app.stop();
+}
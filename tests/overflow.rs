@@ -0,0 +1,52 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+struct Tick(u32);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn overflow_callback_fires_once_per_full_episode() {
+    let mut app = App::new(AppConfig {
+        queue_capacity: 4,
+        ..AppConfig::default()
+    });
+    let bus = app.bus_handle();
+    let hits = Arc::new(AtomicUsize::new(0));
+    let seen = hits.clone();
+    let mut sub = bus
+        .try_subscribe::<Tick>()
+        .unwrap()
+        .on_overflow(move |cap| {
+            assert_eq!(cap, 4);
+            seen.fetch_add(1, Ordering::SeqCst);
+        });
+    app.start().await.unwrap();
+
+    let publish = |n: u32| {
+        let bus = bus.clone();
+        async move {
+            for i in 0..n {
+                bus.publish_any_arc(Arc::new(Tick(i))).await;
+            }
+        }
+    };
+
+    // 未满：不回调
+    publish(2).await;
+    for i in 0..2 {
+        assert_eq!(*sub.recv().await.unwrap(), Tick(i));
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 0);
+
+    // 队列满：整轮排空只回调一次
+    for round in 1..=2 {
+        publish(4).await;
+        for _ in 0..4 {
+            sub.recv().await.unwrap();
+        }
+        assert_eq!(hits.load(Ordering::SeqCst), round);
+    }
+    app.stop();
+}
@@ -0,0 +1,71 @@
+use mmg_microbus::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+static SAVED: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+static DRAWN: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+#[derive(Clone, Debug)]
+struct FileChanged(u32);
+#[derive(Clone, Debug)]
+struct Frame(u32);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Watcher;
+
+#[mmg_microbus::component]
+impl Watcher {
+    #[mmg_microbus::handle(debounce = "50ms")]
+    async fn on_change(&self, e: &FileChanged) {
+        SAVED.lock().unwrap().push(e.0);
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Screen;
+
+#[mmg_microbus::component]
+impl Screen {
+    #[mmg_microbus::handle(throttle = "10/s")]
+    async fn on_frame(&self, f: &Frame) {
+        DRAWN.lock().unwrap().push(f.0);
+    }
+}
+
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn debounce_and_throttle_pace_bursts() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.start().await.expect("start");
+    let bus = app.bus_handle();
+    for i in 0..5 {
+        bus.publish_any_arc(Arc::new(FileChanged(i))).await;
+        bus.publish_any_arc(Arc::new(Frame(i))).await;
+    }
+    settle().await;
+    // debounce：仍在静默期；throttle：首条立即处理，其余合并为待处理的最新一条
+    assert!(SAVED.lock().unwrap().is_empty());
+    assert_eq!(*DRAWN.lock().unwrap(), [0]);
+
+    tokio::time::advance(Duration::from_millis(60)).await;
+    settle().await;
+    assert_eq!(*SAVED.lock().unwrap(), [4]);
+    assert_eq!(*DRAWN.lock().unwrap(), [0]);
+
+    tokio::time::advance(Duration::from_millis(50)).await;
+    settle().await;
+    assert_eq!(*DRAWN.lock().unwrap(), [0, 4]);
+
+    // 静默后再来的消息：throttle 间隔已过，立即处理
+    tokio::time::advance(Duration::from_millis(200)).await;
+    bus.publish_any_arc(Arc::new(Frame(9))).await;
+    settle().await;
+    assert_eq!(*DRAWN.lock().unwrap(), [0, 4, 9]);
+    app.stop();
+    tokio::time::advance(Duration::from_millis(100)).await;
+}
@@ -0,0 +1,69 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::events::HandlerError;
+use mmg_microbus::prelude::*;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Quote(u64);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pricer;
+
+#[mmg_microbus::component]
+impl Pricer {
+    #[mmg_microbus::handle]
+    async fn on_quote(&self, q: &Quote) {
+        assert!(q.0 != 0, "zero quote");
+    }
+}
+
+static LOG: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl std::io::Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        LOG.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// 单线程运行时：三条消息在 handler 运行前全部入队，panic 时队列中仍有两条
+#[tokio::test(flavor = "current_thread")]
+async fn panic_dumps_component_status_and_pending_queues() {
+    tracing_subscriber::fmt()
+        .with_writer(|| Capture)
+        .with_ansi(false)
+        .init();
+    let cfg = AppConfig {
+        publish_handler_errors: true,
+        ..Default::default()
+    };
+    let mut app = App::new(cfg);
+    app.dump_on_panic();
+    let mut errors = app.bus_handle().try_subscribe::<HandlerError>().unwrap();
+    app.start().await.unwrap();
+
+    for q in [0, 1, 2] {
+        app.bus_handle().publish_any_arc(Arc::new(Quote(q))).await;
+    }
+    tokio::time::timeout(Duration::from_secs(2), errors.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    app.stop();
+
+    let log = String::from_utf8(LOG.lock().clone()).unwrap();
+    assert!(log.contains("bus snapshot at panic"), "{log}");
+    assert!(log.contains("panic_dump::Pricer: running"), "{log}");
+    assert!(
+        log.contains("panic_dump::Pricer <- panic_dump::Quote: 2/"),
+        "{log}"
+    );
+}
@@ -0,0 +1,46 @@
+use futures_util::stream;
+use mmg_microbus::pipeline::Pipeline;
+use mmg_microbus::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Tick(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Price(u64);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn closures_wire_source_stages_and_sink() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.add_pipeline(
+        Pipeline::new()
+            .source(stream::iter((1..=5).map(Tick)))
+            .map(|t: &Tick| Price(t.0 * 10))
+            .filter(|p: &Price| p.0 > 20)
+            .publish(),
+    )
+    .add_pipeline(
+        Pipeline::new()
+            .subscribe::<Price>()
+            .sink(move |p: &Price| sink.lock().unwrap().push(p.0)),
+    );
+    app.start().await.unwrap();
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+    while seen.lock().unwrap().len() < 3 {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "{:?}",
+            seen.lock().unwrap()
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(*seen.lock().unwrap(), [30, 40, 50]);
+
+    // 中间阶段融合在组件内，只有源与终端结果经过总线
+    let topo = app.introspect();
+    assert_eq!(topo.components.len(), 3);
+    app.stop();
+}
@@ -0,0 +1,58 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::prelude::*;
+
+#[derive(Debug)]
+struct Tick;
+
+#[derive(Debug)]
+struct Price;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pricer;
+
+#[mmg_microbus::component]
+impl Pricer {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) -> Option<Price> {
+        Some(Price)
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Trader;
+
+#[mmg_microbus::component]
+impl Trader {
+    #[mmg_microbus::handle]
+    async fn on_price(&self, _p: &Price) {}
+}
+
+#[test]
+fn plan_reports_wiring_without_starting() {
+    let cfg = AppConfig {
+        queue_capacity: 64,
+        strict_wiring: true,
+        ..AppConfig::default()
+    };
+    let mut app = App::new(cfg);
+    let Err(MicrobusError::Config(msg)) = app.plan() else {
+        panic!("plan must run the strict wiring check");
+    };
+    assert!(msg.contains("no publisher for"), "{msg}");
+
+    app.declare_publisher::<Tick>();
+    let plan = app.plan().unwrap();
+    assert_eq!(plan.config.queue_capacity, 64);
+    assert_eq!(plan.components.len(), 2);
+    assert_eq!(plan.edges.len(), 1);
+    let edge = &plan.edges[0];
+    assert!(edge.from.ends_with("Pricer") && edge.to.ends_with("Trader"));
+    assert!(edge.message.ends_with("::Price"));
+    assert!(plan.to_string().contains("edges (1):"), "{plan}");
+
+    // 不构建组件、不派生任务
+    assert!(app.introspect().components.is_empty());
+}
@@ -0,0 +1,62 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+static SEEN: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+#[derive(Clone, Debug)]
+struct Quote(u32);
+#[derive(Clone, Debug)]
+struct Warmup;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Cache;
+
+#[mmg_microbus::component]
+impl Cache {
+    // init 返回值在封印前发布
+    #[mmg_microbus::init]
+    async fn init(&mut self) -> Warmup {
+        Warmup
+    }
+    #[mmg_microbus::handle]
+    async fn on_warmup(&self, _w: &Warmup) {
+        SEEN.lock().unwrap().push(0);
+    }
+    #[mmg_microbus::handle]
+    async fn on_quote(&self, q: &Quote) {
+        SEEN.lock().unwrap().push(q.0);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_before_seal_reach_all_subscribers_in_order() {
+    let mut app = App::new(AppConfig {
+        buffer_pre_seal: true,
+        ..AppConfig::default()
+    });
+    let bus = app.bus_handle();
+    // 组件尚未订阅：不缓冲时这些消息会丢失
+    for i in 1..=3 {
+        bus.publish_any_arc(Arc::new(Quote(i))).await;
+    }
+    app.start().await.unwrap();
+    bus.publish_any_arc(Arc::new(Quote(4))).await;
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+    while SEEN.lock().unwrap().len() < 5 {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "{:?}",
+            SEEN.lock().unwrap()
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    let mut quotes = SEEN.lock().unwrap().clone();
+    assert!(quotes.contains(&0), "init output delivered");
+    quotes.retain(|&q| q != 0);
+    assert_eq!(quotes, [1, 2, 3, 4]);
+    app.stop();
+}
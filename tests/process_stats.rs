@@ -0,0 +1,34 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use mmg_microbus::process::{ProcessMonitor, ProcessStats};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn monitor_publishes_periodic_process_stats() {
+    let mut app = App::new(AppConfig::default());
+    app.add_component(ProcessMonitor::new().interval(Duration::from_millis(20)));
+    let bus = app.bus_handle();
+    let mut stats = bus.try_subscribe::<ProcessStats>().unwrap();
+    // 清单声明了 ProcessStats 的发布方：登记外部订阅后严格校验通过
+    app.declare_subscriber::<ProcessStats>();
+    app.validate_strict().unwrap();
+    app.start().await.unwrap();
+
+    let wait = Duration::from_secs(5);
+    let first = tokio::time::timeout(wait, stats.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    let second = tokio::time::timeout(wait, stats.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    let rt = second.runtime.unwrap();
+    assert!(rt.workers > 0 && rt.alive_tasks > 0);
+    if cfg!(target_os = "linux") {
+        assert!(first.rss_bytes.unwrap() > 0);
+        assert!(first.open_fds.unwrap() > 0);
+        assert!(second.cpu_percent.unwrap() >= 0.0);
+    }
+    app.stop();
+}
@@ -0,0 +1,70 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::prelude::*;
+use mmg_microbus::profile::{Profile, Profiles};
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Exchange;
+
+#[mmg_microbus::component]
+impl Exchange {}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Simulator;
+
+#[mmg_microbus::component]
+impl Simulator {}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Strategy;
+
+#[mmg_microbus::component]
+impl Strategy {}
+
+fn profiles() -> Profiles {
+    Profiles::new()
+        .with(
+            Profile::new("backtest")
+                .enable_component("Simulator")
+                .enable_component("Strategy")
+                .config(|cfg| cfg.queue_capacity = 16),
+        )
+        .with(Profile::new("live").disable_component("Simulator"))
+}
+
+fn planned(app: &App) -> Vec<&'static str> {
+    let mut names: Vec<_> = app
+        .plan()
+        .unwrap()
+        .components
+        .iter()
+        .map(|c| c.name.rsplit("::").next().unwrap())
+        .collect();
+    names.sort_unstable();
+    names
+}
+
+#[test]
+fn profile_selects_components_and_config() {
+    let profiles = profiles();
+
+    let backtest =
+        App::with_profile(AppConfig::default(), profiles.select("backtest").unwrap()).unwrap();
+    assert_eq!(planned(&backtest), ["Simulator", "Strategy"]);
+    assert_eq!(backtest.plan().unwrap().config.queue_capacity, 16);
+
+    let live = App::with_profile(AppConfig::default(), profiles.select("live").unwrap()).unwrap();
+    assert_eq!(planned(&live), ["Exchange", "Strategy"]);
+    assert_eq!(live.plan().unwrap().config.queue_capacity, 1024);
+
+    let Err(MicrobusError::Config(msg)) = profiles.select("paper") else {
+        panic!("unknown profile accepted");
+    };
+    assert!(msg.contains("backtest, live"), "{msg}");
+
+    let broken = Profile::new("broken").config(|cfg| cfg.queue_capacity = 0);
+    assert!(App::with_profile(AppConfig::default(), &broken).is_err());
+}
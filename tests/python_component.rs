@@ -0,0 +1,72 @@
+use mmg_microbus::codec::BusMessage;
+use mmg_microbus::python::PyComponent;
+use mmg_microbus::testing::TestApp;
+use pyo3::prelude::*;
+use pyo3::types::PyModule;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Serialize, Deserialize, BusMessage)]
+struct Quote {
+    symbol: String,
+    bid: f64,
+}
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BusMessage)]
+#[bus_message(name = "strategy.Signal")]
+struct Signal {
+    symbol: String,
+    buy: bool,
+}
+
+const STRATEGY: &std::ffi::CStr = c"
+class Strategy:
+    def handle_quote(self, q):
+        if q['bid'] < 0:
+            raise ValueError('negative bid')
+        return ('strategy.Signal', {'symbol': q['symbol'], 'buy': q['bid'] < 10})
+";
+
+#[tokio::test(flavor = "multi_thread")]
+async fn python_object_handles_and_publishes() {
+    let object = Python::attach(|py| {
+        let module = PyModule::from_code(py, STRATEGY, c"strategy.py", c"strategy").unwrap();
+        module
+            .getattr("Strategy")
+            .unwrap()
+            .call0()
+            .unwrap()
+            .unbind()
+    });
+    let strategy = PyComponent::new(object).input::<Quote>().output::<Signal>();
+    let stats = strategy.handle();
+    let app = TestApp::builder()
+        .add_component(strategy)
+        .start()
+        .await
+        .unwrap();
+
+    // 异常只计数，不影响后续消息
+    for bid in [-1.0, 9.5] {
+        app.inject(Quote {
+            symbol: "ABC".into(),
+            bid,
+        })
+        .await;
+    }
+    assert_eq!(
+        *app.expect::<Signal>(Duration::from_secs(5)).await,
+        Signal {
+            symbol: "ABC".into(),
+            buy: true
+        }
+    );
+    assert_eq!((stats.calls(), stats.errors()), (1, 1));
+
+    // 缺少入口方法时启动失败
+    let bare = Python::attach(|py| py.None());
+    let missing = TestApp::builder()
+        .add_component(PyComponent::new(bare).input::<Quote>())
+        .start()
+        .await;
+    assert!(missing.is_err());
+}
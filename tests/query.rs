@@ -0,0 +1,97 @@
+use mmg_microbus::prelude::*;
+use mmg_microbus::query::{Ask, Gather};
+use mmg_microbus::testing::BusProbe;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct StatusQuery;
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Status(&'static str);
+#[derive(Debug, Clone, PartialEq)]
+struct Report(usize);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Binance;
+
+#[mmg_microbus::component]
+impl Binance {
+    #[mmg_microbus::handle]
+    async fn status(&self, q: &Ask<StatusQuery, Status>) {
+        q.reply(Status("binance"));
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Okx;
+
+#[mmg_microbus::component]
+impl Okx {
+    #[mmg_microbus::handle]
+    async fn status(&self, q: &Ask<StatusQuery, Status>) {
+        q.reply(Status("okx"));
+    }
+}
+
+// 慢应答方：短超时下缺席
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Kraken;
+
+#[mmg_microbus::component]
+impl Kraken {
+    #[mmg_microbus::handle]
+    async fn status(&self, q: &Ask<StatusQuery, Status>) {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        q.reply(Status("kraken"));
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Monitor;
+
+#[mmg_microbus::component]
+impl Monitor {
+    #[mmg_microbus::active(once)]
+    async fn poll(&self, ctx: &ComponentContext) -> Report {
+        let all: Vec<Status> = ctx.query(StatusQuery, Duration::from_secs(2)).await;
+        Report(all.len())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn query_gathers_answers_from_all_responders() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let reports = BusProbe::<Report>::attach(&app);
+    app.start().await.unwrap();
+    reports
+        .assert_received_in_order(&[Report(3)], Duration::from_secs(3))
+        .await;
+
+    let bus = app.bus_handle();
+    let mut fast: Vec<Status> = bus
+        .query(StatusQuery, Gather::All, Duration::from_millis(100))
+        .await;
+    fast.sort();
+    assert_eq!(fast, [Status("binance"), Status("okx")]);
+
+    let first: Vec<Status> = bus
+        .query(StatusQuery, Gather::First, Duration::from_secs(2))
+        .await;
+    assert_eq!(first.len(), 1);
+
+    // 法定数超过应答方数时按全部
+    let quorum: Vec<Status> = bus
+        .query(StatusQuery, Gather::Quorum(10), Duration::from_secs(2))
+        .await;
+    assert_eq!(quorum.len(), 3);
+
+    // 无应答方：立即返回空
+    let none: Vec<u8> = bus
+        .query(StatusQuery, Gather::All, Duration::from_secs(2))
+        .await;
+    assert!(none.is_empty());
+    app.stop();
+}
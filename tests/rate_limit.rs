@@ -0,0 +1,90 @@
+use mmg_microbus::config::{AppConfig, RateExcess};
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Heartbeat;
+#[derive(Debug)]
+struct Quote;
+#[derive(Debug)]
+struct Unlimited;
+
+async fn drain<T: Send + Sync + 'static>(sub: &mut mmg_microbus::bus::Subscription<T>) -> usize {
+    let mut n = 0;
+    while let Ok(Some(_)) = tokio::time::timeout(Duration::from_millis(1), sub.recv()).await {
+        n += 1;
+    }
+    n
+}
+
+#[tokio::test(start_paused = true)]
+async fn limited_types_are_dropped_or_delayed_per_policy() {
+    let mut cfg = AppConfig::default();
+    cfg.rate_limit::<Heartbeat>(5, Duration::from_secs(1), RateExcess::Drop)
+        .rate_limit::<Quote>(10, Duration::from_secs(1), RateExcess::Delay);
+    let mut app = App::new(cfg);
+    let bus = app.bus_handle();
+    let mut beats = bus.try_subscribe::<Heartbeat>().unwrap();
+    let mut quotes = bus.try_subscribe::<Quote>().unwrap();
+    let mut free = bus.try_subscribe::<Unlimited>().unwrap();
+    app.start().await.unwrap();
+
+    // Drop：突发 5 条通过，其余丢弃；补充一个令牌后再通过一条
+    for _ in 0..20 {
+        bus.publish_any_arc(Arc::new(Heartbeat)).await;
+    }
+    tokio::time::advance(Duration::from_millis(200)).await;
+    bus.publish_any_box(Box::new(Heartbeat)).await;
+    assert_eq!(drain(&mut beats).await, 6);
+
+    // Delay：突发 10 条立即入队，其余每 100ms 一条，发布方等待而不丢弃
+    let t0 = tokio::time::Instant::now();
+    for _ in 0..20 {
+        bus.publish_any_arc(Arc::new(Quote)).await;
+    }
+    assert!(
+        t0.elapsed() >= Duration::from_millis(1000),
+        "{:?}",
+        t0.elapsed()
+    );
+    assert_eq!(drain(&mut quotes).await, 20);
+
+    for _ in 0..100 {
+        bus.publish_any_arc(Arc::new(Unlimited)).await;
+    }
+    assert_eq!(drain(&mut free).await, 100);
+
+    let limits = app.introspect().rate_limits;
+    let beat = limits
+        .iter()
+        .find(|m| m.type_name.ends_with("Heartbeat"))
+        .unwrap();
+    assert_eq!((beat.dropped, beat.delayed), (15, 0));
+    let quote = limits
+        .iter()
+        .find(|m| m.type_name.ends_with("Quote"))
+        .unwrap();
+    assert_eq!((quote.dropped, quote.delayed), (0, 10));
+    app.stop();
+}
+
+#[test]
+fn zero_rate_is_rejected() {
+    let mut cfg = AppConfig::default();
+    cfg.rate_limit::<Heartbeat>(0, Duration::from_secs(1), RateExcess::Drop);
+    let Err(MicrobusError::Config(msg)) = cfg.validate() else {
+        panic!("zero rate accepted");
+    };
+    assert!(msg.contains("Heartbeat"), "{msg}");
+}
+
+#[test]
+fn sub_nanosecond_interval_is_rejected() {
+    let mut cfg = AppConfig::default();
+    cfg.rate_limit::<Heartbeat>(1_000, Duration::from_nanos(999), RateExcess::Delay);
+    let Err(MicrobusError::Config(msg)) = cfg.validate() else {
+        panic!("sub-nanosecond interval accepted");
+    };
+    assert!(msg.contains("Heartbeat"), "{msg}");
+}
@@ -0,0 +1,67 @@
+use mmg_microbus::bus::ErasedEvent;
+use mmg_microbus::prelude::*;
+use mmg_microbus::recorder::Recorder;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+#[allow(dead_code)] // 字段仅经 Debug 录制读取
+struct Tick(pub u64);
+#[derive(Clone, Debug)]
+struct Opaque;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Source;
+
+#[mmg_microbus::component]
+impl Source {
+    #[mmg_microbus::active(once)]
+    async fn burst(&self) -> Vec<ErasedEvent> {
+        vec![
+            ErasedEvent::new(Tick(1)),
+            ErasedEvent::new(Opaque),
+            ErasedEvent::new(Tick(2)),
+        ]
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn recorder_captures_ring_and_file() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("bus.log");
+    let recorder = Recorder::new().record::<Tick>().record_all().to_file(&path);
+    let handle = recorder.handle();
+
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.add_component(recorder);
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    app.stop();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    // record_all 同样会录制框架生命周期事件，这里只比对业务类型
+    let is_app_type = |type_name: &str| !type_name.starts_with("mmg_microbus::");
+    let recs = handle.snapshot();
+    let kinds: Vec<_> = recs
+        .iter()
+        .filter(|r| is_app_type(r.type_name))
+        .map(|r| (r.type_name.rsplit("::").next().unwrap(), r.payload.clone()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("Tick", Some("Tick(1)".to_string())),
+            ("Opaque", None),
+            ("Tick", Some("Tick(2)".to_string())),
+        ]
+    );
+
+    let text = std::fs::read_to_string(&path).expect("read log");
+    let lines: Vec<_> = text
+        .lines()
+        .filter(|l| is_app_type(l.split('\t').nth(1).unwrap_or_default()))
+        .collect();
+    assert_eq!(lines.len(), 3, "file: {text}");
+    assert!(lines[0].ends_with("\tTick(1)"));
+    assert_eq!(handle.dropped(), 0);
+}
@@ -0,0 +1,88 @@
+use mmg_microbus::component::{Component, ComponentContext};
+use mmg_microbus::prelude::*;
+use mmg_microbus::recorder::Recorder;
+use mmg_microbus::replay::Replay;
+use parking_lot::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Price(u64);
+#[derive(Clone, Debug)]
+struct NotReplayed;
+
+static SEEN: Mutex<Vec<(u64, Instant)>> = Mutex::new(Vec::new());
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Collector;
+
+#[mmg_microbus::component]
+impl Collector {
+    #[mmg_microbus::handle]
+    async fn on_price(&self, p: &Price) {
+        SEEN.lock().push((p.0, Instant::now()));
+    }
+}
+
+// 手写数据源：仅在录制阶段添加
+struct Source;
+
+#[async_trait::async_trait]
+impl Component for Source {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        use mmg_microbus::component::{__publish_auto, __startup_arrive_and_wait};
+        __startup_arrive_and_wait(&ctx).await;
+        __publish_auto(&ctx, Price(1)).await;
+        __publish_auto(&ctx, NotReplayed).await;
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        __publish_auto(&ctx, Price(2)).await;
+        Ok(())
+    }
+}
+
+fn price_codec() -> (
+    impl Fn(&Price) -> String + Send + Sync,
+    impl Fn(&str) -> std::result::Result<Price, String> + Send + Sync,
+) {
+    (
+        |p: &Price| p.0.to_string(),
+        |s: &str| s.parse().map(Price).map_err(|e| format!("{e}")),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn recorded_traffic_replays_with_scaled_timing() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("traffic.log");
+
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.add_component(Source);
+    app.add_component(
+        Recorder::new()
+            .record::<NotReplayed>()
+            .codec(price_codec())
+            .to_file(&path),
+    );
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    app.stop();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    SEEN.lock().clear();
+
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.add_component(Replay::from_file(&path).codec(price_codec()).speed(2.0));
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    app.stop();
+
+    let seen = SEEN.lock().clone();
+    let values: Vec<_> = seen.iter().map(|(v, _)| *v).collect();
+    assert_eq!(values, vec![1, 2]);
+    // 录制间隔约 80ms，二倍速回放约 40ms
+    let gap = seen[1].1 - seen[0].1;
+    assert!(
+        gap >= Duration::from_millis(30) && gap < Duration::from_millis(75),
+        "{gap:?}"
+    );
+}
@@ -0,0 +1,131 @@
+use mmg_microbus::prelude::*;
+use mmg_microbus::query::Ask;
+use mmg_microbus::testing::BusProbe;
+use std::future::IntoFuture;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Ping(u32);
+#[derive(Debug, PartialEq)]
+struct Pong(u32);
+#[derive(Debug)]
+struct Slow;
+#[derive(Debug)]
+struct Done;
+#[derive(Debug)]
+struct Stall;
+#[derive(Debug, Clone, PartialEq)]
+struct Report(u32);
+
+// 应答方观察到放弃，且迟到的应答被拒收
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+static LATE_REPLY_ACCEPTED: AtomicBool = AtomicBool::new(true);
+// 经 ctx 的取消信号观察到请求 future 被丢弃
+static CTX_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Echo;
+
+#[mmg_microbus::component]
+impl Echo {
+    #[mmg_microbus::handle]
+    async fn ping(&self, q: &Ask<Ping, Pong>) {
+        q.reply(Pong(q.0 + 1));
+    }
+    #[mmg_microbus::handle]
+    async fn slow(&self, q: &Ask<Slow, Done>) {
+        tokio::select! {
+            () = q.cancelled() => CANCELLED.store(true, Ordering::SeqCst),
+            () = tokio::time::sleep(Duration::from_secs(30)) => {}
+        }
+        LATE_REPLY_ACCEPTED.store(q.reply(Done), Ordering::SeqCst);
+    }
+    #[mmg_microbus::handle]
+    async fn stall(&self, ctx: &ComponentContext, _q: &Ask<Stall, Done>) {
+        let token = ctx
+            .request_token()
+            .expect("ask handler has a request token");
+        // 派生任务中没有当前请求：克隆带出
+        tokio::spawn(async move { token.cancelled().await })
+            .await
+            .unwrap();
+        CTX_CANCELLED.store(true, Ordering::SeqCst);
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Caller;
+
+#[mmg_microbus::component]
+impl Caller {
+    #[mmg_microbus::active(once)]
+    async fn call(&self, ctx: &ComponentContext) -> Report {
+        assert!(ctx.request_token().is_none());
+        let Pong(n) = ctx
+            .request(Ping(41))
+            .timeout(Duration::from_secs(2))
+            .await
+            .unwrap();
+        Report(n)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn request_times_out_with_typed_error_and_cancels_responder() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let reports = BusProbe::<Report>::attach(&app);
+    app.start().await.unwrap();
+    reports
+        .assert_received_in_order(&[Report(42)], Duration::from_secs(3))
+        .await;
+
+    let bus = app.bus_handle();
+    let pong: Pong = bus.request(Ping(1)).await.unwrap();
+    assert_eq!(pong, Pong(2));
+
+    let err = bus
+        .request::<Slow, Done>(Slow)
+        .timeout(Duration::from_millis(50))
+        .await
+        .unwrap_err();
+    let MicrobusError::Timeout { type_name, after } = err else {
+        panic!("expected timeout, got {err:?}");
+    };
+    assert!(type_name.ends_with("Slow"), "{type_name}");
+    assert_eq!(after, Duration::from_millis(50));
+
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while !CANCELLED.load(Ordering::SeqCst) || LATE_REPLY_ACCEPTED.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("responder did not observe cancellation");
+
+    // 请求 future 被丢弃（未设超时）同样触发应答方 ctx 的取消信号
+    let abandoned = tokio::time::timeout(
+        Duration::from_millis(50),
+        bus.request::<Stall, Done>(Stall).into_future(),
+    )
+    .await;
+    assert!(abandoned.is_err());
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while !CTX_CANCELLED.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("responder ctx token did not fire");
+
+    // 无应答方：立即失败而非等到超时
+    let err = bus
+        .request::<Ping, u8>(Ping(0))
+        .timeout(Duration::from_secs(5))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MicrobusError::Other(_)), "{err:?}");
+    app.stop();
+}
@@ -0,0 +1,56 @@
+use mmg_microbus::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Tick(u64);
+#[derive(Clone, Debug)]
+struct Doubled(u64);
+
+static NEXT: AtomicU64 = AtomicU64::new(1);
+static SUM: AtomicU64 = AtomicU64::new(0);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Source;
+
+#[mmg_microbus::component]
+impl Source {
+    #[mmg_microbus::active]
+    async fn tick(&self) -> Option<Tick> {
+        mmg_microbus::rt::sleep(Duration::from_millis(1)).await;
+        let n = NEXT.fetch_add(1, Ordering::SeqCst);
+        (n <= 3).then_some(Tick(n))
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Doubler;
+
+#[mmg_microbus::component]
+impl Doubler {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, t: &Tick) -> Doubled {
+        Doubled(t.0 * 2)
+    }
+    #[mmg_microbus::handle]
+    async fn on_doubled(&self, d: &Doubled) {
+        SUM.fetch_add(d.0, Ordering::SeqCst);
+    }
+}
+
+// 无 tokio 运行时：组件任务与计时均由 smol 承载
+#[test]
+fn app_runs_on_smol_without_tokio_runtime() {
+    smol::block_on(async {
+        let mut app = App::new(mmg_microbus::config::AppConfig::default());
+        app.start().await.expect("start");
+        let deadline = std::time::Instant::now() + Duration::from_secs(2);
+        while SUM.load(Ordering::SeqCst) < 12 && std::time::Instant::now() < deadline {
+            mmg_microbus::rt::sleep(Duration::from_millis(5)).await;
+        }
+        app.stop();
+    });
+    assert_eq!(SUM.load(Ordering::SeqCst), 12);
+}
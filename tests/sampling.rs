@@ -0,0 +1,63 @@
+use mmg_microbus::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+static EVERY: AtomicU32 = AtomicU32::new(0);
+static RATIO: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Clone, Debug)]
+struct Tick(u32);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Stats;
+
+#[mmg_microbus::component]
+impl Stats {
+    #[mmg_microbus::handle(sample = 10)]
+    async fn every_tenth(&self, t: &Tick) {
+        assert_eq!(t.0 % 10, 0);
+        EVERY.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[mmg_microbus::handle(sample = 0.5)]
+    async fn half(&self, _t: &Tick) {
+        RATIO.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sampled_handlers_skip_and_count() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.start().await.expect("start");
+    let bus = app.bus_handle();
+    for i in 0..1000 {
+        bus.publish_any_arc(Arc::new(Tick(i))).await;
+    }
+
+    // 处理数 + 跳过数 = 到达数
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    let (every, half) = loop {
+        let snap = app.introspect();
+        let skipped = |m: &str| {
+            snap.sampling
+                .iter()
+                .find(|s| s.method == m)
+                .map_or(0, |s| s.skipped)
+        };
+        let every = (EVERY.load(Ordering::SeqCst), skipped("every_tenth"));
+        let half = (RATIO.load(Ordering::SeqCst), skipped("half"));
+        if u64::from(every.0) + every.1 == 1000 && u64::from(half.0) + half.1 == 1000 {
+            break (every, half);
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "sampling incomplete"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    assert_eq!(every, (100, 900));
+    assert!((300..700).contains(&half.0), "ratio sampled {}", half.0);
+    app.stop();
+}
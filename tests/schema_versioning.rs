@@ -0,0 +1,122 @@
+use mmg_microbus::bridge::{BridgeIn, BridgeOut};
+use mmg_microbus::codec::{self, Compatibility, Json, Schema};
+use mmg_microbus::events::SchemaMismatch;
+use mmg_microbus::testing::TestApp;
+use std::time::Duration;
+
+mod v1 {
+    use mmg_microbus::codec::BusMessage;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, Serialize, Deserialize, BusMessage)]
+    pub struct Order {
+        pub id: u64,
+    }
+}
+
+mod v2 {
+    use mmg_microbus::codec::BusMessage;
+    use serde::{Deserialize, Serialize};
+
+    // 新增可缺省字段，接受 v1 对端
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BusMessage)]
+    #[bus_message(version = 2, compatible_from = 1)]
+    pub struct Order {
+        pub id: u64,
+        #[serde(default)]
+        pub venue: String,
+    }
+}
+
+mod v3 {
+    use mmg_microbus::codec::BusMessage;
+    use serde::{Deserialize, Serialize};
+
+    // 不兼容旧版本
+    #[derive(Clone, Debug, Serialize, Deserialize, BusMessage)]
+    #[bus_message(version = 3)]
+    pub struct Order {
+        pub id: u64,
+        pub venue: String,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn bridge_accepts_compatible_versions_and_drops_incompatible() {
+    let versions: Vec<u32> = codec::schemas()
+        .iter()
+        .filter(|s| s.name == "Order")
+        .map(|s| s.version)
+        .collect();
+    assert_eq!(versions, [1, 2, 3]);
+    assert_eq!(Schema::of::<v2::Order>().check(1), Compatibility::Older);
+    assert_eq!(
+        Schema::of::<v3::Order>().check(2),
+        Compatibility::Incompatible
+    );
+
+    let tolerant = BridgeIn::bind_tcp(([127, 0, 0, 1], 0))
+        .unwrap()
+        .receive_message::<v2::Order>(Json);
+    let strict = BridgeIn::bind_tcp(([127, 0, 0, 1], 0))
+        .unwrap()
+        .receive_message::<v3::Order>(Json);
+    let (tolerant_addr, strict_addr) =
+        (tolerant.local_addr().unwrap(), strict.local_addr().unwrap());
+    let strict_stats = strict.handle();
+    let remote = TestApp::builder()
+        .add_component(tolerant)
+        .start()
+        .await
+        .unwrap();
+    let strict_remote = TestApp::builder()
+        .add_component(strict)
+        .start()
+        .await
+        .unwrap();
+
+    let local = TestApp::builder()
+        .add_component(
+            BridgeOut::connect_tcp(tolerant_addr.to_string()).forward_message::<v1::Order>(Json),
+        )
+        .add_component(
+            BridgeOut::connect_tcp(strict_addr.to_string()).forward_message::<v1::Order>(Json),
+        )
+        .start()
+        .await
+        .unwrap();
+    local.inject(v1::Order { id: 7 }).await;
+
+    assert_eq!(
+        *remote.expect::<v2::Order>(Duration::from_secs(2)).await,
+        v2::Order {
+            id: 7,
+            venue: String::new()
+        }
+    );
+    let ev = remote
+        .expect::<SchemaMismatch>(Duration::from_secs(2))
+        .await;
+    assert_eq!(
+        (
+            ev.name,
+            ev.local_version,
+            ev.remote_version,
+            ev.compatibility
+        ),
+        ("Order", 2, 1, Compatibility::Older)
+    );
+    let ev = strict_remote
+        .expect::<SchemaMismatch>(Duration::from_secs(2))
+        .await;
+    assert_eq!(ev.compatibility, Compatibility::Incompatible);
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+    while strict_stats.dropped() == 0 && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!((strict_stats.frames(), strict_stats.dropped()), (0, 1));
+    assert!(strict_remote
+        .try_expect::<v3::Order>(Duration::from_millis(50))
+        .await
+        .is_none());
+}
@@ -0,0 +1,59 @@
+use mmg_microbus::bus::{GapDetector, SeqCheck};
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+struct Fill(u32);
+#[derive(Debug, PartialEq)]
+struct Cancel(u32);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_are_numbered_per_type_when_enabled() {
+    let mut app = App::new(AppConfig {
+        sequence_numbers: true,
+        ..AppConfig::default()
+    });
+    let bus = app.bus_handle();
+    let mut fills = bus.try_subscribe::<Fill>().unwrap();
+    let mut cancels = bus.try_subscribe::<Cancel>().unwrap();
+    app.start().await.unwrap();
+
+    for i in 0..3 {
+        bus.publish_any_arc(Arc::new(Fill(i))).await;
+    }
+    bus.publish_any_box(Box::new(Cancel(9))).await;
+
+    // 各类型独立计数，从 1 开始
+    let mut gaps = GapDetector::new();
+    for i in 0..3 {
+        let env = fills.recv_envelope().await.unwrap();
+        assert_eq!((env.seq(), &*env), (Some(u64::from(i) + 1), &Fill(i)));
+        assert_eq!(gaps.observe(&env), SeqCheck::InOrder);
+    }
+    assert_eq!(cancels.recv_envelope().await.unwrap().seq(), Some(1));
+    assert_eq!(gaps.missing(), 0);
+    app.stop();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn messages_are_unsequenced_by_default() {
+    let mut app = App::new(AppConfig::default());
+    let bus = app.bus_handle();
+    let mut fills = bus.try_subscribe::<Fill>().unwrap();
+    app.start().await.unwrap();
+    bus.publish_any_arc(Arc::new(Fill(1))).await;
+    let env = fills.recv_envelope().await.unwrap();
+    assert_eq!(GapDetector::new().observe(&env), SeqCheck::Unsequenced);
+    app.stop();
+}
+
+#[test]
+fn gap_detector_reports_missing_ranges_and_late_arrivals() {
+    let mut gaps = GapDetector::new();
+    assert_eq!(gaps.observe_seq(Some(7)), SeqCheck::InOrder);
+    assert_eq!(gaps.observe_seq(Some(10)), SeqCheck::Gap { missing: 8..10 });
+    assert_eq!(gaps.observe_seq(Some(9)), SeqCheck::Stale(9));
+    assert_eq!(gaps.observe_seq(Some(11)), SeqCheck::InOrder);
+    assert_eq!(gaps.missing(), 2);
+}
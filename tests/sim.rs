@@ -0,0 +1,58 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::sim::Simulation;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Quote(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Order(u64);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Strategy;
+
+#[mmg_microbus::component]
+impl Strategy {
+    // 模拟下单延迟：虚拟时钟下不占真实时间
+    #[mmg_microbus::handle]
+    async fn on_quote(&self, q: &Quote) -> Option<Order> {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        q.0.is_multiple_of(2).then_some(Order(q.0))
+    }
+}
+
+const HOUR: Duration = Duration::from_secs(3600);
+
+fn backtest() -> Vec<(Duration, Order)> {
+    let report = Simulation::new(AppConfig::default())
+        .schedule((0..10).map(|i| (HOUR * i, Quote(u64::from(i)))))
+        .capture::<Order>()
+        .run_to_completion(HOUR * 24)
+        .unwrap();
+    assert!(report.completed());
+    assert!(report.elapsed() < HOUR * 10);
+    report
+        .messages::<Order>()
+        .into_iter()
+        .map(|(at, o)| (at, (*o).clone()))
+        .collect()
+}
+
+#[test]
+fn schedule_runs_on_virtual_time_and_is_reproducible() {
+    let wall = std::time::Instant::now();
+    let orders = backtest();
+    assert!(wall.elapsed() < Duration::from_secs(5));
+
+    let ids: Vec<u64> = orders.iter().map(|(_, o)| o.0).collect();
+    assert_eq!(ids, [0, 2, 4, 6, 8]);
+    for (at, o) in &orders {
+        // 行情时刻 + 5 秒处理延迟（启动耗时为零虚拟时间）
+        let due = HOUR * u32::try_from(o.0).unwrap() + Duration::from_secs(5);
+        assert!(
+            *at >= due && *at < due + Duration::from_millis(10),
+            "{at:?}"
+        );
+    }
+    assert_eq!(backtest(), orders);
+}
@@ -0,0 +1,70 @@
+use mmg_microbus::prelude::*;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Ping;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pinger;
+
+#[mmg_microbus::component]
+impl Pinger {
+    #[mmg_microbus::active(once)]
+    async fn ping(&self) -> Ping {
+        Ping
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Sluggish;
+
+#[mmg_microbus::component]
+impl Sluggish {
+    #[mmg_microbus::handle]
+    async fn on_ping(&self, _p: &Ping) {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+    }
+}
+
+// 捕获 tracing 输出到内存缓冲，便于断言结构化字段
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn slow_handler_emits_structured_warning() {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_global_default(subscriber);
+
+    let cfg = mmg_microbus::config::AppConfig {
+        slow_handler_threshold: Some(Duration::from_millis(5)),
+        ..Default::default()
+    };
+    let mut app = App::new(cfg);
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    app.stop();
+
+    let out = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert!(out.contains("slow handler invocation"), "log: {out}");
+    assert!(out.contains("method=\"on_ping\""), "log: {out}");
+    assert!(out.contains("Sluggish"), "log: {out}");
+    assert!(out.contains("Ping"), "log: {out}");
+}
@@ -0,0 +1,107 @@
+use mmg_microbus::codec::Json;
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::journal::Journal;
+use mmg_microbus::prelude::*;
+use mmg_microbus::snapshot::{FileSnapshotStore, SnapshotStore, Snapshots};
+use mmg_microbus::testing::BusProbe;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Deposit(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Balance(u64);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Account {
+    total: AtomicU64,
+}
+
+#[mmg_microbus::component]
+impl Account {
+    #[mmg_microbus::handle]
+    async fn on_deposit(&self, d: &Deposit) -> Balance {
+        Balance(self.total.fetch_add(d.0, Ordering::SeqCst) + d.0)
+    }
+    #[mmg_microbus::snapshot]
+    fn save(&self) -> u64 {
+        self.total.load(Ordering::SeqCst)
+    }
+    #[mmg_microbus::snapshot(restore)]
+    fn load(&mut self, total: u64) {
+        *self.total.get_mut() = total;
+    }
+}
+
+const ACCOUNT: &str = "snapshot::Account";
+
+// 启动一轮：journal 记录 Deposit，给出 `store` 时启用快照
+async fn run(journal: &Path, store: Option<&Path>, deposits: &[u64], expect: &[u64]) -> (App, u64) {
+    let mut app = App::new(AppConfig::default());
+    let balances = BusProbe::<Balance>::attach(&app);
+    let journal = Journal::open(journal).codec::<Deposit>(Json);
+    let stats = journal.handle();
+    if let Some(dir) = store {
+        app.snapshots(
+            Snapshots::new(FileSnapshotStore::new(dir))
+                .every(Duration::from_millis(20))
+                .journal(&stats),
+        );
+    }
+    app.add_component(journal);
+    app.start().await.unwrap();
+    // 回放与新发布并发：先等回放产出的余额，再发布本轮存款
+    let replayed = expect.len() - deposits.len();
+    assert!(balances.wait_for(replayed, Duration::from_secs(2)).await);
+    for d in deposits {
+        app.bus_handle()
+            .publish_any_arc(Arc::new(Deposit(*d)))
+            .await;
+    }
+    let expect: Vec<_> = expect.iter().copied().map(Balance).collect();
+    balances
+        .assert_received_in_order(&expect, Duration::from_secs(2))
+        .await;
+    (app, stats.replayed())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn restores_snapshot_and_replays_journal_after_its_sequence() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (journal, snaps) = (
+        dir.path().join("deposits.journal"),
+        dir.path().join("snaps"),
+    );
+    let store = FileSnapshotStore::new(&snaps);
+
+    // 停机时保存：状态 3，覆盖 journal 序号 1..=2
+    let (mut app, _) = run(&journal, Some(&snaps), &[1, 2], &[1, 3]).await;
+    app.stop();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let saved = store.load(ACCOUNT).unwrap().expect("snapshot saved");
+    assert_eq!((saved.seq, saved.data.as_str()), (2, "3"));
+
+    // 未启用快照的一轮：全量回放后追加序号 3
+    let (mut app, replayed) = run(&journal, None, &[4], &[1, 3, 7]).await;
+    assert_eq!(replayed, 2);
+    app.stop();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // 恢复快照后只回放序号 3；周期保存随后覆盖到序号 3
+    let (mut app, replayed) = run(&journal, Some(&snaps), &[], &[7]).await;
+    assert_eq!(replayed, 1);
+    let mut saved = None;
+    for _ in 0..50 {
+        saved = store.load(ACCOUNT).unwrap().filter(|s| s.seq == 3);
+        if saved.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(saved.expect("periodic snapshot").data, "7");
+    app.stop();
+}
@@ -0,0 +1,55 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::Soak;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Tick;
+
+static LEAKED: AtomicU64 = AtomicU64::new(0);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Feeder;
+
+#[mmg_microbus::component]
+impl Feeder {
+    #[mmg_microbus::active]
+    async fn tick(&self) -> Tick {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        Tick
+    }
+}
+
+// 每条消息遗留一个永不结束的任务，并“泄漏”一块内存
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Leaky;
+
+#[mmg_microbus::component]
+impl Leaky {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) {
+        tokio::spawn(std::future::pending::<()>());
+        LEAKED.fetch_add(64, Ordering::Relaxed);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn soak_flags_monotonic_task_and_allocation_growth() {
+    let mut app = App::new(AppConfig::default());
+    app.start().await.unwrap();
+
+    let report = Soak::new(Duration::from_secs(600))
+        .sample_every(Duration::from_secs(30))
+        .allocated(|| LEAKED.load(Ordering::Relaxed))
+        .run(&app)
+        .await;
+    assert_eq!(report.samples.len(), 20);
+    assert_eq!(report.growing(), ["tasks", "allocated"]);
+    let err = std::panic::catch_unwind(|| report.assert_bounded()).unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+    assert!(msg.starts_with("unbounded growth in [\"tasks\", \"allocated\"]"));
+    app.stop();
+}
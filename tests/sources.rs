@@ -0,0 +1,48 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use mmg_microbus::sources::LineSource;
+use mmg_microbus::testing::BusProbe;
+use std::io::Write;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Reading(u64);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tails_appended_lines_through_parser() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sensor.log");
+    std::fs::write(&path, "1\n").unwrap();
+
+    let mut app = App::new(AppConfig::default());
+    let readings = BusProbe::<Reading>::attach(&app);
+    let source = LineSource::tail(&path)
+        .from_start()
+        .poll_interval(Duration::from_millis(10))
+        .parse(|line| line.trim().parse().ok().map(Reading));
+    let stats = source.handle();
+    app.add_component(source);
+    app.start().await.unwrap();
+
+    // 残行等待换行补齐；无法解析的行跳过
+    let mut f = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap();
+    f.write_all(b"2\r\nbad\n3").unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    f.write_all(b"0\n").unwrap();
+    readings
+        .assert_received_in_order(
+            &[Reading(1), Reading(2), Reading(30)],
+            Duration::from_secs(2),
+        )
+        .await;
+    assert_eq!((stats.lines(), stats.skipped()), (4, 1));
+
+    // 截断后从头读取
+    std::fs::write(&path, "7\n").unwrap();
+    assert!(readings.wait_for(4, Duration::from_secs(2)).await);
+    assert_eq!(*readings.received()[3], Reading(7));
+    app.stop();
+}
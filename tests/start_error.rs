@@ -0,0 +1,72 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::events::FailurePhase;
+use mmg_microbus::prelude::*;
+use std::error::Error;
+use std::time::Duration;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Feed;
+
+#[mmg_microbus::component]
+impl Feed {
+    #[mmg_microbus::init]
+    async fn init(&self) -> mmg_microbus::error::Result<()> {
+        let cause = std::io::Error::new(std::io::ErrorKind::NotFound, "feed.csv missing");
+        Err(MicrobusError::Bridge {
+            endpoint: "feed.csv".to_owned(),
+            source: Box::new(cause),
+        })
+    }
+}
+
+// 失败晚于 Feed：报告仍须包含
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Ledger;
+
+#[mmg_microbus::component]
+impl Ledger {
+    #[mmg_microbus::init]
+    async fn init(&self) -> mmg_microbus::error::Result<()> {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        Err(MicrobusError::Config("ledger.path not set".to_owned()))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn start_failure_reports_every_failed_component_with_its_cause_chain() {
+    let mut app = App::new(AppConfig::default());
+    let err = app.start().await.expect_err("init fails");
+    let MicrobusError::Startup { failures } = &err else {
+        panic!("unexpected error: {err:?}");
+    };
+    let mut names: Vec<_> = failures
+        .iter()
+        .map(|f| (f.component.rsplit("::").next().unwrap(), f.phase))
+        .collect();
+    names.sort_unstable_by_key(|n| n.0);
+    assert_eq!(
+        names,
+        [("Feed", FailurePhase::Run), ("Ledger", FailurePhase::Run)]
+    );
+    assert!(err
+        .to_string()
+        .starts_with("2 component(s) failed to start: "));
+    assert!(err
+        .to_string()
+        .contains("invalid configuration: ledger.path not set"));
+
+    let feed = failures
+        .iter()
+        .find(|f| f.component.ends_with("::Feed"))
+        .unwrap();
+    let mut chain = Vec::new();
+    let mut cur: Option<&dyn Error> = Some(&feed.error);
+    while let Some(e) = cur {
+        chain.push(e.to_string());
+        cur = e.source();
+    }
+    assert_eq!(chain, ["bridge feed.csv failed", "feed.csv missing"]);
+}
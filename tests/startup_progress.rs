@@ -0,0 +1,69 @@
+use mmg_microbus::component::{Component, ComponentContext};
+use mmg_microbus::prelude::*;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Quick;
+
+#[mmg_microbus::component]
+impl Quick {
+    #[mmg_microbus::init]
+    async fn init(&self) {}
+}
+
+// 手写组件：模拟卡住的第三方 init，永不到达启动屏障
+struct Hung;
+
+#[async_trait::async_trait]
+impl Component for Hung {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        std::future::pending::<()>().await;
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pending_components_are_reported() {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_global_default(subscriber);
+
+    let cfg = mmg_microbus::config::AppConfig {
+        startup_progress_interval: Some(Duration::from_millis(20)),
+        ..Default::default()
+    };
+    let mut app = App::new(cfg);
+    app.add_component(Hung);
+    let res = tokio::time::timeout(Duration::from_millis(100), app.start()).await;
+    assert!(res.is_err(), "start should hang at the barrier");
+
+    let progress = app.startup_progress();
+    app.stop();
+    assert_eq!(progress.pending.len(), 1, "{progress:?}");
+    assert!(progress.pending[0].ends_with("Hung"));
+    assert!(progress.arrived.iter().any(|c| c.ends_with("Quick")));
+
+    let out = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert!(out.contains("startup barrier still waiting"), "log: {out}");
+    assert!(out.contains("Hung"), "log: {out}");
+}
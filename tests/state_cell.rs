@@ -0,0 +1,71 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use mmg_microbus::state::State;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Fill(&'static str, i64);
+
+#[derive(Debug)]
+struct Check(&'static str);
+
+#[derive(Debug, PartialEq)]
+struct Exposure(i64);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Book {
+    positions: State<BTreeMap<&'static str, i64>>,
+}
+
+#[mmg_microbus::component]
+impl Book {
+    // 唯一写入方
+    #[mmg_microbus::handle(in_order)]
+    async fn on_fill(&self, f: &Fill) {
+        self.positions.update(|p| *p.entry(f.0).or_default() += f.1);
+    }
+
+    #[mmg_microbus::handle]
+    async fn on_check(&self, c: &Check) -> Exposure {
+        Exposure(self.positions.read(|p| p.get(c.0).copied().unwrap_or(0)))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn single_writer_state_is_visible_to_readers() {
+    let cell = State::new(1);
+    let before = cell.get();
+    let waiter = async { *cell.changed().await };
+    let (seen, ()) = tokio::join!(waiter, async {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        cell.update(|v| *v += 1);
+    });
+    assert_eq!(seen, 2);
+    assert_eq!((*before, *cell.get(), cell.version()), (1, 2, 1));
+
+    let mut app = App::new(AppConfig::default());
+    let bus = app.bus_handle();
+    let mut out = bus.try_subscribe::<Exposure>().unwrap();
+    app.start().await.unwrap();
+    for qty in [5, -2, 4] {
+        bus.publish_any_arc(Arc::new(Fill("ESZ6", qty))).await;
+    }
+    // 写入与读取分属不同 worker：轮询直至读方看到全部写入
+    let got = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            bus.publish_any_arc(Arc::new(Check("ESZ6"))).await;
+            let e = out.recv().await.unwrap();
+            if *e == Exposure(7) {
+                return e;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(*got, Exposure(7));
+    app.stop();
+}
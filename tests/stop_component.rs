@@ -0,0 +1,87 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::introspect::ComponentStatus;
+use mmg_microbus::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Tick;
+
+static FEED_STOPPED: AtomicBool = AtomicBool::new(false);
+static AUDITED: AtomicUsize = AtomicUsize::new(0);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Feed;
+
+#[mmg_microbus::component]
+impl Feed {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) {}
+
+    #[mmg_microbus::stop]
+    fn stop(&self) {
+        FEED_STOPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Audit;
+
+#[mmg_microbus::component]
+impl Audit {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) {
+        AUDITED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stopping_one_component_leaves_the_rest_running() {
+    let mut app = App::new(AppConfig::default());
+    app.start().await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), app.stop_component::<Feed>())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(FEED_STOPPED.load(Ordering::SeqCst));
+    let status = |name: &str| {
+        app.introspect()
+            .components
+            .into_iter()
+            .find(|c| c.name.ends_with(name))
+            .unwrap()
+            .status
+    };
+    assert_eq!(status("Feed"), ComponentStatus::Stopped);
+    // start() 在屏障放行时返回，各组件随后才各自置为 Running
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while status("Audit") == ComponentStatus::Ready {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(status("Audit"), ComponentStatus::Running);
+    // 已退出的组件再次停止直接返回
+    app.stop_component::<Feed>().await.unwrap();
+
+    app.bus_handle().publish_any_arc(Arc::new(Tick)).await;
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while AUDITED.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    assert!(matches!(
+        app.stop_component_by_name("nope").await,
+        Err(MicrobusError::Config(_))
+    ));
+    app.stop();
+}
@@ -0,0 +1,44 @@
+use futures_util::{stream, StreamExt};
+use mmg_microbus::component::{Component, ComponentContext};
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Reading(u64);
+
+// 手写组件：以 StreamExt 组合子消费订阅
+struct Summer(parking_lot::Mutex<Option<oneshot::Sender<Vec<u64>>>>);
+
+#[async_trait::async_trait]
+impl Component for Summer {
+    async fn run(self: Box<Self>, ctx: ComponentContext) -> Result<()> {
+        let sub = mmg_microbus::component::__subscribe_any_auto::<Reading>(&ctx);
+        mmg_microbus::component::__startup_arrive_and_wait(&ctx).await;
+        let even: Vec<u64> = sub
+            .map(|r| r.0)
+            .filter(|v| std::future::ready(v % 2 == 0))
+            .take(2)
+            .collect()
+            .await;
+        if let Some(tx) = self.0.lock().take() {
+            let _ = tx.send(even);
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn subscriptions_and_publishing_compose_with_streams() {
+    let (tx, rx) = oneshot::channel();
+    let mut app = App::new(AppConfig::default());
+    app.add_component(Summer(parking_lot::Mutex::new(Some(tx))));
+    app.start().await.unwrap();
+
+    let readings = stream::iter(1..=5).map(Reading);
+    assert_eq!(app.bus_handle().publish_stream(readings).await, 5);
+    let even = tokio::time::timeout(Duration::from_secs(2), rx).await;
+    assert_eq!(even.unwrap().unwrap(), [2, 4]);
+    app.stop();
+}
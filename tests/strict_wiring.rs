@@ -0,0 +1,49 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::prelude::*;
+
+#[derive(Debug)]
+struct Tick;
+
+#[derive(Debug)]
+struct Quote;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Quoter;
+
+#[mmg_microbus::component]
+impl Quoter {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) -> Option<Quote> {
+        Some(Quote)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn strict_wiring_fails_start_on_unconsumed_publication() {
+    let strict = AppConfig {
+        strict_wiring: true,
+        ..AppConfig::default()
+    };
+    let mut app = App::new(strict.clone());
+    app.declare_publisher::<Tick>();
+    app.validate().expect("unconsumed publications only warn");
+    let Err(MicrobusError::Config(msg)) = app.start().await else {
+        panic!("strict wiring must reject a publication without subscriber");
+    };
+    assert!(
+        msg.contains("no subscriber for") && msg.contains("::Quote (published by"),
+        "{msg}"
+    );
+    assert!(msg.contains("Quoter"), "{msg}");
+    assert!(!msg.contains("no publisher"), "{msg}");
+
+    let mut app = App::new(strict);
+    app.declare_publisher::<Tick>()
+        .declare_subscriber::<Quote>();
+    app.start()
+        .await
+        .expect("declared subscriber closes the gap");
+    app.stop();
+}
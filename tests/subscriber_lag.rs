@@ -0,0 +1,69 @@
+use mmg_microbus::events::SubscriberLagging;
+use mmg_microbus::prelude::*;
+use parking_lot::Mutex;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Tick;
+
+static LAGS: Mutex<Vec<SubscriberLagging>> = Mutex::new(Vec::new());
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Flood;
+
+#[mmg_microbus::component]
+impl Flood {
+    #[mmg_microbus::active]
+    async fn tick(&self) -> Tick {
+        Tick
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct SlowConsumer;
+
+#[mmg_microbus::component]
+impl SlowConsumer {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Ops;
+
+#[mmg_microbus::component]
+impl Ops {
+    #[mmg_microbus::handle]
+    async fn on_lag(&self, lag: &SubscriberLagging) {
+        LAGS.lock().push(lag.clone());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn lagging_subscriber_is_published_once() {
+    let cfg = mmg_microbus::config::AppConfig {
+        queue_capacity: 8,
+        subscriber_lag: Some(mmg_microbus::config::LagMonitorConfig {
+            threshold_percent: 50,
+            sustain: Duration::from_millis(20),
+            check_interval: Duration::from_millis(5),
+        }),
+        ..Default::default()
+    };
+    let mut app = App::new(cfg);
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    app.stop();
+
+    let lags = LAGS.lock().clone();
+    assert_eq!(lags.len(), 1, "lag reported once per episode: {lags:?}");
+    assert!(lags[0].component.ends_with("SlowConsumer"));
+    assert!(lags[0].type_name.ends_with("Tick"));
+    assert_eq!(lags[0].capacity, 8);
+    assert!(lags[0].depth >= 4);
+}
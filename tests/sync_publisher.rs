@@ -0,0 +1,38 @@
+use mmg_microbus::adapters::SyncPublisher;
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use mmg_microbus::testing::BusProbe;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Sample(u64);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_from_plain_threads_until_the_app_stops() {
+    let mut app = App::new(AppConfig::default());
+    let samples = BusProbe::<Sample>::attach(&app);
+    let (publisher, source) = SyncPublisher::channel(2);
+    app.add_component(source);
+    app.start().await.unwrap();
+
+    // 容量小于消息数：生产线程在队列满时阻塞而非丢弃
+    let producer = publisher.clone();
+    let thread = std::thread::spawn(move || (1..=10).all(|i| producer.publish(Sample(i)).is_ok()));
+    let expected: Vec<_> = (1..=10).map(Sample).collect();
+    samples
+        .assert_received_in_order(&expected, Duration::from_secs(2))
+        .await;
+    assert!(thread.join().unwrap());
+
+    app.stop();
+    for _ in 0..100 {
+        if publisher.is_closed() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let rejected = std::thread::spawn(move || publisher.publish(Sample(11)))
+        .join()
+        .unwrap();
+    assert_eq!(rejected, Err(Sample(11)));
+}
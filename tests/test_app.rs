@@ -0,0 +1,57 @@
+use mmg_microbus::testing::TestApp;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Tick(u64);
+#[derive(Clone, Debug, PartialEq)]
+struct Price(u64);
+#[derive(Clone, Debug)]
+struct Unrelated;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pricer;
+
+#[mmg_microbus::component]
+impl Pricer {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, t: &Tick) -> Price {
+        Price(t.0 * 10)
+    }
+}
+
+// 未被选中：若被启动，每个 Tick 会额外产生 Unrelated
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Noisy;
+
+#[mmg_microbus::component]
+impl Noisy {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) -> Unrelated {
+        Unrelated
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn inject_and_expect_selected_component() {
+    let t = TestApp::builder()
+        .component::<Pricer>()
+        .start()
+        .await
+        .expect("start");
+    t.inject(Tick(1)).await;
+    t.inject(Tick(2)).await;
+    assert_eq!(*t.expect::<Price>(Duration::from_secs(1)).await, Price(10));
+    assert_eq!(*t.expect::<Price>(Duration::from_secs(1)).await, Price(20));
+    assert!(t
+        .try_expect::<Price>(Duration::from_millis(20))
+        .await
+        .is_none());
+    assert!(t
+        .try_expect::<Unrelated>(Duration::from_millis(20))
+        .await
+        .is_none());
+    let components = t.app().introspect().components;
+    assert_eq!(components.len(), 1, "{components:?}");
+}
@@ -0,0 +1,106 @@
+use mmg_microbus::config::{ActiveScheduling, AppConfig, Topology};
+use mmg_microbus::prelude::*;
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug)]
+struct Job;
+
+#[derive(Debug)]
+struct Worked(Option<String>);
+
+#[derive(Debug)]
+struct Polled(Option<String>);
+
+fn thread_name() -> Option<String> {
+    std::thread::current().name().map(str::to_owned)
+}
+
+async fn busy() {
+    let now = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+    PEAK.fetch_max(now, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    RUNNING.fetch_sub(1, Ordering::SeqCst);
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Left;
+
+#[mmg_microbus::component]
+impl Left {
+    #[mmg_microbus::handle]
+    async fn on_job(&self, _j: &Job) -> Worked {
+        busy().await;
+        Worked(thread_name())
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Right {
+    done: AtomicBool,
+}
+
+#[mmg_microbus::component]
+impl Right {
+    #[mmg_microbus::handle]
+    async fn on_job(&self, _j: &Job) {
+        busy().await;
+    }
+
+    #[mmg_microbus::active]
+    async fn poll(&self) -> ControlFlow<(), Polled> {
+        if self.done.swap(true, Ordering::SeqCst) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(Polled(thread_name()))
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn topology_bounds_handlers_and_places_tasks() {
+    let mut app = App::new(AppConfig {
+        topology: Topology {
+            max_concurrent_handlers: Some(1),
+            worker_threads: Some(2),
+            actives: ActiveScheduling::Dedicated,
+            handler_budget: None,
+        },
+        ..AppConfig::default()
+    });
+    let bus = app.bus_handle();
+    let mut worked = bus.try_subscribe::<Worked>().unwrap();
+    let mut polled = bus.try_subscribe::<Polled>().unwrap();
+    app.start().await.unwrap();
+
+    let wait = Duration::from_secs(5);
+    let p = tokio::time::timeout(wait, polled.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        p.0.as_deref().is_some_and(|n| n.starts_with("microbus:")),
+        "{p:?}"
+    );
+
+    for _ in 0..10 {
+        bus.publish_any_arc(Arc::new(Job)).await;
+    }
+    for _ in 0..10 {
+        let w = tokio::time::timeout(wait, worked.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(w.0.as_deref(), Some("microbus-worker"));
+    }
+    // 两个组件的 handler 共享一个许可
+    assert_eq!(PEAK.load(Ordering::SeqCst), 1);
+    app.stop();
+}
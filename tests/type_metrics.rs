@@ -0,0 +1,48 @@
+use mmg_microbus::bus::ErasedEvent;
+use mmg_microbus::prelude::*;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Small(#[allow(dead_code)] u64);
+#[derive(Clone, Debug)]
+struct Blob(Vec<u8>);
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Source;
+
+#[mmg_microbus::component]
+impl Source {
+    #[mmg_microbus::active(once)]
+    async fn small(&self) -> Vec<ErasedEvent> {
+        (1..=3).map(|i| ErasedEvent::new(Small(i))).collect()
+    }
+    #[mmg_microbus::active(once)]
+    async fn blob(&self) -> Blob {
+        Blob(vec![0; 100])
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn per_type_count_and_bytes() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.message_size_hint::<Blob>(|b| b.0.len());
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let snap = app.introspect();
+    app.stop();
+
+    let find = |suffix: &str| {
+        snap.types
+            .iter()
+            .find(|t| t.type_name.ends_with(suffix))
+            .unwrap_or_else(|| panic!("{suffix} missing: {:?}", snap.types))
+            .clone()
+    };
+    let small = find("::Small");
+    assert_eq!(small.published, 3);
+    assert_eq!(small.bytes, 3 * std::mem::size_of::<Small>() as u64);
+    let blob = find("::Blob");
+    assert_eq!(blob.published, 1);
+    assert_eq!(blob.bytes, (std::mem::size_of::<Blob>() + 100) as u64);
+}
@@ -0,0 +1,54 @@
+use mmg_microbus::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static TICKS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Debug)]
+struct Tick;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Clock;
+
+#[mmg_microbus::component]
+impl Clock {
+    #[mmg_microbus::active]
+    async fn every_minute(&self) -> Tick {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        Tick
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Counter;
+
+#[mmg_microbus::component]
+impl Counter {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) {
+        TICKS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// 让所有就绪任务跑到空闲：暂停时钟下短 sleep 仅在运行时空闲后才自动推进
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn advance_drives_scheduled_actives() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.start().await.expect("start");
+    settle().await;
+    assert_eq!(TICKS.load(Ordering::SeqCst), 0);
+    for n in 1..=3 {
+        tokio::time::advance(Duration::from_secs(60)).await;
+        settle().await;
+        assert_eq!(TICKS.load(Ordering::SeqCst), n);
+    }
+    app.stop();
+    // 停机宽限期同样基于 tokio 时钟
+    tokio::time::advance(Duration::from_millis(100)).await;
+}
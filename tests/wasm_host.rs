@@ -0,0 +1,85 @@
+use mmg_microbus::codec::BusMessage;
+use mmg_microbus::testing::TestApp;
+use mmg_microbus::wasm::WasmHost;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Serialize, Deserialize, BusMessage)]
+struct Ping(u32);
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BusMessage)]
+struct Pong(u32);
+
+// 回显策略：内容首字符 '0' 触发 trap，'1' 死循环（耗尽燃料），其余以 Pong 原样发布
+const ECHO: &str = r#"
+(module
+  (import "microbus" "publish" (func $publish (param i32 i32 i32 i32)))
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 1024))
+  (data (i32.const 0) "Pong")
+  (func (export "microbus_alloc") (param $len i32) (result i32)
+    (local $p i32)
+    (local.set $p (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $len)))
+    (local.get $p))
+  (func (export "microbus_on_message") (param $np i32) (param $nl i32) (param $dp i32) (param $dl i32)
+    (global.set $next (i32.const 1024))
+    (if (i32.eq (i32.load8_u (local.get $dp)) (i32.const 48)) (then unreachable))
+    (if (i32.eq (i32.load8_u (local.get $dp)) (i32.const 49)) (then (loop $spin (br $spin))))
+    (call $publish (i32.const 0) (i32.const 4) (local.get $dp) (local.get $dl))))
+"#;
+
+// 热替换后的策略：固定发布 Pong(99)
+const CONSTANT: &str = r#"
+(module
+  (import "microbus" "publish" (func $publish (param i32 i32 i32 i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "Pong99")
+  (func (export "microbus_alloc") (param $len i32) (result i32) (i32.const 1024))
+  (func (export "microbus_on_message") (param i32 i32 i32 i32)
+    (call $publish (i32.const 0) (i32.const 4) (i32.const 4) (i32.const 2))))
+"#;
+
+fn text<T>(
+    wrap: fn(u32) -> T,
+    get: fn(&T) -> u32,
+) -> (
+    impl Fn(&T) -> String + Send + Sync,
+    impl Fn(&str) -> Result<T, String> + Send + Sync,
+) {
+    (
+        move |v: &T| get(v).to_string(),
+        move |s: &str| s.parse().map(wrap).map_err(|e| format!("{e}")),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn guest_translates_messages_survives_traps_and_reloads() {
+    assert!(WasmHost::from_bytes("(module").is_err());
+    let host = WasmHost::from_bytes(ECHO)
+        .unwrap()
+        .input::<Ping>(text(Ping, |p| p.0))
+        .output::<Pong>(text(Pong, |p| p.0))
+        .fuel_per_call(100_000);
+    let stats = host.handle();
+    let app = TestApp::builder()
+        .add_component(host)
+        .start()
+        .await
+        .unwrap();
+
+    app.inject(Ping(7)).await;
+    assert_eq!(*app.expect::<Pong>(Duration::from_secs(2)).await, Pong(7));
+
+    // trap 与燃料耗尽均丢弃该消息，后续消息由全新实例处理
+    app.inject(Ping(0)).await;
+    app.inject(Ping(1)).await;
+    app.inject(Ping(8)).await;
+    assert_eq!(*app.expect::<Pong>(Duration::from_secs(2)).await, Pong(8));
+    assert_eq!((stats.calls(), stats.traps()), (2, 2));
+
+    assert!(stats.reload("(module").is_err());
+    stats.reload(CONSTANT).unwrap();
+    app.inject(Ping(5)).await;
+    assert_eq!(*app.expect::<Pong>(Duration::from_secs(2)).await, Pong(99));
+    assert_eq!(stats.dropped(), 0);
+}
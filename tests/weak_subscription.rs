@@ -0,0 +1,66 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Tick;
+
+// 无任何发布方：弱订阅不要求装配
+#[derive(Debug)]
+struct Quote;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Dashboard;
+
+#[mmg_microbus::component]
+impl Dashboard {
+    // 观察方处理极慢：若计为普通订阅，发布方会在第 5 条起阻塞
+    #[mmg_microbus::handle(weak)]
+    async fn on_tick(&self, _t: &Tick) {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    }
+
+    #[mmg_microbus::handle(weak, latest)]
+    async fn on_quote(&self, _q: &Quote) {}
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn weak_subscriber_never_blocks_publishers_or_counts_as_consumer() {
+    let cfg = AppConfig {
+        queue_capacity: 4,
+        ..AppConfig::default()
+    };
+    let mut app = App::new(cfg);
+    app.declare_publisher::<Tick>();
+    // 弱订阅不计入装配校验：Quote 无发布方也不报错
+    app.validate_strict().unwrap();
+    let bus = app.bus_handle();
+    let mut observer = bus.try_subscribe_weak::<Tick>().unwrap();
+    app.start().await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        for _ in 0..50 {
+            bus.publish_any_arc(Arc::new(Tick)).await;
+        }
+    })
+    .await
+    .expect("publisher blocked by a weak subscriber");
+
+    let weak: Vec<_> = app
+        .introspect()
+        .subscriptions
+        .into_iter()
+        .filter(|s| s.weak)
+        .collect();
+    assert_eq!(weak.len(), 3);
+    let ticks: Vec<_> = weak
+        .iter()
+        .filter(|s| s.type_name.ends_with("Tick"))
+        .collect();
+    assert!(ticks.iter().all(|s| s.dropped > 0 && s.depth <= 4));
+    // 外部弱订阅照常收到未被丢弃的消息
+    assert!(observer.recv().await.is_some());
+    app.stop();
+}
@@ -0,0 +1,70 @@
+use mmg_microbus::testing::TestApp;
+use mmg_microbus::webhook::WebhookServer;
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Push {
+    repo: String,
+    commits: u32,
+}
+
+async fn post(addr: SocketAddr, path: &str, token: &str, body: &str) -> String {
+    let mut s = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    let req = format!(
+        "POST {path} HTTP/1.1\r\nHost: x\r\nX-Token: {token}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    );
+    s.write_all(req.as_bytes()).await.expect("write");
+    let mut out = String::new();
+    s.read_to_string(&mut out).await.expect("read");
+    out.lines().next().unwrap_or_default().to_string()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn webhook_posts_become_bus_messages() {
+    let server = WebhookServer::bind(([127, 0, 0, 1], 0))
+        .expect("bind")
+        .route::<Push>("/hooks/push")
+        .require_header("x-token", "s3cret")
+        .max_body(256);
+    let addr = server.local_addr();
+    let stats = server.handle();
+    let app = TestApp::builder()
+        .add_component(server)
+        .start()
+        .await
+        .unwrap();
+
+    let ok = r#"{"repo":"microbus","commits":2}"#;
+    let status = post(addr, "/hooks/push?delivery=1", "s3cret", ok).await;
+    assert!(status.contains("202"), "{status}");
+    let push = app.expect::<Push>(Duration::from_secs(2)).await;
+    assert_eq!(
+        *push,
+        Push {
+            repo: "microbus".into(),
+            commits: 2
+        }
+    );
+
+    assert!(post(addr, "/hooks/push", "wrong", ok).await.contains("401"));
+    assert!(post(addr, "/hooks/other", "s3cret", ok)
+        .await
+        .contains("404"));
+    assert!(post(addr, "/hooks/push", "s3cret", "{\"repo\":1}")
+        .await
+        .contains("400"));
+    let big = format!("{{\"repo\":\"{}\",\"commits\":1}}", "x".repeat(300));
+    assert!(post(addr, "/hooks/push", "s3cret", &big)
+        .await
+        .contains("413"));
+    assert!(app
+        .try_expect::<Push>(Duration::from_millis(100))
+        .await
+        .is_none());
+    assert_eq!(stats.accepted(), 1);
+    assert_eq!(stats.rejected(), 4);
+}
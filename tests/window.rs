@@ -0,0 +1,76 @@
+use mmg_microbus::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+static ROLLING: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+#[derive(Debug)]
+struct Trade {
+    qty: u64,
+}
+#[derive(Debug, PartialEq)]
+struct VolumeBar {
+    trades: usize,
+    volume: u64,
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Bars;
+
+#[mmg_microbus::component]
+impl Bars {
+    #[mmg_microbus::handle(window = "1s", emit = VolumeBar)]
+    async fn bar(&self, w: &Window<Trade>) -> VolumeBar {
+        VolumeBar {
+            trades: w.len(),
+            volume: w.iter().map(|t| t.qty).sum(),
+        }
+    }
+
+    #[mmg_microbus::handle(window = "1s", every = "500ms")]
+    async fn rolling(&self, w: &Window<Trade>) {
+        assert_eq!(w.end() - w.start(), Duration::from_secs(1));
+        ROLLING.lock().unwrap().push(w.len());
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn handler_runs_once_per_closed_window() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let bus = app.bus_handle();
+    let mut bars = bus.try_subscribe::<VolumeBar>().unwrap();
+    app.start().await.unwrap();
+    let t0 = tokio::time::Instant::now();
+
+    for qty in [1, 2, 3] {
+        bus.publish_any_arc(Arc::new(Trade { qty })).await;
+    }
+    let bar = bars.recv().await.unwrap();
+    assert_eq!(
+        *bar,
+        VolumeBar {
+            trades: 3,
+            volume: 6
+        }
+    );
+    assert_eq!(t0.elapsed(), Duration::from_secs(1));
+
+    // 下一个滚动窗口只含其间到达的消息
+    tokio::time::sleep_until(t0 + Duration::from_millis(1200)).await;
+    bus.publish_any_arc(Arc::new(Trade { qty: 10 })).await;
+    let bar = bars.recv().await.unwrap();
+    assert_eq!(
+        *bar,
+        VolumeBar {
+            trades: 1,
+            volume: 10
+        }
+    );
+    assert_eq!(t0.elapsed(), Duration::from_secs(2));
+
+    // 滑动窗口每 500ms 关闭一个 1s 窗口：消息出现在相邻两个窗口中，空窗口不调用
+    tokio::time::sleep_until(t0 + Duration::from_secs(3)).await;
+    assert_eq!(*ROLLING.lock().unwrap(), [3, 3, 1, 1]);
+    app.stop();
+}
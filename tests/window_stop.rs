@@ -0,0 +1,54 @@
+use mmg_microbus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Trade {
+    qty: u64,
+}
+#[derive(Debug, PartialEq)]
+struct VolumeBar {
+    trades: usize,
+    volume: u64,
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Bars;
+
+#[mmg_microbus::component]
+impl Bars {
+    #[mmg_microbus::handle(window = "1s", emit = VolumeBar)]
+    async fn bar(&self, w: &Window<Trade>) -> VolumeBar {
+        VolumeBar {
+            trades: w.len(),
+            volume: w.iter().map(|t| t.qty).sum(),
+        }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn partial_window_is_flushed_when_worker_stops() {
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    let bus = app.bus_handle();
+    let mut bars = bus.try_subscribe::<VolumeBar>().unwrap();
+    app.start().await.unwrap();
+    let t0 = tokio::time::Instant::now();
+
+    for qty in [4, 5] {
+        bus.publish_any_arc(Arc::new(Trade { qty })).await;
+    }
+    // 窗口关闭前停止组件：已缓存的两条仍以提前关闭的窗口处理一次
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    app.stop_component::<Bars>().await.unwrap();
+    let bar = bars.recv().await.unwrap();
+    assert_eq!(
+        *bar,
+        VolumeBar {
+            trades: 2,
+            volume: 9
+        }
+    );
+    assert_eq!(t0.elapsed(), Duration::from_millis(300));
+    app.stop();
+}
@@ -0,0 +1,77 @@
+use mmg_microbus::prelude::*;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct Ping;
+#[derive(Clone, Debug)]
+struct Orphan;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pinger;
+
+#[mmg_microbus::component]
+impl Pinger {
+    #[mmg_microbus::active(once)]
+    async fn ping(&self) -> Ping {
+        Ping
+    }
+    #[mmg_microbus::active(once)]
+    async fn orphan(&self) -> Orphan {
+        Orphan
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Listener;
+
+#[mmg_microbus::component]
+impl Listener {
+    #[mmg_microbus::handle]
+    async fn on_ping(&self, _p: &Ping) {}
+}
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn wire_debug_traces_publishes() {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_max_level(tracing::Level::TRACE)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_global_default(subscriber);
+
+    let mut app = App::new(mmg_microbus::config::AppConfig::default());
+    app.set_wire_debug(1);
+    app.start().await.expect("start");
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    app.stop();
+
+    let out = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    let line = |ty: &str| {
+        out.lines()
+            .find(|l| l.contains("mmg_microbus::wire") && l.contains(ty))
+            .unwrap_or_else(|| panic!("no wire trace for {ty}: {out}"))
+            .to_string()
+    };
+    let ping = line("wire_debug::Ping");
+    assert!(ping.contains("origin=\"wire_debug::Pinger\""), "{ping}");
+    assert!(ping.contains("subscribers=1"), "{ping}");
+    assert!(line("wire_debug::Orphan").contains("subscribers=0"));
+}
@@ -0,0 +1,59 @@
+use mmg_microbus::config::AppConfig;
+use mmg_microbus::error::MicrobusError;
+use mmg_microbus::prelude::*;
+
+#[derive(Debug)]
+struct Tick;
+
+#[derive(Debug)]
+struct Price;
+
+#[derive(Debug)]
+struct Orphan;
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Pricer;
+
+#[mmg_microbus::component]
+impl Pricer {
+    #[mmg_microbus::handle]
+    async fn on_tick(&self, _t: &Tick) -> Option<Price> {
+        Some(Price)
+    }
+}
+
+#[mmg_microbus::component]
+#[derive(Default)]
+struct Trader;
+
+#[mmg_microbus::component]
+impl Trader {
+    #[mmg_microbus::handle]
+    async fn on_price(&self, _p: &Price) {}
+
+    #[mmg_microbus::handle]
+    async fn on_orphan(&self, _o: &Envelope<Orphan>) {}
+
+    #[mmg_microbus::handle]
+    async fn on_started(&self, _e: &mmg_microbus::events::ComponentStarted) {}
+}
+
+#[test]
+fn validate_reports_subscriptions_without_publisher() {
+    let mut app = App::new(AppConfig::default());
+    let Err(MicrobusError::Config(msg)) = app.validate() else {
+        panic!("missing publishers must be reported");
+    };
+    assert!(msg.contains("Tick") && msg.contains("Orphan"), "{msg}");
+    assert!(
+        !msg.contains("::Price ") && !msg.contains("ComponentStarted"),
+        "{msg}"
+    );
+    assert!(msg.contains("Trader"), "{msg}");
+
+    app.declare_publisher::<Tick>()
+        .declare_publisher::<Orphan>();
+    app.validate().expect("declared publishers close the gap");
+    app.validate_strict().expect("all wiring is known");
+}